@@ -1,8 +1,265 @@
+use crate::common::ledger::transactions::{add_attr, get_attr};
+use crate::core::profile::profile::Profile;
 use crate::errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult};
+use crate::global::settings;
 use crate::utils::openssl::encode;
+use crate::utils::qualifier;
+use aries_vcx_core::errors::error::AriesVcxCoreErrorKind;
+use chrono::{Datelike, NaiveDate};
 use std::collections::HashMap;
+use std::sync::Arc;
+
+const CATEGORY_ATTRIBUTE_ENCODING: &str = "VCX_ATTRIBUTE_ENCODING";
+const LEDGER_ATTRIBUTE_ENCODING_PREFIX: &str = "attribute_encodings";
+
+static DEFAULT_ENCODING: AttributeEncoding = AttributeEncoding::Default;
+
+/// Scheme used to derive the `encoded` value of a credential attribute from its raw value. All schemes but
+/// [AttributeEncoding::Default] produce non-negative 32-bit integers, so that the attribute can be used in predicates.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AttributeEncoding {
+    /// Integers fitting in 32 bits are kept as is, anything else is encoded as the sha256 hash of the value.
+    Default,
+    /// Date in the `YYYY-MM-DD` format, encoded as its day number counting 0001-01-01 as day 1.
+    Date,
+    /// Decimal number with at most `scale` fractional digits, encoded as the number multiplied by 10^`scale`.
+    Decimal { scale: u32 },
+    /// `true` or `false`, encoded as 1 or 0.
+    Boolean,
+    /// One of `values`, encoded as its index.
+    Enum { values: Vec<String> },
+}
+
+impl AttributeEncoding {
+    pub fn encode(&self, raw: &str) -> VcxResult<String> {
+        let encoded: i64 = match self {
+            AttributeEncoding::Default => return encode(raw),
+            AttributeEncoding::Date => {
+                let date = NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+                    .map_err(|err| invalid_value(raw, self, format!("expected a YYYY-MM-DD date, {}", err)))?;
+                date.num_days_from_ce() as i64
+            }
+            AttributeEncoding::Decimal { scale } => encode_decimal(raw, *scale).ok_or_else(|| {
+                invalid_value(
+                    raw,
+                    self,
+                    format!("expected a decimal number with at most {} fractional digits", scale),
+                )
+            })?,
+            AttributeEncoding::Boolean => match raw.to_lowercase().as_str() {
+                "true" => 1,
+                "false" => 0,
+                _ => return Err(invalid_value(raw, self, "expected true or false")),
+            },
+            AttributeEncoding::Enum { values } => match values.iter().position(|value| value == raw) {
+                Some(index) => index as i64,
+                None => return Err(invalid_value(raw, self, format!("expected one of {:?}", values))),
+            },
+        };
+        if !(0..=i32::MAX as i64).contains(&encoded) {
+            return Err(invalid_value(
+                raw,
+                self,
+                format!("encoded value {} doesn't fit in a non-negative 32-bit integer", encoded),
+            ));
+        }
+        Ok(encoded.to_string())
+    }
+}
+
+fn encode_decimal(raw: &str, scale: u32) -> Option<i64> {
+    let (integer, fraction) = raw.split_once('.').unwrap_or((raw, ""));
+    let digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+    if integer.is_empty() || !digits(integer) || !digits(fraction) || fraction.len() > scale as usize {
+        return None;
+    }
+    let multiplier = 10i64.checked_pow(scale)?;
+    let fraction_multiplier = 10i64.checked_pow(scale - fraction.len() as u32)?;
+    let fraction: i64 = if fraction.is_empty() { 0 } else { fraction.parse().ok()? };
+    integer
+        .parse::<i64>()
+        .ok()?
+        .checked_mul(multiplier)?
+        .checked_add(fraction.checked_mul(fraction_multiplier)?)
+}
+
+fn invalid_value<D>(raw: &str, encoding: &AttributeEncoding, reason: D) -> AriesVcxError
+where
+    D: std::fmt::Display,
+{
+    AriesVcxError::from_msg(
+        AriesVcxErrorKind::EncodeError,
+        format!("Cannot encode \"{}\" with {:?} encoding: {}", raw, encoding, reason),
+    )
+}
+
+/// Attribute encodings declared per schema attribute. Attributes without a declared encoding use
+/// [AttributeEncoding::Default], so an empty registry encodes values the same way as [encode_attributes].
+///
+/// The author of a schema declares its encodings with [AttributeEncodingRegistry::store] and makes them available to
+/// issuers, holders and verifiers with [AttributeEncodingRegistry::publish]. Other parties get them from their own
+/// wallet, or from the ledger when their wallet holds none, in [AttributeEncodingRegistry::load].
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AttributeEncodingRegistry {
+    schemas: HashMap<String, HashMap<String, AttributeEncoding>>,
+}
+
+impl AttributeEncodingRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_encoding(mut self, schema_id: &str, attribute: &str, encoding: AttributeEncoding) -> Self {
+        self.schemas
+            .entry(schema_id.to_string())
+            .or_default()
+            .insert(attribute.to_string(), encoding);
+        self
+    }
+
+    pub fn encoding(&self, schema_id: &str, attribute: &str) -> &AttributeEncoding {
+        self.schemas
+            .get(schema_id)
+            .and_then(|attributes| attributes.get(attribute))
+            .unwrap_or(&DEFAULT_ENCODING)
+    }
+
+    pub fn has_encoding(&self, schema_id: &str, attribute: &str) -> bool {
+        self.schemas
+            .get(schema_id)
+            .map_or(false, |attributes| attributes.contains_key(attribute))
+    }
+
+    pub fn encode(&self, schema_id: &str, attribute: &str, raw: &str) -> VcxResult<String> {
+        self.encoding(schema_id, attribute).encode(raw)
+    }
+
+    /// Checks `encoded` is the encoding of `raw`. Values which cannot be encoded don't match.
+    pub fn matches(&self, schema_id: &str, attribute: &str, raw: &str, encoded: &str) -> bool {
+        self.encode(schema_id, attribute, raw)
+            .map_or(false, |expected| expected == encoded)
+    }
+
+    /// Stores the encodings in the wallet, one record per schema, replacing the encodings previously stored for the
+    /// same schemas.
+    pub async fn store(&self, profile: &Arc<dyn Profile>) -> VcxResult<()> {
+        let wallet = profile.inject_wallet();
+        for (schema_id, attributes) in self.schemas.iter() {
+            let value = serde_json::to_string(attributes)?;
+            match wallet
+                .add_wallet_record(CATEGORY_ATTRIBUTE_ENCODING, schema_id, &value, None)
+                .await
+            {
+                Err(err) if err.kind() == AriesVcxCoreErrorKind::DuplicationWalletRecord => {
+                    wallet
+                        .update_wallet_record_value(CATEGORY_ATTRIBUTE_ENCODING, schema_id, &value)
+                        .await?
+                }
+                res => res?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Publishes the encodings on the ledger, as an attribute of the DID of each schema's author. Must be called by
+    /// the author of the schemas, the attribute being written by the DID of the profile.
+    pub async fn publish(&self, profile: &Arc<dyn Profile>) -> VcxResult<()> {
+        for (schema_id, attributes) in self.schemas.iter() {
+            let attribute_name = ledger_attribute_name(schema_id);
+            let attrib_json = json!({ attribute_name: attributes }).to_string();
+            add_attr(profile, qualifier::issuer_did(schema_id), &attrib_json).await?;
+        }
+        Ok(())
+    }
+
+    /// Loads the encodings stored in the wallet for the given schemas, or published on the ledger for the schemas
+    /// without stored encodings. Schemas without any encodings use the default encoding for all their attributes.
+    pub async fn load(profile: &Arc<dyn Profile>, schema_ids: &[&str]) -> VcxResult<Self> {
+        let mut registry = Self::new();
+        if settings::indy_mocks_enabled() {
+            return Ok(registry);
+        }
+        let wallet = profile.inject_wallet();
+        let options = json!({ "retrieveType": false, "retrieveValue": true, "retrieveTags": false }).to_string();
+        for schema_id in schema_ids {
+            if registry.schemas.contains_key(*schema_id) {
+                continue;
+            }
+            let record = match wallet
+                .get_wallet_record(CATEGORY_ATTRIBUTE_ENCODING, schema_id, &options)
+                .await
+            {
+                Err(err) if err.kind() == AriesVcxCoreErrorKind::WalletRecordNotFound => {
+                    if let Some(attributes) = Self::fetch_published(profile, schema_id).await? {
+                        registry.schemas.insert(schema_id.to_string(), attributes);
+                    }
+                    continue;
+                }
+                res => res?,
+            };
+            let record: serde_json::Value = serde_json::from_str(&record)?;
+            let attributes = record["value"]
+                .as_str()
+                .map(|value| parse_encodings(schema_id, value))
+                .transpose()?
+                .unwrap_or_default();
+            registry.schemas.insert(schema_id.to_string(), attributes);
+        }
+        Ok(registry)
+    }
+
+    async fn fetch_published(
+        profile: &Arc<dyn Profile>,
+        schema_id: &str,
+    ) -> VcxResult<Option<HashMap<String, AttributeEncoding>>> {
+        let attrib = get_attr(
+            profile,
+            qualifier::issuer_did(schema_id),
+            &ledger_attribute_name(schema_id),
+        )
+        .await?;
+        if attrib.is_empty() {
+            return Ok(None);
+        }
+        parse_encodings(schema_id, &attrib).map(Some)
+    }
+}
+
+fn parse_encodings(schema_id: &str, value: &str) -> VcxResult<HashMap<String, AttributeEncoding>> {
+    serde_json::from_str(value).map_err(|err| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidJson,
+            format!(
+                "Cannot deserialize attribute encodings of schema {}: {}",
+                schema_id, err
+            ),
+        )
+    })
+}
+
+fn ledger_attribute_name(schema_id: &str) -> String {
+    format!("{}:{}", LEDGER_ATTRIBUTE_ENCODING_PREFIX, schema_id)
+}
 
 pub fn encode_attributes(attributes: &str) -> VcxResult<String> {
+    _encode_attributes(attributes, |_, raw| encode(raw))
+}
+
+/// Encodes credential attributes like [encode_attributes], using the encodings declared in `registry` for the
+/// attributes of the schema `schema_id`.
+pub fn encode_attributes_for_schema(
+    attributes: &str,
+    schema_id: &str,
+    registry: &AttributeEncodingRegistry,
+) -> VcxResult<String> {
+    _encode_attributes(attributes, |attr, raw| registry.encode(schema_id, attr, raw))
+}
+
+fn _encode_attributes<F>(attributes: &str, encode: F) -> VcxResult<String>
+where
+    F: Fn(&str, &str) -> VcxResult<String>,
+{
     let mut dictionary = HashMap::new();
     match serde_json::from_str::<HashMap<String, serde_json::Value>>(attributes) {
         Ok(mut attributes) => {
@@ -34,7 +291,7 @@ pub fn encode_attributes(attributes: &str) -> VcxResult<String> {
                     }
                 };
 
-                let encoded = encode(first_attr)?;
+                let encoded = encode(attr, first_attr)?;
                 let attrib_values = json!({
                     "raw": first_attr,
                     "encoded": encoded
@@ -63,14 +320,6 @@ pub fn encode_attributes(attributes: &str) -> VcxResult<String> {
                             AriesVcxErrorKind::InvalidAttributesStructure,
                             format!("No 'value' field in cred_value: {:?}", cred_value),
                         ))?;
-                        let encoded = encode(value.as_str().ok_or(AriesVcxError::from_msg(
-                            AriesVcxErrorKind::InvalidAttributesStructure,
-                            format!("Failed to convert value {:?} to string", value),
-                        ))?)?;
-                        let attrib_values = json!({
-                            "raw": value,
-                            "encoded": encoded
-                        });
                         let name = name
                             .as_str()
                             .ok_or(AriesVcxError::from_msg(
@@ -78,6 +327,17 @@ pub fn encode_attributes(attributes: &str) -> VcxResult<String> {
                                 format!("Failed to convert attribute name {:?} to string", cred_value),
                             ))?
                             .to_string();
+                        let encoded = encode(
+                            &name,
+                            value.as_str().ok_or(AriesVcxError::from_msg(
+                                AriesVcxErrorKind::InvalidAttributesStructure,
+                                format!("Failed to convert value {:?} to string", value),
+                            ))?,
+                        )?;
+                        let attrib_values = json!({
+                            "raw": value,
+                            "encoded": encoded
+                        });
                         dictionary.insert(name, attrib_values);
                     }
                     serde_json::to_string_pretty(&dictionary).map_err(|err| {
@@ -102,7 +362,11 @@ pub fn encode_attributes(attributes: &str) -> VcxResult<String> {
 pub mod unit_tests {
     use serde_json::Value;
 
-    use crate::{common::credentials::encoding::encode_attributes, utils::devsetup::*};
+    use crate::common::credentials::encoding::*;
+    use crate::common::test_utils::in_memory_wallet_profile;
+    use crate::utils::devsetup::*;
+    use crate::utils::mockdata::profile::mock_profile::InMemoryWalletProfile;
+    use aries_vcx_core::ledger::file_ledger::FileLedger;
 
     #[test]
     fn test_encode_with_several_attributes_success() {
@@ -348,4 +612,131 @@ pub mod unit_tests {
         let results: Value = serde_json::from_str(&results_json).unwrap();
         assert_eq!(expected, results);
     }
+
+    #[test]
+    fn test_attribute_encodings() {
+        assert_eq!(AttributeEncoding::Date.encode("0001-01-01").unwrap(), "1");
+        assert_eq!(AttributeEncoding::Date.encode("1969-12-31").unwrap(), "719162");
+        assert_eq!(AttributeEncoding::Date.encode("1970-01-01").unwrap(), "719163");
+        assert_eq!(AttributeEncoding::Date.encode("2000-03-01").unwrap(), "730180");
+        assert!(AttributeEncoding::Date.encode("-0001-12-31").is_err());
+        assert!(AttributeEncoding::Date.encode("01/03/2000").is_err());
+
+        let decimal = AttributeEncoding::Decimal { scale: 2 };
+        assert_eq!(decimal.encode("12.5").unwrap(), "1250");
+        assert_eq!(decimal.encode("12.05").unwrap(), "1205");
+        assert_eq!(decimal.encode("7").unwrap(), "700");
+        assert!(decimal.encode("1.234").is_err());
+        assert!(decimal.encode("-1.2").is_err());
+        assert!(decimal.encode("99999999").is_err());
+
+        assert_eq!(AttributeEncoding::Boolean.encode("True").unwrap(), "1");
+        assert_eq!(AttributeEncoding::Boolean.encode("false").unwrap(), "0");
+        assert!(AttributeEncoding::Boolean.encode("yes").is_err());
+
+        let level = AttributeEncoding::Enum {
+            values: vec!["bronze".to_string(), "silver".to_string(), "gold".to_string()],
+        };
+        assert_eq!(level.encode("gold").unwrap(), "2");
+        assert_eq!(
+            level.encode("platinum").unwrap_err().kind(),
+            AriesVcxErrorKind::EncodeError
+        );
+    }
+
+    #[test]
+    fn test_encode_attributes_for_schema() {
+        let _setup = SetupDefaults::init();
+
+        let registry = AttributeEncodingRegistry::new()
+            .with_encoding("schema_1", "birthdate", AttributeEncoding::Date)
+            .with_encoding("schema_2", "city", AttributeEncoding::Boolean);
+        let results_json =
+            encode_attributes_for_schema(r#"{"birthdate": "2000-03-01", "city": "SLC"}"#, "schema_1", &registry)
+                .unwrap();
+
+        let results: Value = serde_json::from_str(&results_json).unwrap();
+        assert_eq!(
+            results,
+            json!({
+                "birthdate": { "raw": "2000-03-01", "encoded": "730180" },
+                "city": {
+                    "raw": "SLC",
+                    "encoded": "101327353979588246869873249766058188995681113722618593621043638294296500696424"
+                }
+            })
+        );
+        assert!(registry.matches("schema_1", "birthdate", "2000-03-01", "730180"));
+        assert!(!registry.matches("schema_2", "birthdate", "2000-03-01", "730180"));
+        assert!(encode_attributes_for_schema(r#"{"city": "SLC"}"#, "schema_2", &registry).is_err());
+    }
+
+    #[test]
+    fn test_encoding_of_undeclared_attributes() {
+        let _setup = SetupDefaults::init();
+
+        let registry = AttributeEncodingRegistry::new().with_encoding("schema_1", "birthdate", AttributeEncoding::Date);
+        assert!(registry.matches("schema_1", "birthdate", "2000-03-01", "730180"));
+        assert!(!registry.matches("schema_1", "birthdate", "2000-03-01", "730181"));
+
+        // attributes without a declared encoding are checked against the default encoding
+        assert!(!registry.matches("schema_2", "birthdate", "2000-03-01", "730180"));
+        assert!(registry.matches("schema_2", "age", "65", "65"));
+        assert!(!registry.matches("schema_2", "age", "65", "25"));
+        assert!(registry.matches("schema_2", "city", "SLC", &encode("SLC").unwrap()));
+        assert!(!registry.matches("schema_2", "city", "SLC", &encode("LA").unwrap()));
+    }
+
+    #[tokio::test]
+    async fn test_store_and_load_attribute_encodings() {
+        let profile = in_memory_wallet_profile();
+
+        let registry = AttributeEncodingRegistry::new()
+            .with_encoding("schema_1", "birthdate", AttributeEncoding::Date)
+            .with_encoding("schema_2", "height", AttributeEncoding::Decimal { scale: 1 });
+        registry.store(&profile).await.unwrap();
+
+        let loaded = AttributeEncodingRegistry::load(&profile, &["schema_1", "schema_2", "schema_3"])
+            .await
+            .unwrap();
+        assert_eq!(loaded, registry);
+
+        // storing again replaces the encodings of the schema
+        AttributeEncodingRegistry::new()
+            .with_encoding("schema_1", "birthdate", AttributeEncoding::Boolean)
+            .store(&profile)
+            .await
+            .unwrap();
+        let loaded = AttributeEncodingRegistry::load(&profile, &["schema_1"]).await.unwrap();
+        assert_eq!(loaded.encoding("schema_1", "birthdate"), &AttributeEncoding::Boolean);
+        assert_eq!(loaded.encoding("schema_2", "height"), &AttributeEncoding::Default);
+    }
+
+    #[tokio::test]
+    async fn test_publish_and_load_attribute_encodings() {
+        let ledger = Arc::new(FileLedger::in_memory());
+        let author: Arc<dyn Profile> = Arc::new(InMemoryWalletProfile::with_ledger(ledger.clone()));
+        let verifier: Arc<dyn Profile> = Arc::new(InMemoryWalletProfile::with_ledger(ledger));
+
+        let schema_id = "V4SGRU86Z58d6TV7PBUe6f:2:gvt:1.0";
+        let other_schema_id = "V4SGRU86Z58d6TV7PBUe6f:2:xyz:1.0";
+        let registry = AttributeEncodingRegistry::new().with_encoding(schema_id, "birthdate", AttributeEncoding::Date);
+        registry.store(&author).await.unwrap();
+        registry.publish(&author).await.unwrap();
+
+        let loaded = AttributeEncodingRegistry::load(&verifier, &[schema_id, other_schema_id])
+            .await
+            .unwrap();
+        assert_eq!(loaded, registry);
+        assert!(loaded.matches(schema_id, "birthdate", "2000-03-01", "730180"));
+
+        // encodings stored in the wallet take precedence over the published ones
+        AttributeEncodingRegistry::new()
+            .with_encoding(schema_id, "birthdate", AttributeEncoding::Boolean)
+            .store(&verifier)
+            .await
+            .unwrap();
+        let loaded = AttributeEncodingRegistry::load(&verifier, &[schema_id]).await.unwrap();
+        assert_eq!(loaded.encoding(schema_id, "birthdate"), &AttributeEncoding::Boolean);
+    }
 }
@@ -8,6 +8,8 @@ use crate::errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult};
 use super::primitives::revocation_registry_delta::RevocationRegistryDelta;
 
 pub mod encoding;
+pub mod revocation_status;
+pub mod storage_time;
 
 #[derive(Serialize, Deserialize)]
 struct ProverCredential {
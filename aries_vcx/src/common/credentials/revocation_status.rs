@@ -0,0 +1,369 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use aries_vcx_core::anoncreds::types::{AnonCredsObject, RevocationRegistryDelta};
+use aries_vcx_core::errors::error::AriesVcxCoreErrorKind;
+use aries_vcx_core::wallet::base_wallet::AsyncFnIteratorCollect;
+use time::OffsetDateTime;
+
+use crate::core::profile::profile::Profile;
+use crate::errors::error::prelude::*;
+
+use super::ProverCredential;
+
+const CATEGORY_REVOKED_CREDENTIAL: &str = "VCX_REVOKED_CREDENTIAL";
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RevocationStatus {
+    NonRevocable,
+    Valid,
+    Revoked,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CredentialRevocationStatus {
+    pub cred_id: String,
+    pub cred_def_id: String,
+    pub rev_reg_id: Option<String>,
+    pub cred_rev_id: Option<String>,
+    pub status: RevocationStatus,
+    // timestamp of the revocation registry state the status was determined from
+    pub timestamp: Option<u64>,
+    // time the status was checked at
+    pub checked_at: u64,
+}
+
+/// Checks the revocation status of the credentials in the wallet matching `filter_json` (all of them if `None`),
+/// fetching the revocation registry delta of each registry once. If `mark_revoked` is set, revoked credentials are
+/// recorded in the wallet, so that [filter_revoked_credentials] can exclude them when building proofs, while the
+/// records of checked credentials which are not revoked, and of credentials no longer in the wallet, are removed.
+pub async fn check_credentials_revocation_status(
+    profile: &Arc<dyn Profile>,
+    filter_json: Option<&str>,
+    mark_revoked: bool,
+) -> VcxResult<Vec<CredentialRevocationStatus>> {
+    trace!(
+        "check_credentials_revocation_status >>> filter_json: {:?}, mark_revoked: {}",
+        filter_json,
+        mark_revoked
+    );
+    let anoncreds = Arc::clone(profile).inject_anoncreds();
+    let ledger = Arc::clone(profile).inject_ledger();
+
+    let credentials_json = anoncreds.prover_get_credentials(filter_json).await?;
+    let credentials: Vec<ProverCredential> = serde_json::from_str(&credentials_json).map_err(|err| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::SerializationError,
+            format!("Failed to deserialize anoncreds credentials: {}", err),
+        )
+    })?;
+
+    let checked_at = OffsetDateTime::now_utc().unix_timestamp() as u64;
+    let mut deltas: HashMap<String, (RevocationRegistryDelta, u64)> = HashMap::new();
+    let mut statuses = Vec::with_capacity(credentials.len());
+    for credential in credentials {
+        let (status, timestamp) = match (&credential.rev_reg_id, &credential.cred_rev_id) {
+            (Some(rev_reg_id), Some(cred_rev_id)) => {
+                if !deltas.contains_key(rev_reg_id) {
+                    let (_, delta_json, timestamp) = ledger
+                        .get_rev_reg_delta_json(rev_reg_id, None, Some(checked_at))
+                        .await?;
+                    deltas.insert(
+                        rev_reg_id.clone(),
+                        (RevocationRegistryDelta::from_json(&delta_json)?, timestamp),
+                    );
+                }
+                let (delta, timestamp) = &deltas[rev_reg_id];
+                (status_from_delta(delta, cred_rev_id)?, Some(*timestamp))
+            }
+            _ => (RevocationStatus::NonRevocable, None),
+        };
+        statuses.push(CredentialRevocationStatus {
+            cred_id: credential.referent,
+            cred_def_id: credential.cred_def_id,
+            rev_reg_id: credential.rev_reg_id,
+            cred_rev_id: credential.cred_rev_id,
+            status,
+            timestamp,
+            checked_at,
+        });
+    }
+
+    if mark_revoked {
+        update_revoked_marks(profile, &statuses, filter_json.is_none()).await?;
+    }
+    Ok(statuses)
+}
+
+// `all_credentials` tells whether the statuses cover every credential in the wallet, in which case a credential
+// marked as revoked without a status was deleted
+async fn update_revoked_marks(
+    profile: &Arc<dyn Profile>,
+    statuses: &[CredentialRevocationStatus],
+    all_credentials: bool,
+) -> VcxResult<()> {
+    let mut stale = get_revoked_credential_ids(profile).await?;
+    for status in statuses.iter().filter(|s| s.status == RevocationStatus::Revoked) {
+        mark_credential_revoked(profile, status).await?;
+        stale.remove(&status.cred_id);
+    }
+    if !all_credentials {
+        let checked: HashSet<&str> = statuses.iter().map(|status| status.cred_id.as_str()).collect();
+        stale.retain(|cred_id| checked.contains(cred_id.as_str()));
+    }
+    for cred_id in stale {
+        unmark_credential_revoked(profile, &cred_id).await?;
+    }
+    Ok(())
+}
+
+fn status_from_delta(delta: &RevocationRegistryDelta, cred_rev_id: &str) -> VcxResult<RevocationStatus> {
+    let cred_rev_id: u32 = cred_rev_id.parse().map_err(|err| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidRevocationDetails,
+            format!("Invalid credential revocation id {}: {}", cred_rev_id, err),
+        )
+    })?;
+    if delta.value.revoked.contains(&cred_rev_id) {
+        Ok(RevocationStatus::Revoked)
+    } else {
+        Ok(RevocationStatus::Valid)
+    }
+}
+
+async fn mark_credential_revoked(profile: &Arc<dyn Profile>, status: &CredentialRevocationStatus) -> VcxResult<()> {
+    let wallet = profile.inject_wallet();
+    let value = serde_json::to_string(status)?;
+    let tags = json!({ "cred_def_id": status.cred_def_id }).to_string();
+    match wallet
+        .add_wallet_record(CATEGORY_REVOKED_CREDENTIAL, &status.cred_id, &value, Some(&tags))
+        .await
+    {
+        Err(err) if err.kind() == AriesVcxCoreErrorKind::DuplicationWalletRecord => wallet
+            .update_wallet_record_value(CATEGORY_REVOKED_CREDENTIAL, &status.cred_id, &value)
+            .await
+            .map_err(|err| err.into()),
+        res => res.map_err(|err| err.into()),
+    }
+}
+
+/// Removes the record marking the credential as revoked, if any. Meant to be called when the credential is deleted.
+pub async fn unmark_credential_revoked(profile: &Arc<dyn Profile>, cred_id: &str) -> VcxResult<()> {
+    match profile
+        .inject_wallet()
+        .delete_wallet_record(CATEGORY_REVOKED_CREDENTIAL, cred_id)
+        .await
+    {
+        Err(err) if err.kind() == AriesVcxCoreErrorKind::WalletRecordNotFound => Ok(()),
+        res => res.map_err(|err| err.into()),
+    }
+}
+
+/// IDs of the credentials marked as revoked by [check_credentials_revocation_status].
+pub async fn get_revoked_credential_ids(profile: &Arc<dyn Profile>) -> VcxResult<HashSet<String>> {
+    let records = profile
+        .inject_wallet()
+        .iterate_wallet_records(CATEGORY_REVOKED_CREDENTIAL, "{}", "{}")
+        .await?
+        .collect()
+        .await?;
+
+    records
+        .iter()
+        .map(|record| {
+            let record: serde_json::Value = serde_json::from_str(record)?;
+            record["id"].as_str().map(String::from).ok_or_else(|| {
+                AriesVcxError::from_msg(
+                    AriesVcxErrorKind::InvalidJson,
+                    format!("Revoked credential record has no id: {}", record),
+                )
+            })
+        })
+        .collect()
+}
+
+/// Removes the credentials marked as revoked from credentials retrieved for a presentation request, as returned by
+/// `BaseAnonCreds::prover_get_credentials_for_proof_req`.
+pub async fn filter_revoked_credentials(profile: &Arc<dyn Profile>, retrieved_credentials: &str) -> VcxResult<String> {
+    let revoked = get_revoked_credential_ids(profile).await?;
+    let mut retrieved: serde_json::Value = serde_json::from_str(retrieved_credentials).map_err(|err| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidJson,
+            format!("Cannot deserialize retrieved credentials: {}", err),
+        )
+    })?;
+    if revoked.is_empty() {
+        return Ok(retrieved_credentials.to_string());
+    }
+
+    for section in ["attrs", "predicates"] {
+        if let Some(referents) = retrieved.get_mut(section).and_then(|s| s.as_object_mut()) {
+            for candidates in referents.values_mut().filter_map(|c| c.as_array_mut()) {
+                candidates.retain(|candidate| {
+                    candidate["cred_info"]["referent"]
+                        .as_str()
+                        .map_or(true, |referent| !revoked.contains(referent))
+                });
+            }
+        }
+    }
+    Ok(retrieved.to_string())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+pub mod unit_tests {
+    use crate::common::test_utils::in_memory_wallet_profile;
+
+    use super::*;
+
+    fn _status(cred_id: &str, status: RevocationStatus) -> CredentialRevocationStatus {
+        CredentialRevocationStatus {
+            cred_id: cred_id.to_string(),
+            cred_def_id: "cred_def_id".to_string(),
+            rev_reg_id: Some("rev_reg_id".to_string()),
+            cred_rev_id: Some("1".to_string()),
+            status,
+            timestamp: Some(1),
+            checked_at: 2,
+        }
+    }
+
+    #[test]
+    fn test_status_from_delta() {
+        let delta = RevocationRegistryDelta::from_json(
+            &json!({ "ver": "1.0", "value": { "accum": "accum", "issued": [1, 2], "revoked": [3] } }).to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(status_from_delta(&delta, "1").unwrap(), RevocationStatus::Valid);
+        assert_eq!(status_from_delta(&delta, "3").unwrap(), RevocationStatus::Revoked);
+        assert_eq!(
+            status_from_delta(&delta, "abc").unwrap_err().kind(),
+            AriesVcxErrorKind::InvalidRevocationDetails
+        );
+    }
+
+    #[tokio::test]
+    async fn test_filter_revoked_credentials() {
+        let profile = in_memory_wallet_profile();
+        let retrieved = json!({
+            "attrs": {
+                "attr_1": [
+                    { "cred_info": { "referent": "cred_1" }, "interval": null },
+                    { "cred_info": { "referent": "cred_2" }, "interval": null }
+                ]
+            },
+            "predicates": {
+                "pred_1": [ { "cred_info": { "referent": "cred_2" }, "interval": null } ]
+            }
+        })
+        .to_string();
+
+        assert_eq!(
+            filter_revoked_credentials(&profile, &retrieved).await.unwrap(),
+            retrieved
+        );
+
+        let status = _status("cred_2", RevocationStatus::Revoked);
+        mark_credential_revoked(&profile, &status).await.unwrap();
+        // marking twice updates the record
+        mark_credential_revoked(&profile, &status).await.unwrap();
+
+        let filtered: serde_json::Value =
+            serde_json::from_str(&filter_revoked_credentials(&profile, &retrieved).await.unwrap()).unwrap();
+        assert_eq!(filtered["attrs"]["attr_1"].as_array().unwrap().len(), 1);
+        assert_eq!(filtered["attrs"]["attr_1"][0]["cred_info"]["referent"], "cred_1");
+        assert!(filtered["predicates"]["pred_1"].as_array().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_update_revoked_marks_removes_stale_marks() {
+        let profile = in_memory_wallet_profile();
+        for cred_id in ["cred_1", "cred_2", "cred_3"] {
+            mark_credential_revoked(&profile, &_status(cred_id, RevocationStatus::Revoked))
+                .await
+                .unwrap();
+        }
+        let statuses = vec![
+            _status("cred_1", RevocationStatus::Valid),
+            _status("cred_2", RevocationStatus::Revoked),
+            _status("cred_4", RevocationStatus::Revoked),
+        ];
+
+        // cred_3 was not checked, it may still be in the wallet
+        update_revoked_marks(&profile, &statuses, false).await.unwrap();
+        let revoked = get_revoked_credential_ids(&profile).await.unwrap();
+        assert_eq!(revoked, HashSet::from(["cred_2", "cred_3", "cred_4"].map(String::from)));
+
+        // all credentials were checked, cred_3 was deleted
+        update_revoked_marks(&profile, &statuses, true).await.unwrap();
+        let revoked = get_revoked_credential_ids(&profile).await.unwrap();
+        assert_eq!(revoked, HashSet::from(["cred_2", "cred_4"].map(String::from)));
+
+        unmark_credential_revoked(&profile, "cred_2").await.unwrap();
+        unmark_credential_revoked(&profile, "cred_2").await.unwrap();
+        let revoked = get_revoked_credential_ids(&profile).await.unwrap();
+        assert_eq!(revoked, HashSet::from(["cred_4".to_string()]));
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod integration_tests {
+    use super::*;
+
+    use crate::common::test_utils::create_and_store_credential;
+    use crate::utils::constants::DEFAULT_SCHEMA_ATTRS;
+    use crate::utils::devsetup::{init_holder_setup_in_indy_context, SetupProfile};
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_pool_check_credentials_revocation_status() {
+        SetupProfile::run_indy(|setup| async move {
+            let holder_setup = init_holder_setup_in_indy_context(&setup).await;
+
+            let res = create_and_store_credential(
+                &setup.profile,
+                &holder_setup.profile,
+                &setup.institution_did,
+                DEFAULT_SCHEMA_ATTRS,
+            )
+            .await;
+            let cred_id = res.7;
+            let rev_reg_id = res.8;
+            let cred_rev_id = res.9;
+            let tails_file = res.10;
+
+            let statuses = check_credentials_revocation_status(&holder_setup.profile, None, true)
+                .await
+                .unwrap();
+            assert_eq!(statuses.len(), 1);
+            assert_eq!(statuses[0].cred_id, cred_id);
+            assert_eq!(statuses[0].status, RevocationStatus::Valid);
+
+            let anoncreds = Arc::clone(&setup.profile).inject_anoncreds();
+            anoncreds
+                .revoke_credential_local(&tails_file, &rev_reg_id, &cred_rev_id)
+                .await
+                .unwrap();
+            anoncreds
+                .publish_local_revocations(&setup.institution_did, &rev_reg_id)
+                .await
+                .unwrap();
+
+            std::thread::sleep(std::time::Duration::from_millis(500));
+
+            let statuses = check_credentials_revocation_status(&holder_setup.profile, None, true)
+                .await
+                .unwrap();
+            assert_eq!(statuses[0].status, RevocationStatus::Revoked);
+            assert!(get_revoked_credential_ids(&holder_setup.profile)
+                .await
+                .unwrap()
+                .contains(&cred_id));
+        })
+        .await;
+    }
+}
@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use aries_vcx_core::errors::error::AriesVcxCoreErrorKind;
+use aries_vcx_core::wallet::base_wallet::AsyncFnIteratorCollect;
+use time::OffsetDateTime;
+
+use crate::core::profile::profile::Profile;
+use crate::errors::error::prelude::*;
+
+const CATEGORY_CREDENTIAL_STORED_AT: &str = "VCX_CREDENTIAL_STORED_AT";
+const TAG_STORED_AT: &str = "stored_at";
+
+/// Records the time the credential was stored in the wallet at, as the `stored_at` tag of a record keyed by the
+/// credential id. Meant to be called when the credential is stored.
+pub async fn record_credential_stored(profile: &Arc<dyn Profile>, cred_id: &str) -> VcxResult<()> {
+    let wallet = profile.inject_wallet();
+    let stored_at = OffsetDateTime::now_utc().unix_timestamp().to_string();
+    let tags = json!({ TAG_STORED_AT: stored_at }).to_string();
+    match wallet
+        .add_wallet_record(CATEGORY_CREDENTIAL_STORED_AT, cred_id, &stored_at, Some(&tags))
+        .await
+    {
+        Err(err) if err.kind() == AriesVcxCoreErrorKind::DuplicationWalletRecord => {
+            wallet
+                .update_wallet_record_value(CATEGORY_CREDENTIAL_STORED_AT, cred_id, &stored_at)
+                .await?;
+            wallet
+                .update_wallet_record_tags(CATEGORY_CREDENTIAL_STORED_AT, cred_id, &tags)
+                .await
+                .map_err(|err| err.into())
+        }
+        res => res.map_err(|err| err.into()),
+    }
+}
+
+/// Removes the record of the time the credential was stored at, if any. Meant to be called when the credential is
+/// deleted.
+pub async fn forget_credential_stored(profile: &Arc<dyn Profile>, cred_id: &str) -> VcxResult<()> {
+    match profile
+        .inject_wallet()
+        .delete_wallet_record(CATEGORY_CREDENTIAL_STORED_AT, cred_id)
+        .await
+    {
+        Err(err) if err.kind() == AriesVcxCoreErrorKind::WalletRecordNotFound => Ok(()),
+        res => res.map_err(|err| err.into()),
+    }
+}
+
+/// Times, in seconds since the epoch, the credentials recorded by [record_credential_stored] were stored at, by
+/// credential id.
+pub async fn get_credential_storage_times(profile: &Arc<dyn Profile>) -> VcxResult<HashMap<String, u64>> {
+    let records = profile
+        .inject_wallet()
+        .iterate_wallet_records(CATEGORY_CREDENTIAL_STORED_AT, "{}", r#"{"retrieveTags": true}"#)
+        .await?
+        .collect()
+        .await?;
+
+    records
+        .iter()
+        .map(|record| {
+            let record: serde_json::Value = serde_json::from_str(record)?;
+            let cred_id = record["id"].as_str();
+            let stored_at = record["tags"][TAG_STORED_AT]
+                .as_str()
+                .and_then(|stored_at| stored_at.parse().ok());
+            match (cred_id, stored_at) {
+                (Some(cred_id), Some(stored_at)) => Ok((cred_id.to_string(), stored_at)),
+                _ => Err(AriesVcxError::from_msg(
+                    AriesVcxErrorKind::InvalidJson,
+                    format!("Invalid credential storage time record: {}", record),
+                )),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+pub mod unit_tests {
+    use crate::common::test_utils::in_memory_wallet_profile;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_record_credential_storage_times() {
+        let profile = in_memory_wallet_profile();
+        let before = OffsetDateTime::now_utc().unix_timestamp() as u64;
+
+        record_credential_stored(&profile, "cred_1").await.unwrap();
+        record_credential_stored(&profile, "cred_2").await.unwrap();
+        record_credential_stored(&profile, "cred_2").await.unwrap();
+        forget_credential_stored(&profile, "cred_1").await.unwrap();
+        forget_credential_stored(&profile, "cred_1").await.unwrap();
+
+        let times = get_credential_storage_times(&profile).await.unwrap();
+        assert_eq!(times.len(), 1);
+        assert!(times["cred_2"] >= before);
+    }
+}
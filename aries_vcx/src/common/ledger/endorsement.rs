@@ -0,0 +1,163 @@
+use std::sync::Arc;
+
+use crate::core::profile::profile::Profile;
+use crate::errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult};
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EndorsableTransactionType {
+    Nym,
+    Schema,
+    CredDef,
+    RevRegDef,
+    RevRegDelta,
+}
+
+/// Ledger write request built and signed by its author, with the endorser set. It is handed over to the endorser
+/// (see [EndorsableTransaction::to_string]), who multi-signs and submits it to the ledger
+/// (see [EndorsableTransaction::endorse]).
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EndorsableTransaction {
+    txn_type: EndorsableTransactionType,
+    author_did: String,
+    endorser_did: String,
+    request: String,
+}
+
+impl EndorsableTransaction {
+    /// Sets the endorser on a write request built by `author_did` and signs it with the author's key.
+    pub async fn prepare(
+        profile: &Arc<dyn Profile>,
+        txn_type: EndorsableTransactionType,
+        author_did: &str,
+        endorser_did: &str,
+        request: &str,
+    ) -> VcxResult<Self> {
+        trace!(
+            "EndorsableTransaction::prepare >>> txn_type: {:?}, author_did: {}, endorser_did: {}",
+            txn_type,
+            author_did,
+            endorser_did
+        );
+        let ledger = Arc::clone(profile).inject_ledger();
+        let request = ledger.set_endorser(author_did, request, endorser_did).await?;
+        Ok(Self {
+            txn_type,
+            author_did: author_did.to_string(),
+            endorser_did: endorser_did.to_string(),
+            request,
+        })
+    }
+
+    pub async fn prepare_nym(
+        profile: &Arc<dyn Profile>,
+        author_did: &str,
+        endorser_did: &str,
+        target_did: &str,
+        verkey: Option<&str>,
+        role: Option<&str>,
+    ) -> VcxResult<Self> {
+        let ledger = Arc::clone(profile).inject_ledger();
+        let request = ledger
+            .build_nym_request(author_did, target_did, verkey, None, role)
+            .await?;
+        Self::prepare(
+            profile,
+            EndorsableTransactionType::Nym,
+            author_did,
+            endorser_did,
+            &request,
+        )
+        .await
+    }
+
+    /// Multi-signs the transaction with the endorser's key and submits it to the ledger. Must be called on the
+    /// endorser's profile.
+    pub async fn endorse(&self, profile: &Arc<dyn Profile>) -> VcxResult<()> {
+        trace!(
+            "EndorsableTransaction::endorse >>> txn_type: {:?}, author_did: {}, endorser_did: {}",
+            self.txn_type,
+            self.author_did,
+            self.endorser_did
+        );
+        let ledger = Arc::clone(profile).inject_ledger();
+        ledger
+            .endorse_transaction(&self.endorser_did, &self.request)
+            .await
+            .map_err(|err| err.into())
+    }
+
+    pub fn get_txn_type(&self) -> EndorsableTransactionType {
+        self.txn_type
+    }
+
+    pub fn get_author_did(&self) -> &str {
+        &self.author_did
+    }
+
+    pub fn get_endorser_did(&self) -> &str {
+        &self.endorser_did
+    }
+
+    pub fn get_request(&self) -> &str {
+        &self.request
+    }
+
+    pub fn to_string(&self) -> VcxResult<String> {
+        serde_json::to_string(&self).map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::SerializationError,
+                format!("Cannot serialize endorsable transaction: {:?}", err),
+            )
+        })
+    }
+
+    pub fn from_string(data: &str) -> VcxResult<Self> {
+        serde_json::from_str(data).map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidJson,
+                format!("Cannot deserialize endorsable transaction: {:?}", err),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use crate::utils::constants::{REQUEST_WITH_ENDORSER, SCHEMA_TXN};
+    use crate::utils::mockdata::profile::mock_profile::MockProfile;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_prepare_and_endorse_transaction() {
+        let profile: Arc<dyn Profile> = Arc::new(MockProfile);
+        let txn = EndorsableTransaction::prepare(
+            &profile,
+            EndorsableTransactionType::Schema,
+            "V4SGRU86Z58d6TV7PBUe6f",
+            "2hoqvcwupRTUNkXn6ArYzs",
+            SCHEMA_TXN,
+        )
+        .await
+        .unwrap();
+        assert_eq!(txn.get_request(), REQUEST_WITH_ENDORSER);
+
+        let txn = EndorsableTransaction::from_string(&txn.to_string().unwrap()).unwrap();
+        assert_eq!(txn.get_txn_type(), EndorsableTransactionType::Schema);
+        assert_eq!(txn.get_author_did(), "V4SGRU86Z58d6TV7PBUe6f");
+        assert_eq!(txn.get_endorser_did(), "2hoqvcwupRTUNkXn6ArYzs");
+        txn.endorse(&profile).await.unwrap();
+    }
+
+    #[test]
+    fn test_from_string_fails_for_invalid_json() {
+        assert_eq!(
+            EndorsableTransaction::from_string(r#"{"request": "{}"}"#)
+                .unwrap_err()
+                .kind(),
+            AriesVcxErrorKind::InvalidJson
+        );
+    }
+}
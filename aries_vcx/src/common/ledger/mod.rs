@@ -1,2 +1,3 @@
+pub mod endorsement;
 pub mod service_didsov;
 pub mod transactions;
@@ -135,7 +135,7 @@ pub async fn into_did_doc(profile: &Arc<dyn Profile>, invitation: &AnyInvitation
     Ok(did_doc)
 }
 
-fn _ed25519_public_key_to_did_key(public_key_base58: &str) -> VcxResult<String> {
+pub(crate) fn ed25519_public_key_to_did_key(public_key_base58: &str) -> VcxResult<String> {
     let public_key_bytes = bs58::decode(public_key_base58).into_vec().map_err(|_| {
         AriesVcxError::from_msg(
             AriesVcxErrorKind::InvalidDid,
@@ -149,33 +149,45 @@ fn _ed25519_public_key_to_did_key(public_key_base58: &str) -> VcxResult<String>
     Ok(did_key)
 }
 
+pub(crate) fn did_key_to_ed25519_public_key(did_key: &str) -> VcxResult<String> {
+    let stripped_didkey = did_key.strip_prefix(DID_KEY_PREFIX).ok_or_else(|| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidDid,
+            format!("did:key prefix is missing: {}", did_key),
+        )
+    })?;
+    let stripped = if let Some(stripped) = stripped_didkey.strip_prefix('z') {
+        stripped
+    } else {
+        Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidDid,
+            format!("z prefix is missing: {}", did_key),
+        ))?
+    };
+    let decoded_value = bs58::decode(stripped).into_vec().map_err(|_| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidDid,
+            format!("Could not decode base58: {} as portion of {}", stripped, did_key),
+        )
+    })?;
+    if let Some(public_key_bytes) = decoded_value.strip_prefix(&ED25519_MULTIBASE_CODEC) {
+        Ok(bs58::encode(public_key_bytes).into_string())
+    } else {
+        Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidDid,
+            format!(
+                "Only Ed25519-based did:keys are currently supported, got key: {}",
+                did_key
+            ),
+        ))
+    }
+}
+
 fn normalize_keys_as_naked(keys_list: Vec<String>) -> VcxResult<Vec<String>> {
     let mut result = Vec::new();
     for key in keys_list {
-        if let Some(stripped_didkey) = key.strip_prefix(DID_KEY_PREFIX) {
-            let stripped = if let Some(stripped) = stripped_didkey.strip_prefix('z') {
-                stripped
-            } else {
-                Err(AriesVcxError::from_msg(
-                    AriesVcxErrorKind::InvalidDid,
-                    format!("z prefix is missing: {}", key),
-                ))?
-            };
-            let decoded_value = bs58::decode(stripped).into_vec().map_err(|_| {
-                AriesVcxError::from_msg(
-                    AriesVcxErrorKind::InvalidDid,
-                    format!("Could not decode base58: {} as portion of {}", stripped, key),
-                )
-            })?;
-            let verkey = if let Some(public_key_bytes) = decoded_value.strip_prefix(&ED25519_MULTIBASE_CODEC) {
-                Ok(bs58::encode(public_key_bytes).into_string())
-            } else {
-                Err(AriesVcxError::from_msg(
-                    AriesVcxErrorKind::InvalidDid,
-                    format!("Only Ed25519-based did:keys are currently supported, got key: {}", key),
-                ))
-            }?;
-            result.push(verkey);
+        if key.starts_with(DID_KEY_PREFIX) {
+            result.push(did_key_to_ed25519_public_key(&key)?);
         } else {
             result.push(key);
         }
@@ -387,7 +399,7 @@ fn get_data_from_response(resp: &str) -> VcxResult<serde_json::Value> {
 //     async fn test_public_key_to_did_naked_with_previously_known_keys_suggested() {
 //         let did_pub_with_key = "did:key:z6MkwHgArrRJq3tTdhQZKVAa1sdFgSAs5P5N1C4RJcD11Ycv".to_string();
 //         let did_pub = "HqR8GcAsVWPzXCZrdvCjAn5Frru1fVq1KB9VULEz6KqY".to_string();
-//         let did_raw = _ed25519_public_key_to_did_key(&did_pub).unwrap();
+//         let did_raw = ed25519_public_key_to_did_key(&did_pub).unwrap();
 //         let recipient_keys = vec![did_raw];
 //         let expected_output = vec![did_pub_with_key];
 //         assert_eq!(recipient_keys, expected_output);
@@ -397,7 +409,7 @@ fn get_data_from_response(resp: &str) -> VcxResult<serde_json::Value> {
 //     async fn test_public_key_to_did_naked_with_previously_known_keys_rfc_0360() {
 //         let did_pub_with_key_rfc_0360 = "did:key:z6MkmjY8GnV5i9YTDtPETC2uUAW6ejw3nk5mXF5yci5ab7th".to_string();
 //         let did_pub_rfc_0360 = "8HH5gYEeNc3z7PYXmd54d4x6qAfCNrqQqEB3nS7Zfu7K".to_string();
-//         let did_raw = _ed25519_public_key_to_did_key(&did_pub_rfc_0360).unwrap();
+//         let did_raw = ed25519_public_key_to_did_key(&did_pub_rfc_0360).unwrap();
 //         let recipient_keys = vec![did_raw];
 //         let expected_output = vec![did_pub_with_key_rfc_0360];
 //         assert_eq!(recipient_keys, expected_output);
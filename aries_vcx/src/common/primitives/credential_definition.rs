@@ -1,6 +1,7 @@
 use aries_vcx_core::errors::error::AriesVcxCoreErrorKind;
 use aries_vcx_core::ledger::base_ledger::BaseLedger;
 
+use crate::common::ledger::endorsement::{EndorsableTransaction, EndorsableTransactionType};
 use crate::core::profile::profile::Profile;
 use crate::errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult};
 use crate::utils::constants::{CRED_DEF_ID, CRED_DEF_JSON, DEFAULT_SERIALIZE_VERSION};
@@ -187,6 +188,43 @@ impl CredentialDef {
         })
     }
 
+    /// Builds the credential definition write request and signs it as author, instead of submitting it to the
+    /// ledger. The credential definition remains in `Built` state until the endorser submits it, see
+    /// [CredentialDef::update_state].
+    pub async fn prepare_for_endorser(
+        &self,
+        profile: &Arc<dyn Profile>,
+        endorser_did: &str,
+    ) -> VcxResult<EndorsableTransaction> {
+        trace!(
+            "CredentialDef::prepare_for_endorser >>> issuer_did: {}, cred_def_id: {}, endorser_did: {}",
+            self.issuer_did,
+            self.id,
+            endorser_did
+        );
+        let ledger = Arc::clone(profile).inject_ledger();
+        if let Some(ledger_cred_def_json) = _try_get_cred_def_from_ledger(&ledger, &self.issuer_did, &self.id).await? {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::CredDefAlreadyCreated,
+                format!(
+                    "Credential definition with id {} already exists on the ledger: {}",
+                    self.id, ledger_cred_def_json
+                ),
+            ));
+        }
+        let request = ledger
+            .build_cred_def_request(&self.issuer_did, &self.cred_def_json)
+            .await?;
+        EndorsableTransaction::prepare(
+            profile,
+            EndorsableTransactionType::CredDef,
+            &self.issuer_did,
+            endorser_did,
+            &request,
+        )
+        .await
+    }
+
     pub fn from_string(data: &str) -> VcxResult<Self> {
         ObjectWithVersion::deserialize(data)
             .map(|obj: ObjectWithVersion<Self>| obj.data)
@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use crate::common::ledger::endorsement::{EndorsableTransaction, EndorsableTransactionType};
 use crate::core::profile::profile::Profile;
 use crate::errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult};
 use crate::global::settings;
@@ -132,6 +133,27 @@ impl Schema {
         })
     }
 
+    /// Builds the schema write request and signs it as author, instead of submitting it to the ledger. The schema
+    /// remains in `Built` state until the endorser submits it, see [Schema::update_state].
+    pub async fn prepare_for_endorser(
+        &self,
+        profile: &Arc<dyn Profile>,
+        endorser_did: &str,
+    ) -> VcxResult<EndorsableTransaction> {
+        trace!("Schema::prepare_for_endorser >>> endorser_did: {}", endorser_did);
+        let schema_json = self.get_schema_json(profile).await?;
+        let ledger = Arc::clone(profile).inject_ledger();
+        let request = ledger.build_schema_request(&self.submitter_did, &schema_json).await?;
+        EndorsableTransaction::prepare(
+            profile,
+            EndorsableTransactionType::Schema,
+            &self.submitter_did,
+            endorser_did,
+            &request,
+        )
+        .await
+    }
+
     pub fn get_source_id(&self) -> String {
         self.source_id.clone()
     }
@@ -2,6 +2,8 @@ pub mod credential_definition;
 pub mod credential_schema;
 pub mod revocation_registry;
 pub mod revocation_registry_delta;
+pub mod revocation_registry_pool;
+pub mod tails;
 
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
@@ -1,13 +1,21 @@
+use std::path::Path;
 use std::sync::Arc;
 
+use aries_vcx_core::anoncreds::types::{AnonCredsObject, RevocationRegistryDelta as LedgerRevocationRegistryDelta};
+pub use aries_vcx_core::anoncreds::types::{RevocationRegistryDefinition, RevocationRegistryDefinitionValue};
 use aries_vcx_core::errors::error::AriesVcxCoreErrorKind;
+use aries_vcx_core::wallet::base_wallet::{AsyncFnIteratorCollect, WalletTransaction};
 
+use crate::common::ledger::endorsement::{EndorsableTransaction, EndorsableTransactionType};
 use crate::core::profile::profile::Profile;
 use crate::errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult};
 use crate::global::settings;
 use crate::utils::constants::REV_REG_ID;
 
 use super::credential_definition::PublicEntityStateType;
+use super::tails::TailsUploader;
+
+pub(crate) const CATEGORY_PENDING_REVOCATION: &str = "VCX_PENDING_REVOCATION";
 
 #[derive(Clone, Deserialize, Debug, Serialize, PartialEq, Eq)]
 pub struct RevocationRegistry {
@@ -72,6 +80,10 @@ impl RevocationRegistry {
         self.cred_def_id.clone()
     }
 
+    pub fn get_issuer_did(&self) -> String {
+        self.issuer_did.clone()
+    }
+
     pub fn get_rev_reg_def(&self) -> RevocationRegistryDefinition {
         self.rev_reg_def.clone()
     }
@@ -135,6 +147,80 @@ impl RevocationRegistry {
         Ok(())
     }
 
+    /// Builds the revocation registry definition write request and signs it as author, instead of submitting it to
+    /// the ledger. The definition remains in `Built` state until the endorser submits it, see
+    /// [RevocationRegistry::update_state].
+    pub async fn prepare_rev_reg_def_for_endorser(
+        &mut self,
+        profile: &Arc<dyn Profile>,
+        tails_url: &str,
+        endorser_did: &str,
+    ) -> VcxResult<EndorsableTransaction> {
+        trace!(
+            "RevocationRegistry::prepare_rev_reg_def_for_endorser >>> rev_reg_id: {}, endorser_did: {}",
+            self.rev_reg_id,
+            endorser_did
+        );
+        self.rev_reg_def.value.tails_location = String::from(tails_url);
+        let ledger = Arc::clone(profile).inject_ledger();
+        let request = ledger
+            .build_rev_reg_def_request(&self.issuer_did, &json!(self.rev_reg_def).to_string())
+            .await?;
+        EndorsableTransaction::prepare(
+            profile,
+            EndorsableTransactionType::RevRegDef,
+            &self.issuer_did,
+            endorser_did,
+            &request,
+        )
+        .await
+    }
+
+    /// Builds the revocation registry entry write request and signs it as author, instead of submitting it to the
+    /// ledger. The entry can only be endorsed after the revocation registry definition is on the ledger.
+    pub async fn prepare_rev_reg_delta_for_endorser(
+        &self,
+        profile: &Arc<dyn Profile>,
+        endorser_did: &str,
+    ) -> VcxResult<EndorsableTransaction> {
+        trace!(
+            "RevocationRegistry::prepare_rev_reg_delta_for_endorser >>> rev_reg_id: {}, endorser_did: {}",
+            self.rev_reg_id,
+            endorser_did
+        );
+        let ledger = Arc::clone(profile).inject_ledger();
+        let request = ledger
+            .build_rev_reg_delta_request(&self.issuer_did, &self.rev_reg_id, &self.rev_reg_entry)
+            .await?;
+        EndorsableTransaction::prepare(
+            profile,
+            EndorsableTransactionType::RevRegDelta,
+            &self.issuer_did,
+            endorser_did,
+            &request,
+        )
+        .await
+    }
+
+    /// Marks the revocation registry definition and delta as published once they are found on the ledger, e.g.
+    /// after they were submitted by an endorser. The delta only counts as published once the accumulator on the
+    /// ledger is the one of the endorsed entry.
+    pub async fn update_state(&mut self, profile: &Arc<dyn Profile>) -> VcxResult<()> {
+        let ledger = Arc::clone(profile).inject_ledger();
+        if !self.was_rev_reg_def_published() && ledger.get_rev_reg_def_json(&self.rev_reg_id).await.is_ok() {
+            self.rev_reg_def_state = PublicEntityStateType::Published;
+        }
+        if self.was_rev_reg_def_published() && !self.was_rev_reg_delta_published() {
+            if let Ok((_, ledger_delta, _)) = ledger.get_rev_reg_delta_object(&self.rev_reg_id, None, None).await {
+                let endorsed_delta = LedgerRevocationRegistryDelta::from_json(&self.rev_reg_entry)?;
+                if ledger_delta.value.accum == endorsed_delta.value.accum {
+                    self.rev_reg_delta_state = PublicEntityStateType::Published;
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub async fn publish_revocation_primitives(
         &mut self,
         profile: &Arc<dyn Profile>,
@@ -148,6 +234,28 @@ impl RevocationRegistry {
         self.publish_built_rev_reg_delta(profile).await
     }
 
+    /// Uploads the tails file with `uploader` and publishes the revocation registry definition pointing to the
+    /// uploaded file, followed by the revocation registry delta.
+    pub async fn distribute_revocation_primitives(
+        &mut self,
+        profile: &Arc<dyn Profile>,
+        uploader: &dyn TailsUploader,
+    ) -> VcxResult<()> {
+        trace!(
+            "RevocationRegistry::distribute_revocation_primitives >>> rev_reg_id: {}",
+            self.rev_reg_id
+        );
+        let tails_url = self.upload_tails_file(uploader).await?;
+        self.publish_revocation_primitives(profile, &tails_url).await
+    }
+
+    /// Uploads the tails file from `tails_dir` with `uploader`, returns the URL holders can download it from.
+    pub async fn upload_tails_file(&self, uploader: &dyn TailsUploader) -> VcxResult<String> {
+        let tails_hash = &self.rev_reg_def.value.tails_hash;
+        let tails_file_path = Path::new(&self.tails_dir).join(tails_hash);
+        uploader.upload(tails_hash, &tails_file_path).await
+    }
+
     async fn publish_built_rev_reg_delta(&mut self, profile: &Arc<dyn Profile>) -> VcxResult<()> {
         let issuer_did = &self.issuer_did.clone();
         if self.was_rev_reg_delta_published() {
@@ -187,43 +295,107 @@ impl RevocationRegistry {
     }
 
     pub async fn revoke_credential_local(&self, profile: &Arc<dyn Profile>, cred_rev_id: &str) -> VcxResult<()> {
-        let anoncreds = Arc::clone(profile).inject_anoncreds();
-        anoncreds
-            .revoke_credential_local(&self.tails_dir, &self.rev_reg_id, cred_rev_id)
-            .await
-            .map_err(|err| err.into())
+        let tags = json!({ "rev_reg_id": self.rev_reg_id }).to_string();
+        revoke_credential_local(profile, &self.tails_dir, &self.rev_reg_id, cred_rev_id, &tags).await
     }
 
     pub async fn publish_local_revocations(&self, profile: &Arc<dyn Profile>, submitter_did: &str) -> VcxResult<()> {
-        let anoncreds = Arc::clone(profile).inject_anoncreds();
+        publish_local_revocations(profile, submitter_did, &self.rev_reg_id).await
+    }
 
-        anoncreds
-            .publish_local_revocations(submitter_did, &self.rev_reg_id)
-            .await
-            .map_err(|err| err.into())
+    pub async fn get_pending_revocations_count(&self, profile: &Arc<dyn Profile>) -> VcxResult<u32> {
+        if settings::indy_mocks_enabled() {
+            return Ok(0);
+        }
+
+        let pending_records = profile
+            .inject_wallet()
+            .iterate_wallet_records(
+                CATEGORY_PENDING_REVOCATION,
+                &json!({ "rev_reg_id": self.rev_reg_id }).to_string(),
+                "{}",
+            )
+            .await?
+            .collect()
+            .await?;
+
+        Ok(pending_records.len() as u32)
     }
 }
 
-#[derive(Clone, Deserialize, Debug, Serialize, PartialEq, Eq, Default)]
-#[serde(rename_all = "camelCase")]
-pub struct RevocationRegistryDefinitionValue {
-    pub issuance_type: String,
-    pub max_cred_num: u32,
-    pub public_keys: serde_json::Value,
-    pub tails_hash: String,
-    pub tails_location: String,
+/// Revokes the credential locally and marks it as pending publication, in the same wallet transaction. The marker
+/// is keyed by `<rev_reg_id>:<cred_rev_id>` and holds `tags_json`, which must include the `rev_reg_id` tag.
+pub(crate) async fn revoke_credential_local(
+    profile: &Arc<dyn Profile>,
+    tails_dir: &str,
+    rev_reg_id: &str,
+    cred_rev_id: &str,
+    tags_json: &str,
+) -> VcxResult<()> {
+    let anoncreds = Arc::clone(profile).inject_anoncreds();
+
+    if settings::indy_mocks_enabled() {
+        anoncreds
+            .revoke_credential_local(tails_dir, rev_reg_id, cred_rev_id)
+            .await?;
+        return Ok(());
+    }
+
+    // a revocation in the local delta always has a marker, the pending count is derived from them
+    let mut transaction = WalletTransaction::new();
+    transaction.add_wallet_record(
+        CATEGORY_PENDING_REVOCATION,
+        &format!("{}:{}", rev_reg_id, cred_rev_id),
+        cred_rev_id,
+        Some(tags_json),
+    );
+    anoncreds
+        .revoke_credential_local_in_transaction(tails_dir, rev_reg_id, cred_rev_id, transaction)
+        .await?;
+
+    Ok(())
 }
 
-#[derive(Clone, Deserialize, Debug, Serialize, PartialEq, Eq, Default)]
-#[serde(rename_all = "camelCase")]
-pub struct RevocationRegistryDefinition {
-    pub id: String,
-    pub revoc_def_type: String,
-    pub tag: String,
-    pub cred_def_id: String,
-    pub value: RevocationRegistryDefinitionValue,
-    pub ver: String,
+/// Publishes the local revocations of the registry `rev_reg_id` and clears its pending revocation markers.
+pub(crate) async fn publish_local_revocations(
+    profile: &Arc<dyn Profile>,
+    submitter_did: &str,
+    rev_reg_id: &str,
+) -> VcxResult<()> {
+    let anoncreds = Arc::clone(profile).inject_anoncreds();
+
+    anoncreds.publish_local_revocations(submitter_did, rev_reg_id).await?;
+
+    if settings::indy_mocks_enabled() {
+        return Ok(());
+    }
+
+    let wallet = profile.inject_wallet();
+    let pending_records = wallet
+        .iterate_wallet_records(
+            CATEGORY_PENDING_REVOCATION,
+            &json!({ "rev_reg_id": rev_reg_id }).to_string(),
+            "{}",
+        )
+        .await?
+        .collect()
+        .await?;
+
+    let mut transaction = WalletTransaction::new();
+    for record in pending_records {
+        let record: serde_json::Value = serde_json::from_str(&record)?;
+        let id = record["id"].as_str().ok_or_else(|| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidJson,
+                format!("Pending revocation record is missing an id: {}", record),
+            )
+        })?;
+        transaction.delete_wallet_record(CATEGORY_PENDING_REVOCATION, id);
+    }
+
+    wallet.commit_transaction(transaction).await.map_err(|err| err.into())
 }
+
 pub async fn generate_rev_reg(
     profile: &Arc<dyn Profile>,
     issuer_did: &str,
@@ -251,21 +423,11 @@ pub async fn generate_rev_reg(
 
     let anoncreds = Arc::clone(profile).inject_anoncreds();
 
-    let (rev_reg_id, rev_reg_def_json, rev_reg_entry_json) = anoncreds
-        .issuer_create_and_store_revoc_reg(issuer_did, cred_def_id, tails_dir, max_creds, tag)
+    let (rev_reg_def, rev_reg_entry_json) = anoncreds
+        .issuer_create_and_store_revoc_reg_object(issuer_did, cred_def_id, tails_dir, max_creds, tag)
         .await?;
 
-    let rev_reg_def: RevocationRegistryDefinition = serde_json::from_str(&rev_reg_def_json).map_err(|err| {
-        AriesVcxError::from_msg(
-            AriesVcxErrorKind::SerializationError,
-            format!(
-                "Failed to deserialize rev_reg_def: {:?}, error: {:?}",
-                rev_reg_def_json, err
-            ),
-        )
-    })?;
-
-    Ok((rev_reg_id, rev_reg_def, rev_reg_entry_json))
+    Ok((rev_reg_def.id.clone(), rev_reg_def, rev_reg_entry_json))
 }
 
 // consider impl revoke_credential_local in a generic (non-vdrtools) fashion
@@ -1,35 +1,14 @@
 use std::sync::Arc;
 
+use aries_vcx_core::anoncreds::types;
+pub use aries_vcx_core::anoncreds::types::RevocationRegistryDeltaValue;
+
 use crate::core::profile::profile::Profile;
 use crate::errors::error::prelude::*;
 
 #[derive(Clone, Deserialize, Debug, Serialize, Default)]
-pub struct RevocationRegistryDelta {
-    value: RevocationRegistryDeltaValue,
-    #[serde(rename = "ver")]
-    version: String,
-}
-
-#[derive(Clone, Deserialize, Debug, Serialize, Default)]
-#[serde(rename_all = "camelCase")]
-pub struct RevocationRegistryDeltaValue {
-    prev_accum: Option<String>,
-    accum: String,
-    #[serde(default)]
-    issued: Vec<u32>,
-    #[serde(default)]
-    revoked: Vec<u32>,
-}
-
-impl RevocationRegistryDeltaValue {
-    pub fn issued(&self) -> &[u32] {
-        self.issued.as_ref()
-    }
-
-    pub fn revoked(&self) -> &[u32] {
-        self.revoked.as_ref()
-    }
-}
+#[serde(transparent)]
+pub struct RevocationRegistryDelta(types::RevocationRegistryDelta);
 
 impl RevocationRegistryDelta {
     pub async fn create_from_ledger(
@@ -39,21 +18,16 @@ impl RevocationRegistryDelta {
         to: Option<u64>,
     ) -> VcxResult<Self> {
         let ledger = Arc::clone(profile).inject_ledger();
-        let (_, rev_reg_delta_json, _) = ledger.get_rev_reg_delta_json(rev_reg_id, from, to).await?;
-        serde_json::from_str(&rev_reg_delta_json).map_err(|err| {
-            AriesVcxError::from_msg(
-                AriesVcxErrorKind::SerializationError,
-                format!("Failed to deserialize rev_reg_delta_json from ledger, err: {}", err),
-            )
-        })
+        let (_, rev_reg_delta, _) = ledger.get_rev_reg_delta_object(rev_reg_id, from, to).await?;
+        Ok(Self(rev_reg_delta))
     }
 
     pub fn issued(&self) -> &[u32] {
-        self.value.issued()
+        self.0.value.issued.as_ref()
     }
 
     pub fn revoked(&self) -> &[u32] {
-        self.value.revoked()
+        self.0.value.revoked.as_ref()
     }
 }
 
@@ -0,0 +1,307 @@
+use std::sync::Arc;
+
+use crate::core::profile::profile::Profile;
+use crate::errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult};
+use crate::handlers::util::OfferInfo;
+
+use super::revocation_registry::RevocationRegistry;
+use super::tails::TailsUploader;
+
+/// Revocation registries of a credential definition, rotated as they fill up. Every new credential is routed to
+/// the active registry. Once `rotation_threshold` credentials were issued from the active registry, the next
+/// registry is created and published ahead of time, and it becomes active when the current one is full.
+/// Issuance is counted when a credential offer is built, so an offer which is never accepted still uses up a
+/// revocation index of its registry.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RevocationRegistryPool {
+    issuer_did: String,
+    cred_def_id: String,
+    tails_dir: String,
+    max_creds: u32,
+    rotation_threshold: u32,
+    active: RevocationRegistry,
+    issued: u32,
+    next: Option<RevocationRegistry>,
+    retired_rev_reg_ids: Vec<String>,
+}
+
+impl RevocationRegistryPool {
+    /// Creates the first registry of the pool and publishes it, along with its tails file.
+    pub async fn create(
+        profile: &Arc<dyn Profile>,
+        uploader: &dyn TailsUploader,
+        issuer_did: &str,
+        cred_def_id: &str,
+        tails_dir: &str,
+        max_creds: u32,
+        rotation_threshold: u32,
+    ) -> VcxResult<Self> {
+        trace!(
+            "RevocationRegistryPool::create >>> issuer_did: {}, cred_def_id: {}, tails_dir: {}, max_creds: {}, rotation_threshold: {}",
+            issuer_did,
+            cred_def_id,
+            tails_dir,
+            max_creds,
+            rotation_threshold
+        );
+        validate_rotation_threshold(max_creds, rotation_threshold)?;
+        let mut registry =
+            RevocationRegistry::create(profile, issuer_did, cred_def_id, tails_dir, max_creds, 1).await?;
+        registry.distribute_revocation_primitives(profile, uploader).await?;
+        Self::from_registry(registry, 0, rotation_threshold)
+    }
+
+    /// Creates a pool continuing from an already published registry, out of which `issued` credentials were
+    /// already issued. Next registries are created with the same issuer, tails directory and size.
+    pub fn from_registry(registry: RevocationRegistry, issued: u32, rotation_threshold: u32) -> VcxResult<Self> {
+        validate_rotation_threshold(registry.max_creds, rotation_threshold)?;
+        if !registry.was_rev_reg_def_published() || !registry.was_rev_reg_delta_published() {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidState,
+                format!("Revocation registry {} is not published", registry.rev_reg_id),
+            ));
+        }
+        Ok(RevocationRegistryPool {
+            issuer_did: registry.get_issuer_did(),
+            cred_def_id: registry.get_cred_def_id(),
+            tails_dir: registry.get_tails_dir(),
+            max_creds: registry.max_creds,
+            rotation_threshold,
+            active: registry,
+            issued,
+            next: None,
+            retired_rev_reg_ids: vec![],
+        })
+    }
+
+    pub fn get_cred_def_id(&self) -> String {
+        self.cred_def_id.clone()
+    }
+
+    pub fn get_active_registry(&self) -> &RevocationRegistry {
+        &self.active
+    }
+
+    pub fn get_issued_count(&self) -> u32 {
+        self.issued
+    }
+
+    /// IDs of the registries which were filled up, oldest first. Credentials issued from them can still be revoked.
+    pub fn get_retired_rev_reg_ids(&self) -> &[String] {
+        &self.retired_rev_reg_ids
+    }
+
+    /// Reserves a revocation index for a new credential, rotating registries when needed, and returns the ID and
+    /// tails directory of the registry the credential must be issued from.
+    pub async fn reserve(
+        &mut self,
+        profile: &Arc<dyn Profile>,
+        uploader: &dyn TailsUploader,
+    ) -> VcxResult<(String, String)> {
+        if self.issued >= self.max_creds {
+            self.rotate(profile, uploader).await?;
+        }
+        // the index is only counted once the next registry is in place, so a failure can be retried
+        let issued = self.issued + 1;
+        if issued >= self.rotation_threshold && self.next.is_none() {
+            self.next = Some(self.create_next_registry(profile, uploader).await?);
+        }
+        self.issued = issued;
+        Ok((self.active.get_rev_reg_id(), self.active.get_tails_dir()))
+    }
+
+    /// Builds the [OfferInfo] of a new credential of this credential definition, routed to the active registry.
+    pub async fn build_offer_info(
+        &mut self,
+        profile: &Arc<dyn Profile>,
+        uploader: &dyn TailsUploader,
+        credential_json: String,
+    ) -> VcxResult<OfferInfo> {
+        let (rev_reg_id, tails_dir) = self.reserve(profile, uploader).await?;
+        Ok(OfferInfo::new(
+            credential_json,
+            self.cred_def_id.clone(),
+            Some(rev_reg_id),
+            Some(tails_dir),
+        ))
+    }
+
+    async fn rotate(&mut self, profile: &Arc<dyn Profile>, uploader: &dyn TailsUploader) -> VcxResult<()> {
+        let next = match self.next.take() {
+            Some(next) => next,
+            // the threshold was set to the registry size, the next registry is created just in time
+            None => self.create_next_registry(profile, uploader).await?,
+        };
+        let retired = std::mem::replace(&mut self.active, next);
+        info!(
+            "RevocationRegistryPool::rotate >>> revocation registry {} is full, switching to {}",
+            retired.rev_reg_id, self.active.rev_reg_id
+        );
+        self.retired_rev_reg_ids.push(retired.rev_reg_id);
+        self.issued = 0;
+        Ok(())
+    }
+
+    async fn create_next_registry(
+        &self,
+        profile: &Arc<dyn Profile>,
+        uploader: &dyn TailsUploader,
+    ) -> VcxResult<RevocationRegistry> {
+        let tag = self.next.as_ref().unwrap_or(&self.active).tag + 1;
+        let mut registry = RevocationRegistry::create(
+            profile,
+            &self.issuer_did,
+            &self.cred_def_id,
+            &self.tails_dir,
+            self.max_creds,
+            tag,
+        )
+        .await?;
+        registry.distribute_revocation_primitives(profile, uploader).await?;
+        Ok(registry)
+    }
+
+    pub fn to_string(&self) -> VcxResult<String> {
+        serde_json::to_string(&self).map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::SerializationError,
+                format!("Cannot serialize revocation registry pool: {:?}", err),
+            )
+        })
+    }
+
+    pub fn from_string(pool_data: &str) -> VcxResult<Self> {
+        serde_json::from_str(pool_data).map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidJson,
+                format!("Cannot deserialize revocation registry pool: {:?}", err),
+            )
+        })
+    }
+}
+
+fn validate_rotation_threshold(max_creds: u32, rotation_threshold: u32) -> VcxResult<()> {
+    if rotation_threshold == 0 || rotation_threshold > max_creds {
+        return Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidOption,
+            format!(
+                "Rotation threshold must be between 1 and the registry size {}, got {}",
+                max_creds, rotation_threshold
+            ),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+pub mod unit_tests {
+    use std::path::Path;
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+
+    use crate::common::test_utils::mock_profile;
+    use crate::utils::devsetup::SetupMocks;
+
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct RecordingUploader {
+        uploads: Mutex<u32>,
+    }
+
+    #[async_trait]
+    impl TailsUploader for RecordingUploader {
+        async fn upload(&self, tails_hash: &str, _tails_file_path: &Path) -> VcxResult<String> {
+            *self.uploads.lock().unwrap() += 1;
+            Ok(format!("https://tails.example.org/{}", tails_hash))
+        }
+    }
+
+    #[derive(Debug, Default)]
+    struct FailingUploader;
+
+    #[async_trait]
+    impl TailsUploader for FailingUploader {
+        async fn upload(&self, _tails_hash: &str, _tails_file_path: &Path) -> VcxResult<String> {
+            Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::IOError,
+                "Tails upload failed",
+            ))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pool_rotates_registries() {
+        let _setup = SetupMocks::init();
+        let profile = mock_profile();
+        let uploader = RecordingUploader::default();
+
+        let mut pool = RevocationRegistryPool::create(&profile, &uploader, "issuer_did", "cred_def_id", "tails", 3, 2)
+            .await
+            .unwrap();
+        assert_eq!(*uploader.uploads.lock().unwrap(), 1);
+        assert_eq!(pool.get_active_registry().tag, 1);
+
+        pool.reserve(&profile, &uploader).await.unwrap();
+        assert!(pool.next.is_none());
+
+        // threshold reached, next registry is published ahead of time
+        pool.reserve(&profile, &uploader).await.unwrap();
+        assert_eq!(*uploader.uploads.lock().unwrap(), 2);
+        assert_eq!(pool.next.as_ref().unwrap().tag, 2);
+        assert!(pool.next.as_ref().unwrap().was_rev_reg_def_published());
+
+        pool.reserve(&profile, &uploader).await.unwrap();
+        assert_eq!(pool.get_active_registry().tag, 1);
+        assert_eq!(pool.get_issued_count(), 3);
+
+        // registry full, credential is routed to the next one
+        let offer_info = pool
+            .build_offer_info(&profile, &uploader, "{}".to_string())
+            .await
+            .unwrap();
+        assert_eq!(pool.get_active_registry().tag, 2);
+        assert_eq!(pool.get_issued_count(), 1);
+        assert_eq!(pool.get_retired_rev_reg_ids().len(), 1);
+        assert_eq!(offer_info.rev_reg_id, Some(pool.get_active_registry().get_rev_reg_id()));
+        assert_eq!(offer_info.tails_file, Some("tails".to_string()));
+
+        let pool = RevocationRegistryPool::from_string(&pool.to_string().unwrap()).unwrap();
+        assert_eq!(pool.get_active_registry().tag, 2);
+    }
+
+    #[tokio::test]
+    async fn test_pool_rejects_invalid_threshold() {
+        let _setup = SetupMocks::init();
+        let profile = mock_profile();
+        let uploader = RecordingUploader::default();
+
+        let err = RevocationRegistryPool::create(&profile, &uploader, "issuer_did", "cred_def_id", "tails", 3, 4)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidOption);
+        assert_eq!(*uploader.uploads.lock().unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_pool_does_not_count_reservation_when_next_registry_fails() {
+        let _setup = SetupMocks::init();
+        let profile = mock_profile();
+        let uploader = RecordingUploader::default();
+
+        let mut pool = RevocationRegistryPool::create(&profile, &uploader, "issuer_did", "cred_def_id", "tails", 3, 2)
+            .await
+            .unwrap();
+        pool.reserve(&profile, &uploader).await.unwrap();
+
+        pool.reserve(&profile, &FailingUploader).await.unwrap_err();
+        assert_eq!(pool.get_issued_count(), 1);
+        assert!(pool.next.is_none());
+
+        pool.reserve(&profile, &uploader).await.unwrap();
+        assert_eq!(pool.get_issued_count(), 2);
+        assert_eq!(pool.next.as_ref().unwrap().tag, 2);
+    }
+}
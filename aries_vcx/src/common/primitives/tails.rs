@@ -0,0 +1,353 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use url::Url;
+
+use crate::errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult};
+
+use super::revocation_registry::RevocationRegistryDefinition;
+
+/// Name of the directory in the system temp directory where holders cache downloaded tails files by default.
+pub const DEFAULT_TAILS_CACHE_DIR: &str = "tails_cache";
+
+/// Publishes tails files of revocation registries, so that holders can download them to create revocation
+/// states.
+#[async_trait]
+pub trait TailsUploader: std::fmt::Debug + Send + Sync {
+    /// Uploads the tails file with hash `tails_hash` located at `tails_file_path`, returns the URL it can be
+    /// downloaded from.
+    async fn upload(&self, tails_hash: &str, tails_file_path: &Path) -> VcxResult<String>;
+}
+
+/// [TailsUploader] storing tails files on a tails server, with an HTTP PUT of the raw file content to
+/// `<base_url>/<tails hash>`. Files are downloaded from the same URL.
+#[derive(Debug)]
+pub struct HttpTailsUploader {
+    base_url: Url,
+    client: reqwest::Client,
+}
+
+impl HttpTailsUploader {
+    pub fn new(base_url: &str) -> VcxResult<Self> {
+        let base_url = Url::parse(base_url).map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidUrl,
+                format!("Invalid tails server URL {}: {}", base_url, err),
+            )
+        })?;
+        Ok(HttpTailsUploader {
+            base_url,
+            client: reqwest::Client::new(),
+        })
+    }
+
+    fn tails_url(&self, tails_hash: &str) -> VcxResult<Url> {
+        let mut url = self.base_url.clone();
+        url.path_segments_mut()
+            .map_err(|_| {
+                AriesVcxError::from_msg(
+                    AriesVcxErrorKind::InvalidUrl,
+                    format!("Tails server URL {} cannot be a base", self.base_url),
+                )
+            })?
+            .pop_if_empty()
+            .push(tails_hash);
+        Ok(url)
+    }
+}
+
+#[async_trait]
+impl TailsUploader for HttpTailsUploader {
+    async fn upload(&self, tails_hash: &str, tails_file_path: &Path) -> VcxResult<String> {
+        trace!(
+            "HttpTailsUploader::upload >>> tails_hash: {}, tails_file_path: {:?}",
+            tails_hash,
+            tails_file_path
+        );
+        let content = read_tails_file(tails_file_path)?;
+        verify_tails_hash(tails_hash, &content)?;
+
+        let url = self.tails_url(tails_hash)?;
+        let response = self.client.put(url.clone()).body(content).send().await.map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::PostMessageFailed,
+                format!("Failed to upload tails file to {}: {}", url, err),
+            )
+        })?;
+        if !response.status().is_success() {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidHttpResponse,
+                format!("Tails server {} rejected the tails file: {}", url, response.status()),
+            ));
+        }
+        Ok(url.to_string())
+    }
+}
+
+/// Downloads tails files on the holder side. Files are cached in `cache_dir` under their hash, so a tails file is
+/// only downloaded once and can be used as `tails_dir` for
+/// [BaseAnonCreds::create_revocation_state](aries_vcx_core::anoncreds::base_anoncreds::BaseAnonCreds::create_revocation_state).
+#[derive(Debug)]
+pub struct TailsFetcher {
+    cache_dir: PathBuf,
+    client: reqwest::Client,
+}
+
+impl TailsFetcher {
+    pub fn new(cache_dir: PathBuf) -> Self {
+        TailsFetcher {
+            cache_dir,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    pub fn get_cache_dir(&self) -> &Path {
+        &self.cache_dir
+    }
+
+    /// Makes sure the tails file of the revocation registry is in the cache, downloading it from `tailsLocation`
+    /// if needed, and returns the cache directory. Downloaded files are only cached if their content matches
+    /// `tailsHash`.
+    pub async fn fetch(&self, rev_reg_def: &RevocationRegistryDefinition) -> VcxResult<String> {
+        let tails_hash = &rev_reg_def.value.tails_hash;
+        let tails_location = &rev_reg_def.value.tails_location;
+        trace!(
+            "TailsFetcher::fetch >>> rev_reg_id: {}, tails_hash: {}, tails_location: {}",
+            rev_reg_def.id,
+            tails_hash,
+            tails_location
+        );
+        // the hash is used as file name, make sure it can't escape the cache directory
+        if tails_hash.is_empty() || !tails_hash.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidRevocationDetails,
+                format!("Invalid tails hash: {}", tails_hash),
+            ));
+        }
+
+        let tails_file_path = self.cache_dir.join(tails_hash);
+        if tails_file_path.exists() {
+            verify_tails_hash(tails_hash, &read_tails_file(&tails_file_path)?)?;
+            return Ok(self.cache_dir_string());
+        }
+
+        let url = Url::parse(tails_location).map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidUrl,
+                format!("Invalid tails location {}: {}", tails_location, err),
+            )
+        })?;
+        let response = self.client.get(url).send().await.map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::PostMessageFailed,
+                format!("Failed to download tails file from {}: {}", tails_location, err),
+            )
+        })?;
+        if !response.status().is_success() {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidHttpResponse,
+                format!(
+                    "Failed to download tails file from {}: {}",
+                    tails_location,
+                    response.status()
+                ),
+            ));
+        }
+        let content = response.bytes().await.map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidHttpResponse,
+                format!("Failed to download tails file from {}: {}", tails_location, err),
+            )
+        })?;
+        verify_tails_hash(tails_hash, &content)?;
+
+        // written under a temporary name first, so that a partially written file is never picked up by the cache
+        fs::create_dir_all(&self.cache_dir).map_err(io_err)?;
+        let tmp_file_path = self
+            .cache_dir
+            .join(format!("{}.{}.tmp", tails_hash, uuid::Uuid::new_v4()));
+        fs::write(&tmp_file_path, &content).map_err(io_err)?;
+        fs::rename(&tmp_file_path, &tails_file_path).map_err(io_err)?;
+
+        Ok(self.cache_dir_string())
+    }
+
+    fn cache_dir_string(&self) -> String {
+        self.cache_dir.to_string_lossy().to_string()
+    }
+}
+
+/// Hash of a tails file as found in the `tailsHash` of revocation registry definitions: base58 encoded SHA-256 of
+/// the file content.
+pub fn tails_hash(content: &[u8]) -> String {
+    bs58::encode(openssl::sha::sha256(content)).into_string()
+}
+
+fn verify_tails_hash(expected_hash: &str, content: &[u8]) -> VcxResult<()> {
+    let actual_hash = tails_hash(content);
+    if actual_hash != expected_hash {
+        return Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidRevocationDetails,
+            format!(
+                "Tails file hash mismatch, expected: {}, actual: {}",
+                expected_hash, actual_hash
+            ),
+        ));
+    }
+    Ok(())
+}
+
+fn read_tails_file(tails_file_path: &Path) -> VcxResult<Vec<u8>> {
+    fs::read(tails_file_path).map_err(|err| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::IOError,
+            format!("Cannot read tails file {:?}: {}", tails_file_path, err),
+        )
+    })
+}
+
+fn io_err(err: std::io::Error) -> AriesVcxError {
+    AriesVcxError::from_msg(
+        AriesVcxErrorKind::IOError,
+        format!("Cannot write tails file to cache: {}", err),
+    )
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+pub mod unit_tests {
+    use std::collections::HashMap;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::sync::{Arc, Mutex};
+
+    use crate::utils::get_temp_dir_path;
+
+    use super::*;
+
+    // minimal stand-in for a tails server, storing PUT bodies and serving them on GET
+    fn start_tails_server(files: Arc<Mutex<HashMap<String, Vec<u8>>>>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                handle_request(stream.unwrap(), &files);
+            }
+        });
+        format!("http://{}/tails", address)
+    }
+
+    fn handle_request(mut stream: TcpStream, files: &Mutex<HashMap<String, Vec<u8>>>) {
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).unwrap();
+        let mut content_length = 0;
+        loop {
+            let mut header = String::new();
+            reader.read_line(&mut header).unwrap();
+            if header.trim().is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+        }
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).unwrap();
+
+        let mut parts = request_line.split_whitespace();
+        let (method, path) = (parts.next().unwrap(), parts.next().unwrap().to_string());
+        let (status, body) = match method {
+            "PUT" => {
+                files.lock().unwrap().insert(path, body);
+                ("200 OK", vec![])
+            }
+            _ => match files.lock().unwrap().get(&path) {
+                Some(content) => ("200 OK", content.clone()),
+                None => ("404 Not Found", vec![]),
+            },
+        };
+        write!(
+            stream,
+            "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            status,
+            body.len()
+        )
+        .unwrap();
+        stream.write_all(&body).unwrap();
+    }
+
+    fn write_tails_file(dir: &str, content: &[u8]) -> (String, PathBuf) {
+        let hash = tails_hash(content);
+        let path = get_temp_dir_path(dir).join(&hash);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, content).unwrap();
+        (hash, path)
+    }
+
+    fn rev_reg_def(tails_hash: &str, tails_location: &str) -> RevocationRegistryDefinition {
+        let mut rev_reg_def = RevocationRegistryDefinition::default();
+        rev_reg_def.id = "rev_reg_id".to_string();
+        rev_reg_def.value.tails_hash = tails_hash.to_string();
+        rev_reg_def.value.tails_location = tails_location.to_string();
+        rev_reg_def
+    }
+
+    #[tokio::test]
+    async fn test_upload_and_fetch_tails_file() {
+        let files = Arc::new(Mutex::new(HashMap::new()));
+        let server_url = start_tails_server(Arc::clone(&files));
+        let content = format!("tails {}", uuid::Uuid::new_v4()).into_bytes();
+        let (hash, path) = write_tails_file("tails_upload", &content);
+
+        let tails_url = HttpTailsUploader::new(&server_url)
+            .unwrap()
+            .upload(&hash, &path)
+            .await
+            .unwrap();
+        assert_eq!(tails_url, format!("{}/{}", server_url, hash));
+
+        let cache_dir = get_temp_dir_path(&format!("tails_cache_{}", uuid::Uuid::new_v4()));
+        let fetcher = TailsFetcher::new(cache_dir.clone());
+        let tails_dir = fetcher.fetch(&rev_reg_def(&hash, &tails_url)).await.unwrap();
+        assert_eq!(PathBuf::from(&tails_dir), cache_dir);
+        assert_eq!(fs::read(cache_dir.join(&hash)).unwrap(), content);
+
+        // served from the cache once downloaded
+        files.lock().unwrap().clear();
+        assert_eq!(fetcher.fetch(&rev_reg_def(&hash, &tails_url)).await.unwrap(), tails_dir);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_rejects_tails_file_with_wrong_hash() {
+        let files = Arc::new(Mutex::new(HashMap::new()));
+        let server_url = start_tails_server(Arc::clone(&files));
+        let hash = tails_hash(b"expected content");
+        files
+            .lock()
+            .unwrap()
+            .insert(format!("/tails/{}", hash), b"tampered content".to_vec());
+
+        let cache_dir = get_temp_dir_path(&format!("tails_cache_{}", uuid::Uuid::new_v4()));
+        let err = TailsFetcher::new(cache_dir.clone())
+            .fetch(&rev_reg_def(&hash, &format!("{}/{}", server_url, hash)))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidRevocationDetails);
+        assert!(!cache_dir.join(&hash).exists());
+    }
+
+    #[tokio::test]
+    async fn test_upload_rejects_tails_file_with_wrong_hash() {
+        let (_, path) = write_tails_file("tails_upload", b"some content");
+        let err = HttpTailsUploader::new("http://127.0.0.1:1/tails")
+            .unwrap()
+            .upload(&tails_hash(b"other content"), &path)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidRevocationDetails);
+    }
+}
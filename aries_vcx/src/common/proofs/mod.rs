@@ -1,4 +1,5 @@
 pub mod proof_request;
+pub mod proof_request_builder;
 pub mod proof_request_internal;
 pub mod prover;
 pub mod verifier;
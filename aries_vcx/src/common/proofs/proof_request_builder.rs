@@ -0,0 +1,502 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+pub use aries_vcx_core::anoncreds::types::PredicateType;
+use serde_json::{Map, Value};
+
+use crate::core::profile::profile::Profile;
+use crate::errors::error::prelude::*;
+
+use super::proof_request::ProofRequestData;
+use super::proof_request_internal::{AttrInfo, NonRevokedInterval, PredicateInfo, Restrictions};
+
+/// Conditions a credential must all meet. Several restrictions on an attribute or predicate are alternatives, the
+/// credential must meet at least one of them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Restriction {
+    conditions: Vec<(String, String)>,
+}
+
+impl Restriction {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn schema_id(self, schema_id: &str) -> Self {
+        self.condition("schema_id", schema_id)
+    }
+
+    pub fn schema_issuer_did(self, schema_issuer_did: &str) -> Self {
+        self.condition("schema_issuer_did", schema_issuer_did)
+    }
+
+    pub fn schema_name(self, schema_name: &str) -> Self {
+        self.condition("schema_name", schema_name)
+    }
+
+    pub fn schema_version(self, schema_version: &str) -> Self {
+        self.condition("schema_version", schema_version)
+    }
+
+    pub fn issuer_did(self, issuer_did: &str) -> Self {
+        self.condition("issuer_did", issuer_did)
+    }
+
+    pub fn cred_def_id(self, cred_def_id: &str) -> Self {
+        self.condition("cred_def_id", cred_def_id)
+    }
+
+    /// Requires the credential attribute `attr_name` to have the raw value `value`.
+    pub fn attr_value(self, attr_name: &str, value: &str) -> Self {
+        self.condition(&format!("attr::{}::value", attr_name), value)
+    }
+
+    fn condition(mut self, key: &str, value: &str) -> Self {
+        self.conditions.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    fn validate(&self) -> VcxResult<()> {
+        if self.conditions.is_empty() {
+            return Err(invalid_proof_request("Restriction must contain at least one condition"));
+        }
+        let mut keys = HashSet::new();
+        for (key, value) in self.conditions.iter() {
+            if !keys.insert(key) {
+                return Err(invalid_proof_request(format!(
+                    "Restriction contains condition {} more than once",
+                    key
+                )));
+            }
+            if value.is_empty() {
+                return Err(invalid_proof_request(format!(
+                    "Restriction condition {} has an empty value",
+                    key
+                )));
+            }
+            if let Some(attr_name) = key.strip_prefix("attr::").and_then(|key| key.strip_suffix("::value")) {
+                if attr_name.is_empty() || attr_name.contains("::") {
+                    return Err(invalid_proof_request(format!(
+                        "Invalid attribute name in restriction condition {}",
+                        key
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Object(
+            self.conditions
+                .iter()
+                .map(|(key, value)| (key.clone(), Value::String(value.clone())))
+                .collect::<Map<String, Value>>(),
+        )
+    }
+}
+
+fn build_restrictions(restrictions: &[Restriction]) -> VcxResult<Option<Restrictions>> {
+    if restrictions.is_empty() {
+        return Ok(None);
+    }
+    for restriction in restrictions {
+        restriction.validate()?;
+    }
+    Ok(Some(Restrictions::V2(Value::Array(
+        restrictions.iter().map(Restriction::to_value).collect(),
+    ))))
+}
+
+fn build_interval(from: Option<u64>, to: Option<u64>) -> VcxResult<NonRevokedInterval> {
+    match (from, to) {
+        (None, None) => Err(invalid_proof_request(
+            "Non-revocation interval must have at least one bound",
+        )),
+        (Some(from), Some(to)) if from > to => Err(invalid_proof_request(format!(
+            "Non-revocation interval starts at {} after it ends at {}",
+            from, to
+        ))),
+        (from, to) => Ok(NonRevokedInterval { from, to }),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestedAttribute {
+    names: Vec<String>,
+    group: bool,
+    restrictions: Vec<Restriction>,
+    non_revoked: Option<(Option<u64>, Option<u64>)>,
+    self_attest_allowed: Option<bool>,
+}
+
+impl RequestedAttribute {
+    /// Requests a single attribute.
+    pub fn name(name: &str) -> Self {
+        Self::new(vec![name.to_string()], false)
+    }
+
+    /// Requests a group of attributes, which must all be disclosed from the same credential.
+    pub fn names(names: &[&str]) -> Self {
+        Self::new(names.iter().map(|name| name.to_string()).collect(), true)
+    }
+
+    fn new(names: Vec<String>, group: bool) -> Self {
+        Self {
+            names,
+            group,
+            restrictions: vec![],
+            non_revoked: None,
+            self_attest_allowed: None,
+        }
+    }
+
+    pub fn restriction(mut self, restriction: Restriction) -> Self {
+        self.restrictions.push(restriction);
+        self
+    }
+
+    pub fn non_revoked(mut self, from: Option<u64>, to: Option<u64>) -> Self {
+        self.non_revoked = Some((from, to));
+        self
+    }
+
+    pub fn self_attest_allowed(mut self, self_attest_allowed: bool) -> Self {
+        self.self_attest_allowed = Some(self_attest_allowed);
+        self
+    }
+
+    fn build(&self, referent: &str) -> VcxResult<AttrInfo> {
+        if self.names.is_empty() || self.names.iter().any(|name| name.is_empty()) {
+            return Err(invalid_proof_request(format!(
+                "Requested attribute {} must have non-empty attribute names",
+                referent
+            )));
+        }
+        if self.names.iter().collect::<HashSet<_>>().len() != self.names.len() {
+            return Err(invalid_proof_request(format!(
+                "Requested attribute {} contains an attribute name more than once",
+                referent
+            )));
+        }
+        let (name, names) = if self.group {
+            (None, Some(self.names.clone()))
+        } else {
+            (Some(self.names[0].clone()), None)
+        };
+        Ok(AttrInfo {
+            name,
+            names,
+            restrictions: build_restrictions(&self.restrictions)?,
+            non_revoked: self
+                .non_revoked
+                .map(|(from, to)| build_interval(from, to))
+                .transpose()?,
+            self_attest_allowed: self.self_attest_allowed,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestedPredicate {
+    name: String,
+    p_type: PredicateType,
+    p_value: i32,
+    restrictions: Vec<Restriction>,
+    non_revoked: Option<(Option<u64>, Option<u64>)>,
+}
+
+impl RequestedPredicate {
+    pub fn new(name: &str, p_type: PredicateType, p_value: i32) -> Self {
+        Self {
+            name: name.to_string(),
+            p_type,
+            p_value,
+            restrictions: vec![],
+            non_revoked: None,
+        }
+    }
+
+    pub fn restriction(mut self, restriction: Restriction) -> Self {
+        self.restrictions.push(restriction);
+        self
+    }
+
+    pub fn non_revoked(mut self, from: Option<u64>, to: Option<u64>) -> Self {
+        self.non_revoked = Some((from, to));
+        self
+    }
+
+    fn build(&self, referent: &str) -> VcxResult<PredicateInfo> {
+        if self.name.is_empty() {
+            return Err(invalid_proof_request(format!(
+                "Requested predicate {} must have a non-empty attribute name",
+                referent
+            )));
+        }
+        Ok(PredicateInfo {
+            name: self.name.clone(),
+            p_type: self.p_type.as_str().to_string(),
+            p_value: self.p_value,
+            restrictions: build_restrictions(&self.restrictions)?,
+            non_revoked: self
+                .non_revoked
+                .map(|(from, to)| build_interval(from, to))
+                .transpose()?,
+        })
+    }
+}
+
+/// Typed alternative to building [ProofRequestData] out of raw JSON. All the requested items are validated by
+/// [ProofRequestBuilder::build].
+#[derive(Debug, Clone, Default)]
+pub struct ProofRequestBuilder {
+    name: String,
+    nonce: Option<String>,
+    attributes: Vec<(String, RequestedAttribute)>,
+    predicates: Vec<(String, RequestedPredicate)>,
+    non_revoked: Option<(Option<u64>, Option<u64>)>,
+}
+
+impl ProofRequestBuilder {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Self::default()
+        }
+    }
+
+    pub fn nonce(mut self, nonce: &str) -> Self {
+        self.nonce = Some(nonce.to_string());
+        self
+    }
+
+    pub fn attribute(mut self, referent: &str, attribute: RequestedAttribute) -> Self {
+        self.attributes.push((referent.to_string(), attribute));
+        self
+    }
+
+    pub fn predicate(mut self, referent: &str, predicate: RequestedPredicate) -> Self {
+        self.predicates.push((referent.to_string(), predicate));
+        self
+    }
+
+    /// Non-revocation interval applying to all the requested items which don't specify their own.
+    pub fn non_revoked(mut self, from: Option<u64>, to: Option<u64>) -> Self {
+        self.non_revoked = Some((from, to));
+        self
+    }
+
+    /// Builds the proof request, which requires a nonce to have been set. Use
+    /// [ProofRequestBuilder::build_with_profile] to have one generated.
+    pub fn build(self) -> VcxResult<ProofRequestData> {
+        let nonce = match self.nonce {
+            Some(nonce) if !nonce.is_empty() => nonce,
+            _ => return Err(invalid_proof_request("Proof request must have a non-empty nonce")),
+        };
+
+        let mut referents = HashSet::new();
+        let all_referents = self
+            .attributes
+            .iter()
+            .map(|(referent, _)| referent)
+            .chain(self.predicates.iter().map(|(referent, _)| referent));
+        for referent in all_referents {
+            if referent.is_empty() {
+                return Err(invalid_proof_request("Referent must not be empty"));
+            }
+            if !referents.insert(referent) {
+                return Err(invalid_proof_request(format!(
+                    "Referent {} is used more than once",
+                    referent
+                )));
+            }
+        }
+
+        let requested_attributes = self
+            .attributes
+            .iter()
+            .map(|(referent, attribute)| Ok((referent.clone(), attribute.build(referent)?)))
+            .collect::<VcxResult<HashMap<_, _>>>()?;
+        let requested_predicates = self
+            .predicates
+            .iter()
+            .map(|(referent, predicate)| Ok((referent.clone(), predicate.build(referent)?)))
+            .collect::<VcxResult<HashMap<_, _>>>()?;
+
+        Ok(ProofRequestData {
+            nonce,
+            name: self.name,
+            requested_attributes,
+            requested_predicates,
+            non_revoked: self
+                .non_revoked
+                .map(|(from, to)| build_interval(from, to))
+                .transpose()?,
+            ..ProofRequestData::default()
+        })
+    }
+
+    /// Builds the proof request with a nonce generated by the profile, unless one was set.
+    pub async fn build_with_profile(mut self, profile: &Arc<dyn Profile>) -> VcxResult<ProofRequestData> {
+        if self.nonce.is_none() {
+            self.nonce = Some(Arc::clone(profile).inject_anoncreds().generate_nonce().await?);
+        }
+        self.build()
+    }
+}
+
+fn invalid_proof_request<D>(msg: D) -> AriesVcxError
+where
+    D: std::fmt::Display + std::fmt::Debug + Send + Sync + 'static,
+{
+    AriesVcxError::from_msg(AriesVcxErrorKind::InvalidProofRequest, msg)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use crate::common::test_utils::mock_profile;
+    use crate::utils::devsetup::SetupDefaults;
+
+    use super::*;
+
+    #[test]
+    fn test_build_proof_request() {
+        let request = ProofRequestBuilder::new("Test")
+            .nonce("123")
+            .attribute(
+                "attr_name",
+                RequestedAttribute::name("name").restriction(
+                    Restriction::new()
+                        .cred_def_id("V4SGRU86Z58d6TV7PBUe6f:3:CL:1:tag")
+                        .attr_value("name", "Alice"),
+                ),
+            )
+            .attribute(
+                "attr_address",
+                RequestedAttribute::names(&["street", "city"])
+                    .restriction(Restriction::new().issuer_did("V4SGRU86Z58d6TV7PBUe6f"))
+                    .restriction(Restriction::new().schema_id("V4SGRU86Z58d6TV7PBUe6f:2:address:1.0"))
+                    .non_revoked(None, Some(1600000000)),
+            )
+            .predicate(
+                "pred_age",
+                RequestedPredicate::new("age", PredicateType::GreaterOrEqual, 18),
+            )
+            .non_revoked(Some(1100000000), Some(1600000000))
+            .build()
+            .unwrap();
+
+        let request: Value = serde_json::to_value(&request).unwrap();
+        assert_eq!(
+            request,
+            json!({
+                "nonce": "123",
+                "name": "Test",
+                "version": "1.0",
+                "requested_attributes": {
+                    "attr_name": {
+                        "name": "name",
+                        "restrictions": [{
+                            "cred_def_id": "V4SGRU86Z58d6TV7PBUe6f:3:CL:1:tag",
+                            "attr::name::value": "Alice"
+                        }]
+                    },
+                    "attr_address": {
+                        "names": ["street", "city"],
+                        "restrictions": [
+                            { "issuer_did": "V4SGRU86Z58d6TV7PBUe6f" },
+                            { "schema_id": "V4SGRU86Z58d6TV7PBUe6f:2:address:1.0" }
+                        ],
+                        "non_revoked": { "from": null, "to": 1600000000 }
+                    }
+                },
+                "requested_predicates": {
+                    "pred_age": { "name": "age", "p_type": ">=", "p_value": 18 }
+                },
+                "non_revoked": { "from": 1100000000, "to": 1600000000 }
+            })
+        );
+    }
+
+    #[test]
+    fn test_build_rejects_duplicate_referents() {
+        let err = ProofRequestBuilder::new("Test")
+            .nonce("123")
+            .attribute("age", RequestedAttribute::name("age"))
+            .predicate("age", RequestedPredicate::new("age", PredicateType::Greater, 18))
+            .build()
+            .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidProofRequest);
+    }
+
+    #[test]
+    fn test_build_rejects_malformed_restrictions() {
+        let build_with = |restriction: Restriction| {
+            ProofRequestBuilder::new("Test")
+                .nonce("123")
+                .attribute("name", RequestedAttribute::name("name").restriction(restriction))
+                .build()
+        };
+
+        assert!(build_with(Restriction::new()).is_err());
+        assert!(build_with(Restriction::new().issuer_did("")).is_err());
+        assert!(build_with(Restriction::new().issuer_did("a").issuer_did("b")).is_err());
+        assert!(build_with(Restriction::new().attr_value("", "Alice")).is_err());
+        assert!(build_with(Restriction::new().attr_value("first::name", "Alice")).is_err());
+        assert!(build_with(Restriction::new().attr_value("name", "Alice")).is_ok());
+    }
+
+    #[test]
+    fn test_build_rejects_invalid_items() {
+        assert!(ProofRequestBuilder::new("Test")
+            .nonce("123")
+            .attribute("names", RequestedAttribute::names(&[]))
+            .build()
+            .is_err());
+        assert!(ProofRequestBuilder::new("Test")
+            .nonce("123")
+            .attribute("names", RequestedAttribute::names(&["name", "name"]))
+            .build()
+            .is_err());
+        assert!(ProofRequestBuilder::new("Test")
+            .nonce("123")
+            .attribute("name", RequestedAttribute::name("name").non_revoked(Some(2), Some(1)))
+            .build()
+            .is_err());
+        assert!(ProofRequestBuilder::new("Test")
+            .nonce("123")
+            .non_revoked(None, None)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_build_requires_nonce() {
+        let err = ProofRequestBuilder::new("Test")
+            .attribute("name", RequestedAttribute::name("name"))
+            .build()
+            .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidProofRequest);
+
+        let err = ProofRequestBuilder::new("Test")
+            .nonce("")
+            .attribute("name", RequestedAttribute::name("name"))
+            .build()
+            .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidProofRequest);
+    }
+
+    #[tokio::test]
+    async fn test_build_with_profile_generates_nonce() {
+        let _setup = SetupDefaults::init();
+
+        let request = ProofRequestBuilder::new("Test")
+            .attribute("name", RequestedAttribute::name("name"))
+            .build_with_profile(&mock_profile())
+            .await
+            .unwrap();
+        assert!(!request.nonce.is_empty());
+    }
+}
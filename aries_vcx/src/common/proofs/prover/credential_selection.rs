@@ -0,0 +1,431 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use serde_json::Value;
+
+use crate::common::credentials::revocation_status::get_revoked_credential_ids;
+use crate::common::credentials::storage_time::get_credential_storage_times;
+use crate::common::proofs::proof_request::ProofRequestData;
+use crate::core::profile::profile::Profile;
+use crate::errors::error::prelude::*;
+use crate::utils::qualifier;
+
+/// Criterion ranking the credentials able to satisfy a referent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SelectionCriterion {
+    /// Prefers credentials stored more recently, going by the time recorded by
+    /// [crate::common::credentials::storage_time::record_credential_stored]. Credentials without a recorded time are
+    /// considered the oldest.
+    Newest,
+    /// Prefers credentials already used for another referent, then credentials able to satisfy the most referents,
+    /// to disclose as few distinct credentials as possible and so limit correlation.
+    FewestCredentials,
+    /// Prefers credentials of the issuers listed, in the order listed, over credentials of other issuers.
+    IssuerPreference(Vec<String>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelectionPolicy {
+    /// Leaves out credentials found revoked by
+    /// [crate::common::credentials::revocation_status::check_credentials_revocation_status].
+    pub exclude_revoked: bool,
+    /// Criteria applied in order, the next one only breaking ties of the previous ones. Candidates still tied
+    /// after all criteria are picked in the order the wallet listed them.
+    pub criteria: Vec<SelectionCriterion>,
+}
+
+impl Default for SelectionPolicy {
+    fn default() -> Self {
+        Self {
+            exclude_revoked: true,
+            criteria: vec![SelectionCriterion::FewestCredentials, SelectionCriterion::Newest],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnsatisfiedReason {
+    /// No credential in the wallet meets the restrictions of the referent.
+    NoMatchingCredential,
+    /// All the credentials meeting the restrictions of the referent were revoked.
+    AllCandidatesRevoked { revoked: Vec<String> },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnsatisfiedReferent {
+    pub referent: String,
+    pub reason: UnsatisfiedReason,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CredentialSelection {
+    /// ID of the credential selected, by referent.
+    pub selected: HashMap<String, String>,
+    pub unsatisfied: Vec<UnsatisfiedReferent>,
+    selected_credentials: Value,
+}
+
+impl CredentialSelection {
+    pub fn is_complete(&self) -> bool {
+        self.unsatisfied.is_empty()
+    }
+
+    /// Selected credentials in the format expected by `Prover::generate_presentation`.
+    pub fn selected_credentials(&self) -> String {
+        self.selected_credentials.to_string()
+    }
+}
+
+struct Candidate<'a> {
+    cred_id: &'a str,
+    issuer_did: &'a str,
+    stored_at: Option<u64>,
+    value: &'a Value,
+}
+
+/// Picks a credential for every referent of the proof request out of `retrieved_credentials`, as returned by
+/// `BaseAnonCreds::prover_get_credentials_for_proof_req`. Referents which can't be satisfied are reported along with
+/// the reason, and left out of the selected credentials. `revoked` holds the ids of the credentials known to be
+/// revoked, `stored_at` the times the credentials were stored at, by credential id.
+pub fn select_credentials(
+    retrieved_credentials: &str,
+    proof_request: &ProofRequestData,
+    policy: &SelectionPolicy,
+    revoked: &HashSet<String>,
+    stored_at: &HashMap<String, u64>,
+) -> VcxResult<CredentialSelection> {
+    trace!(
+        "select_credentials >>> retrieved_credentials: {}, policy: {:?}",
+        secret!(retrieved_credentials),
+        policy
+    );
+    let retrieved: Value = serde_json::from_str(retrieved_credentials).map_err(|err| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidJson,
+            format!("Cannot deserialize retrieved credentials: {}", err),
+        )
+    })?;
+
+    let referents = proof_request
+        .requested_attributes
+        .keys()
+        .map(|referent| (referent, &retrieved["attrs"][referent]))
+        .chain(
+            proof_request
+                .requested_predicates
+                .keys()
+                .map(|referent| (referent, &retrieved["predicates"][referent])),
+        );
+
+    let mut selection = CredentialSelection::default();
+    let mut candidates_by_referent = Vec::new();
+    for (referent, candidates) in referents {
+        let candidates = parse_candidates(referent, candidates, stored_at)?;
+        let (revoked_candidates, candidates): (Vec<_>, Vec<_>) = candidates
+            .into_iter()
+            .partition(|candidate| policy.exclude_revoked && revoked.contains(candidate.cred_id));
+        if candidates.is_empty() {
+            let reason = if revoked_candidates.is_empty() {
+                UnsatisfiedReason::NoMatchingCredential
+            } else {
+                UnsatisfiedReason::AllCandidatesRevoked {
+                    revoked: revoked_candidates.iter().map(|c| c.cred_id.to_string()).collect(),
+                }
+            };
+            selection.unsatisfied.push(UnsatisfiedReferent {
+                referent: referent.clone(),
+                reason,
+            });
+        } else {
+            candidates_by_referent.push((referent, candidates));
+        }
+    }
+    selection.unsatisfied.sort_by(|a, b| a.referent.cmp(&b.referent));
+
+    let mut coverage: HashMap<&str, usize> = HashMap::new();
+    for (_, candidates) in candidates_by_referent.iter() {
+        for candidate in candidates {
+            *coverage.entry(candidate.cred_id).or_default() += 1;
+        }
+    }
+
+    // most constrained referents first, so that their choice drives the choice for the others
+    candidates_by_referent
+        .sort_by(|(referent_a, a), (referent_b, b)| a.len().cmp(&b.len()).then_with(|| referent_a.cmp(referent_b)));
+
+    let mut selected_credentials = serde_json::Map::new();
+    let mut used: HashSet<&str> = HashSet::new();
+    for (referent, candidates) in candidates_by_referent.iter() {
+        let best = candidates
+            .iter()
+            .reduce(
+                |best, candidate| match compare_candidates(candidate, best, &policy.criteria, &used, &coverage) {
+                    Ordering::Greater => candidate,
+                    _ => best,
+                },
+            )
+            .ok_or_else(|| {
+                AriesVcxError::from_msg(
+                    AriesVcxErrorKind::InvalidState,
+                    format!("No candidate left for referent {}", referent),
+                )
+            })?;
+        used.insert(best.cred_id);
+        selection
+            .selected
+            .insert(referent.to_string(), best.cred_id.to_string());
+        selected_credentials.insert(referent.to_string(), json!({ "credential": best.value }));
+    }
+    selection.selected_credentials = json!({ "attrs": selected_credentials });
+
+    Ok(selection)
+}
+
+/// Same as [select_credentials], using the credentials marked as revoked and the storage times recorded in the wallet
+/// of the profile.
+pub async fn select_credentials_for_profile(
+    profile: &Arc<dyn Profile>,
+    retrieved_credentials: &str,
+    proof_request: &ProofRequestData,
+    policy: &SelectionPolicy,
+) -> VcxResult<CredentialSelection> {
+    let revoked = if policy.exclude_revoked {
+        get_revoked_credential_ids(profile).await?
+    } else {
+        HashSet::new()
+    };
+    let stored_at = if policy.criteria.contains(&SelectionCriterion::Newest) {
+        get_credential_storage_times(profile).await?
+    } else {
+        HashMap::new()
+    };
+    select_credentials(retrieved_credentials, proof_request, policy, &revoked, &stored_at)
+}
+
+fn parse_candidates<'a>(
+    referent: &str,
+    candidates: &'a Value,
+    stored_at: &HashMap<String, u64>,
+) -> VcxResult<Vec<Candidate<'a>>> {
+    let candidates = match candidates {
+        Value::Null => return Ok(vec![]),
+        Value::Array(candidates) => candidates,
+        _ => {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidJson,
+                format!("Retrieved credentials for referent {} are not a list", referent),
+            ))
+        }
+    };
+    candidates
+        .iter()
+        .map(|value| {
+            match (
+                value["cred_info"]["referent"].as_str(),
+                value["cred_info"]["cred_def_id"].as_str(),
+            ) {
+                (Some(cred_id), Some(cred_def_id)) => Ok(Candidate {
+                    cred_id,
+                    issuer_did: qualifier::issuer_did(cred_def_id),
+                    stored_at: stored_at.get(cred_id).copied(),
+                    value,
+                }),
+                _ => Err(AriesVcxError::from_msg(
+                    AriesVcxErrorKind::InvalidProofCredentialData,
+                    format!("Retrieved credential for referent {} has no identifiers", referent),
+                )),
+            }
+        })
+        .collect()
+}
+
+// Ordering::Greater if `a` is a better choice than `b`
+fn compare_candidates(
+    a: &Candidate,
+    b: &Candidate,
+    criteria: &[SelectionCriterion],
+    used: &HashSet<&str>,
+    coverage: &HashMap<&str, usize>,
+) -> Ordering {
+    let issuer_rank = |candidate: &Candidate, issuers: &[String]| {
+        issuers
+            .iter()
+            .position(|issuer| issuer == candidate.issuer_did)
+            .unwrap_or(issuers.len())
+    };
+    criteria.iter().fold(Ordering::Equal, |ordering, criterion| {
+        ordering.then_with(|| match criterion {
+            SelectionCriterion::Newest => a.stored_at.cmp(&b.stored_at),
+            SelectionCriterion::FewestCredentials => used
+                .contains(a.cred_id)
+                .cmp(&used.contains(b.cred_id))
+                .then_with(|| coverage.get(a.cred_id).cmp(&coverage.get(b.cred_id))),
+            SelectionCriterion::IssuerPreference(issuers) => issuer_rank(b, issuers).cmp(&issuer_rank(a, issuers)),
+        })
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+pub mod unit_tests {
+    use super::*;
+
+    fn _candidate(cred_id: &str, issuer_did: &str) -> Value {
+        json!({
+            "cred_info": {
+                "referent": cred_id,
+                "attrs": {},
+                "schema_id": format!("{}:2:schema:1.0", issuer_did),
+                "cred_def_id": format!("{}:3:CL:1:tag", issuer_did),
+                "rev_reg_id": null,
+                "cred_rev_id": null
+            },
+            "interval": null
+        })
+    }
+
+    fn _proof_request() -> ProofRequestData {
+        serde_json::from_value(json!({
+            "nonce": "123432421212",
+            "name": "proof_req_1",
+            "version": "1.0",
+            "requested_attributes": {
+                "name": { "name": "name" },
+                "address": { "names": ["street", "city"] }
+            },
+            "requested_predicates": {
+                "age": { "name": "age", "p_type": ">=", "p_value": 18 }
+            }
+        }))
+        .unwrap()
+    }
+
+    fn _retrieved_credentials() -> String {
+        json!({
+            "attrs": {
+                "name": [_candidate("passport", "issuer_1"), _candidate("licence", "issuer_2")],
+                "address": [_candidate("utility_bill", "issuer_3"), _candidate("licence", "issuer_2")]
+            },
+            "predicates": {
+                "age": [_candidate("passport", "issuer_1"), _candidate("licence", "issuer_2")]
+            }
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_select_fewest_credentials() {
+        let selection = select_credentials(
+            &_retrieved_credentials(),
+            &_proof_request(),
+            &SelectionPolicy::default(),
+            &HashSet::new(),
+            &HashMap::new(),
+        )
+        .unwrap();
+
+        assert!(selection.is_complete());
+        assert_eq!(selection.selected["name"], "licence");
+        assert_eq!(selection.selected["address"], "licence");
+        assert_eq!(selection.selected["age"], "licence");
+
+        let selected: Value = serde_json::from_str(&selection.selected_credentials()).unwrap();
+        assert_eq!(
+            selected["attrs"]["age"]["credential"]["cred_info"]["referent"],
+            "licence"
+        );
+    }
+
+    #[test]
+    fn test_select_by_issuer_preference() {
+        let policy = SelectionPolicy {
+            exclude_revoked: true,
+            criteria: vec![SelectionCriterion::IssuerPreference(vec![
+                "issuer_3".to_string(),
+                "issuer_1".to_string(),
+            ])],
+        };
+        let selection = select_credentials(
+            &_retrieved_credentials(),
+            &_proof_request(),
+            &policy,
+            &HashSet::new(),
+            &HashMap::new(),
+        )
+        .unwrap();
+
+        assert_eq!(selection.selected["name"], "passport");
+        assert_eq!(selection.selected["address"], "utility_bill");
+        assert_eq!(selection.selected["age"], "passport");
+    }
+
+    #[test]
+    fn test_select_newest() {
+        let policy = SelectionPolicy {
+            exclude_revoked: false,
+            criteria: vec![SelectionCriterion::Newest],
+        };
+        let stored_at = HashMap::from([
+            ("passport".to_string(), 1_700_000_200),
+            ("licence".to_string(), 1_700_000_100),
+        ]);
+        let selection = select_credentials(
+            &_retrieved_credentials(),
+            &_proof_request(),
+            &policy,
+            &HashSet::new(),
+            &stored_at,
+        )
+        .unwrap();
+
+        assert_eq!(selection.selected["name"], "passport");
+        assert_eq!(selection.selected["age"], "passport");
+        // utility_bill has no recorded storage time
+        assert_eq!(selection.selected["address"], "licence");
+    }
+
+    #[test]
+    fn test_select_explains_unsatisfied_referents() {
+        let retrieved = json!({
+            "attrs": {
+                "name": [_candidate("passport", "issuer_1")],
+                "address": []
+            },
+            "predicates": {
+                "age": [_candidate("passport", "issuer_1"), _candidate("licence", "issuer_2")]
+            }
+        })
+        .to_string();
+        let revoked = HashSet::from(["passport".to_string()]);
+
+        let selection = select_credentials(
+            &retrieved,
+            &_proof_request(),
+            &SelectionPolicy::default(),
+            &revoked,
+            &HashMap::new(),
+        )
+        .unwrap();
+
+        assert!(!selection.is_complete());
+        assert_eq!(selection.selected.len(), 1);
+        assert_eq!(selection.selected["age"], "licence");
+        assert_eq!(
+            selection.unsatisfied,
+            vec![
+                UnsatisfiedReferent {
+                    referent: "address".to_string(),
+                    reason: UnsatisfiedReason::NoMatchingCredential,
+                },
+                UnsatisfiedReferent {
+                    referent: "name".to_string(),
+                    reason: UnsatisfiedReason::AllCandidatesRevoked {
+                        revoked: vec!["passport".to_string()]
+                    },
+                },
+            ]
+        );
+    }
+}
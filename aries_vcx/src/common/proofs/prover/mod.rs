@@ -1,2 +1,3 @@
+pub mod credential_selection;
 pub mod prover;
 mod prover_internal;
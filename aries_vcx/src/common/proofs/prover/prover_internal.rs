@@ -1,9 +1,12 @@
 use std::{collections::HashMap, sync::Arc};
 
-use aries_vcx_core::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind};
+use aries_vcx_core::anoncreds::types::{AnonCredsObject, RevocationRegistryDefinition};
+use aries_vcx_core::errors::error::AriesVcxCoreErrorKind;
 use serde_json::Value;
 
+use crate::common::primitives::tails::{TailsFetcher, DEFAULT_TAILS_CACHE_DIR};
 use crate::errors::error::prelude::*;
+use crate::utils::get_temp_dir_path;
 use crate::{
     common::proofs::{proof_request::ProofRequestData, proof_request_internal::NonRevokedInterval},
     core::profile::profile::Profile,
@@ -36,19 +39,12 @@ pub async fn build_schemas_json_prover(
 
     for cred_info in credentials_identifiers {
         if rtn.get(&cred_info.schema_id).is_none() {
-            let schema_json = ledger
-                .get_schema(&cred_info.schema_id, None)
+            let schema = ledger
+                .get_schema_object(&cred_info.schema_id, None)
                 .await
                 .map_err(|err| err.map(AriesVcxCoreErrorKind::InvalidSchema, "Cannot get schema"))?;
 
-            let schema_json = serde_json::from_str(&schema_json).map_err(|err| {
-                AriesVcxCoreError::from_msg(
-                    AriesVcxCoreErrorKind::InvalidSchema,
-                    format!("Cannot deserialize schema: {}", err),
-                )
-            })?;
-
-            rtn[cred_info.schema_id.to_owned()] = schema_json;
+            rtn[cred_info.schema_id.to_owned()] = json!(schema);
         }
     }
     Ok(rtn.to_string())
@@ -67,21 +63,17 @@ pub async fn build_cred_defs_json_prover(
 
     for cred_info in credentials_identifiers {
         if rtn.get(&cred_info.cred_def_id).is_none() {
-            let credential_def = ledger.get_cred_def(&cred_info.cred_def_id, None).await.map_err(|err| {
-                err.map(
-                    AriesVcxCoreErrorKind::InvalidProofCredentialData,
-                    "Cannot get credential definition",
-                )
-            })?;
-
-            let credential_def = serde_json::from_str(&credential_def).map_err(|err| {
-                AriesVcxError::from_msg(
-                    AriesVcxErrorKind::InvalidProofCredentialData,
-                    format!("Cannot deserialize credential definition: {}", err),
-                )
-            })?;
+            let credential_def = ledger
+                .get_cred_def_object(&cred_info.cred_def_id, None)
+                .await
+                .map_err(|err| {
+                    err.map(
+                        AriesVcxCoreErrorKind::InvalidProofCredentialData,
+                        "Cannot get credential definition",
+                    )
+                })?;
 
-            rtn[cred_info.cred_def_id.to_owned()] = credential_def;
+            rtn[cred_info.cred_def_id.to_owned()] = json!(credential_def);
         }
     }
     Ok(rtn.to_string())
@@ -162,6 +154,17 @@ fn _get_revocation_interval(attr_name: &str, proof_req: &ProofRequestData) -> Vc
 pub async fn build_rev_states_json(
     profile: &Arc<dyn Profile>,
     credentials_identifiers: &mut Vec<CredInfoProver>,
+) -> VcxResult<String> {
+    let tails_fetcher = TailsFetcher::new(get_temp_dir_path(DEFAULT_TAILS_CACHE_DIR));
+    build_rev_states_json_with_fetcher(profile, credentials_identifiers, &tails_fetcher).await
+}
+
+/// Builds the revocation states of the credentials. Tails files of credentials without `tails_file` are
+/// downloaded by `tails_fetcher`.
+pub async fn build_rev_states_json_with_fetcher(
+    profile: &Arc<dyn Profile>,
+    credentials_identifiers: &mut Vec<CredInfoProver>,
+    tails_fetcher: &TailsFetcher,
 ) -> VcxResult<String> {
     trace!(
         "build_rev_states_json >> credentials_identifiers: {:?}",
@@ -173,9 +176,7 @@ pub async fn build_rev_states_json(
     let mut timestamps: HashMap<String, u64> = HashMap::new();
 
     for cred_info in credentials_identifiers.iter_mut() {
-        if let (Some(rev_reg_id), Some(cred_rev_id), Some(tails_file)) =
-            (&cred_info.rev_reg_id, &cred_info.cred_rev_id, &cred_info.tails_file)
-        {
+        if let (Some(rev_reg_id), Some(cred_rev_id)) = (&cred_info.rev_reg_id, &cred_info.cred_rev_id) {
             if rtn.get(rev_reg_id).is_none() {
                 // Does this make sense in case cred_info's for same rev_reg_ids have different revocation intervals
                 let (from, to) = if let Some(ref interval) = cred_info.revocation_interval {
@@ -185,13 +186,21 @@ pub async fn build_rev_states_json(
                 };
 
                 let rev_reg_def_json = ledger.get_rev_reg_def_json(rev_reg_id).await?;
+                let tails_dir = match &cred_info.tails_file {
+                    Some(tails_file) => tails_file.to_string(),
+                    // no local copy of the tails file, download it from the location in the definition
+                    None => {
+                        let rev_reg_def = RevocationRegistryDefinition::from_json(&rev_reg_def_json)?;
+                        tails_fetcher.fetch(&rev_reg_def).await?
+                    }
+                };
 
                 let (rev_reg_id, rev_reg_delta_json, timestamp) =
                     ledger.get_rev_reg_delta_json(rev_reg_id, from, to).await?;
 
                 let rev_state_json = anoncreds
                     .create_revocation_state(
-                        tails_file,
+                        &tails_dir,
                         &rev_reg_def_json,
                         &rev_reg_delta_json,
                         timestamp,
@@ -1,2 +1,4 @@
+pub mod policy;
+pub mod verification_report;
 pub mod verifier;
 mod verifier_internal;
@@ -0,0 +1,433 @@
+use std::collections::{HashMap, HashSet};
+
+use regex::Regex;
+use time::OffsetDateTime;
+
+use super::verification_report::{NonRevocationReport, ProofVerificationReport};
+use crate::utils::qualifier;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolicyViolation {
+    pub rule: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub referent: Option<String>,
+    pub reason: String,
+}
+
+impl PolicyViolation {
+    fn new(rule: &str, referent: Option<&str>, reason: String) -> Self {
+        Self {
+            rule: rule.to_string(),
+            referent: referent.map(String::from),
+            reason,
+        }
+    }
+}
+
+/// Outcome of the evaluation of verifier policies over a cryptographically valid presentation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolicyEvaluation {
+    pub evaluated_at: u64,
+    pub violations: Vec<PolicyViolation>,
+}
+
+impl PolicyEvaluation {
+    pub fn is_accepted(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    // description of the violations, sent to the prover in the problem report
+    pub fn describe(&self) -> String {
+        let violations: Vec<String> = self
+            .violations
+            .iter()
+            .map(|violation| match &violation.referent {
+                Some(referent) => format!("{} ({}): {}", violation.rule, referent, violation.reason),
+                None => format!("{}: {}", violation.rule, violation.reason),
+            })
+            .collect();
+        format!("Presentation rejected by verifier policy: {}", violations.join("; "))
+    }
+}
+
+/// Business rule a presentation must meet beyond being cryptographically valid, evaluated on its
+/// [ProofVerificationReport] at the unix time `now`.
+pub trait PresentationPolicy: Send + Sync {
+    fn evaluate(&self, report: &ProofVerificationReport, now: u64) -> Vec<PolicyViolation>;
+}
+
+/// Set of policies a presentation must all meet.
+#[derive(Default)]
+pub struct PresentationPolicies {
+    rules: Vec<Box<dyn PresentationPolicy>>,
+}
+
+impl PresentationPolicies {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_rule(mut self, rule: impl PresentationPolicy + 'static) -> Self {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    pub fn evaluate_at(&self, report: &ProofVerificationReport, now: u64) -> PolicyEvaluation {
+        PolicyEvaluation {
+            evaluated_at: now,
+            violations: self.evaluate(report, now),
+        }
+    }
+
+    pub fn evaluate_now(&self, report: &ProofVerificationReport) -> PolicyEvaluation {
+        self.evaluate_at(report, OffsetDateTime::now_utc().unix_timestamp() as u64)
+    }
+}
+
+impl PresentationPolicy for PresentationPolicies {
+    fn evaluate(&self, report: &ProofVerificationReport, now: u64) -> Vec<PolicyViolation> {
+        self.rules.iter().flat_map(|rule| rule.evaluate(report, now)).collect()
+    }
+}
+
+// revealed values of `attribute`, by referent. The raw value is only given if it was checked to match the encoded
+// value the proof guarantees, policies must reject the others rather than trust values the prover could have changed.
+fn revealed_values<'a>(report: &'a ProofVerificationReport, attribute: &str) -> Vec<(&'a str, Option<&'a str>)> {
+    let mut values: Vec<(&str, Option<&str>)> = report
+        .revealed_attributes
+        .iter()
+        .filter_map(|(referent, revealed)| {
+            revealed
+                .values
+                .get(attribute)
+                .map(|value| (referent.as_str(), value.encoding_valid.then_some(value.raw.as_str())))
+        })
+        .collect();
+    values.sort();
+    values
+}
+
+fn unchecked_value_violation(rule: &str, referent: &str, attribute: &str) -> PolicyViolation {
+    PolicyViolation::new(
+        rule,
+        Some(referent),
+        format!("Value of attribute {} doesn't match its encoded value", attribute),
+    )
+}
+
+/// Issuers trusted for credentials, keyed by credential definition ID, or by schema ID to trust issuers for any
+/// credential definition of the schema. Credentials of other credential definitions are not restricted.
+#[derive(Debug, Clone, Default)]
+pub struct TrustedIssuers {
+    trusted: HashMap<String, HashSet<String>>,
+}
+
+impl TrustedIssuers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn trust(mut self, cred_def_or_schema_id: &str, issuer_dids: &[&str]) -> Self {
+        self.trusted
+            .entry(cred_def_or_schema_id.to_string())
+            .or_default()
+            .extend(issuer_dids.iter().map(|did| did.to_string()));
+        self
+    }
+}
+
+impl PresentationPolicy for TrustedIssuers {
+    fn evaluate(&self, report: &ProofVerificationReport, _now: u64) -> Vec<PolicyViolation> {
+        report
+            .identifiers
+            .iter()
+            .filter_map(|identifier| {
+                let trusted = self
+                    .trusted
+                    .get(&identifier.cred_def_id)
+                    .or_else(|| self.trusted.get(&identifier.schema_id))?;
+                let issuer_did = qualifier::issuer_did(&identifier.cred_def_id);
+                (!trusted.contains(issuer_did)).then(|| {
+                    PolicyViolation::new(
+                        "trusted_issuers",
+                        None,
+                        format!(
+                            "Issuer {} of credential definition {} is not trusted",
+                            issuer_did, identifier.cred_def_id
+                        ),
+                    )
+                })
+            })
+            .collect()
+    }
+}
+
+/// Requires credentials to be issued at most `max_age_secs` ago. Indy credentials don't carry their issuance date,
+/// so it is read from the revealed attribute `issuance_date_attribute`, as a unix timestamp.
+#[derive(Debug, Clone)]
+pub struct MaxCredentialAge {
+    pub issuance_date_attribute: String,
+    pub max_age_secs: u64,
+}
+
+impl PresentationPolicy for MaxCredentialAge {
+    fn evaluate(&self, report: &ProofVerificationReport, now: u64) -> Vec<PolicyViolation> {
+        let values = revealed_values(report, &self.issuance_date_attribute);
+        if values.is_empty() {
+            return vec![PolicyViolation::new(
+                "max_credential_age",
+                None,
+                format!("Attribute {} is not revealed", self.issuance_date_attribute),
+            )];
+        }
+        values
+            .into_iter()
+            .filter_map(|(referent, value)| {
+                let Some(value) = value else {
+                    return Some(unchecked_value_violation(
+                        "max_credential_age",
+                        referent,
+                        &self.issuance_date_attribute,
+                    ));
+                };
+                match value.parse::<u64>() {
+                    Ok(issued_at) if now.saturating_sub(issued_at) <= self.max_age_secs => None,
+                    Ok(issued_at) => Some(PolicyViolation::new(
+                        "max_credential_age",
+                        Some(referent),
+                        format!(
+                            "Credential issued at {} is older than {} seconds",
+                            issued_at, self.max_age_secs
+                        ),
+                    )),
+                    Err(_) => Some(PolicyViolation::new(
+                        "max_credential_age",
+                        Some(referent),
+                        format!("Issuance date {} is not a unix timestamp", value),
+                    )),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Requires revocable credentials to be proven non-revoked as of at most `max_age_secs` ago.
+#[derive(Debug, Clone)]
+pub struct NonRevocationFreshness {
+    pub max_age_secs: u64,
+}
+
+impl PresentationPolicy for NonRevocationFreshness {
+    fn evaluate(&self, report: &ProofVerificationReport, now: u64) -> Vec<PolicyViolation> {
+        report
+            .identifiers
+            .iter()
+            .filter_map(|identifier| {
+                let reason = match identifier.non_revocation {
+                    NonRevocationReport::NotRevocable => return None,
+                    NonRevocationReport::ProvenAt { timestamp }
+                        if now.saturating_sub(timestamp) <= self.max_age_secs =>
+                    {
+                        return None
+                    }
+                    NonRevocationReport::ProvenAt { timestamp } => format!(
+                        "Credential of {} proven non-revoked at {}, more than {} seconds ago",
+                        identifier.cred_def_id, timestamp, self.max_age_secs
+                    ),
+                    NonRevocationReport::NotProven => {
+                        format!("Credential of {} not proven non-revoked", identifier.cred_def_id)
+                    }
+                };
+                Some(PolicyViolation::new("non_revocation_freshness", None, reason))
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ValueConstraint {
+    OneOf(Vec<String>),
+    Matches(Regex),
+}
+
+impl ValueConstraint {
+    fn is_met(&self, value: &str) -> bool {
+        match self {
+            ValueConstraint::OneOf(allowed) => allowed.iter().any(|allowed| allowed == value),
+            ValueConstraint::Matches(regex) => regex.is_match(value),
+        }
+    }
+}
+
+/// Constrains the revealed values of an attribute beyond what Indy predicates can express. The attribute must be
+/// revealed.
+#[derive(Debug, Clone)]
+pub struct AttributeConstraint {
+    pub attribute: String,
+    pub constraint: ValueConstraint,
+}
+
+impl PresentationPolicy for AttributeConstraint {
+    fn evaluate(&self, report: &ProofVerificationReport, _now: u64) -> Vec<PolicyViolation> {
+        let values = revealed_values(report, &self.attribute);
+        if values.is_empty() {
+            return vec![PolicyViolation::new(
+                "attribute_constraint",
+                None,
+                format!("Attribute {} is not revealed", self.attribute),
+            )];
+        }
+        values
+            .into_iter()
+            .filter_map(|(referent, value)| match value {
+                None => Some(unchecked_value_violation(
+                    "attribute_constraint",
+                    referent,
+                    &self.attribute,
+                )),
+                Some(value) if self.constraint.is_met(value) => None,
+                Some(value) => Some(PolicyViolation::new(
+                    "attribute_constraint",
+                    Some(referent),
+                    format!(
+                        "Value {} of attribute {} doesn't meet {:?}",
+                        value, self.attribute, self.constraint
+                    ),
+                )),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+pub mod unit_tests {
+    use crate::common::proofs::verifier::verification_report::{
+        IdentifierReport, RevealedAttributeReport, RevealedValue,
+    };
+
+    use super::*;
+
+    fn _report(non_revocation: NonRevocationReport) -> ProofVerificationReport {
+        let revealed = |raw: &str| RevealedValue {
+            raw: raw.to_string(),
+            encoded: String::new(),
+            encoding_valid: true,
+        };
+        ProofVerificationReport {
+            valid: true,
+            revealed_attributes: HashMap::from([
+                (
+                    "country_ref".to_string(),
+                    RevealedAttributeReport {
+                        sub_proof_index: 0,
+                        values: HashMap::from([("country".to_string(), revealed("CZ"))]),
+                    },
+                ),
+                (
+                    "issued_ref".to_string(),
+                    RevealedAttributeReport {
+                        sub_proof_index: 0,
+                        values: HashMap::from([("issued_at".to_string(), revealed("1000"))]),
+                    },
+                ),
+            ]),
+            identifiers: vec![IdentifierReport {
+                schema_id: "V4SGRU86Z58d6TV7PBUe6f:2:passport:1.0".to_string(),
+                cred_def_id: "V4SGRU86Z58d6TV7PBUe6f:3:CL:47:tag1".to_string(),
+                rev_reg_id: Some("rev_reg_id".to_string()),
+                non_revocation,
+            }],
+            ..ProofVerificationReport::default()
+        }
+    }
+
+    #[test]
+    fn test_policies_accept_presentation() {
+        let policies = PresentationPolicies::new()
+            .with_rule(
+                TrustedIssuers::new().trust("V4SGRU86Z58d6TV7PBUe6f:2:passport:1.0", &["V4SGRU86Z58d6TV7PBUe6f"]),
+            )
+            .with_rule(MaxCredentialAge {
+                issuance_date_attribute: "issued_at".to_string(),
+                max_age_secs: 100,
+            })
+            .with_rule(NonRevocationFreshness { max_age_secs: 10 })
+            .with_rule(AttributeConstraint {
+                attribute: "country".to_string(),
+                constraint: ValueConstraint::Matches(Regex::new("^[A-Z]{2}$").unwrap()),
+            });
+
+        let evaluation = policies.evaluate_at(&_report(NonRevocationReport::ProvenAt { timestamp: 1050 }), 1050);
+        assert!(evaluation.is_accepted());
+        assert_eq!(evaluation.evaluated_at, 1050);
+    }
+
+    #[test]
+    fn test_policies_report_violations() {
+        let policies = PresentationPolicies::new()
+            .with_rule(TrustedIssuers::new().trust("V4SGRU86Z58d6TV7PBUe6f:3:CL:47:tag1", &["8XFh8yBzrpJQmNyZzgoTqB"]))
+            .with_rule(MaxCredentialAge {
+                issuance_date_attribute: "issued_at".to_string(),
+                max_age_secs: 100,
+            })
+            .with_rule(NonRevocationFreshness { max_age_secs: 10 })
+            .with_rule(AttributeConstraint {
+                attribute: "country".to_string(),
+                constraint: ValueConstraint::OneOf(vec!["SK".to_string()]),
+            })
+            .with_rule(AttributeConstraint {
+                attribute: "email".to_string(),
+                constraint: ValueConstraint::OneOf(vec![]),
+            });
+
+        let evaluation = policies.evaluate_at(&_report(NonRevocationReport::NotProven), 2000);
+        let rules: Vec<&str> = evaluation.violations.iter().map(|v| v.rule.as_str()).collect();
+        assert_eq!(
+            rules,
+            vec![
+                "trusted_issuers",
+                "max_credential_age",
+                "non_revocation_freshness",
+                "attribute_constraint",
+                "attribute_constraint"
+            ]
+        );
+        assert_eq!(evaluation.violations[1].referent, Some("issued_ref".to_string()));
+        assert!(!evaluation.is_accepted());
+        assert!(evaluation.describe().contains("not trusted"));
+    }
+
+    #[test]
+    fn test_policies_reject_values_not_matching_encoding() {
+        let policies = PresentationPolicies::new()
+            .with_rule(MaxCredentialAge {
+                issuance_date_attribute: "issued_at".to_string(),
+                max_age_secs: 100,
+            })
+            .with_rule(AttributeConstraint {
+                attribute: "country".to_string(),
+                constraint: ValueConstraint::OneOf(vec!["CZ".to_string()]),
+            });
+        let mut report = _report(NonRevocationReport::ProvenAt { timestamp: 1050 });
+        for revealed in report.revealed_attributes.values_mut() {
+            for value in revealed.values.values_mut() {
+                value.encoding_valid = false;
+            }
+        }
+
+        let evaluation = policies.evaluate_at(&report, 1050);
+        let violations: Vec<(&str, Option<&str>)> = evaluation
+            .violations
+            .iter()
+            .map(|v| (v.rule.as_str(), v.referent.as_deref()))
+            .collect();
+        assert_eq!(
+            violations,
+            vec![
+                ("max_credential_age", Some("issued_ref")),
+                ("attribute_constraint", Some("country_ref"))
+            ]
+        );
+    }
+}
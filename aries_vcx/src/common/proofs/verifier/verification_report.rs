@@ -0,0 +1,676 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use aries_vcx_core::anoncreds::types::{AnonCredsObject, Proof, ProofIdentifier};
+use serde_json::{Map, Value};
+
+use crate::common::credentials::encoding::AttributeEncodingRegistry;
+use crate::common::proofs::proof_request::ProofRequestData;
+use crate::common::proofs::proof_request_internal::{NonRevokedInterval, Restrictions};
+use crate::core::profile::profile::Profile;
+use crate::errors::error::prelude::*;
+use crate::utils::mockdata::mock_settings::get_mock_result_for_validate_indy_proof;
+use crate::utils::qualifier;
+
+use super::verifier::{load_proof_encodings, verify_proof_against_ledger};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RevealedValue {
+    pub raw: String,
+    pub encoded: String,
+    // whether `encoded` is the encoding of `raw`, the proof only guarantees the encoded value, see
+    // `AttributeEncodingRegistry::matches`
+    pub encoding_valid: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RevealedAttributeReport {
+    pub sub_proof_index: usize,
+    // revealed values, by attribute name
+    pub values: HashMap<String, RevealedValue>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PredicateReport {
+    pub name: String,
+    pub p_type: String,
+    pub p_value: i32,
+    pub sub_proof_index: Option<usize>,
+    // predicates are proven cryptographically, they pass only if the whole proof verifies
+    pub passed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "status")]
+pub enum NonRevocationReport {
+    NotRevocable,
+    ProvenAt { timestamp: u64 },
+    NotProven,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IdentifierReport {
+    pub schema_id: String,
+    pub cred_def_id: String,
+    pub rev_reg_id: Option<String>,
+    pub non_revocation: NonRevocationReport,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FailedCondition {
+    pub condition: String,
+    pub expected: String,
+    pub actual: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "failure")]
+pub enum VerificationFailure {
+    /// The anoncreds backend rejected the proof.
+    CryptographicVerification { message: Option<String> },
+    /// A revealed raw value doesn't match its encoded value, see `validate_proof_revealed_attributes`.
+    EncodingMismatch {
+        referent: String,
+        attribute: String,
+        expected_encoded: String,
+        encoded: String,
+    },
+    /// A requested attribute or predicate isn't answered by the proof.
+    MissingReferent { referent: String },
+    /// None of the restrictions of the referent is met, each entry lists the conditions of a restriction not met.
+    RestrictionNotMet {
+        referent: String,
+        failed_restrictions: Vec<Vec<FailedCondition>>,
+    },
+    /// Non-revocation was requested for the referent, but the credential was not proven non-revoked in the interval.
+    NonRevocationNotProven {
+        referent: String,
+        interval: NonRevokedInterval,
+        timestamp: Option<u64>,
+    },
+}
+
+/// Outcome of the verification of a presentation, detailing what was proven and why it was accepted or rejected.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofVerificationReport {
+    pub valid: bool,
+    pub revealed_attributes: HashMap<String, RevealedAttributeReport>,
+    pub unrevealed_attributes: HashMap<String, usize>,
+    pub self_attested_attributes: HashMap<String, String>,
+    pub predicates: HashMap<String, PredicateReport>,
+    // identifiers of the credentials used, indexed by sub proof index
+    pub identifiers: Vec<IdentifierReport>,
+    pub failures: Vec<VerificationFailure>,
+}
+
+/// Verifies a proof like [super::verifier::validate_indy_proof], but reports the details of the verification rather
+/// than a boolean. Only malformed inputs are reported as errors.
+pub async fn verify_indy_proof_with_report(
+    profile: &Arc<dyn Profile>,
+    proof_json: &str,
+    proof_req_json: &str,
+) -> VcxResult<ProofVerificationReport> {
+    let proof = Proof::from_json(proof_json)?;
+    let proof_request = parse_proof_request(proof_req_json)?;
+
+    let verification = match get_mock_result_for_validate_indy_proof() {
+        Some(mock_result) => mock_result,
+        None => verify_proof_against_ledger(profile, &proof, proof_json, proof_req_json).await,
+    };
+    let cryptographic_failure = match verification {
+        Ok(true) => None,
+        Ok(false) => Some(VerificationFailure::CryptographicVerification { message: None }),
+        Err(err) => Some(VerificationFailure::CryptographicVerification {
+            message: Some(err.to_string()),
+        }),
+    };
+
+    let registry = load_proof_encodings(profile, &proof).await?;
+    let mut report = build_report(&proof, &proof_request, &registry, cryptographic_failure.is_none())?;
+    report.failures.extend(cryptographic_failure);
+    report.valid = report.failures.is_empty();
+    Ok(report)
+}
+
+/// Builds the report of a proof whose cryptographic verification was already done, see
+/// [verify_indy_proof_with_report]. Revealed values are checked against the encodings declared in `registry`.
+pub fn build_verification_report(
+    proof_json: &str,
+    proof_req_json: &str,
+    registry: &AttributeEncodingRegistry,
+    cryptographically_valid: bool,
+) -> VcxResult<ProofVerificationReport> {
+    let proof = Proof::from_json(proof_json)?;
+    let proof_request = parse_proof_request(proof_req_json)?;
+    let mut report = build_report(&proof, &proof_request, registry, cryptographically_valid)?;
+    if !cryptographically_valid {
+        report
+            .failures
+            .push(VerificationFailure::CryptographicVerification { message: None });
+    }
+    report.valid = report.failures.is_empty();
+    Ok(report)
+}
+
+fn parse_proof_request(proof_req_json: &str) -> VcxResult<ProofRequestData> {
+    serde_json::from_str(proof_req_json).map_err(|err| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidJson,
+            format!("Cannot deserialize proof request: {}", err),
+        )
+    })
+}
+
+fn build_report(
+    proof: &Proof,
+    proof_request: &ProofRequestData,
+    registry: &AttributeEncodingRegistry,
+    cryptographically_valid: bool,
+) -> VcxResult<ProofVerificationReport> {
+    let requested_proof = &proof.requested_proof;
+    let mut report = ProofVerificationReport {
+        identifiers: proof.identifiers.iter().map(identifier_report).collect(),
+        ..ProofVerificationReport::default()
+    };
+
+    let schema_of_sub_proof = |index: usize| {
+        proof
+            .identifiers
+            .get(index)
+            .map(|identifier| identifier.schema_id.as_str())
+            .unwrap_or_default()
+    };
+
+    let revealed_attrs = requested_proof["revealed_attrs"].as_object().into_iter().flatten();
+    for (referent, revealed) in revealed_attrs {
+        let attribute = proof_request
+            .requested_attributes
+            .get(referent)
+            .and_then(|attr| attr.name.clone())
+            .unwrap_or_else(|| referent.clone());
+        let index = sub_proof_index(referent, revealed)?;
+        let value = revealed_value(referent, revealed, registry, schema_of_sub_proof(index), &attribute)?;
+        let mut values = HashMap::new();
+        values.insert(attribute, value);
+        report.revealed_attributes.insert(
+            referent.clone(),
+            RevealedAttributeReport {
+                sub_proof_index: index,
+                values,
+            },
+        );
+    }
+    let revealed_groups = requested_proof["revealed_attr_groups"]
+        .as_object()
+        .into_iter()
+        .flatten();
+    for (referent, group) in revealed_groups {
+        let index = sub_proof_index(referent, group)?;
+        let values = group["values"]
+            .as_object()
+            .into_iter()
+            .flatten()
+            .map(|(name, value)| {
+                let value = revealed_value(referent, value, registry, schema_of_sub_proof(index), name)?;
+                Ok((name.clone(), value))
+            })
+            .collect::<VcxResult<HashMap<_, _>>>()?;
+        report.revealed_attributes.insert(
+            referent.clone(),
+            RevealedAttributeReport {
+                sub_proof_index: index,
+                values,
+            },
+        );
+    }
+    for (referent, unrevealed) in requested_proof["unrevealed_attrs"].as_object().into_iter().flatten() {
+        report
+            .unrevealed_attributes
+            .insert(referent.clone(), sub_proof_index(referent, unrevealed)?);
+    }
+    for (referent, value) in requested_proof["self_attested_attrs"].as_object().into_iter().flatten() {
+        report
+            .self_attested_attributes
+            .insert(referent.clone(), value.as_str().unwrap_or_default().to_string());
+    }
+
+    let mut referent_sub_proofs: HashMap<&str, usize> = HashMap::new();
+    for (referent, revealed) in report.revealed_attributes.iter() {
+        referent_sub_proofs.insert(referent, revealed.sub_proof_index);
+        for (attribute, value) in revealed.values.iter().filter(|(_, value)| !value.encoding_valid) {
+            // a raw value which cannot be encoded with the declared encoding has no expected encoded value
+            let expected_encoded = registry
+                .encode(schema_of_sub_proof(revealed.sub_proof_index), attribute, &value.raw)
+                .unwrap_or_default();
+            report.failures.push(VerificationFailure::EncodingMismatch {
+                referent: referent.clone(),
+                attribute: attribute.clone(),
+                expected_encoded,
+                encoded: value.encoded.clone(),
+            });
+        }
+    }
+    for (referent, index) in report.unrevealed_attributes.iter() {
+        referent_sub_proofs.insert(referent, *index);
+    }
+
+    for (referent, predicate) in proof_request.requested_predicates.iter() {
+        let index = requested_proof["predicates"][referent]
+            .get("sub_proof_index")
+            .map(|_| sub_proof_index(referent, &requested_proof["predicates"][referent]))
+            .transpose()?;
+        report.predicates.insert(
+            referent.clone(),
+            PredicateReport {
+                name: predicate.name.clone(),
+                p_type: predicate.p_type.clone(),
+                p_value: predicate.p_value,
+                sub_proof_index: index,
+                passed: cryptographically_valid && index.is_some(),
+            },
+        );
+    }
+
+    let requested = proof_request
+        .requested_attributes
+        .iter()
+        .map(|(referent, attr)| (referent, attr.restrictions.as_ref(), attr.non_revoked.as_ref()))
+        .chain(
+            proof_request
+                .requested_predicates
+                .iter()
+                .map(|(referent, pred)| (referent, pred.restrictions.as_ref(), pred.non_revoked.as_ref())),
+        );
+    let mut referent_failures = vec![];
+    for (referent, restrictions, non_revoked) in requested {
+        let index = referent_sub_proofs
+            .get(referent.as_str())
+            .copied()
+            .or_else(|| report.predicates.get(referent).and_then(|p| p.sub_proof_index));
+        let index = match index {
+            Some(index) => index,
+            None if report.self_attested_attributes.contains_key(referent) => continue,
+            None => {
+                referent_failures.push(VerificationFailure::MissingReferent {
+                    referent: referent.clone(),
+                });
+                continue;
+            }
+        };
+        let identifier = proof.identifiers.get(index).ok_or_else(|| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidProof,
+                format!("Proof has no identifiers for sub proof {} of {}", index, referent),
+            )
+        })?;
+
+        if let Some(restrictions) = restrictions {
+            let revealed = revealed_values_of_sub_proof(&report, index);
+            if let Some(failed_restrictions) = evaluate_restrictions(restrictions, identifier, &revealed) {
+                referent_failures.push(VerificationFailure::RestrictionNotMet {
+                    referent: referent.clone(),
+                    failed_restrictions,
+                });
+            }
+        }
+
+        if let Some(interval) = non_revoked.or(proof_request.non_revoked.as_ref()) {
+            let proven = match (&identifier.rev_reg_id, identifier.timestamp) {
+                (None, _) => true,
+                (Some(_), Some(timestamp)) => {
+                    interval.from.map_or(true, |from| timestamp >= from)
+                        && interval.to.map_or(true, |to| timestamp <= to)
+                }
+                (Some(_), None) => false,
+            };
+            if !proven {
+                referent_failures.push(VerificationFailure::NonRevocationNotProven {
+                    referent: referent.clone(),
+                    interval: interval.clone(),
+                    timestamp: identifier.timestamp,
+                });
+            }
+        }
+    }
+    referent_failures.sort_by_key(failure_referent);
+    report.failures.extend(referent_failures);
+    Ok(report)
+}
+
+fn failure_referent(failure: &VerificationFailure) -> String {
+    match failure {
+        VerificationFailure::MissingReferent { referent }
+        | VerificationFailure::RestrictionNotMet { referent, .. }
+        | VerificationFailure::NonRevocationNotProven { referent, .. }
+        | VerificationFailure::EncodingMismatch { referent, .. } => referent.clone(),
+        VerificationFailure::CryptographicVerification { .. } => String::new(),
+    }
+}
+
+fn identifier_report(identifier: &ProofIdentifier) -> IdentifierReport {
+    let non_revocation = match (&identifier.rev_reg_id, identifier.timestamp) {
+        (None, _) => NonRevocationReport::NotRevocable,
+        (Some(_), Some(timestamp)) => NonRevocationReport::ProvenAt { timestamp },
+        (Some(_), None) => NonRevocationReport::NotProven,
+    };
+    IdentifierReport {
+        schema_id: identifier.schema_id.clone(),
+        cred_def_id: identifier.cred_def_id.clone(),
+        rev_reg_id: identifier.rev_reg_id.clone(),
+        non_revocation,
+    }
+}
+
+fn sub_proof_index(referent: &str, value: &Value) -> VcxResult<usize> {
+    value["sub_proof_index"]
+        .as_u64()
+        .map(|index| index as usize)
+        .ok_or_else(|| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidProof,
+                format!("Cannot get sub proof index of \"{}\"", referent),
+            )
+        })
+}
+
+fn revealed_value(
+    referent: &str,
+    value: &Value,
+    registry: &AttributeEncodingRegistry,
+    schema_id: &str,
+    attribute: &str,
+) -> VcxResult<RevealedValue> {
+    let (raw, encoded) = match (value["raw"].as_str(), value["encoded"].as_str()) {
+        (Some(raw), Some(encoded)) => (raw, encoded),
+        _ => {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidProof,
+                format!("Cannot get raw and encoded values of \"{}\"", referent),
+            ))
+        }
+    };
+    Ok(RevealedValue {
+        raw: raw.to_string(),
+        encoded: encoded.to_string(),
+        encoding_valid: registry.matches(schema_id, attribute, raw, encoded),
+    })
+}
+
+fn revealed_values_of_sub_proof(report: &ProofVerificationReport, index: usize) -> HashMap<&str, &str> {
+    report
+        .revealed_attributes
+        .values()
+        .filter(|revealed| revealed.sub_proof_index == index)
+        .flat_map(|revealed| revealed.values.iter())
+        .map(|(name, value)| (name.as_str(), value.raw.as_str()))
+        .collect()
+}
+
+// Returns the conditions not met by each restriction if none of them is met. Conditions which can't be checked out
+// of the proof (e.g. the value of an unrevealed attribute) are left to the cryptographic verification.
+fn evaluate_restrictions(
+    restrictions: &Restrictions,
+    identifier: &ProofIdentifier,
+    revealed: &HashMap<&str, &str>,
+) -> Option<Vec<Vec<FailedCondition>>> {
+    let restrictions: Vec<Map<String, Value>> = match restrictions {
+        Restrictions::V1(filters) => filters
+            .iter()
+            .filter_map(|filter| serde_json::to_value(filter).ok())
+            .filter_map(|filter| filter.as_object().cloned())
+            .collect(),
+        Restrictions::V2(Value::Array(restrictions)) => restrictions
+            .iter()
+            .filter_map(|restriction| restriction.as_object().cloned())
+            .collect(),
+        Restrictions::V2(Value::Object(restriction)) => vec![restriction.clone()],
+        Restrictions::V2(_) => vec![],
+    };
+    if restrictions.is_empty() {
+        return None;
+    }
+
+    let mut failed_restrictions = vec![];
+    for restriction in restrictions.iter() {
+        let failed_conditions: Vec<FailedCondition> = restriction
+            .iter()
+            .filter_map(|(condition, expected)| {
+                let expected = expected.as_str()?;
+                match condition_value(condition, identifier, revealed) {
+                    Some(actual) if actual == expected => None,
+                    Some(actual) => Some(FailedCondition {
+                        condition: condition.clone(),
+                        expected: expected.to_string(),
+                        actual: Some(actual),
+                    }),
+                    None => None,
+                }
+            })
+            .collect();
+        if failed_conditions.is_empty() {
+            return None;
+        }
+        failed_restrictions.push(failed_conditions);
+    }
+    Some(failed_restrictions)
+}
+
+fn condition_value(condition: &str, identifier: &ProofIdentifier, revealed: &HashMap<&str, &str>) -> Option<String> {
+    // legacy schema IDs are formatted as <issuer did>:2:<name>:<version>
+    let schema_id_part = |index: usize| {
+        let parts: Vec<&str> = identifier.schema_id.split(':').collect();
+        (parts.len() == 4).then(|| parts[index].to_string())
+    };
+    match condition {
+        "schema_id" => Some(identifier.schema_id.clone()),
+        "cred_def_id" => Some(identifier.cred_def_id.clone()),
+        "rev_reg_id" => identifier.rev_reg_id.clone(),
+        "issuer_did" => Some(qualifier::issuer_did(&identifier.cred_def_id).to_string()),
+        "schema_issuer_did" => schema_id_part(0),
+        "schema_name" => schema_id_part(2),
+        "schema_version" => schema_id_part(3),
+        _ => condition
+            .strip_prefix("attr::")
+            .and_then(|attr| attr.strip_suffix("::value"))
+            .and_then(|attr| revealed.get(attr))
+            .map(|value| value.to_string()),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+pub mod unit_tests {
+    use crate::common::credentials::encoding::AttributeEncoding;
+    use crate::utils::openssl::encode;
+
+    use super::*;
+
+    const SCHEMA_ID: &str = "V4SGRU86Z58d6TV7PBUe6f:2:licence:1.0";
+    const CRED_DEF_ID: &str = "V4SGRU86Z58d6TV7PBUe6f:3:CL:47:tag1";
+    const REV_REG_ID: &str = "V4SGRU86Z58d6TV7PBUe6f:4:V4SGRU86Z58d6TV7PBUe6f:3:CL:47:tag1:CL_ACCUM:tag1";
+
+    fn _proof(timestamp: Option<u64>, encoded_name: &str) -> Proof {
+        serde_json::from_value(json!({
+            "proof": {},
+            "requested_proof": {
+                "revealed_attrs": {
+                    "name_ref": { "sub_proof_index": 0, "raw": "Alice", "encoded": encoded_name }
+                },
+                "revealed_attr_groups": {
+                    "address_ref": {
+                        "sub_proof_index": 0,
+                        "values": {
+                            "city": { "raw": "1234", "encoded": "1234" },
+                            "street": { "raw": "42", "encoded": "42" }
+                        }
+                    }
+                },
+                "self_attested_attrs": { "nickname_ref": "Al" },
+                "unrevealed_attrs": {},
+                "predicates": { "age_ref": { "sub_proof_index": 0 } }
+            },
+            "identifiers": [{
+                "schema_id": SCHEMA_ID,
+                "cred_def_id": CRED_DEF_ID,
+                "rev_reg_id": REV_REG_ID,
+                "timestamp": timestamp
+            }]
+        }))
+        .unwrap()
+    }
+
+    fn _proof_request(issuer_did: &str) -> ProofRequestData {
+        serde_json::from_value(json!({
+            "nonce": "123432421212",
+            "name": "proof_req_1",
+            "version": "1.0",
+            "requested_attributes": {
+                "name_ref": {
+                    "name": "name",
+                    "restrictions": [
+                        { "issuer_did": issuer_did },
+                        { "schema_name": "passport", "attr::name::value": "Alice" }
+                    ]
+                },
+                "address_ref": { "names": ["city", "street"] },
+                "nickname_ref": { "name": "nickname", "self_attest_allowed": true }
+            },
+            "requested_predicates": {
+                "age_ref": { "name": "age", "p_type": ">=", "p_value": 18 }
+            },
+            "non_revoked": { "from": 100, "to": 200 }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_report_of_valid_proof() {
+        let proof = _proof(Some(150), &encode("Alice").unwrap());
+        let report = build_report(
+            &proof,
+            &_proof_request("V4SGRU86Z58d6TV7PBUe6f"),
+            &AttributeEncodingRegistry::new(),
+            true,
+        )
+        .unwrap();
+
+        assert!(report.failures.is_empty());
+        assert_eq!(report.revealed_attributes["name_ref"].values["name"].raw, "Alice");
+        assert!(report.revealed_attributes["name_ref"].values["name"].encoding_valid);
+        assert_eq!(report.revealed_attributes["address_ref"].values["city"].raw, "1234");
+        assert_eq!(report.self_attested_attributes["nickname_ref"], "Al");
+        assert!(report.predicates["age_ref"].passed);
+        assert_eq!(
+            report.identifiers[0].non_revocation,
+            NonRevocationReport::ProvenAt { timestamp: 150 }
+        );
+    }
+
+    #[test]
+    fn test_report_lists_failures() {
+        let proof = _proof(None, &encode("Bob").unwrap());
+        let report = build_report(
+            &proof,
+            &_proof_request("8XFh8yBzrpJQmNyZzgoTqB"),
+            &AttributeEncodingRegistry::new(),
+            true,
+        )
+        .unwrap();
+
+        assert_eq!(report.identifiers[0].non_revocation, NonRevocationReport::NotProven);
+        assert!(report.failures.contains(&VerificationFailure::EncodingMismatch {
+            referent: "name_ref".to_string(),
+            attribute: "name".to_string(),
+            expected_encoded: encode("Alice").unwrap(),
+            encoded: encode("Bob").unwrap(),
+        }));
+        assert!(report.failures.contains(&VerificationFailure::RestrictionNotMet {
+            referent: "name_ref".to_string(),
+            failed_restrictions: vec![
+                vec![FailedCondition {
+                    condition: "issuer_did".to_string(),
+                    expected: "8XFh8yBzrpJQmNyZzgoTqB".to_string(),
+                    actual: Some("V4SGRU86Z58d6TV7PBUe6f".to_string()),
+                }],
+                vec![FailedCondition {
+                    condition: "schema_name".to_string(),
+                    expected: "passport".to_string(),
+                    actual: Some("licence".to_string()),
+                }],
+            ],
+        }));
+        assert!(report.failures.contains(&VerificationFailure::NonRevocationNotProven {
+            referent: "age_ref".to_string(),
+            interval: NonRevokedInterval {
+                from: Some(100),
+                to: Some(200)
+            },
+            timestamp: None,
+        }));
+    }
+
+    #[test]
+    fn test_report_rejects_timestamp_outside_interval() {
+        let interval = NonRevokedInterval {
+            from: Some(100),
+            to: Some(200),
+        };
+        for timestamp in [50, 250] {
+            let proof = _proof(Some(timestamp), &encode("Alice").unwrap());
+            let report = build_report(
+                &proof,
+                &_proof_request("V4SGRU86Z58d6TV7PBUe6f"),
+                &AttributeEncodingRegistry::new(),
+                true,
+            )
+            .unwrap();
+
+            assert!(report.failures.contains(&VerificationFailure::NonRevocationNotProven {
+                referent: "age_ref".to_string(),
+                interval: interval.clone(),
+                timestamp: Some(timestamp),
+            }));
+        }
+    }
+
+    #[test]
+    fn test_report_missing_referent() {
+        let proof = _proof(Some(150), &encode("Alice").unwrap());
+        let mut proof_request = _proof_request("V4SGRU86Z58d6TV7PBUe6f");
+        proof_request.requested_attributes.insert(
+            "email_ref".to_string(),
+            serde_json::from_value(json!({ "name": "email" })).unwrap(),
+        );
+
+        let report = build_report(&proof, &proof_request, &AttributeEncodingRegistry::new(), false).unwrap();
+
+        assert_eq!(
+            report.failures,
+            vec![VerificationFailure::MissingReferent {
+                referent: "email_ref".to_string()
+            }]
+        );
+        assert!(!report.predicates["age_ref"].passed);
+    }
+
+    #[test]
+    fn test_report_uses_declared_encodings() {
+        let proof = _proof(Some(150), &encode("Alice").unwrap());
+        let registry =
+            AttributeEncodingRegistry::new().with_encoding(SCHEMA_ID, "city", AttributeEncoding::Decimal { scale: 3 });
+
+        let report = build_report(&proof, &_proof_request("V4SGRU86Z58d6TV7PBUe6f"), &registry, true).unwrap();
+
+        assert!(report.revealed_attributes["name_ref"].values["name"].encoding_valid);
+        assert!(!report.revealed_attributes["address_ref"].values["city"].encoding_valid);
+        assert_eq!(
+            report.failures,
+            vec![VerificationFailure::EncodingMismatch {
+                referent: "address_ref".to_string(),
+                attribute: "city".to_string(),
+                expected_encoded: "1234000".to_string(),
+                encoded: "1234".to_string(),
+            }]
+        );
+    }
+}
@@ -1,8 +1,11 @@
 use std::sync::Arc;
 
+use aries_vcx_core::anoncreds::types::{AnonCredsObject, Proof, ProofRequest};
+
+use crate::common::credentials::encoding::AttributeEncodingRegistry;
 use crate::common::proofs::verifier::verifier_internal::{
     build_cred_defs_json_verifier, build_rev_reg_defs_json, build_rev_reg_json, build_schemas_json_verifier,
-    get_credential_info, validate_proof_revealed_attributes,
+    get_credential_info, validate_proof_revealed_attributes_with_encodings,
 };
 use crate::core::profile::profile::Profile;
 use crate::errors::error::prelude::*;
@@ -17,10 +20,36 @@ pub async fn validate_indy_proof(
         return mock_result;
     }
 
-    let anoncreds = Arc::clone(profile).inject_anoncreds();
-    validate_proof_revealed_attributes(proof_json)?;
+    let proof = Proof::from_json(proof_json)?;
+    let proof_request = ProofRequest::from_json(proof_req_json)?;
+    let registry = load_proof_encodings(profile, &proof).await?;
+    validate_proof_revealed_attributes_with_encodings(proof_json, Some(&proof_request), &registry)?;
+
+    verify_proof_against_ledger(profile, &proof, proof_json, proof_req_json).await
+}
+
+// loads the attribute encodings of the schemas of the credentials the proof was built from
+pub(super) async fn load_proof_encodings(
+    profile: &Arc<dyn Profile>,
+    proof: &Proof,
+) -> VcxResult<AttributeEncodingRegistry> {
+    let schema_ids: Vec<&str> = proof
+        .identifiers
+        .iter()
+        .map(|identifier| identifier.schema_id.as_str())
+        .collect();
+    AttributeEncodingRegistry::load(profile, &schema_ids).await
+}
 
-    let credential_data = get_credential_info(proof_json)?;
+// verifies the proof with the anoncreds backend, using the ledger objects referenced by its identifiers
+pub(super) async fn verify_proof_against_ledger(
+    profile: &Arc<dyn Profile>,
+    proof: &Proof,
+    proof_json: &str,
+    proof_req_json: &str,
+) -> VcxResult<bool> {
+    let anoncreds = Arc::clone(profile).inject_anoncreds();
+    let credential_data = get_credential_info(proof);
 
     let credential_defs_json = build_cred_defs_json_verifier(profile, &credential_data)
         .await
@@ -1,13 +1,14 @@
 use std::sync::Arc;
 
+use aries_vcx_core::anoncreds::types::{Proof, ProofRequest};
 use aries_vcx_core::errors::error::AriesVcxCoreErrorKind;
 use serde_json;
 use serde_json::Value;
 
+use crate::common::credentials::encoding::AttributeEncodingRegistry;
 use crate::core::profile::profile::Profile;
 use crate::errors::error::prelude::*;
 use crate::global::settings;
-use crate::utils::openssl::encode;
 
 #[derive(Debug, Deserialize, Serialize, PartialEq, Eq)]
 pub struct CredInfoVerifier {
@@ -17,43 +18,30 @@ pub struct CredInfoVerifier {
     pub timestamp: Option<u64>,
 }
 
-pub fn get_credential_info(proof: &str) -> VcxResult<Vec<CredInfoVerifier>> {
-    let mut rtn = Vec::new();
-
-    let credentials: Value = serde_json::from_str(proof).map_err(|err| {
-        AriesVcxError::from_msg(
-            AriesVcxErrorKind::InvalidJson,
-            format!("Cannot deserialize libndy proof: {}", err),
-        )
-    })?;
-
-    if let Value::Array(ref identifiers) = credentials["identifiers"] {
-        for identifier in identifiers {
-            if let (Some(schema_id), Some(cred_def_id)) =
-                (identifier["schema_id"].as_str(), identifier["cred_def_id"].as_str())
-            {
-                let rev_reg_id = identifier["rev_reg_id"].as_str().map(|x| x.to_string());
-
-                let timestamp = identifier["timestamp"].as_u64();
-                rtn.push(CredInfoVerifier {
-                    schema_id: schema_id.to_string(),
-                    cred_def_id: cred_def_id.to_string(),
-                    rev_reg_id,
-                    timestamp,
-                });
-            } else {
-                return Err(AriesVcxError::from_msg(
-                    AriesVcxErrorKind::InvalidProofCredentialData,
-                    "Cannot get identifiers",
-                ));
-            }
-        }
-    }
-
-    Ok(rtn)
+pub fn get_credential_info(proof: &Proof) -> Vec<CredInfoVerifier> {
+    proof
+        .identifiers
+        .iter()
+        .map(|identifier| CredInfoVerifier {
+            schema_id: identifier.schema_id.clone(),
+            cred_def_id: identifier.cred_def_id.clone(),
+            rev_reg_id: identifier.rev_reg_id.clone(),
+            timestamp: identifier.timestamp,
+        })
+        .collect()
 }
 
 pub fn validate_proof_revealed_attributes(proof_json: &str) -> VcxResult<()> {
+    validate_proof_revealed_attributes_with_encodings(proof_json, None, &AttributeEncodingRegistry::new())
+}
+
+/// Validates the encoded values of the revealed attributes, using the encodings declared in `registry` for the
+/// schema of the credential each attribute was revealed from. Attribute names are taken from `proof_request`.
+pub fn validate_proof_revealed_attributes_with_encodings(
+    proof_json: &str,
+    proof_request: Option<&ProofRequest>,
+    registry: &AttributeEncodingRegistry,
+) -> VcxResult<()> {
     if settings::indy_mocks_enabled() {
         return Ok(());
     }
@@ -80,9 +68,17 @@ pub fn validate_proof_revealed_attributes(proof_json: &str) -> VcxResult<()> {
             format!("Cannot get encoded value for \"{}\" attribute", attr1_referent),
         ))?;
 
-        let expected_encoded = encode(raw)?;
-
-        if expected_encoded != *encoded_ {
+        let attribute = proof_request
+            .and_then(|proof_request| proof_request.requested_attributes.get(attr1_referent))
+            .and_then(|requested_attribute| requested_attribute.name.as_deref())
+            .unwrap_or_default();
+        let schema_id = info["sub_proof_index"]
+            .as_u64()
+            .and_then(|index| proof["identifiers"][index as usize]["schema_id"].as_str())
+            .unwrap_or_default();
+
+        if !registry.matches(schema_id, attribute, raw, encoded_) {
+            let expected_encoded = registry.encode(schema_id, attribute, raw)?;
             return Err(AriesVcxError::from_msg(
                 AriesVcxErrorKind::InvalidProof,
                 format!(
@@ -107,16 +103,9 @@ pub async fn build_cred_defs_json_verifier(
     for cred_info in credential_data.iter() {
         if credential_json.get(&cred_info.cred_def_id).is_none() {
             let cred_def_id = &cred_info.cred_def_id;
-            let credential_def = ledger.get_cred_def(cred_def_id, None).await?;
-
-            let credential_def = serde_json::from_str(&credential_def).map_err(|err| {
-                AriesVcxError::from_msg(
-                    AriesVcxErrorKind::InvalidProofCredentialData,
-                    format!("Cannot deserialize credential definition: {}", err),
-                )
-            })?;
+            let credential_def = ledger.get_cred_def_object(cred_def_id, None).await?;
 
-            credential_json[cred_def_id] = credential_def;
+            credential_json[cred_def_id] = json!(credential_def);
         }
     }
 
@@ -135,17 +124,11 @@ pub async fn build_schemas_json_verifier(
     for cred_info in credential_data.iter() {
         if schemas_json.get(&cred_info.schema_id).is_none() {
             let schema_id = &cred_info.schema_id;
-            let schema_json = ledger
-                .get_schema(schema_id, None)
+            let schema = ledger
+                .get_schema_object(schema_id, None)
                 .await
                 .map_err(|err| err.map(AriesVcxCoreErrorKind::InvalidSchema, "Cannot get schema"))?;
-            let schema_val = serde_json::from_str(&schema_json).map_err(|err| {
-                AriesVcxError::from_msg(
-                    AriesVcxErrorKind::InvalidSchema,
-                    format!("Cannot deserialize schema: {}", err),
-                )
-            })?;
-            schemas_json[schema_id] = schema_val;
+            schemas_json[schema_id] = json!(schema);
         }
     }
 
@@ -168,12 +151,8 @@ pub async fn build_rev_reg_defs_json(
         ))?;
 
         if rev_reg_defs_json.get(rev_reg_id).is_none() {
-            let json = ledger.get_rev_reg_def_json(rev_reg_id).await?;
-            let rev_reg_def_json = serde_json::from_str(&json).or(Err(AriesVcxError::from_msg(
-                AriesVcxErrorKind::InvalidJson,
-                format!("Failed to deserialize as json rev_reg_def: {}", json),
-            )))?;
-            rev_reg_defs_json[rev_reg_id] = rev_reg_def_json;
+            let rev_reg_def = ledger.get_rev_reg_def_object(rev_reg_id).await?;
+            rev_reg_defs_json[rev_reg_id] = json!(rev_reg_def);
         }
     }
 
@@ -198,12 +177,8 @@ pub async fn build_rev_reg_json(profile: &Arc<dyn Profile>, credential_data: &[C
         ))?;
 
         if rev_regs_json.get(rev_reg_id).is_none() {
-            let (id, rev_reg_json, timestamp) = ledger.get_rev_reg(rev_reg_id, timestamp.to_owned()).await?;
-            let rev_reg_json: Value = serde_json::from_str(&rev_reg_json).or(Err(AriesVcxError::from_msg(
-                AriesVcxErrorKind::InvalidJson,
-                format!("Failed to deserialize as json: {}", rev_reg_json),
-            )))?;
-            let rev_reg_json = json!({ timestamp.to_string(): rev_reg_json });
+            let (id, rev_reg, timestamp) = ledger.get_rev_reg_object(rev_reg_id, timestamp.to_owned()).await?;
+            let rev_reg_json = json!({ timestamp.to_string(): rev_reg });
             rev_regs_json[id] = rev_reg_json;
         }
     }
@@ -2,10 +2,14 @@ use std::sync::Arc;
 
 use aries_vcx_core::wallet::base_wallet::BaseWallet;
 use base64;
+use diddoc::aries::diddoc::AriesDidDoc;
+use messages::decorators::attachment::{Attachment, AttachmentData, AttachmentType};
+use messages::misc::MimeType;
 use messages::msg_fields::protocols::connection::response::{ConnectionSignature, ResponseContent};
 use messages::msg_fields::protocols::connection::ConnectionData;
 use time;
 
+use crate::common::ledger::transactions::ed25519_public_key_to_did_key;
 use crate::errors::error::prelude::*;
 use crate::global::settings;
 
@@ -78,6 +82,102 @@ pub async fn decode_signed_connection_response(
     Ok(connection)
 }
 
+/// Wraps `did_doc` into a base64url encoded attachment signed by `key`, as used by the
+/// `did_doc~attach` field of DID exchange messages.
+///
+/// The signature is stored as a detached compact JWS (`<protected>..<signature>`) using `EdDSA`.
+pub async fn sign_did_doc_attachment(
+    wallet: &Arc<dyn BaseWallet>,
+    key: &str,
+    did_doc: &AriesDidDoc,
+) -> VcxResult<Attachment> {
+    let payload = base64::encode_config(json!(did_doc).to_string().as_bytes(), base64::URL_SAFE_NO_PAD);
+    let protected = json!({
+        "alg": "EdDSA",
+        "kid": ed25519_public_key_to_did_key(key)?
+    })
+    .to_string();
+    let protected = base64::encode_config(protected.as_bytes(), base64::URL_SAFE_NO_PAD);
+
+    let signature = wallet.sign(key, format!("{protected}.{payload}").as_bytes()).await?;
+    let signature = base64::encode_config(&signature, base64::URL_SAFE_NO_PAD);
+
+    let mut data = AttachmentData::new(AttachmentType::Base64(payload));
+    data.jws = Some(format!("{protected}..{signature}"));
+
+    let mut attachment = Attachment::new(data);
+    attachment.mime_type = Some(MimeType::Json);
+
+    Ok(attachment)
+}
+
+/// Verifies that the `did_doc~attach` `attachment` was signed by `their_vk` and returns the attached [AriesDidDoc].
+pub async fn verify_did_doc_attachment(
+    wallet: &Arc<dyn BaseWallet>,
+    attachment: &Attachment,
+    their_vk: &str,
+) -> VcxResult<AriesDidDoc> {
+    let AttachmentType::Base64(payload) = &attachment.data.content else {
+        return Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidJson,
+            "Signed DIDDoc attachment must be base64 encoded",
+        ));
+    };
+
+    let Some((protected, signature)) = attachment.data.jws.as_deref().and_then(|jws| jws.split_once("..")) else {
+        return Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidJson,
+            "DIDDoc attachment is not signed",
+        ));
+    };
+
+    let signature = base64::decode_config(signature, base64::URL_SAFE_NO_PAD).map_err(|err| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidJson,
+            format!("Cannot decode DIDDoc attachment signature: {:?}", err),
+        )
+    })?;
+
+    let payload = payload.trim_end_matches('=');
+    if !wallet
+        .verify(their_vk, format!("{protected}.{payload}").as_bytes(), &signature)
+        .await?
+    {
+        return Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidJson,
+            "DIDDoc attachment signature is invalid for the expected signer",
+        ));
+    }
+
+    decode_did_doc_attachment(attachment)
+}
+
+/// Returns the [AriesDidDoc] carried by a `did_doc~attach` `attachment`, without checking its signature.
+pub fn decode_did_doc_attachment(attachment: &Attachment) -> VcxResult<AriesDidDoc> {
+    let did_doc = match &attachment.data.content {
+        AttachmentType::Base64(payload) => {
+            let bytes = base64::decode_config(payload.trim_end_matches('='), base64::URL_SAFE_NO_PAD)
+                .or_else(|_| base64::decode(payload))
+                .map_err(|err| {
+                    AriesVcxError::from_msg(
+                        AriesVcxErrorKind::InvalidJson,
+                        format!("Cannot decode DIDDoc attachment: {:?}", err),
+                    )
+                })?;
+            serde_json::from_slice(&bytes)
+        }
+        AttachmentType::Json(value) => serde_json::from_value(value.clone()),
+        AttachmentType::Links(_) => {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidJson,
+                "Linked DIDDoc attachments are not supported",
+            ))
+        }
+    };
+
+    did_doc.map_err(|err| AriesVcxError::from_msg(AriesVcxErrorKind::InvalidJson, err.to_string()))
+}
+
 pub async fn unpack_message_to_string(wallet: &Arc<dyn BaseWallet>, msg: &[u8]) -> VcxResult<String> {
     if settings::indy_mocks_enabled() {
         return Ok(String::new());
@@ -158,3 +258,49 @@ pub async fn unpack_message_to_string(wallet: &Arc<dyn BaseWallet>, msg: &[u8])
 //         .await;
 //     }
 // }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+pub mod unit_tests {
+    use aries_vcx_core::wallet::in_memory_wallet::InMemoryWallet;
+    use diddoc::aries::diddoc::AriesDidDoc;
+
+    use super::*;
+
+    fn _con_data() -> ConnectionData {
+        ConnectionData::new("VsKV7grR1BUE29mG2Fm2kX".to_string(), AriesDidDoc::default())
+    }
+
+    async fn _signed_response(wallet: &Arc<dyn BaseWallet>, key: &str) -> ResponseContent {
+        let connection_sig = sign_connection_response(wallet, key, &_con_data()).await.unwrap();
+        ResponseContent::new(connection_sig)
+    }
+
+    #[tokio::test]
+    async fn test_response_encode_works() {
+        let wallet: Arc<dyn BaseWallet> = Arc::new(InMemoryWallet::new());
+        let (_, key) = wallet.create_and_store_my_did(None, None).await.unwrap();
+
+        let response = _signed_response(&wallet, &key).await;
+
+        assert_eq!(
+            _con_data(),
+            decode_signed_connection_response(&wallet, response, &key)
+                .await
+                .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_decode_returns_error_if_signer_differs() {
+        let wallet: Arc<dyn BaseWallet> = Arc::new(InMemoryWallet::new());
+        let (_, key) = wallet.create_and_store_my_did(None, None).await.unwrap();
+        let (_, other_key) = wallet.create_and_store_my_did(None, None).await.unwrap();
+
+        let response = _signed_response(&wallet, &key).await;
+
+        decode_signed_connection_response(&wallet, response, &other_key)
+            .await
+            .unwrap_err();
+    }
+}
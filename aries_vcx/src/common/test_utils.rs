@@ -1,11 +1,12 @@
 #![allow(clippy::unwrap_used)]
 
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::Duration;
 
 use aries_vcx_core::PoolHandle;
 use aries_vcx_core::WalletHandle;
+use messages::AriesMessage;
 
 use crate::common::credentials::encoding::encode_attributes;
 use crate::common::primitives::credential_definition::CredentialDef;
@@ -14,10 +15,12 @@ use crate::common::primitives::revocation_registry::RevocationRegistry;
 use crate::core::profile::profile::Profile;
 #[cfg(feature = "vdrtools")]
 use crate::core::profile::vdrtools_profile::VdrtoolsProfile;
+use crate::errors::error::VcxResult;
 use crate::global::settings;
+use crate::protocols::SendClosure;
 use crate::utils::constants::{DEFAULT_SCHEMA_ATTRS, TAILS_DIR, TEST_TAILS_URL, TRUSTEE_SEED};
 use crate::utils::get_temp_dir_path;
-use crate::utils::mockdata::profile::mock_profile::MockProfile;
+use crate::utils::mockdata::profile::mock_profile::{InMemoryWalletProfile, MockProfile};
 
 pub async fn create_schema(profile: &Arc<dyn Profile>, attr_list: &str, submitter_did: &str) -> (String, String) {
     let data = attr_list.to_string();
@@ -439,6 +442,34 @@ pub fn mock_profile() -> Arc<dyn Profile> {
     Arc::new(MockProfile {})
 }
 
+// used for profiles with mocked ledger and anoncreds, but a working in-memory wallet
+pub fn in_memory_wallet_profile() -> Arc<dyn Profile> {
+    Arc::new(InMemoryWalletProfile::new())
+}
+
+// collects the messages a protocol under test sends to its counterparty
+#[derive(Clone, Default)]
+pub struct Outbox(Arc<Mutex<Vec<AriesMessage>>>);
+
+impl Outbox {
+    pub fn send_closure(&self) -> SendClosure {
+        let messages = Arc::clone(&self.0);
+        Box::new(move |message: AriesMessage| {
+            messages.lock().unwrap().push(message);
+            Box::pin(async { VcxResult::Ok(()) })
+        })
+    }
+
+    // takes the last sent message, converted to the action of the receiving protocol
+    pub fn pop<T: From<AriesMessage>>(&self) -> T {
+        self.0.lock().unwrap().pop().expect("No message was sent").into()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.lock().unwrap().is_empty()
+    }
+}
+
 // TODO - FUTURE - should only be used for quick mock setups, should be removable after full detachment from vdrtools dep
 #[cfg(feature = "vdrtools")]
 pub fn indy_handles_to_profile(wallet_handle: WalletHandle, pool_handle: PoolHandle) -> Arc<dyn Profile> {
@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use aries_vcx_core::anoncreds::anoncreds_rs_anoncreds::AnoncredsRsAnonCreds;
+use aries_vcx_core::anoncreds::base_anoncreds::BaseAnonCreds;
+use aries_vcx_core::ledger::base_ledger::BaseLedger;
+use aries_vcx_core::wallet::base_wallet::BaseWallet;
+
+use super::profile::Profile;
+
+/// Profile using anoncreds-rs for anoncreds operations. As the AnonCreds specification is ledger agnostic,
+/// the ledger is provided by the caller, e.g. an [aries_vcx_core::ledger::multi_ledger::MultiLedger].
+#[derive(Debug)]
+pub struct AnoncredsRsProfile {
+    wallet: Arc<dyn BaseWallet>,
+    ledger: Arc<dyn BaseLedger>,
+    anoncreds: Arc<dyn BaseAnonCreds>,
+}
+
+impl AnoncredsRsProfile {
+    pub fn new(wallet: Arc<dyn BaseWallet>, ledger: Arc<dyn BaseLedger>) -> Self {
+        let anoncreds = Arc::new(AnoncredsRsAnonCreds::new_with_ledger(
+            Arc::clone(&wallet),
+            Arc::clone(&ledger),
+        ));
+        AnoncredsRsProfile {
+            wallet,
+            ledger,
+            anoncreds,
+        }
+    }
+}
+
+impl Profile for AnoncredsRsProfile {
+    fn inject_ledger(self: Arc<Self>) -> Arc<dyn BaseLedger> {
+        Arc::clone(&self.ledger)
+    }
+
+    fn inject_anoncreds(self: Arc<Self>) -> Arc<dyn BaseAnonCreds> {
+        Arc::clone(&self.anoncreds)
+    }
+
+    fn inject_wallet(&self) -> Arc<dyn BaseWallet> {
+        Arc::clone(&self.wallet)
+    }
+}
@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use aries_vcx_core::{
+    anoncreds::base_anoncreds::BaseAnonCreds,
+    ledger::{
+        base_ledger::BaseLedger,
+        caching_ledger::{CachingLedger, LedgerCacheConfig},
+    },
+    wallet::base_wallet::BaseWallet,
+};
+
+use super::profile::Profile;
+
+/// [Profile] wrapping any other profile, serving its ledger reads through a [CachingLedger].
+/// The wallet and anoncreds of the wrapped profile are injected as they are.
+#[derive(Debug)]
+pub struct CachingLedgerProfile {
+    inner: Arc<dyn Profile>,
+    ledger: Arc<dyn BaseLedger>,
+}
+
+impl CachingLedgerProfile {
+    pub fn new(inner: Arc<dyn Profile>, config: LedgerCacheConfig) -> Self {
+        let ledger = Arc::new(CachingLedger::new(Arc::clone(&inner).inject_ledger(), config));
+        CachingLedgerProfile { inner, ledger }
+    }
+}
+
+impl Profile for CachingLedgerProfile {
+    fn inject_ledger(self: Arc<Self>) -> Arc<dyn BaseLedger> {
+        Arc::clone(&self.ledger)
+    }
+
+    fn inject_anoncreds(self: Arc<Self>) -> Arc<dyn BaseAnonCreds> {
+        Arc::clone(&self.inner).inject_anoncreds()
+    }
+
+    fn inject_wallet(&self) -> Arc<dyn BaseWallet> {
+        self.inner.inject_wallet()
+    }
+}
@@ -1,3 +1,6 @@
+#[cfg(feature = "anoncreds_rs")]
+pub mod anoncreds_rs_profile;
+pub mod caching_ledger_profile;
 #[cfg(feature = "modular_libs")]
 pub mod modular_libs_profile;
 pub mod profile;
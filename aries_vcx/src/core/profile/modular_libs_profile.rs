@@ -29,6 +29,17 @@ impl ModularLibsProfile {
             anoncreds,
         })
     }
+
+    /// Creates a profile reading from and writing to `ledger` instead of an indy pool, e.g. a
+    /// [aries_vcx_core::ledger::file_ledger::FileLedger].
+    pub fn new_with_ledger(wallet: Arc<dyn BaseWallet>, ledger: Arc<dyn BaseLedger>) -> Self {
+        let anoncreds = Arc::new(IndyCredxAnonCreds::new(Arc::clone(&wallet)));
+        ModularLibsProfile {
+            wallet,
+            ledger,
+            anoncreds,
+        }
+    }
 }
 
 impl Profile for ModularLibsProfile {
@@ -4,7 +4,7 @@ use aries_vcx_core::{
     anoncreds::{base_anoncreds::BaseAnonCreds, indy_anoncreds::IndySdkAnonCreds},
     ledger::{base_ledger::BaseLedger, indy_ledger::IndySdkLedger},
     wallet::{base_wallet::BaseWallet, indy_wallet::IndySdkWallet},
-    PoolHandle, WalletHandle,
+    PoolHandle, WalletHandle, INVALID_POOL_HANDLE,
 };
 
 use super::profile::Profile;
@@ -27,6 +27,19 @@ impl VdrtoolsProfile {
             anoncreds,
         }
     }
+
+    /// Creates a profile reading from and writing to `ledger` instead of an indy pool, e.g. a
+    /// [aries_vcx_core::ledger::file_ledger::FileLedger]. As no pool is open, local revocations
+    /// can't be published through [BaseAnonCreds::publish_local_revocations].
+    pub fn new_with_ledger(indy_wallet_handle: WalletHandle, ledger: Arc<dyn BaseLedger>) -> Self {
+        let wallet = Arc::new(IndySdkWallet::new(indy_wallet_handle));
+        let anoncreds = Arc::new(IndySdkAnonCreds::new(indy_wallet_handle, INVALID_POOL_HANDLE));
+        VdrtoolsProfile {
+            wallet,
+            ledger,
+            anoncreds,
+        }
+    }
 }
 
 impl Profile for VdrtoolsProfile {
@@ -16,6 +16,7 @@ use crate::handlers::connection::mediated_connection::MediatedConnection;
 use crate::handlers::revocation_notification::receiver::RevocationNotificationReceiver;
 use crate::protocols::issuance::actions::CredentialIssuanceAction;
 use crate::protocols::issuance::holder::state_machine::{HolderSM, HolderState};
+use crate::protocols::issuance::negotiation::{HolderNegotiationHook, OfferDecision};
 use crate::protocols::SendClosure;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -76,6 +77,22 @@ impl Holder {
         Ok(())
     }
 
+    /// Answers the received credential offer as decided by `hook`: requests the offered credential, proposes a
+    /// credential with changed values, or declines the offer.
+    pub async fn negotiate_offer(
+        &mut self,
+        profile: &Arc<dyn Profile>,
+        hook: &dyn HolderNegotiationHook,
+        send_message: SendClosure,
+    ) -> VcxResult<()> {
+        let offer = self.get_offer()?;
+        match hook.on_offer(&offer) {
+            OfferDecision::Accept { my_pw_did } => self.send_request(profile, my_pw_did, send_message).await,
+            OfferDecision::Counter(proposal) => self.send_proposal(proposal, send_message).await,
+            OfferDecision::Decline { comment } => self.decline_offer(comment.as_deref(), send_message).await,
+        }
+    }
+
     pub async fn process_credential(
         &mut self,
         profile: &Arc<dyn Profile>,
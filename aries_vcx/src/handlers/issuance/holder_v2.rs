@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use agency_client::agency_client::AgencyClient;
+use messages::msg_fields::protocols::cred_issuance::v2::issue_credential::IssueCredentialV2;
+use messages::msg_fields::protocols::cred_issuance::v2::offer_credential::OfferCredentialV2;
+use messages::msg_fields::protocols::cred_issuance::v2::propose_credential::ProposeCredentialV2;
+use messages::AriesMessage;
+
+use crate::core::profile::profile::Profile;
+use crate::errors::error::prelude::*;
+use crate::handlers::connection::mediated_connection::MediatedConnection;
+use crate::protocols::issuance::holder::state_machine::HolderState;
+use crate::protocols::issuance::v2::holder::HolderV2SM;
+use crate::protocols::issuance::v2::CredentialIssuanceV2Action;
+use crate::protocols::SendClosure;
+
+/// Holder of AnonCreds credentials received over issue-credential 2.0.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct HolderV2 {
+    holder_sm: HolderV2SM,
+}
+
+impl HolderV2 {
+    pub fn create(source_id: &str) -> VcxResult<HolderV2> {
+        trace!("HolderV2::create >>> source_id: {:?}", source_id);
+        let holder_sm = HolderV2SM::new(source_id.to_string());
+        Ok(HolderV2 { holder_sm })
+    }
+
+    pub fn create_from_offer(source_id: &str, credential_offer: OfferCredentialV2) -> VcxResult<HolderV2> {
+        trace!(
+            "HolderV2::create_from_offer >>> source_id: {:?}, credential_offer: {:?}",
+            source_id,
+            credential_offer
+        );
+        let holder_sm = HolderV2SM::from_offer(credential_offer, source_id.to_string());
+        Ok(HolderV2 { holder_sm })
+    }
+
+    pub async fn send_proposal(
+        &mut self,
+        credential_proposal: ProposeCredentialV2,
+        send_message: SendClosure,
+    ) -> VcxResult<()> {
+        self.holder_sm = self
+            .holder_sm
+            .clone()
+            .send_proposal(credential_proposal, send_message)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn send_request(
+        &mut self,
+        profile: &Arc<dyn Profile>,
+        my_pw_did: String,
+        send_message: SendClosure,
+    ) -> VcxResult<()> {
+        self.holder_sm = self
+            .holder_sm
+            .clone()
+            .send_request(profile, my_pw_did, send_message)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn decline_offer(&mut self, comment: Option<String>, send_message: SendClosure) -> VcxResult<()> {
+        self.holder_sm = self.holder_sm.clone().decline_offer(comment, send_message).await?;
+        Ok(())
+    }
+
+    pub async fn process_credential(
+        &mut self,
+        profile: &Arc<dyn Profile>,
+        credential: IssueCredentialV2,
+        send_message: SendClosure,
+    ) -> VcxResult<()> {
+        self.holder_sm = self
+            .holder_sm
+            .clone()
+            .receive_credential(profile, credential, send_message)
+            .await?;
+        Ok(())
+    }
+
+    pub fn get_state(&self) -> HolderState {
+        self.holder_sm.get_state()
+    }
+
+    pub fn get_source_id(&self) -> String {
+        self.holder_sm.get_source_id()
+    }
+
+    pub fn get_thread_id(&self) -> VcxResult<String> {
+        self.holder_sm.get_thread_id()
+    }
+
+    pub fn get_offer(&self) -> VcxResult<OfferCredentialV2> {
+        self.holder_sm.get_offer()
+    }
+
+    pub fn get_attributes(&self) -> VcxResult<String> {
+        self.holder_sm.get_attributes()
+    }
+
+    pub fn get_credential(&self) -> VcxResult<(String, AriesMessage)> {
+        let (cred_id, credential) = self.holder_sm.get_credential()?;
+        Ok((cred_id, credential.into()))
+    }
+
+    pub fn get_cred_id(&self) -> VcxResult<String> {
+        self.holder_sm.get_cred_id()
+    }
+
+    pub fn get_credential_status(&self) -> VcxResult<u32> {
+        Ok(self.holder_sm.credential_status())
+    }
+
+    pub fn is_terminal_state(&self) -> bool {
+        self.holder_sm.is_terminal_state()
+    }
+
+    pub fn find_message_to_handle(&self, messages: HashMap<String, AriesMessage>) -> Option<(String, AriesMessage)> {
+        self.holder_sm.find_message_to_handle(messages)
+    }
+
+    pub async fn step(
+        &mut self,
+        profile: &Arc<dyn Profile>,
+        message: CredentialIssuanceV2Action,
+        send_message: Option<SendClosure>,
+    ) -> VcxResult<()> {
+        self.holder_sm = self
+            .holder_sm
+            .clone()
+            .handle_message(profile, message, send_message)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn update_state(
+        &mut self,
+        profile: &Arc<dyn Profile>,
+        agency_client: &AgencyClient,
+        connection: &MediatedConnection,
+    ) -> VcxResult<HolderState> {
+        trace!("HolderV2::update_state >>>");
+        if self.is_terminal_state() {
+            return Ok(self.get_state());
+        }
+        let send_message = connection.send_message_closure(profile).await?;
+        let messages = connection.get_messages(agency_client).await?;
+        if let Some((uid, msg)) = self.find_message_to_handle(messages) {
+            self.step(profile, msg.into(), Some(send_message)).await?;
+            connection.update_message_status(&uid, agency_client).await?;
+        }
+        Ok(self.get_state())
+    }
+}
@@ -18,6 +18,7 @@ use crate::handlers::revocation_notification::sender::RevocationNotificationSend
 use crate::handlers::util::OfferInfo;
 use crate::protocols::issuance::actions::CredentialIssuanceAction;
 use crate::protocols::issuance::issuer::state_machine::{IssuerSM, IssuerState, RevocationInfoV1};
+use crate::protocols::issuance::negotiation::{offer_info_from_proposal, IssuerNegotiationHook, ProposalDecision};
 use crate::protocols::revocation_notification::sender::state_machine::SenderConfigBuilder;
 use crate::protocols::SendClosure;
 
@@ -33,7 +34,7 @@ pub struct IssuerConfig {
     pub tails_file: Option<String>,
 }
 
-fn _build_credential_preview(credential_json: &str) -> VcxResult<CredentialPreview> {
+pub(super) fn _build_credential_preview(credential_json: &str) -> VcxResult<CredentialPreview> {
     trace!(
         "Issuer::_build_credential_preview >>> credential_json: {:?}",
         secret!(credential_json)
@@ -161,6 +162,33 @@ impl Issuer {
         Ok(())
     }
 
+    pub async fn decline_proposal(&mut self, comment: Option<String>, send_message: SendClosure) -> VcxResult<()> {
+        self.issuer_sm = self.issuer_sm.clone().decline_proposal(comment, send_message).await?;
+        Ok(())
+    }
+
+    /// Answers the received credential proposal as decided by `hook`: offers the proposed credential, offers a
+    /// credential with changed values, or declines the proposal.
+    pub async fn negotiate_proposal(
+        &mut self,
+        profile: &Arc<dyn Profile>,
+        hook: &dyn IssuerNegotiationHook,
+        send_message: SendClosure,
+    ) -> VcxResult<()> {
+        let proposal = self.get_proposal()?;
+        let (offer_info, comment) = match hook.on_proposal(&proposal) {
+            ProposalDecision::Accept {
+                rev_reg_id,
+                tails_file,
+                comment,
+            } => (offer_info_from_proposal(&proposal, rev_reg_id, tails_file)?, comment),
+            ProposalDecision::Counter { offer_info, comment } => (offer_info, comment),
+            ProposalDecision::Decline { comment } => return self.decline_proposal(comment, send_message).await,
+        };
+        self.build_credential_offer_msg(profile, offer_info, comment).await?;
+        self.send_credential_offer(send_message).await
+    }
+
     pub fn process_credential_request(&mut self, request: RequestCredential) -> VcxResult<()> {
         self.issuer_sm = self.issuer_sm.clone().receive_request(request)?;
         Ok(())
@@ -0,0 +1,171 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use agency_client::agency_client::AgencyClient;
+use messages::msg_fields::protocols::cred_issuance::v2::propose_credential::ProposeCredentialV2;
+use messages::msg_fields::protocols::cred_issuance::v2::CredentialPreviewV2;
+use messages::AriesMessage;
+
+use crate::core::profile::profile::Profile;
+use crate::errors::error::prelude::*;
+use crate::handlers::connection::mediated_connection::MediatedConnection;
+use crate::handlers::issuance::issuer::_build_credential_preview;
+use crate::handlers::util::OfferInfo;
+use crate::protocols::issuance::issuer::state_machine::IssuerState;
+use crate::protocols::issuance::v2::issuer::IssuerV2SM;
+use crate::protocols::issuance::v2::{AnoncredsAttachmentFormat, CredentialIssuanceV2Action};
+use crate::protocols::SendClosure;
+
+/// Issuer of AnonCreds credentials over issue-credential 2.0.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct IssuerV2 {
+    issuer_sm: IssuerV2SM,
+}
+
+impl IssuerV2 {
+    pub fn create(source_id: &str, format: AnoncredsAttachmentFormat) -> VcxResult<IssuerV2> {
+        trace!("IssuerV2::create >>> source_id: {:?}, format: {:?}", source_id, format);
+        let issuer_sm = IssuerV2SM::new(source_id, format);
+        Ok(IssuerV2 { issuer_sm })
+    }
+
+    pub fn create_from_proposal(source_id: &str, credential_proposal: &ProposeCredentialV2) -> VcxResult<IssuerV2> {
+        trace!(
+            "IssuerV2::create_from_proposal >>> source_id: {:?}, credential_proposal: {:?}",
+            source_id,
+            credential_proposal
+        );
+        let issuer_sm = IssuerV2SM::from_proposal(source_id, credential_proposal);
+        Ok(IssuerV2 { issuer_sm })
+    }
+
+    pub async fn build_credential_offer_msg(
+        &mut self,
+        profile: &Arc<dyn Profile>,
+        offer_info: OfferInfo,
+        comment: Option<String>,
+    ) -> VcxResult<()> {
+        let anoncreds = Arc::clone(profile).inject_anoncreds();
+        let credential_preview = _build_credential_preview(&offer_info.credential_json)?;
+        let cred_offer = anoncreds
+            .issuer_create_credential_offer(&offer_info.cred_def_id)
+            .await?;
+        self.issuer_sm = self.issuer_sm.clone().build_credential_offer_msg(
+            &cred_offer,
+            CredentialPreviewV2::new(credential_preview.attributes),
+            comment,
+            &offer_info,
+        )?;
+        Ok(())
+    }
+
+    pub fn get_credential_offer_msg(&self) -> VcxResult<AriesMessage> {
+        let offer = self.issuer_sm.get_credential_offer_msg()?;
+        Ok(offer.into())
+    }
+
+    pub fn mark_credential_offer_msg_sent(&mut self) -> VcxResult<()> {
+        self.issuer_sm = self.issuer_sm.clone().mark_credential_offer_msg_sent()?;
+        Ok(())
+    }
+
+    pub async fn send_credential_offer(&mut self, send_message: SendClosure) -> VcxResult<()> {
+        self.issuer_sm = self.issuer_sm.clone().send_credential_offer(send_message).await?;
+        Ok(())
+    }
+
+    pub async fn decline_proposal(&mut self, comment: Option<String>, send_message: SendClosure) -> VcxResult<()> {
+        self.issuer_sm = self.issuer_sm.clone().decline_proposal(comment, send_message).await?;
+        Ok(())
+    }
+
+    pub async fn send_credential(&mut self, profile: &Arc<dyn Profile>, send_message: SendClosure) -> VcxResult<()> {
+        self.issuer_sm = self.issuer_sm.clone().send_credential(profile, send_message).await?;
+        Ok(())
+    }
+
+    pub fn get_state(&self) -> IssuerState {
+        self.issuer_sm.get_state()
+    }
+
+    pub fn get_source_id(&self) -> VcxResult<String> {
+        Ok(self.issuer_sm.get_source_id())
+    }
+
+    pub fn get_thread_id(&self) -> VcxResult<String> {
+        self.issuer_sm.thread_id()
+    }
+
+    pub fn get_format(&self) -> AnoncredsAttachmentFormat {
+        self.issuer_sm.get_format()
+    }
+
+    pub fn get_proposal(&self) -> VcxResult<ProposeCredentialV2> {
+        self.issuer_sm.get_proposal()
+    }
+
+    pub fn get_rev_reg_id(&self) -> VcxResult<String> {
+        self.issuer_sm
+            .get_revocation_info()
+            .and_then(|info| info.rev_reg_id)
+            .ok_or(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidState,
+                "Credential has not yet been created or is irrevocable",
+            ))
+    }
+
+    pub fn get_rev_id(&self) -> VcxResult<String> {
+        self.issuer_sm
+            .get_revocation_info()
+            .and_then(|info| info.cred_rev_id)
+            .ok_or(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidState,
+                "Credential has not yet been created or is irrevocable",
+            ))
+    }
+
+    pub fn get_credential_status(&self) -> VcxResult<u32> {
+        Ok(self.issuer_sm.credential_status())
+    }
+
+    pub fn is_terminal_state(&self) -> bool {
+        self.issuer_sm.is_terminal_state()
+    }
+
+    pub fn find_message_to_handle(&self, messages: HashMap<String, AriesMessage>) -> Option<(String, AriesMessage)> {
+        self.issuer_sm.find_message_to_handle(messages)
+    }
+
+    pub async fn step(
+        &mut self,
+        profile: &Arc<dyn Profile>,
+        message: CredentialIssuanceV2Action,
+        send_message: Option<SendClosure>,
+    ) -> VcxResult<()> {
+        self.issuer_sm = self
+            .issuer_sm
+            .clone()
+            .handle_message(profile, message, send_message)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn update_state(
+        &mut self,
+        profile: &Arc<dyn Profile>,
+        agency_client: &AgencyClient,
+        connection: &MediatedConnection,
+    ) -> VcxResult<IssuerState> {
+        trace!("IssuerV2::update_state >>>");
+        if self.is_terminal_state() {
+            return Ok(self.get_state());
+        }
+        let send_message = connection.send_message_closure(profile).await?;
+        let messages = connection.get_messages(agency_client).await?;
+        if let Some((uid, msg)) = self.find_message_to_handle(messages) {
+            self.step(profile, msg.into(), Some(send_message)).await?;
+            connection.update_message_status(&uid, agency_client).await?;
+        }
+        Ok(self.get_state())
+    }
+}
@@ -1,2 +1,5 @@
 pub mod holder;
+pub mod holder_v2;
 pub mod issuer;
+pub mod issuer_v2;
+pub mod revocation_service;
@@ -0,0 +1,410 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use aries_vcx_core::errors::error::AriesVcxCoreErrorKind;
+use aries_vcx_core::wallet::base_wallet::AsyncFnIteratorCollect;
+use messages::decorators::please_ack::AckOn;
+
+use crate::common::primitives::revocation_registry::{
+    publish_local_revocations, revoke_credential_local, CATEGORY_PENDING_REVOCATION,
+};
+use crate::core::profile::profile::Profile;
+use crate::errors::error::prelude::*;
+use crate::handlers::revocation_notification::sender::RevocationNotificationSender;
+use crate::protocols::revocation_notification::sender::state_machine::SenderConfigBuilder;
+use crate::protocols::SendClosure;
+
+/// Credential to be revoked, along with the connection of its holder to notify once the revocation is published.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PendingRevocation {
+    pub rev_reg_id: String,
+    pub cred_rev_id: String,
+    pub tails_dir: String,
+    pub connection_id: Option<String>,
+    #[serde(default)]
+    pub queued_at: u64,
+}
+
+impl PendingRevocation {
+    pub fn new(rev_reg_id: &str, cred_rev_id: &str, tails_dir: &str, connection_id: Option<&str>) -> Self {
+        PendingRevocation {
+            rev_reg_id: rev_reg_id.to_string(),
+            cred_rev_id: cred_rev_id.to_string(),
+            tails_dir: tails_dir.to_string(),
+            connection_id: connection_id.map(String::from),
+            queued_at: 0,
+        }
+    }
+
+    fn record_id(&self) -> String {
+        format!("{}:{}", self.rev_reg_id, self.cred_rev_id)
+    }
+
+    // the queue is shared with [crate::common::primitives::revocation_registry::RevocationRegistry], which keys
+    // the records the same way and keeps the credential revocation ID as value, the rest goes into the tags
+    fn record_tags(&self) -> String {
+        let mut tags = json!({
+            "rev_reg_id": self.rev_reg_id,
+            "tails_dir": self.tails_dir,
+            "queued_at": self.queued_at.to_string(),
+        });
+        if let Some(connection_id) = &self.connection_id {
+            tags["connection_id"] = json!(connection_id);
+        }
+        tags.to_string()
+    }
+
+    fn from_record(record: &str) -> VcxResult<Self> {
+        let record: serde_json::Value = serde_json::from_str(record)?;
+        let cred_rev_id = record["value"].as_str().ok_or_else(|| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidJson,
+                format!("Pending revocation record has no value: {}", record),
+            )
+        })?;
+        let tags = &record["tags"];
+        let rev_reg_id = tags["rev_reg_id"].as_str().ok_or_else(|| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidJson,
+                format!("Pending revocation record has no revocation registry id: {}", record),
+            )
+        })?;
+
+        let mut revocation = PendingRevocation::new(
+            rev_reg_id,
+            cred_rev_id,
+            tags["tails_dir"].as_str().unwrap_or_default(),
+            tags["connection_id"].as_str(),
+        );
+        revocation.queued_at = tags["queued_at"]
+            .as_str()
+            .and_then(|queued_at| queued_at.parse().ok())
+            .unwrap_or_default();
+        Ok(revocation)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RevocationServiceConfig {
+    pub issuer_did: String,
+    // pending revocations are published once there are at least `max_pending` of them ...
+    pub max_pending: usize,
+    // ... or once the oldest of them is pending for `publish_interval_secs`
+    pub publish_interval_secs: u64,
+    pub ack_on: Vec<AckOn>,
+    pub comment: Option<String>,
+}
+
+/// Outcome of [RevocationService::publish]. Revocations are published even when notifying their holder fails.
+#[derive(Debug, Default)]
+pub struct PublishedRevocations {
+    pub published: Vec<PendingRevocation>,
+    pub notification_failures: Vec<(PendingRevocation, AriesVcxError)>,
+}
+
+/// Revokes credentials in batches. Revocations are queued in the wallet and applied locally right away, the
+/// accumulated revocation registry deltas are published on schedule (see [RevocationServiceConfig]), after which
+/// the holders are sent a revocation notification.
+#[derive(Clone, Debug)]
+pub struct RevocationService {
+    config: RevocationServiceConfig,
+}
+
+impl RevocationService {
+    pub fn new(config: RevocationServiceConfig) -> Self {
+        RevocationService { config }
+    }
+
+    /// Revokes the credentials locally, each in the same wallet transaction as its addition to the pending
+    /// revocation queue. The whole batch is rejected if any of the credentials is already queued. If a revocation
+    /// fails, the credentials revoked before it stay queued, while it and the rest of the batch are not revoked.
+    pub async fn revoke(&self, profile: &Arc<dyn Profile>, mut revocations: Vec<PendingRevocation>) -> VcxResult<()> {
+        trace!("RevocationService::revoke >>> revocations: {:?}", revocations);
+        let wallet = profile.inject_wallet();
+        let queued_at = now();
+
+        for revocation in revocations.iter() {
+            match wallet
+                .get_wallet_record(CATEGORY_PENDING_REVOCATION, &revocation.record_id(), "{}")
+                .await
+            {
+                Err(err) if err.kind() == AriesVcxCoreErrorKind::WalletRecordNotFound => {}
+                Err(err) => return Err(err.into()),
+                Ok(_) => {
+                    return Err(AriesVcxError::from_msg(
+                        AriesVcxErrorKind::DuplicationWalletRecord,
+                        format!("Revocation of {} is already pending", revocation.record_id()),
+                    ))
+                }
+            }
+        }
+
+        for revocation in revocations.iter_mut() {
+            revocation.queued_at = queued_at;
+            revoke_credential_local(
+                profile,
+                &revocation.tails_dir,
+                &revocation.rev_reg_id,
+                &revocation.cred_rev_id,
+                &revocation.record_tags(),
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    pub async fn get_pending_revocations(&self, profile: &Arc<dyn Profile>) -> VcxResult<Vec<PendingRevocation>> {
+        let records = profile
+            .inject_wallet()
+            .iterate_wallet_records(CATEGORY_PENDING_REVOCATION, "{}", r#"{"retrieveTags": true}"#)
+            .await?
+            .collect()
+            .await?;
+
+        records
+            .iter()
+            .map(|record| PendingRevocation::from_record(record))
+            .collect()
+    }
+
+    pub async fn is_publish_due(&self, profile: &Arc<dyn Profile>) -> VcxResult<bool> {
+        let pending = self.get_pending_revocations(profile).await?;
+        Ok(self.is_due(&pending, now()))
+    }
+
+    fn is_due(&self, pending: &[PendingRevocation], now: u64) -> bool {
+        match pending.iter().map(|revocation| revocation.queued_at).min() {
+            Some(oldest) => {
+                pending.len() >= self.config.max_pending
+                    || now.saturating_sub(oldest) >= self.config.publish_interval_secs
+            }
+            None => false,
+        }
+    }
+
+    /// Publishes the pending revocations if either threshold of the configuration is reached, see [Self::publish].
+    /// Meant to be called periodically, as well as after [Self::revoke].
+    pub async fn publish_if_due<F>(
+        &self,
+        profile: &Arc<dyn Profile>,
+        send_closure_for: F,
+    ) -> VcxResult<Option<PublishedRevocations>>
+    where
+        F: Fn(&str) -> VcxResult<SendClosure>,
+    {
+        if self.is_publish_due(profile).await? {
+            Ok(Some(self.publish(profile, send_closure_for).await?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Publishes the revocation registry deltas of all pending revocations, then notifies the holders, using
+    /// `send_closure_for` to get the closure sending messages over a holder's connection, by connection ID.
+    pub async fn publish<F>(&self, profile: &Arc<dyn Profile>, send_closure_for: F) -> VcxResult<PublishedRevocations>
+    where
+        F: Fn(&str) -> VcxResult<SendClosure>,
+    {
+        let mut pending_by_rev_reg: HashMap<String, Vec<PendingRevocation>> = HashMap::new();
+        for revocation in self.get_pending_revocations(profile).await? {
+            pending_by_rev_reg
+                .entry(revocation.rev_reg_id.clone())
+                .or_default()
+                .push(revocation);
+        }
+
+        let mut result = PublishedRevocations::default();
+        for (rev_reg_id, revocations) in pending_by_rev_reg {
+            publish_local_revocations(profile, &self.config.issuer_did, &rev_reg_id).await?;
+
+            for revocation in revocations {
+                if let Some(connection_id) = &revocation.connection_id {
+                    if let Err(err) = self.notify(&revocation, send_closure_for(connection_id)).await {
+                        warn!(
+                            "RevocationService::publish >>> failed to notify holder on connection {} about revocation of {}: {}",
+                            connection_id,
+                            revocation.record_id(),
+                            err
+                        );
+                        result.notification_failures.push((revocation.clone(), err));
+                    }
+                }
+                result.published.push(revocation);
+            }
+        }
+        Ok(result)
+    }
+
+    async fn notify(&self, revocation: &PendingRevocation, send_message: VcxResult<SendClosure>) -> VcxResult<()> {
+        let config = SenderConfigBuilder::default()
+            .rev_reg_id(revocation.rev_reg_id.clone())
+            .cred_rev_id(revocation.cred_rev_id.clone())
+            .comment(self.config.comment.clone())
+            .ack_on(self.config.ack_on.clone())
+            .build()?;
+        RevocationNotificationSender::build()
+            .send_revocation_notification(config, send_message?)
+            .await?;
+        Ok(())
+    }
+}
+
+fn now() -> u64 {
+    time::OffsetDateTime::now_utc().unix_timestamp() as u64
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+pub mod unit_tests {
+    use std::sync::Mutex;
+
+    use messages::AriesMessage;
+
+    use crate::common::test_utils::in_memory_wallet_profile;
+
+    use super::*;
+
+    fn service(max_pending: usize) -> RevocationService {
+        RevocationService::new(RevocationServiceConfig {
+            issuer_did: "issuer_did".to_string(),
+            max_pending,
+            publish_interval_secs: 3600,
+            ack_on: vec![],
+            comment: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_revocations_are_queued_and_published_in_batch() {
+        let profile = in_memory_wallet_profile();
+        let service = service(3);
+        let sent = Arc::new(Mutex::new(vec![]));
+
+        service
+            .revoke(
+                &profile,
+                vec![
+                    PendingRevocation::new("rev_reg_1", "1", "tails", Some("connection_1")),
+                    PendingRevocation::new("rev_reg_2", "1", "tails", None),
+                ],
+            )
+            .await
+            .unwrap();
+        assert_eq!(service.get_pending_revocations(&profile).await.unwrap().len(), 2);
+        assert!(!service.is_publish_due(&profile).await.unwrap());
+
+        service
+            .revoke(
+                &profile,
+                vec![PendingRevocation::new("rev_reg_1", "2", "tails", Some("connection_2"))],
+            )
+            .await
+            .unwrap();
+
+        let send_closure_for = |connection_id: &str| -> VcxResult<SendClosure> {
+            let sent = Arc::clone(&sent);
+            let connection_id = connection_id.to_string();
+            Ok(Box::new(move |message: AriesMessage| {
+                Box::pin(async move {
+                    sent.lock().unwrap().push((connection_id, message));
+                    VcxResult::Ok(())
+                })
+            }))
+        };
+        let published = service
+            .publish_if_due(&profile, send_closure_for)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(published.published.len(), 3);
+        assert!(published.notification_failures.is_empty());
+        assert!(service.get_pending_revocations(&profile).await.unwrap().is_empty());
+        let mut notified: Vec<String> = sent.lock().unwrap().iter().map(|(id, _)| id.clone()).collect();
+        notified.sort();
+        assert_eq!(notified, vec!["connection_1", "connection_2"]);
+    }
+
+    #[tokio::test]
+    async fn test_failed_notification_does_not_block_publishing() {
+        let profile = in_memory_wallet_profile();
+        let service = service(1);
+
+        service
+            .revoke(
+                &profile,
+                vec![PendingRevocation::new("rev_reg_1", "1", "tails", Some("connection_1"))],
+            )
+            .await
+            .unwrap();
+        let published = service
+            .publish(&profile, |_| {
+                Err(AriesVcxError::from_msg(
+                    AriesVcxErrorKind::NotReady,
+                    "Connection is not established",
+                ))
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(published.published.len(), 1);
+        assert_eq!(published.notification_failures.len(), 1);
+        assert!(service.get_pending_revocations(&profile).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_batch_with_queued_revocation_is_rejected() {
+        let profile = in_memory_wallet_profile();
+        let service = service(10);
+
+        service
+            .revoke(&profile, vec![PendingRevocation::new("rev_reg_1", "1", "tails", None)])
+            .await
+            .unwrap();
+        service
+            .revoke(
+                &profile,
+                vec![
+                    PendingRevocation::new("rev_reg_1", "2", "tails", None),
+                    PendingRevocation::new("rev_reg_1", "1", "tails", None),
+                ],
+            )
+            .await
+            .unwrap_err();
+
+        let pending = service.get_pending_revocations(&profile).await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].cred_rev_id, "1");
+    }
+
+    #[tokio::test]
+    async fn test_revocations_queued_by_revocation_registry_are_pending() {
+        let profile = in_memory_wallet_profile();
+        let service = service(10);
+
+        profile
+            .inject_wallet()
+            .add_wallet_record(
+                CATEGORY_PENDING_REVOCATION,
+                "rev_reg_1:1",
+                "1",
+                Some(&json!({ "rev_reg_id": "rev_reg_1" }).to_string()),
+            )
+            .await
+            .unwrap();
+
+        let pending = service.get_pending_revocations(&profile).await.unwrap();
+        assert_eq!(pending, vec![PendingRevocation::new("rev_reg_1", "1", "", None)]);
+    }
+
+    #[test]
+    fn test_publish_is_due_after_interval() {
+        let service = service(10);
+        let mut revocation = PendingRevocation::new("rev_reg_1", "1", "tails", None);
+        revocation.queued_at = 1000;
+
+        assert!(!service.is_due(&[], 1000));
+        assert!(!service.is_due(&[revocation.clone()], 1000 + 3599));
+        assert!(service.is_due(&[revocation], 1000 + 3600));
+    }
+}
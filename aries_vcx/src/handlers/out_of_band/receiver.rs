@@ -6,6 +6,7 @@ use agency_client::agency_client::AgencyClient;
 use diddoc::aries::diddoc::AriesDidDoc;
 use messages::decorators::attachment::AttachmentType;
 use messages::decorators::thread::Thread;
+use messages::maybe_known::MaybeKnown;
 use messages::msg_fields::protocols::cred_issuance::issue_credential::IssueCredential;
 use messages::msg_fields::protocols::cred_issuance::offer_credential::OfferCredential;
 use messages::msg_fields::protocols::cred_issuance::request_credential::RequestCredential;
@@ -15,6 +16,7 @@ use messages::msg_fields::protocols::out_of_band::OutOfBand;
 use messages::msg_fields::protocols::present_proof::present::Presentation;
 use messages::msg_fields::protocols::present_proof::request::RequestPresentation;
 use messages::msg_fields::protocols::present_proof::PresentProof;
+use messages::msg_types::Protocol;
 use messages::AriesMessage;
 use serde::Deserialize;
 use serde_json::Value;
@@ -24,8 +26,28 @@ use crate::core::profile::profile::Profile;
 use crate::errors::error::prelude::*;
 use crate::handlers::connection::mediated_connection::MediatedConnection;
 use crate::handlers::util::{AnyInvitation, AttachmentId};
+use crate::protocols::connection::invitee::states::invited::Invited;
+use crate::protocols::connection::invitee::InviteeConnection;
+use crate::protocols::connection::pairwise_info::PairwiseInfo;
 use crate::protocols::connection::GenericConnection;
 
+/// The connection protocols an out-of-band invitation can request in its `handshake_protocols`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum HandshakeProtocol {
+    Connections,
+    DidExchange,
+}
+
+impl HandshakeProtocol {
+    fn from_protocol(protocol: &MaybeKnown<Protocol>) -> Option<Self> {
+        match protocol {
+            MaybeKnown::Known(Protocol::ConnectionType(_)) => Some(Self::Connections),
+            MaybeKnown::Known(Protocol::DidExchangeType(_)) => Some(Self::DidExchange),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct OutOfBandReceiver {
     pub oob: Invitation,
@@ -254,6 +276,49 @@ impl OutOfBandReceiver {
         Ok(None)
     }
 
+    /// Returns the handshake protocol to use for connecting to the inviter.
+    ///
+    /// The inviter lists the handshake protocols in order of preference,
+    /// so the first one supported is picked.
+    ///
+    /// # Errors
+    ///
+    /// Will error out if the invitation does not request any supported handshake protocol.
+    pub fn handshake_protocol(&self) -> VcxResult<HandshakeProtocol> {
+        self.oob
+            .content
+            .handshake_protocols
+            .iter()
+            .flatten()
+            .find_map(HandshakeProtocol::from_protocol)
+            .ok_or_else(|| {
+                AriesVcxError::from_msg(
+                    AriesVcxErrorKind::ActionNotSupported,
+                    format!(
+                        "Out of band invitation {} does not request a supported handshake protocol",
+                        self.oob.id
+                    ),
+                )
+            })
+    }
+
+    /// Accepts the invitation into a non-mediated [`InviteeConnection<Invited>`].
+    ///
+    /// Depending on [`OutOfBandReceiver::handshake_protocol`], the connection is then continued
+    /// either through `send_request` (`connections/1.0`) or `send_did_exchange_request` (`didexchange/1.0`).
+    pub async fn build_nonmediated_connection(
+        &self,
+        profile: &Arc<dyn Profile>,
+        pairwise_info: PairwiseInfo,
+    ) -> VcxResult<InviteeConnection<Invited>> {
+        trace!("OutOfBandReceiver::build_nonmediated_connection >>>");
+        self.handshake_protocol()?;
+
+        InviteeConnection::new_invitee(self.oob.id.clone(), pairwise_info)
+            .accept_invitation(profile, AnyInvitation::Oob(self.oob.clone()))
+            .await
+    }
+
     pub async fn build_connection(
         &self,
         profile: &Arc<dyn Profile>,
@@ -265,6 +330,24 @@ impl OutOfBandReceiver {
             "OutOfBandReceiver::build_connection >>> autohop_enabled: {}",
             autohop_enabled
         );
+
+        // Mediated connections only implement the `connections/1.0` protocol.
+        let lists_connections = self.oob.content.handshake_protocols.as_ref().map(|protocols| {
+            protocols
+                .iter()
+                .any(|p| HandshakeProtocol::from_protocol(p) == Some(HandshakeProtocol::Connections))
+        });
+
+        if lists_connections == Some(false) {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::ActionNotSupported,
+                format!(
+                    "Out of band invitation {} does not support the connections protocol, use a non-mediated connection instead",
+                    self.oob.id
+                ),
+            ));
+        }
+
         MediatedConnection::create_with_invite(
             &self.oob.id,
             profile,
@@ -290,3 +373,35 @@ impl OutOfBandReceiver {
         })
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use messages::msg_fields::protocols::out_of_band::invitation::{InvitationContent, InvitationDecorators};
+    use messages::msg_types::protocols::{connection::ConnectionTypeV1, did_exchange::DidExchangeTypeV1};
+
+    use super::*;
+
+    fn make_receiver(handshake_protocols: Option<Vec<Protocol>>) -> OutOfBandReceiver {
+        let mut content = InvitationContent::new(vec![OobService::Did("test_service_did".to_owned())]);
+        content.handshake_protocols = handshake_protocols.map(|p| p.into_iter().map(MaybeKnown::Known).collect());
+
+        OutOfBandReceiver {
+            oob: Invitation::with_decorators("test_oob_id".to_owned(), content, InvitationDecorators::default()),
+        }
+    }
+
+    #[test]
+    fn test_handshake_protocol_follows_inviter_preference() {
+        let connections = Protocol::from(ConnectionTypeV1::new_v1_0());
+        let did_exchange = Protocol::from(DidExchangeTypeV1::new_v1_0());
+
+        let receiver = make_receiver(Some(vec![did_exchange, connections]));
+        assert_eq!(receiver.handshake_protocol().unwrap(), HandshakeProtocol::DidExchange);
+
+        let receiver = make_receiver(Some(vec![connections, did_exchange]));
+        assert_eq!(receiver.handshake_protocol().unwrap(), HandshakeProtocol::Connections);
+
+        make_receiver(None).handshake_protocol().unwrap_err();
+    }
+}
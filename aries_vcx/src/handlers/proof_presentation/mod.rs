@@ -1,2 +1,12 @@
+use crate::handlers::connection::mediated_connection::MediatedConnection;
+use crate::protocols::proof_presentation::v2::PresentProofVersion;
+
 pub mod prover;
+pub mod prover_v2;
 pub mod verifier;
+pub mod verifier_v2;
+
+/// Picks the present-proof version to run over the connection, from the protocols the counterparty disclosed.
+pub fn negotiate_version(connection: &MediatedConnection) -> PresentProofVersion {
+    PresentProofVersion::negotiate(connection.get_remote_protocols().as_deref())
+}
@@ -9,6 +9,11 @@ use messages::msg_fields::protocols::present_proof::propose::PresentationPreview
 use messages::msg_fields::protocols::present_proof::request::RequestPresentation;
 use messages::AriesMessage;
 
+use crate::common::credentials::revocation_status::filter_revoked_credentials;
+use crate::common::proofs::proof_request::ProofRequestData;
+use crate::common::proofs::prover::credential_selection::{
+    select_credentials_for_profile, CredentialSelection, SelectionPolicy,
+};
 use crate::core::profile::profile::Profile;
 use crate::errors::error::prelude::*;
 use crate::handlers::connection::mediated_connection::MediatedConnection;
@@ -59,6 +64,33 @@ impl Prover {
             .map_err(|err| err.into())
     }
 
+    /// Same as [Self::retrieve_credentials], but leaves out the credentials found revoked by
+    /// [crate::common::credentials::revocation_status::check_credentials_revocation_status].
+    pub async fn retrieve_unrevoked_credentials(&self, profile: &Arc<dyn Profile>) -> VcxResult<String> {
+        trace!("Prover::retrieve_unrevoked_credentials >>>");
+        let retrieved_credentials = self.retrieve_credentials(profile).await?;
+        filter_revoked_credentials(profile, &retrieved_credentials).await
+    }
+
+    /// Picks the credentials to present under the given policy, see [select_credentials_for_profile]. Once complete,
+    /// the selection can be passed on to [Self::generate_presentation].
+    pub async fn select_credentials(
+        &self,
+        profile: &Arc<dyn Profile>,
+        policy: &SelectionPolicy,
+    ) -> VcxResult<CredentialSelection> {
+        trace!("Prover::select_credentials >>> policy: {:?}", policy);
+        let presentation_request: ProofRequestData =
+            serde_json::from_str(&self.presentation_request_data()?).map_err(|err| {
+                AriesVcxError::from_msg(
+                    AriesVcxErrorKind::InvalidJson,
+                    format!("Cannot deserialize presentation request: {}", err),
+                )
+            })?;
+        let retrieved_credentials = self.retrieve_credentials(profile).await?;
+        select_credentials_for_profile(profile, &retrieved_credentials, &presentation_request, policy).await
+    }
+
     pub async fn generate_presentation(
         &mut self,
         profile: &Arc<dyn Profile>,
@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use agency_client::agency_client::AgencyClient;
+use messages::msg_fields::protocols::present_proof::v2::present::PresentationV2;
+use messages::msg_fields::protocols::present_proof::v2::propose::ProposePresentationV2;
+use messages::msg_fields::protocols::present_proof::v2::request::RequestPresentationV2;
+use messages::AriesMessage;
+
+use crate::core::profile::profile::Profile;
+use crate::errors::error::prelude::*;
+use crate::handlers::connection::mediated_connection::MediatedConnection;
+use crate::protocols::proof_presentation::prover::state_machine::ProverState;
+use crate::protocols::proof_presentation::v2::prover::ProverV2SM;
+use crate::protocols::proof_presentation::v2::PresentProofV2Action;
+use crate::protocols::SendClosure;
+
+/// Prover of AnonCreds presentations over present-proof 2.0.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ProverV2 {
+    prover_sm: ProverV2SM,
+}
+
+impl ProverV2 {
+    pub fn create(source_id: &str) -> VcxResult<ProverV2> {
+        trace!("ProverV2::create >>> source_id: {:?}", source_id);
+        let prover_sm = ProverV2SM::new(source_id.to_string());
+        Ok(ProverV2 { prover_sm })
+    }
+
+    pub fn create_from_request(source_id: &str, presentation_request: RequestPresentationV2) -> VcxResult<ProverV2> {
+        trace!(
+            "ProverV2::create_from_request >>> source_id: {:?}, presentation_request: {:?}",
+            source_id,
+            presentation_request
+        );
+        let prover_sm = ProverV2SM::from_request(presentation_request, source_id.to_string());
+        Ok(ProverV2 { prover_sm })
+    }
+
+    pub async fn retrieve_credentials(&self, profile: &Arc<dyn Profile>) -> VcxResult<String> {
+        trace!("ProverV2::retrieve_credentials >>>");
+        let presentation_request = self.presentation_request_data()?;
+        let anoncreds = Arc::clone(profile).inject_anoncreds();
+        anoncreds
+            .prover_get_credentials_for_proof_req(&presentation_request)
+            .await
+            .map_err(|err| err.into())
+    }
+
+    pub async fn send_proposal(&mut self, proposal: ProposePresentationV2, send_message: SendClosure) -> VcxResult<()> {
+        self.prover_sm = self.prover_sm.clone().send_proposal(proposal, send_message).await?;
+        Ok(())
+    }
+
+    pub async fn send_presentation(
+        &mut self,
+        profile: &Arc<dyn Profile>,
+        credentials: String,
+        self_attested_attrs: String,
+        send_message: SendClosure,
+    ) -> VcxResult<()> {
+        trace!(
+            "ProverV2::send_presentation >>> credentials: {}, self_attested_attrs: {:?}",
+            secret!(&credentials),
+            secret!(&self_attested_attrs)
+        );
+        self.prover_sm = self
+            .prover_sm
+            .clone()
+            .send_presentation(profile, credentials, self_attested_attrs, send_message)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn decline_presentation_request(
+        &mut self,
+        comment: Option<String>,
+        send_message: SendClosure,
+    ) -> VcxResult<()> {
+        self.prover_sm = self
+            .prover_sm
+            .clone()
+            .decline_presentation_request(comment, send_message)
+            .await?;
+        Ok(())
+    }
+
+    pub fn presentation_request_data(&self) -> VcxResult<String> {
+        self.prover_sm.get_presentation_request_data()
+    }
+
+    pub fn get_presentation_request(&self) -> VcxResult<RequestPresentationV2> {
+        self.prover_sm.get_presentation_request()
+    }
+
+    pub fn get_presentation_msg(&self) -> VcxResult<PresentationV2> {
+        self.prover_sm.get_presentation_msg()
+    }
+
+    pub fn get_state(&self) -> ProverState {
+        self.prover_sm.get_state()
+    }
+
+    pub fn presentation_status(&self) -> u32 {
+        self.prover_sm.presentation_status()
+    }
+
+    pub fn get_source_id(&self) -> String {
+        self.prover_sm.get_source_id()
+    }
+
+    pub fn get_thread_id(&self) -> VcxResult<String> {
+        self.prover_sm.get_thread_id()
+    }
+
+    pub fn is_terminal_state(&self) -> bool {
+        self.prover_sm.is_terminal_state()
+    }
+
+    pub fn find_message_to_handle(&self, messages: HashMap<String, AriesMessage>) -> Option<(String, AriesMessage)> {
+        self.prover_sm.find_message_to_handle(messages)
+    }
+
+    pub async fn step(
+        &mut self,
+        profile: &Arc<dyn Profile>,
+        message: PresentProofV2Action,
+        send_message: Option<SendClosure>,
+    ) -> VcxResult<()> {
+        self.prover_sm = self
+            .prover_sm
+            .clone()
+            .handle_message(profile, message, send_message)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn update_state(
+        &mut self,
+        profile: &Arc<dyn Profile>,
+        agency_client: &AgencyClient,
+        connection: &MediatedConnection,
+    ) -> VcxResult<ProverState> {
+        trace!("ProverV2::update_state >>>");
+        if self.is_terminal_state() {
+            return Ok(self.get_state());
+        }
+        let send_message = connection.send_message_closure(profile).await?;
+        let messages = connection.get_messages(agency_client).await?;
+        if let Some((uid, msg)) = self.find_message_to_handle(messages) {
+            self.step(profile, msg.into(), Some(send_message)).await?;
+            connection.update_message_status(&uid, agency_client).await?;
+        }
+        Ok(self.get_state())
+    }
+}
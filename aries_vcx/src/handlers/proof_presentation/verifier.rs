@@ -9,6 +9,8 @@ use messages::msg_fields::protocols::present_proof::request::RequestPresentation
 use messages::AriesMessage;
 
 use crate::common::proofs::proof_request::PresentationRequestData;
+use crate::common::proofs::verifier::policy::{PolicyEvaluation, PresentationPolicies};
+use crate::common::proofs::verifier::verification_report::{verify_indy_proof_with_report, ProofVerificationReport};
 use crate::core::profile::profile::Profile;
 use crate::errors::error::prelude::*;
 use crate::handlers::connection::mediated_connection::MediatedConnection;
@@ -102,6 +104,24 @@ impl Verifier {
         Ok(())
     }
 
+    /// Verifies the presentation, then enforces the verifier's business rules over it. The ack or problem report
+    /// sent depends on both, see [Self::get_policy_evaluation] for the outcome of the policies.
+    pub async fn verify_presentation_with_policies(
+        &mut self,
+        profile: &Arc<dyn Profile>,
+        presentation: Presentation,
+        policies: &PresentationPolicies,
+        send_message: SendClosure,
+    ) -> VcxResult<()> {
+        trace!("Verifier::verify_presentation_with_policies >>>");
+        self.verifier_sm = self
+            .verifier_sm
+            .clone()
+            .verify_presentation_with_policies(profile, presentation, Some(policies), send_message)
+            .await?;
+        Ok(())
+    }
+
     pub fn set_request(
         &mut self,
         presentation_request_data: PresentationRequestData,
@@ -146,11 +166,25 @@ impl Verifier {
         self.verifier_sm.get_verification_status()
     }
 
+    pub fn get_policy_evaluation(&self) -> Option<PolicyEvaluation> {
+        self.verifier_sm.get_policy_evaluation()
+    }
+
     pub fn get_presentation_attachment(&self) -> VcxResult<String> {
         let presentation = &self.verifier_sm.get_presentation_msg()?;
         Ok(get_attach_as_string!(presentation.content.presentations_attach))
     }
 
+    /// Details of the verification of the received presentation, for auditing why it was accepted or rejected.
+    pub async fn get_verification_report(&self, profile: &Arc<dyn Profile>) -> VcxResult<ProofVerificationReport> {
+        verify_indy_proof_with_report(
+            profile,
+            &self.get_presentation_attachment()?,
+            &self.get_presentation_request_attachment()?,
+        )
+        .await
+    }
+
     pub fn get_presentation_proposal(&self) -> VcxResult<ProposePresentation> {
         self.verifier_sm.presentation_proposal()
     }
@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use agency_client::agency_client::AgencyClient;
+use messages::msg_fields::protocols::present_proof::v2::present::PresentationV2;
+use messages::msg_fields::protocols::present_proof::v2::propose::ProposePresentationV2;
+use messages::msg_fields::protocols::present_proof::v2::request::RequestPresentationV2;
+use messages::AriesMessage;
+
+use crate::common::proofs::proof_request::PresentationRequestData;
+use crate::core::profile::profile::Profile;
+use crate::errors::error::prelude::*;
+use crate::handlers::connection::mediated_connection::MediatedConnection;
+use crate::protocols::issuance::v2::AnoncredsAttachmentFormat;
+use crate::protocols::proof_presentation::v2::verifier::VerifierV2SM;
+use crate::protocols::proof_presentation::v2::PresentProofV2Action;
+use crate::protocols::proof_presentation::verifier::state_machine::VerifierState;
+use crate::protocols::proof_presentation::verifier::verification_status::PresentationVerificationStatus;
+use crate::protocols::SendClosure;
+
+/// Verifier of AnonCreds presentations over present-proof 2.0.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct VerifierV2 {
+    verifier_sm: VerifierV2SM,
+}
+
+impl VerifierV2 {
+    pub fn create(source_id: &str, format: AnoncredsAttachmentFormat) -> VcxResult<VerifierV2> {
+        trace!(
+            "VerifierV2::create >>> source_id: {:?}, format: {:?}",
+            source_id,
+            format
+        );
+        let verifier_sm = VerifierV2SM::new(source_id, format);
+        Ok(VerifierV2 { verifier_sm })
+    }
+
+    pub fn create_from_proposal(
+        source_id: &str,
+        presentation_proposal: &ProposePresentationV2,
+    ) -> VcxResult<VerifierV2> {
+        trace!(
+            "VerifierV2::create_from_proposal >>> source_id: {:?}, presentation_proposal: {:?}",
+            source_id,
+            presentation_proposal
+        );
+        let verifier_sm = VerifierV2SM::from_proposal(source_id, presentation_proposal);
+        Ok(VerifierV2 { verifier_sm })
+    }
+
+    pub async fn send_presentation_request(
+        &mut self,
+        request_data: &PresentationRequestData,
+        comment: Option<String>,
+        will_confirm: bool,
+        send_message: SendClosure,
+    ) -> VcxResult<()> {
+        self.verifier_sm = self
+            .verifier_sm
+            .clone()
+            .send_presentation_request(request_data, comment, will_confirm, send_message)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn decline_presentation_proposal(
+        &mut self,
+        comment: Option<String>,
+        send_message: SendClosure,
+    ) -> VcxResult<()> {
+        self.verifier_sm = self.verifier_sm.clone().decline_proposal(comment, send_message).await?;
+        Ok(())
+    }
+
+    pub async fn verify_presentation(
+        &mut self,
+        profile: &Arc<dyn Profile>,
+        presentation: PresentationV2,
+        send_message: SendClosure,
+    ) -> VcxResult<()> {
+        self.verifier_sm = self
+            .verifier_sm
+            .clone()
+            .verify_presentation(profile, presentation, send_message)
+            .await?;
+        Ok(())
+    }
+
+    pub fn get_presentation_proposal(&self) -> VcxResult<ProposePresentationV2> {
+        self.verifier_sm.get_presentation_proposal()
+    }
+
+    pub fn get_presentation_request_msg(&self) -> VcxResult<RequestPresentationV2> {
+        self.verifier_sm.get_presentation_request_msg()
+    }
+
+    pub fn get_presentation_msg(&self) -> VcxResult<PresentationV2> {
+        self.verifier_sm.get_presentation_msg()
+    }
+
+    pub fn get_verification_status(&self) -> PresentationVerificationStatus {
+        self.verifier_sm.get_verification_status()
+    }
+
+    pub fn get_state(&self) -> VerifierState {
+        self.verifier_sm.get_state()
+    }
+
+    pub fn presentation_status(&self) -> u32 {
+        self.verifier_sm.presentation_status()
+    }
+
+    pub fn get_source_id(&self) -> String {
+        self.verifier_sm.source_id()
+    }
+
+    pub fn get_thread_id(&self) -> VcxResult<String> {
+        Ok(self.verifier_sm.thread_id())
+    }
+
+    pub fn get_format(&self) -> AnoncredsAttachmentFormat {
+        self.verifier_sm.get_format()
+    }
+
+    pub fn is_terminal_state(&self) -> bool {
+        self.verifier_sm.is_terminal_state()
+    }
+
+    pub fn find_message_to_handle(&self, messages: HashMap<String, AriesMessage>) -> Option<(String, AriesMessage)> {
+        self.verifier_sm.find_message_to_handle(messages)
+    }
+
+    pub async fn step(
+        &mut self,
+        profile: &Arc<dyn Profile>,
+        message: PresentProofV2Action,
+        send_message: Option<SendClosure>,
+    ) -> VcxResult<()> {
+        self.verifier_sm = self
+            .verifier_sm
+            .clone()
+            .handle_message(profile, message, send_message)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn update_state(
+        &mut self,
+        profile: &Arc<dyn Profile>,
+        agency_client: &AgencyClient,
+        connection: &MediatedConnection,
+    ) -> VcxResult<VerifierState> {
+        trace!("VerifierV2::update_state >>>");
+        if self.is_terminal_state() {
+            return Ok(self.get_state());
+        }
+        let send_message = connection.send_message_closure(profile).await?;
+        let messages = connection.get_messages(agency_client).await?;
+        if let Some((uid, msg)) = self.find_message_to_handle(messages) {
+            self.step(profile, msg.into(), Some(send_message)).await?;
+            connection.update_message_status(&uid, agency_client).await?;
+        }
+        Ok(self.get_state())
+    }
+}
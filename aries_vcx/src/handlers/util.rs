@@ -1,12 +1,15 @@
 use messages::{
     msg_fields::protocols::{
         connection::{invitation::Invitation, Connection},
-        cred_issuance::CredentialIssuance,
+        cred_issuance::{v2::CredentialIssuanceV2, CredentialIssuance},
+        did_exchange::DidExchange,
+        did_rotate::DidRotate,
         discover_features::DiscoverFeatures,
         notification::Notification,
         out_of_band::{invitation::Invitation as OobInvitation, OutOfBand},
         present_proof::{
             propose::{Predicate, PresentationAttr},
+            v2::PresentProofV2,
             PresentProof,
         },
         report_problem::ProblemReport,
@@ -98,6 +101,32 @@ pub fn verify_thread_id(thread_id: &str, message: &AriesMessage) -> VcxResult<()
         AriesMessage::CredentialIssuance(CredentialIssuance::RequestCredential(msg)) => {
             matches_opt_thread_id!(msg, thread_id)
         }
+        AriesMessage::CredentialIssuance(CredentialIssuance::V2(CredentialIssuanceV2::Ack(msg))) => {
+            matches_thread_id!(msg, thread_id)
+        }
+        AriesMessage::CredentialIssuance(CredentialIssuance::V2(CredentialIssuanceV2::IssueCredential(msg))) => {
+            matches_thread_id!(msg, thread_id)
+        }
+        AriesMessage::CredentialIssuance(CredentialIssuance::V2(CredentialIssuanceV2::OfferCredential(msg))) => {
+            matches_opt_thread_id!(msg, thread_id)
+        }
+        AriesMessage::CredentialIssuance(CredentialIssuance::V2(CredentialIssuanceV2::ProposeCredential(msg))) => {
+            matches_opt_thread_id!(msg, thread_id)
+        }
+        AriesMessage::CredentialIssuance(CredentialIssuance::V2(CredentialIssuanceV2::RequestCredential(msg))) => {
+            matches_opt_thread_id!(msg, thread_id)
+        }
+        AriesMessage::CredentialIssuance(CredentialIssuance::V2(CredentialIssuanceV2::ProblemReport(msg))) => {
+            matches_opt_thread_id!(msg, thread_id)
+        }
+        AriesMessage::DidExchange(DidExchange::Request(msg)) => matches_opt_thread_id!(msg, thread_id),
+        AriesMessage::DidExchange(DidExchange::Response(msg)) => matches_thread_id!(msg, thread_id),
+        AriesMessage::DidExchange(DidExchange::Complete(msg)) => matches_thread_id!(msg, thread_id),
+        AriesMessage::DidExchange(DidExchange::ProblemReport(msg)) => matches_opt_thread_id!(msg, thread_id),
+        AriesMessage::DidRotate(DidRotate::Rotate(msg)) => matches_opt_thread_id!(msg, thread_id),
+        AriesMessage::DidRotate(DidRotate::Ack(msg)) => matches_thread_id!(msg, thread_id),
+        AriesMessage::DidRotate(DidRotate::ProblemReport(msg)) => matches_opt_thread_id!(msg, thread_id),
+        AriesMessage::DidRotate(DidRotate::Hangup(msg)) => matches_opt_thread_id!(msg, thread_id),
         AriesMessage::DiscoverFeatures(DiscoverFeatures::Query(msg)) => msg.id == thread_id,
         AriesMessage::DiscoverFeatures(DiscoverFeatures::Disclose(msg)) => matches_thread_id!(msg, thread_id),
         AriesMessage::Notification(Notification::Ack(msg)) => matches_thread_id!(msg, thread_id),
@@ -109,6 +138,19 @@ pub fn verify_thread_id(thread_id: &str, message: &AriesMessage) -> VcxResult<()
         AriesMessage::PresentProof(PresentProof::Presentation(msg)) => matches_thread_id!(msg, thread_id),
         AriesMessage::PresentProof(PresentProof::ProposePresentation(msg)) => matches_opt_thread_id!(msg, thread_id),
         AriesMessage::PresentProof(PresentProof::RequestPresentation(msg)) => matches_opt_thread_id!(msg, thread_id),
+        AriesMessage::PresentProof(PresentProof::V2(PresentProofV2::Ack(msg))) => matches_thread_id!(msg, thread_id),
+        AriesMessage::PresentProof(PresentProof::V2(PresentProofV2::Presentation(msg))) => {
+            matches_thread_id!(msg, thread_id)
+        }
+        AriesMessage::PresentProof(PresentProof::V2(PresentProofV2::ProposePresentation(msg))) => {
+            matches_opt_thread_id!(msg, thread_id)
+        }
+        AriesMessage::PresentProof(PresentProof::V2(PresentProofV2::RequestPresentation(msg))) => {
+            matches_opt_thread_id!(msg, thread_id)
+        }
+        AriesMessage::PresentProof(PresentProof::V2(PresentProofV2::ProblemReport(msg))) => {
+            matches_opt_thread_id!(msg, thread_id)
+        }
         AriesMessage::ReportProblem(msg) => matches_opt_thread_id!(msg, thread_id),
         AriesMessage::Revocation(Revocation::Revoke(msg)) => matches_opt_thread_id!(msg, thread_id),
         AriesMessage::Revocation(Revocation::Ack(msg)) => matches_thread_id!(msg, thread_id),
@@ -1,9 +1,63 @@
+use std::fmt::Debug;
+
 use messages::{
     decorators::thread::Thread,
-    msg_fields::protocols::report_problem::{ProblemReport, ProblemReportContent, ProblemReportDecorators},
+    msg_fields::protocols::{
+        notification::{ack::Ack, Notification},
+        report_problem::{ProblemReport, ProblemReportContent, ProblemReportDecorators},
+    },
+    msg_parts::MsgParts,
+    AriesMessage,
 };
 use uuid::Uuid;
 
+use crate::errors::error::prelude::*;
+use crate::global::settings;
+
+/// Action handled by a protocol state machine, which may carry a message of the protocol thread.
+pub trait ThreadedAction: Debug {
+    fn thread_id_matches(&self, thread_id: &str) -> bool;
+}
+
+pub fn verify_thread_id(thread_id: &str, message: &impl ThreadedAction) -> VcxResult<()> {
+    if !settings::indy_mocks_enabled() && !message.thread_id_matches(thread_id) {
+        return Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidJson,
+            format!(
+                "Cannot handle message {:?}: thread id does not match, expected {:?}",
+                message, thread_id
+            ),
+        ));
+    };
+    Ok(())
+}
+
+/// Acks and problem reports which protocols accept in place of their own.
+pub(crate) enum GenericMessage {
+    Ack(Ack),
+    ProblemReport(ProblemReport),
+}
+
+impl GenericMessage {
+    pub(crate) fn from_message(msg: AriesMessage) -> Option<Self> {
+        match msg {
+            AriesMessage::Notification(Notification::Ack(ack)) => Some(Self::Ack(ack)),
+            AriesMessage::ReportProblem(report) => Some(Self::ProblemReport(report)),
+            AriesMessage::Notification(Notification::ProblemReport(report)) => {
+                let MsgParts {
+                    id,
+                    content,
+                    decorators,
+                } = report;
+                Some(Self::ProblemReport(ProblemReport::with_decorators(
+                    id, content.0, decorators,
+                )))
+            }
+            _ => None,
+        }
+    }
+}
+
 pub fn build_problem_report_msg(comment: Option<String>, thread_id: &str) -> ProblemReport {
     let id = Uuid::new_v4().to_string();
     let content = ProblemReportContent::new(comment.unwrap_or_default());
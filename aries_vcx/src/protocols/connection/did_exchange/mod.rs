@@ -0,0 +1,407 @@
+//! Typestate implementation of the [DID exchange protocol](https://github.com/hyperledger/aries-rfcs/blob/main/features/0023-did-exchange/README.md).
+//!
+//! DID exchange reuses the [`Initial`](super::invitee::states::initial::Initial),
+//! [`Invited`](super::invitee::states::invited::Invited) and `Completed` states of the
+//! `connections/1.0` implementation, so a connection established through it is an ordinary
+//! [`InviteeConnection<Completed>`](super::invitee::InviteeConnection) or
+//! [`InviterConnection<Completed>`](super::inviter::InviterConnection).
+//! Only the intermediate states are specific to this protocol.
+
+pub mod requester;
+pub mod responder;
+
+use std::sync::Arc;
+
+use aries_vcx_core::wallet::base_wallet::BaseWallet;
+use chrono::Utc;
+use diddoc::aries::diddoc::AriesDidDoc;
+use messages::{
+    decorators::{attachment::Attachment, thread::Thread, timing::Timing},
+    msg_fields::protocols::{
+        did_exchange::problem_report::{DidExchangeProblemReport, DidExchangeProblemReportContent},
+        report_problem::ProblemReportDecorators,
+    },
+};
+use url::Url;
+use uuid::Uuid;
+
+use super::{wrap_and_send_msg, Connection};
+use crate::{
+    common::{
+        ledger::transactions::{did_key_to_ed25519_public_key, ed25519_public_key_to_did_key, get_service},
+        signing::{decode_did_doc_attachment, verify_did_doc_attachment},
+    },
+    core::profile::profile::Profile,
+    errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult},
+    transport::Transport,
+};
+
+const DID_KEY_PREFIX: &str = "did:key:";
+const DID_PEER_PREFIX: &str = "did:peer:";
+/// Numalgo 0 `did:peer` DIDs are made of the multibase encoded inception key, just like `did:key` DIDs.
+const DID_PEER_NUMALGO_0_PREFIX: &str = "did:peer:0";
+
+/// Problem code defined by the DID exchange RFC for rejected responses.
+const RESPONSE_NOT_ACCEPTED: &str = "response_not_accepted";
+
+/// Derives the numalgo 0 `did:peer` DID of an Ed25519 verkey.
+pub fn peer_did_from_verkey(verkey: &str) -> VcxResult<String> {
+    let did_key = ed25519_public_key_to_did_key(verkey)?;
+    Ok(did_key.replacen(DID_KEY_PREFIX, DID_PEER_NUMALGO_0_PREFIX, 1))
+}
+
+/// Recovers the Ed25519 verkey a numalgo 0 `did:peer` DID was derived from.
+pub fn verkey_from_peer_did(did: &str) -> VcxResult<String> {
+    let fingerprint = did.strip_prefix(DID_PEER_NUMALGO_0_PREFIX).ok_or_else(|| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidDid,
+            format!("Not a numalgo 0 did:peer DID: {did}"),
+        )
+    })?;
+    did_key_to_ed25519_public_key(&format!("{DID_KEY_PREFIX}{fingerprint}"))
+}
+
+/// Builds the [`AriesDidDoc`] of our side of the exchange, identified by the `did:peer` DID of `verkey`.
+fn build_peer_did_doc(verkey: &str, service_endpoint: Url, routing_keys: Vec<String>) -> VcxResult<AriesDidDoc> {
+    let mut did_doc = AriesDidDoc::default();
+    did_doc.set_id(peer_did_from_verkey(verkey)?);
+    did_doc.set_service_endpoint(service_endpoint);
+    did_doc.set_routing_keys(routing_keys);
+    did_doc.set_recipient_keys(vec![verkey.to_owned()]);
+    Ok(did_doc)
+}
+
+/// Resolves the [`AriesDidDoc`] of the DID received in a DID exchange request or response.
+///
+/// An attached DIDDoc always takes precedence. When `signer` is provided, the attachment must be
+/// signed by it. Otherwise the attached DIDDoc must be the one of `did`, derived from its key for
+/// numalgo 0 `did:peer` DIDs, and a signature, if present, must come from that key.
+///
+/// Without an attachment, the DID must be a public DID, whose service gets resolved from the ledger.
+/// `did:peer` DIDs cannot be resolved and must come with their DIDDoc attached.
+async fn resolve_did_doc(
+    profile: &Arc<dyn Profile>,
+    did: &str,
+    attachment: Option<&Attachment>,
+    signer: Option<&str>,
+) -> VcxResult<AriesDidDoc> {
+    let mut did_doc = match (attachment, signer) {
+        (Some(attachment), Some(signer)) => {
+            verify_did_doc_attachment(&profile.inject_wallet(), attachment, signer).await?
+        }
+        (Some(attachment), None) => {
+            let did_doc = decode_did_doc_attachment(attachment)?;
+            if did_doc.id != did {
+                return Err(AriesVcxError::from_msg(
+                    AriesVcxErrorKind::InvalidDid,
+                    format!("Attached DIDDoc {} is not the DIDDoc of {did}", did_doc.id),
+                ));
+            }
+
+            let their_vk = did_doc.recipient_keys()?.first().cloned().ok_or_else(|| {
+                AriesVcxError::from_msg(AriesVcxErrorKind::InvalidDid, "Attached DIDDoc has no recipient keys")
+            })?;
+            if did.starts_with(DID_PEER_NUMALGO_0_PREFIX) && verkey_from_peer_did(did)? != their_vk {
+                return Err(AriesVcxError::from_msg(
+                    AriesVcxErrorKind::InvalidDid,
+                    format!("Recipient key {their_vk} of the attached DIDDoc was not used to derive {did}"),
+                ));
+            }
+
+            if attachment.data.jws.is_some() {
+                verify_did_doc_attachment(&profile.inject_wallet(), attachment, &their_vk).await?;
+            }
+
+            did_doc
+        }
+        (None, _) if did.starts_with(DID_PEER_PREFIX) => {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidDid,
+                format!("Cannot resolve {did}: did:peer DIDs must come with an attached DIDDoc"),
+            ));
+        }
+        (None, _) => {
+            let service = get_service(profile, &did.to_owned()).await?;
+
+            let mut did_doc = AriesDidDoc::default();
+            did_doc.set_id(did.to_owned());
+            did_doc.set_service_endpoint(service.service_endpoint);
+            did_doc.set_recipient_keys(service.recipient_keys);
+            did_doc.set_routing_keys(service.routing_keys);
+            did_doc
+        }
+    };
+
+    if did_doc.id.is_empty() {
+        did_doc.set_id(did.to_owned());
+    }
+
+    did_doc.validate()?;
+
+    Ok(did_doc)
+}
+
+impl<I, S> Connection<I, S> {
+    /// Sends a DID exchange problem report, which, unlike the `connections/1.0` one,
+    /// carries one of the problem codes defined by the DID exchange RFC.
+    async fn send_did_exchange_problem_report<T>(
+        &self,
+        wallet: &Arc<dyn BaseWallet>,
+        code: &str,
+        thread_id: &str,
+        did_doc: &AriesDidDoc,
+        transport: &T,
+    ) where
+        T: Transport,
+    {
+        let content = DidExchangeProblemReportContent::new(code.to_owned());
+
+        let mut decorators = ProblemReportDecorators::default();
+        decorators.thread = Some(Thread::new(thread_id.to_owned()));
+        let mut timing = Timing::default();
+        timing.out_time = Some(Utc::now());
+        decorators.timing = Some(timing);
+
+        let problem_report = DidExchangeProblemReport::with_decorators(Uuid::new_v4().to_string(), content, decorators);
+
+        let sender_verkey = &self.pairwise_info().pw_vk;
+        let res = wrap_and_send_msg(wallet, &problem_report.into(), sender_verkey, did_doc, transport).await;
+
+        if let Err(e) = res {
+            trace!("Error encountered when sending ProblemReport: {}", e);
+        } else {
+            info!("Error report sent!");
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+    use messages::{
+        msg_fields::protocols::did_exchange::{
+            response::{Response, ResponseContent, ResponseDecorators},
+            DidExchange,
+        },
+        AriesMessage,
+    };
+
+    use super::*;
+    use crate::{
+        common::signing::sign_did_doc_attachment,
+        protocols::connection::{
+            invitee::{states::invited::Invited as InviteeInvited, InviteeConnection},
+            inviter::{states::invited::Invited as InviterInvited, InviterConnection},
+            pairwise_info::PairwiseInfo,
+        },
+        utils::mockdata::profile::mock_profile::MockProfile,
+    };
+
+    const SERVICE_ENDPOINT: &str = "https://localhost:8080";
+
+    /// Keeps the sent messages around so they can be fed to the other party.
+    #[derive(Default)]
+    struct CapturingTransport(Mutex<Vec<Vec<u8>>>);
+
+    #[async_trait]
+    impl Transport for CapturingTransport {
+        async fn send_message(&self, msg: Vec<u8>, _service_endpoint: Url) -> VcxResult<()> {
+            self.0.lock().unwrap().push(msg);
+            Ok(())
+        }
+    }
+
+    impl CapturingTransport {
+        fn take_message(&self) -> DidExchange {
+            let msg = self.0.lock().unwrap().pop().unwrap();
+            match serde_json::from_slice(&msg).unwrap() {
+                AriesMessage::DidExchange(msg) => msg,
+                msg => panic!("Expected a DID exchange message, got {msg:?}"),
+            }
+        }
+    }
+
+    fn make_profile() -> Arc<dyn Profile> {
+        Arc::new(MockProfile)
+    }
+
+    async fn make_connections() -> (InviterConnection<InviterInvited>, InviteeConnection<InviteeInvited>) {
+        let profile = make_profile();
+        let wallet = profile.inject_wallet();
+
+        let pairwise_info = PairwiseInfo::create(&wallet).await.unwrap();
+        let inviter = InviterConnection::new_inviter("inviter".to_owned(), pairwise_info)
+            .create_invitation(vec![], SERVICE_ENDPOINT.parse().unwrap());
+
+        let pairwise_info = PairwiseInfo::create(&wallet).await.unwrap();
+        let invitee = InviteeConnection::new_invitee("invitee".to_owned(), pairwise_info)
+            .accept_invitation(&profile, inviter.get_invitation().clone())
+            .await
+            .unwrap();
+
+        (inviter, invitee)
+    }
+
+    #[test]
+    fn test_peer_did_from_verkey() {
+        let did = peer_did_from_verkey("8HH5gYEeNc3z7PYXmd54d4x6qAfCNrqQqEB3nS7Zfu7K").unwrap();
+        assert_eq!(did, "did:peer:0z6MkmjY8GnV5i9YTDtPETC2uUAW6ejw3nk5mXF5yci5ab7th");
+    }
+
+    #[test]
+    fn test_verkey_from_peer_did() {
+        let verkey = verkey_from_peer_did("did:peer:0z6MkmjY8GnV5i9YTDtPETC2uUAW6ejw3nk5mXF5yci5ab7th").unwrap();
+        assert_eq!(verkey, "8HH5gYEeNc3z7PYXmd54d4x6qAfCNrqQqEB3nS7Zfu7K");
+
+        assert!(verkey_from_peer_did("did:key:z6MkmjY8GnV5i9YTDtPETC2uUAW6ejw3nk5mXF5yci5ab7th").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_did_doc_rejects_did_doc_of_other_did() {
+        let profile = make_profile();
+        let wallet = profile.inject_wallet();
+        let verkey = PairwiseInfo::create(&wallet).await.unwrap().pw_vk;
+        let other_did = peer_did_from_verkey("8HH5gYEeNc3z7PYXmd54d4x6qAfCNrqQqEB3nS7Zfu7K").unwrap();
+
+        let did_doc = build_peer_did_doc(&verkey, SERVICE_ENDPOINT.parse().unwrap(), vec![]).unwrap();
+        let attachment = sign_did_doc_attachment(&wallet, &verkey, &did_doc).await.unwrap();
+        resolve_did_doc(&profile, &did_doc.id, Some(&attachment), None)
+            .await
+            .unwrap();
+        resolve_did_doc(&profile, &other_did, Some(&attachment), None)
+            .await
+            .unwrap_err();
+
+        // claims a did:peer DID derived from another key
+        let mut forged_did_doc = did_doc.clone();
+        forged_did_doc.set_id(other_did.clone());
+        let attachment = sign_did_doc_attachment(&wallet, &verkey, &forged_did_doc)
+            .await
+            .unwrap();
+        resolve_did_doc(&profile, &other_did, Some(&attachment), None)
+            .await
+            .unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn test_did_exchange_completes() {
+        let profile = make_profile();
+        let transport = CapturingTransport::default();
+        let (inviter, invitee) = make_connections().await;
+        let invitation_id = inviter.thread_id().to_owned();
+
+        let invitee = invitee
+            .send_did_exchange_request(&profile, SERVICE_ENDPOINT.parse().unwrap(), vec![], &transport)
+            .await
+            .unwrap();
+        let DidExchange::Request(request) = transport.take_message() else {
+            panic!("Expected a DID exchange request");
+        };
+        assert_eq!(
+            request.content.did,
+            peer_did_from_verkey(&invitee.pairwise_info().pw_vk).unwrap()
+        );
+        assert_eq!(
+            request.decorators.thread.as_ref().unwrap().pthid.as_deref(),
+            Some(invitation_id.as_str())
+        );
+
+        let inviter = inviter
+            .handle_did_exchange_request(&profile, request, SERVICE_ENDPOINT.parse().unwrap(), vec![])
+            .await
+            .unwrap();
+        assert_eq!(inviter.thread_id(), invitee.thread_id());
+
+        let inviter = inviter.send_response(&profile, &transport).await.unwrap();
+        let DidExchange::Response(response) = transport.take_message() else {
+            panic!("Expected a DID exchange response");
+        };
+        assert!(response.content.did_doc.as_ref().unwrap().data.jws.is_some());
+
+        let invitee = invitee
+            .handle_response(&profile, response, &transport)
+            .await
+            .unwrap()
+            .send_complete(&profile, &transport)
+            .await
+            .unwrap();
+        let DidExchange::Complete(complete) = transport.take_message() else {
+            panic!("Expected a DID exchange complete");
+        };
+        assert_eq!(
+            complete.decorators.thread.pthid.as_deref(),
+            Some(invitation_id.as_str())
+        );
+
+        let inviter = inviter.handle_complete(complete).unwrap();
+        assert_eq!(inviter.thread_id(), invitee.thread_id());
+        assert_eq!(
+            inviter.remote_did(),
+            peer_did_from_verkey(&invitee.pairwise_info().pw_vk).unwrap()
+        );
+        assert_eq!(
+            invitee.remote_did(),
+            peer_did_from_verkey(&inviter.pairwise_info().pw_vk).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_did_exchange_rejects_request_without_invitation_as_parent_thread() {
+        let profile = make_profile();
+        let transport = CapturingTransport::default();
+        let (inviter, invitee) = make_connections().await;
+
+        invitee
+            .send_did_exchange_request(&profile, SERVICE_ENDPOINT.parse().unwrap(), vec![], &transport)
+            .await
+            .unwrap();
+        let DidExchange::Request(request) = transport.take_message() else {
+            panic!("Expected a DID exchange request");
+        };
+
+        let mut without_thread = request.clone();
+        without_thread.decorators.thread = None;
+        inviter
+            .clone()
+            .handle_did_exchange_request(&profile, without_thread, SERVICE_ENDPOINT.parse().unwrap(), vec![])
+            .await
+            .unwrap_err();
+
+        let mut other_invitation = request;
+        other_invitation.decorators.thread.as_mut().unwrap().pthid = Some(Uuid::new_v4().to_string());
+        inviter
+            .handle_did_exchange_request(&profile, other_invitation, SERVICE_ENDPOINT.parse().unwrap(), vec![])
+            .await
+            .unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn test_did_exchange_rejects_peer_did_without_did_doc() {
+        let profile = make_profile();
+        let transport = CapturingTransport::default();
+        let (_, invitee) = make_connections().await;
+
+        let invitee = invitee
+            .send_did_exchange_request(&profile, SERVICE_ENDPOINT.parse().unwrap(), vec![], &transport)
+            .await
+            .unwrap();
+        transport.take_message();
+
+        let content = ResponseContent::new("did:peer:0z6MkmjY8GnV5i9YTDtPETC2uUAW6ejw3nk5mXF5yci5ab7th".to_owned());
+        let decorators = ResponseDecorators::new(Thread::new(invitee.thread_id().to_owned()));
+        let response = Response::with_decorators(Uuid::new_v4().to_string(), content, decorators);
+
+        invitee
+            .handle_response(&profile, response, &transport)
+            .await
+            .unwrap_err();
+
+        let DidExchange::ProblemReport(problem_report) = transport.take_message() else {
+            panic!("Expected a DID exchange problem report");
+        };
+        assert_eq!(problem_report.content.0.description.code, RESPONSE_NOT_ACCEPTED);
+    }
+}
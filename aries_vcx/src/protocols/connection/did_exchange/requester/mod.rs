@@ -0,0 +1,211 @@
+pub mod states;
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use messages::{
+    decorators::{thread::Thread, timing::Timing},
+    msg_fields::protocols::did_exchange::{
+        complete::{Complete, CompleteContent, CompleteDecorators},
+        request::{Request, RequestContent, RequestDecorators},
+        response::Response,
+    },
+};
+use url::Url;
+use uuid::Uuid;
+
+use self::states::{request_sent::RequestSent, response_received::ResponseReceived};
+use super::{build_peer_did_doc, resolve_did_doc, RESPONSE_NOT_ACCEPTED};
+use crate::{
+    common::signing::sign_did_doc_attachment,
+    core::profile::profile::Profile,
+    errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult},
+    handlers::util::matches_thread_id,
+    protocols::connection::{
+        initiation_type::Invitee,
+        invitee::{
+            states::{completed::Completed, invited::Invited},
+            InviteeConnection,
+        },
+        trait_bounds::ThreadId,
+        Connection,
+    },
+    transport::Transport,
+};
+
+impl InviteeConnection<Invited> {
+    /// Sends a DID exchange [`Request`] to the inviter and transitions to [`InviteeConnection<RequestSent>`].
+    ///
+    /// The request carries the `did:peer` DID of our pairwise verkey, along with a signed
+    /// DIDDoc pointing to the provided service endpoint.
+    ///
+    /// # Errors
+    ///
+    /// Will error out if signing the DIDDoc or sending the request fails.
+    pub async fn send_did_exchange_request<T>(
+        self,
+        profile: &Arc<dyn Profile>,
+        service_endpoint: Url,
+        routing_keys: Vec<String>,
+        transport: &T,
+    ) -> VcxResult<InviteeConnection<RequestSent>>
+    where
+        T: Transport,
+    {
+        trace!("Connection::send_did_exchange_request");
+
+        let wallet = profile.inject_wallet();
+        let did_doc = build_peer_did_doc(&self.pairwise_info.pw_vk, service_endpoint, routing_keys)?;
+        let attachment = sign_did_doc_attachment(&wallet, &self.pairwise_info.pw_vk, &did_doc).await?;
+
+        let mut content = RequestContent::new(self.source_id.to_string(), did_doc.id.clone());
+        content.did_doc = Some(attachment);
+
+        // Every invitee may reuse the same invitation, so the request starts its own thread,
+        // while the invitation ID is used as the parent thread ID.
+        let id = Uuid::new_v4().to_string();
+        let invitation_id = self.state.thread_id().to_owned();
+        let mut thread = Thread::new(id.clone());
+        thread.pthid = Some(invitation_id.clone());
+
+        let mut decorators = RequestDecorators::default();
+        let mut timing = Timing::default();
+        timing.out_time = Some(Utc::now());
+        decorators.timing = Some(timing);
+        decorators.thread = Some(thread);
+
+        let request = Request::with_decorators(id.clone(), content, decorators);
+
+        self.send_message(&wallet, &request.into(), transport).await?;
+
+        Ok(Connection {
+            state: RequestSent::new(self.state.did_doc, id, invitation_id),
+            source_id: self.source_id,
+            pairwise_info: self.pairwise_info,
+            initiation_type: Invitee,
+        })
+    }
+}
+
+impl InviteeConnection<RequestSent> {
+    /// Processes a DID exchange [`Response`] from the inviter and transitions to
+    /// [`InviteeConnection<ResponseReceived>`].
+    ///
+    /// # Errors
+    ///
+    /// Will error out if:
+    ///     * the thread ID of the response does not match the connection thread ID
+    ///     * the attached DIDDoc is not signed with the invitation key
+    ///     * the DIDDoc of a response without attachment cannot be resolved
+    pub async fn handle_response<T>(
+        self,
+        profile: &Arc<dyn Profile>,
+        response: Response,
+        transport: &T,
+    ) -> VcxResult<InviteeConnection<ResponseReceived>>
+    where
+        T: Transport,
+    {
+        trace!("Connection::handle_response >>> response: {:?}", response);
+
+        if !matches_thread_id!(response, self.state.thread_id()) {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidJson,
+                format!(
+                    "Cannot handle message {:?}: thread id does not match, expected {:?}",
+                    response,
+                    self.state.thread_id()
+                ),
+            ));
+        };
+
+        let keys = self.state.did_doc.recipient_keys()?;
+        let invitation_vk = keys.first().ok_or(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidState,
+            "Cannot handle response: invitation verkey not found",
+        ))?;
+
+        let did_doc = match resolve_did_doc(
+            profile,
+            &response.content.did,
+            response.content.did_doc.as_ref(),
+            Some(invitation_vk.as_str()),
+        )
+        .await
+        {
+            Ok(did_doc) => did_doc,
+            Err(err) => {
+                error!("Response DidDoc validation failed! Sending ProblemReport...");
+
+                self.send_did_exchange_problem_report(
+                    &profile.inject_wallet(),
+                    RESPONSE_NOT_ACCEPTED,
+                    self.thread_id(),
+                    &self.state.did_doc,
+                    transport,
+                )
+                .await;
+
+                return Err(err);
+            }
+        };
+
+        let state = ResponseReceived::new(
+            did_doc,
+            self.state.did_doc,
+            self.state.thread_id,
+            self.state.invitation_id,
+        );
+
+        Ok(Connection {
+            state,
+            source_id: self.source_id,
+            pairwise_info: self.pairwise_info,
+            initiation_type: Invitee,
+        })
+    }
+}
+
+impl InviteeConnection<ResponseReceived> {
+    /// Sends a DID exchange [`Complete`] message to the inviter and transitions to
+    /// [`InviteeConnection<Completed>`].
+    ///
+    /// # Errors
+    ///
+    /// Will error out if sending the message fails.
+    pub async fn send_complete<T>(
+        self,
+        profile: &Arc<dyn Profile>,
+        transport: &T,
+    ) -> VcxResult<InviteeConnection<Completed>>
+    where
+        T: Transport,
+    {
+        let mut thread = Thread::new(self.state.thread_id.clone());
+        thread.pthid = Some(self.state.invitation_id.clone());
+
+        let mut decorators = CompleteDecorators::new(thread);
+        let mut timing = Timing::default();
+        timing.out_time = Some(Utc::now());
+        decorators.timing = Some(timing);
+
+        let complete = Complete::with_decorators(Uuid::new_v4().to_string(), CompleteContent::default(), decorators);
+
+        self.send_message(&profile.inject_wallet(), &complete.into(), transport)
+            .await?;
+
+        let state = Completed::new(
+            self.state.did_doc,
+            self.state.bootstrap_did_doc,
+            self.state.thread_id,
+            None,
+        );
+
+        Ok(Connection {
+            state,
+            source_id: self.source_id,
+            pairwise_info: self.pairwise_info,
+            initiation_type: Invitee,
+        })
+    }
+}
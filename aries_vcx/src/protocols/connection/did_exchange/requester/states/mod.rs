@@ -0,0 +1,2 @@
+pub mod request_sent;
+pub mod response_received;
@@ -0,0 +1,34 @@
+use diddoc::aries::diddoc::AriesDidDoc;
+
+use crate::protocols::connection::trait_bounds::{BootstrapDidDoc, TheirDidDoc, ThreadId};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RequestSent {
+    pub(crate) did_doc: AriesDidDoc,
+    pub(crate) thread_id: String,
+    pub(crate) invitation_id: String,
+}
+
+impl RequestSent {
+    pub fn new(did_doc: AriesDidDoc, thread_id: String, invitation_id: String) -> Self {
+        Self {
+            did_doc,
+            thread_id,
+            invitation_id,
+        }
+    }
+}
+
+impl TheirDidDoc for RequestSent {
+    fn their_did_doc(&self) -> &AriesDidDoc {
+        &self.did_doc
+    }
+}
+
+impl BootstrapDidDoc for RequestSent {}
+
+impl ThreadId for RequestSent {
+    fn thread_id(&self) -> &str {
+        &self.thread_id
+    }
+}
@@ -0,0 +1,173 @@
+pub mod states;
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use messages::{
+    decorators::{thread::Thread, timing::Timing},
+    msg_fields::protocols::did_exchange::{
+        complete::Complete,
+        request::Request,
+        response::{Response, ResponseContent, ResponseDecorators},
+    },
+};
+use url::Url;
+use uuid::Uuid;
+
+use self::states::{request_received::RequestReceived, response_sent::ResponseSent};
+use super::{build_peer_did_doc, resolve_did_doc};
+use crate::{
+    common::signing::sign_did_doc_attachment,
+    core::profile::profile::Profile,
+    errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult},
+    handlers::util::matches_thread_id,
+    protocols::connection::{
+        inviter::{
+            states::{completed::Completed, invited::Invited},
+            InviterConnection,
+        },
+        pairwise_info::PairwiseInfo,
+        trait_bounds::ThreadId,
+        Connection,
+    },
+    transport::Transport,
+};
+
+impl InviterConnection<Invited> {
+    /// Processes a DID exchange [`Request`] and transitions to [`InviterConnection<RequestReceived>`].
+    ///
+    /// The requester's DIDDoc is either taken from the request attachment, as is the case for
+    /// `did:peer` DIDs, or resolved from the ledger for public DIDs.
+    ///
+    /// The [`Response`] prepared for the requester carries the `did:peer` DID of a newly generated
+    /// [`PairwiseInfo`], with its DIDDoc signed by the invitation key.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if either:
+    ///     * the [`Request`]'s parent thread ID does not match the invitation ID
+    ///     * the requester's DIDDoc cannot be resolved or is not valid
+    ///     * generating new [`PairwiseInfo`] fails
+    pub async fn handle_did_exchange_request(
+        self,
+        profile: &Arc<dyn Profile>,
+        request: Request,
+        new_service_endpoint: Url,
+        new_routing_keys: Vec<String>,
+    ) -> VcxResult<InviterConnection<RequestReceived>> {
+        trace!(
+            "Connection::handle_did_exchange_request >>> request: {:?}, service_endpoint: {}, routing_keys: {:?}",
+            request,
+            new_service_endpoint,
+            new_routing_keys,
+        );
+
+        // The request must explicitly name the invitation as its parent thread,
+        // a request without a thread could otherwise answer any invitation.
+        let pthid = request.decorators.thread.as_ref().and_then(|t| t.pthid.as_deref());
+        if pthid != Some(self.thread_id()) {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidJson,
+                format!(
+                    "Cannot handle request {:?}: parent thread id does not match, expected {:?}",
+                    request,
+                    self.thread_id()
+                ),
+            ));
+        }
+
+        // The requester's DIDDoc could not be obtained, so there is nobody to send a problem report to.
+        let did_doc = resolve_did_doc(profile, &request.content.did, request.content.did_doc.as_ref(), None).await?;
+
+        let wallet = profile.inject_wallet();
+        let new_pairwise_info = PairwiseInfo::create(&wallet).await?;
+        let new_did_doc = build_peer_did_doc(&new_pairwise_info.pw_vk, new_service_endpoint, new_routing_keys)?;
+
+        // Signing our new DIDDoc with the invitation key proves the requester that
+        // it is talking to the party that issued the invitation.
+        let attachment = sign_did_doc_attachment(&wallet, &self.pairwise_info.pw_vk, &new_did_doc).await?;
+
+        let mut content = ResponseContent::new(new_did_doc.id.clone());
+        content.did_doc = Some(attachment);
+
+        let thread_id = request.decorators.thread.map(|t| t.thid).unwrap_or(request.id);
+        let mut thread = Thread::new(thread_id);
+        thread.pthid = Some(self.thread_id().to_owned());
+
+        let mut decorators = ResponseDecorators::new(thread);
+        let mut timing = Timing::default();
+        timing.out_time = Some(Utc::now());
+        decorators.timing = Some(timing);
+
+        let response = Response::with_decorators(Uuid::new_v4().to_string(), content, decorators);
+
+        Ok(Connection {
+            source_id: self.source_id,
+            pairwise_info: new_pairwise_info,
+            initiation_type: self.initiation_type,
+            state: RequestReceived::new(response, did_doc),
+        })
+    }
+}
+
+impl InviterConnection<RequestReceived> {
+    /// Sends the DID exchange [`Response`] to the requester and transitions to
+    /// [`InviterConnection<ResponseSent>`].
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if sending the response fails.
+    pub async fn send_response<T>(
+        self,
+        profile: &Arc<dyn Profile>,
+        transport: &T,
+    ) -> VcxResult<InviterConnection<ResponseSent>>
+    where
+        T: Transport,
+    {
+        trace!("Connection::send_response >>> response: {:?}", &self.state.response);
+
+        let thread_id = self.state.response.decorators.thread.thid.clone();
+
+        self.send_message(&profile.inject_wallet(), &self.state.response.clone().into(), transport)
+            .await?;
+
+        Ok(Connection {
+            state: ResponseSent::new(self.state.did_doc, thread_id),
+            source_id: self.source_id,
+            pairwise_info: self.pairwise_info,
+            initiation_type: self.initiation_type,
+        })
+    }
+}
+
+impl InviterConnection<ResponseSent> {
+    /// Processes the requester's DID exchange [`Complete`] message and transitions to
+    /// [`InviterConnection<Completed>`].
+    ///
+    /// # Errors
+    ///
+    /// Will error out if the message's thread ID does not match
+    /// the ID of the thread context used in this connection.
+    pub fn handle_complete(self, complete: Complete) -> VcxResult<InviterConnection<Completed>> {
+        if !matches_thread_id!(complete, self.state.thread_id()) {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidJson,
+                format!(
+                    "Cannot handle message {:?}: thread id does not match, expected {:?}",
+                    complete,
+                    self.state.thread_id()
+                ),
+            ));
+        };
+
+        let state = Completed::new(self.state.did_doc, self.state.thread_id, None);
+
+        Ok(Connection {
+            source_id: self.source_id,
+            pairwise_info: self.pairwise_info,
+            initiation_type: self.initiation_type,
+            state,
+        })
+    }
+}
@@ -0,0 +1,2 @@
+pub mod request_received;
+pub mod response_sent;
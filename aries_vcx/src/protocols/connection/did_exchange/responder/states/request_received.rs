@@ -0,0 +1,28 @@
+use diddoc::aries::diddoc::AriesDidDoc;
+use messages::msg_fields::protocols::did_exchange::response::Response;
+
+use crate::protocols::connection::trait_bounds::{TheirDidDoc, ThreadId};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RequestReceived {
+    pub(crate) response: Response,
+    pub(crate) did_doc: AriesDidDoc,
+}
+
+impl RequestReceived {
+    pub fn new(response: Response, did_doc: AriesDidDoc) -> Self {
+        Self { response, did_doc }
+    }
+}
+
+impl TheirDidDoc for RequestReceived {
+    fn their_did_doc(&self) -> &AriesDidDoc {
+        &self.did_doc
+    }
+}
+
+impl ThreadId for RequestReceived {
+    fn thread_id(&self) -> &str {
+        self.response.decorators.thread.thid.as_str()
+    }
+}
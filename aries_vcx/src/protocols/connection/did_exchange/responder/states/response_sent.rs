@@ -0,0 +1,27 @@
+use diddoc::aries::diddoc::AriesDidDoc;
+
+use crate::protocols::connection::trait_bounds::{TheirDidDoc, ThreadId};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ResponseSent {
+    pub(crate) did_doc: AriesDidDoc,
+    pub(crate) thread_id: String,
+}
+
+impl ResponseSent {
+    pub fn new(did_doc: AriesDidDoc, thread_id: String) -> Self {
+        Self { did_doc, thread_id }
+    }
+}
+
+impl TheirDidDoc for ResponseSent {
+    fn their_did_doc(&self) -> &AriesDidDoc {
+        &self.did_doc
+    }
+}
+
+impl ThreadId for ResponseSent {
+    fn thread_id(&self) -> &str {
+        &self.thread_id
+    }
+}
@@ -55,4 +55,8 @@ impl CompletedState for Completed {
     fn handle_disclose(&mut self, disclose: Disclose) {
         self.protocols = Some(disclose.content.protocols)
     }
+
+    fn handle_did_rotate(&mut self, did_doc: AriesDidDoc) {
+        self.did_doc = did_doc
+    }
 }
@@ -1,3 +1,4 @@
+pub mod did_exchange;
 mod generic;
 pub mod initiation_type;
 pub mod invitee;
@@ -13,7 +14,15 @@ use messages::{
     decorators::{thread::Thread, timing::Timing},
     msg_fields::protocols::{
         connection::problem_report::{ProblemReport, ProblemReportContent, ProblemReportDecorators},
+        did_rotate::{
+            ack::{AckDidRotate, AckDidRotateContent},
+            hangup::{Hangup, HangupContent, HangupDecorators},
+            problem_report::{DidRotateProblemReport, DidRotateProblemReportContent},
+            rotate::{Rotate, RotateContent, RotateDecorators},
+        },
         discover_features::{disclose::Disclose, query::QueryContent, ProtocolDescriptor},
+        notification::ack::{AckDecorators, AckStatus},
+        report_problem::ProblemReportDecorators as DidRotateProblemReportDecorators,
     },
     AriesMessage,
 };
@@ -22,11 +31,13 @@ use uuid::Uuid;
 
 use crate::{
     errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult},
+    handlers::util::verify_thread_id,
     transport::Transport,
     utils::encryption_envelope::EncryptionEnvelope,
 };
 
 use self::{
+    did_exchange::{peer_did_from_verkey, verkey_from_peer_did},
     generic::GenericState,
     pairwise_info::PairwiseInfo,
     trait_bounds::{CompletedState, HandleProblem, TheirDidDoc, ThreadId},
@@ -34,6 +45,9 @@ use self::{
 
 pub use self::generic::{GenericConnection, State, ThinState};
 
+/// Problem code defined by the DID rotate RFC for DIDs that cannot be resolved.
+const DID_UNRESOLVABLE: &str = "e.did.unresolvable";
+
 /// A state machine for progressing through the [connection protocol](https://github.com/hyperledger/aries-rfcs/blob/main/features/0160-connection-protocol/README.md).
 #[derive(Clone, Deserialize)]
 #[serde(try_from = "GenericConnection")]
@@ -181,6 +195,166 @@ where
     }
 }
 
+impl<I, S> Connection<I, S>
+where
+    S: CompletedState + TheirDidDoc,
+{
+    /// Starts rotating the verkey of our pairwise DID by sending the counterparty a [`Rotate`] message,
+    /// as defined by the [DID rotate protocol](https://github.com/hyperledger/aries-rfcs/blob/main/features/0794-did-rotate/README.md).
+    ///
+    /// The DID rotated to is the numalgo 0 `did:peer` DID of a newly generated verkey, which the counterparty
+    /// resolves without a ledger. The connection keeps using the current verkey until the counterparty
+    /// acknowledges the rotation, see [`Self::handle_did_rotate_ack`]. A failed send or a problem report
+    /// from the counterparty therefore leave the connection as it was, and the rotation can be started again.
+    ///
+    /// Returns the sent [`Rotate`] message, which the counterparty's acknowledgement gets matched against.
+    pub async fn rotate_keys<T>(&self, wallet: &Arc<dyn BaseWallet>, transport: &T) -> VcxResult<Rotate>
+    where
+        T: Transport,
+    {
+        trace!("Connection::rotate_keys >>>");
+
+        let new_pw_vk = wallet.replace_did_keys_start(&self.pairwise_info.pw_did).await?;
+        let content = RotateContent::new(peer_did_from_verkey(&new_pw_vk)?);
+
+        let mut decorators = RotateDecorators::default();
+        let mut timing = Timing::default();
+        timing.out_time = Some(Utc::now());
+        decorators.timing = Some(timing);
+
+        let rotate = Rotate::with_decorators(Uuid::new_v4().to_string(), content, decorators);
+        self.send_message(wallet, &rotate.clone().into(), transport).await?;
+
+        Ok(rotate)
+    }
+
+    /// Applies the rotation started by [`Self::rotate_keys`] once the counterparty acknowledged `rotate`,
+    /// switching our pairwise DID over to the new verkey.
+    ///
+    /// Only the most recently started rotation can be applied, as starting another one replaces
+    /// the verkey generated for the previous.
+    pub async fn handle_did_rotate_ack(
+        &mut self,
+        wallet: &Arc<dyn BaseWallet>,
+        rotate: &Rotate,
+        ack: AckDidRotate,
+    ) -> VcxResult<()> {
+        trace!("Connection::handle_did_rotate_ack >>> ack: {:?}", ack);
+
+        verify_thread_id(&rotate.id, &ack.into())?;
+
+        let pw_did = &self.pairwise_info.pw_did;
+        wallet.replace_did_keys_apply(pw_did).await?;
+        self.pairwise_info.pw_vk = wallet.key_for_local_did(pw_did).await?;
+
+        Ok(())
+    }
+
+    /// Processes a [`Rotate`] message from the counterparty, switching their stored [`AriesDidDoc`]
+    /// over to the DID rotated to, and acknowledges the rotation.
+    ///
+    /// `sender_vk` is the verkey the message was packed with, as returned when unpacking it.
+    /// Only numalgo 0 `did:peer` DIDs can be rotated to, as their verkey is part of the DID itself.
+    /// The counterparty's service endpoint and routing keys are kept.
+    ///
+    /// # Errors
+    ///
+    /// Will error out if the message was not sent with the counterparty's current verkey.
+    /// If the DID rotated to cannot be resolved, a problem report is sent before erroring out.
+    pub async fn handle_did_rotate<T>(
+        &mut self,
+        wallet: &Arc<dyn BaseWallet>,
+        rotate: Rotate,
+        sender_vk: &str,
+        transport: &T,
+    ) -> VcxResult<()>
+    where
+        T: Transport,
+    {
+        trace!("Connection::handle_did_rotate >>> rotate: {:?}", rotate);
+
+        let their_vk = self.remote_vk()?;
+        if sender_vk != their_vk {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidJson,
+                format!("Cannot handle DID rotation: message was sent by {sender_vk} instead of {their_vk}"),
+            ));
+        }
+
+        let thread_id = rotate.decorators.thread.map(|thread| thread.thid).unwrap_or(rotate.id);
+
+        let new_their_vk = match verkey_from_peer_did(&rotate.content.to_did) {
+            Ok(verkey) => verkey,
+            Err(err) => {
+                self.send_did_rotate_problem_report(wallet, DID_UNRESOLVABLE, &thread_id, transport)
+                    .await;
+                return Err(err);
+            }
+        };
+
+        let mut did_doc = self.their_did_doc().clone();
+        did_doc.set_id(rotate.content.to_did);
+        did_doc.set_recipient_keys(vec![new_their_vk]);
+        self.state.handle_did_rotate(did_doc);
+
+        let mut decorators = AckDecorators::new(Thread::new(thread_id));
+        let mut timing = Timing::default();
+        timing.out_time = Some(Utc::now());
+        decorators.timing = Some(timing);
+
+        let ack = AckDidRotate::with_decorators(
+            Uuid::new_v4().to_string(),
+            AckDidRotateContent::new(AckStatus::Ok),
+            decorators,
+        );
+
+        self.send_message(wallet, &ack.into(), transport).await
+    }
+
+    /// Ends the relationship by sending the counterparty a [`Hangup`] message.
+    /// Both parties are expected to discard the connection afterwards.
+    pub async fn send_hangup<T>(&self, wallet: &Arc<dyn BaseWallet>, transport: &T) -> VcxResult<()>
+    where
+        T: Transport,
+    {
+        trace!("Connection::send_hangup >>>");
+
+        let mut decorators = HangupDecorators::default();
+        let mut timing = Timing::default();
+        timing.out_time = Some(Utc::now());
+        decorators.timing = Some(timing);
+
+        let hangup = Hangup::with_decorators(Uuid::new_v4().to_string(), HangupContent::default(), decorators);
+        self.send_message(wallet, &hangup.into(), transport).await
+    }
+
+    async fn send_did_rotate_problem_report<T>(
+        &self,
+        wallet: &Arc<dyn BaseWallet>,
+        code: &str,
+        thread_id: &str,
+        transport: &T,
+    ) where
+        T: Transport,
+    {
+        let content = DidRotateProblemReportContent::new(code.to_owned());
+
+        let mut decorators = DidRotateProblemReportDecorators::default();
+        decorators.thread = Some(Thread::new(thread_id.to_owned()));
+        let mut timing = Timing::default();
+        timing.out_time = Some(Utc::now());
+        decorators.timing = Some(timing);
+
+        let problem_report = DidRotateProblemReport::with_decorators(Uuid::new_v4().to_string(), content, decorators);
+
+        if let Err(e) = self.send_message(wallet, &problem_report.into(), transport).await {
+            trace!("Error encountered when sending ProblemReport: {}", e);
+        } else {
+            info!("Error report sent!");
+        }
+    }
+}
+
 pub(crate) async fn wrap_and_send_msg<T>(
     wallet: &Arc<dyn BaseWallet>,
     message: &AriesMessage,
@@ -199,3 +373,215 @@ where
 
     transport.send_message(msg, service_endpoint).await
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+    use messages::msg_fields::protocols::did_rotate::DidRotate;
+    use url::Url;
+
+    use super::*;
+    use crate::{
+        common::test_utils::in_memory_wallet_profile,
+        protocols::connection::{
+            initiation_type::{Invitee, Inviter},
+            invitee::states::completed::Completed as InviteeCompleted,
+            inviter::states::completed::Completed as InviterCompleted,
+        },
+    };
+
+    const SERVICE_ENDPOINT: &str = "https://localhost:8080";
+    const THREAD_ID: &str = "test_thread_id";
+
+    /// Keeps the sent messages around so they can be fed to the other party.
+    #[derive(Default)]
+    struct CapturingTransport(Mutex<Vec<Vec<u8>>>);
+
+    #[async_trait]
+    impl Transport for CapturingTransport {
+        async fn send_message(&self, msg: Vec<u8>, _service_endpoint: Url) -> VcxResult<()> {
+            self.0.lock().unwrap().push(msg);
+            Ok(())
+        }
+    }
+
+    impl CapturingTransport {
+        /// Unpacks the last sent message, returning it along with the verkey it was packed with.
+        async fn take_message(&self, wallet: &Arc<dyn BaseWallet>) -> (DidRotate, String) {
+            let msg = self.0.lock().unwrap().pop().unwrap();
+            match EncryptionEnvelope::anon_unpack(wallet, msg).await.unwrap() {
+                (AriesMessage::DidRotate(msg), Some(sender_vk)) => (msg, sender_vk),
+                msg => panic!("Expected an authcrypted DID rotate message, got {msg:?}"),
+            }
+        }
+    }
+
+    struct FailingTransport;
+
+    #[async_trait]
+    impl Transport for FailingTransport {
+        async fn send_message(&self, _msg: Vec<u8>, _service_endpoint: Url) -> VcxResult<()> {
+            Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::IOError,
+                "Transport unavailable",
+            ))
+        }
+    }
+
+    fn make_did_doc(pairwise_info: &PairwiseInfo) -> AriesDidDoc {
+        let mut did_doc = AriesDidDoc::default();
+        did_doc.set_id(pairwise_info.pw_did.clone());
+        did_doc.set_service_endpoint(SERVICE_ENDPOINT.parse().unwrap());
+        did_doc.set_recipient_keys(vec![pairwise_info.pw_vk.clone()]);
+        did_doc
+    }
+
+    /// Creates the two completed ends of a connection, sharing a single wallet.
+    async fn make_connections(
+        wallet: &Arc<dyn BaseWallet>,
+    ) -> (
+        Connection<Inviter, InviterCompleted>,
+        Connection<Invitee, InviteeCompleted>,
+    ) {
+        let inviter_info = PairwiseInfo::create(wallet).await.unwrap();
+        let invitee_info = PairwiseInfo::create(wallet).await.unwrap();
+
+        let inviter_state = InviterCompleted::new(make_did_doc(&invitee_info), THREAD_ID.to_owned(), None);
+        let invitee_did_doc = make_did_doc(&inviter_info);
+        let invitee_state = InviteeCompleted::new(invitee_did_doc.clone(), invitee_did_doc, THREAD_ID.to_owned(), None);
+
+        (
+            Connection::from_parts("inviter".to_owned(), inviter_info, Inviter, inviter_state),
+            Connection::from_parts("invitee".to_owned(), invitee_info, Invitee, invitee_state),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_did_rotate_round_trip() {
+        let wallet = in_memory_wallet_profile().inject_wallet();
+        let transport = CapturingTransport::default();
+        let (mut inviter, mut invitee) = make_connections(&wallet).await;
+        let old_pw_vk = inviter.pairwise_info().pw_vk.clone();
+
+        let rotate = inviter.rotate_keys(&wallet, &transport).await.unwrap();
+        assert_eq!(inviter.pairwise_info().pw_vk, old_pw_vk);
+
+        let (DidRotate::Rotate(received), sender_vk) = transport.take_message(&wallet).await else {
+            panic!("Expected a DID rotate message");
+        };
+        assert_eq!(received.id, rotate.id);
+        assert_eq!(received.content, rotate.content);
+        assert_eq!(sender_vk, old_pw_vk);
+
+        invitee
+            .handle_did_rotate(&wallet, received, &sender_vk, &transport)
+            .await
+            .unwrap();
+        let new_pw_vk = verkey_from_peer_did(&rotate.content.to_did).unwrap();
+        assert_eq!(invitee.remote_did(), rotate.content.to_did);
+        assert_eq!(invitee.remote_vk().unwrap(), new_pw_vk);
+        assert_eq!(
+            invitee.their_did_doc().get_endpoint(),
+            Some(SERVICE_ENDPOINT.parse().unwrap())
+        );
+
+        let (DidRotate::Ack(ack), _) = transport.take_message(&wallet).await else {
+            panic!("Expected a DID rotate ack");
+        };
+        inviter.handle_did_rotate_ack(&wallet, &rotate, ack).await.unwrap();
+        assert_eq!(inviter.pairwise_info().pw_vk, new_pw_vk);
+        assert_eq!(
+            wallet.key_for_local_did(&inviter.pairwise_info().pw_did).await.unwrap(),
+            new_pw_vk
+        );
+
+        // Both parties now use the rotated verkey
+        invitee.send_hangup(&wallet, &transport).await.unwrap();
+        let (DidRotate::Hangup(_), _) = transport.take_message(&wallet).await else {
+            panic!("Expected a DID rotate hangup");
+        };
+        inviter.send_hangup(&wallet, &transport).await.unwrap();
+        let (_, sender_vk) = transport.take_message(&wallet).await;
+        assert_eq!(sender_vk, new_pw_vk);
+    }
+
+    #[tokio::test]
+    async fn test_did_rotate_keeps_current_key_when_sending_fails() {
+        let wallet = in_memory_wallet_profile().inject_wallet();
+        let (inviter, _) = make_connections(&wallet).await;
+        let old_pw_vk = inviter.pairwise_info().pw_vk.clone();
+
+        inviter.rotate_keys(&wallet, &FailingTransport).await.unwrap_err();
+
+        assert_eq!(inviter.pairwise_info().pw_vk, old_pw_vk);
+        assert_eq!(
+            wallet.key_for_local_did(&inviter.pairwise_info().pw_did).await.unwrap(),
+            old_pw_vk
+        );
+    }
+
+    #[tokio::test]
+    async fn test_did_rotate_ack_for_other_thread_is_rejected() {
+        let wallet = in_memory_wallet_profile().inject_wallet();
+        let transport = CapturingTransport::default();
+        let (mut inviter, _) = make_connections(&wallet).await;
+        let old_pw_vk = inviter.pairwise_info().pw_vk.clone();
+
+        let rotate = inviter.rotate_keys(&wallet, &transport).await.unwrap();
+        let ack = AckDidRotate::with_decorators(
+            Uuid::new_v4().to_string(),
+            AckDidRotateContent::new(AckStatus::Ok),
+            AckDecorators::new(Thread::new("other_thread_id".to_owned())),
+        );
+
+        inviter.handle_did_rotate_ack(&wallet, &rotate, ack).await.unwrap_err();
+        assert_eq!(inviter.pairwise_info().pw_vk, old_pw_vk);
+    }
+
+    #[tokio::test]
+    async fn test_did_rotate_from_other_sender_is_rejected() {
+        let wallet = in_memory_wallet_profile().inject_wallet();
+        let transport = CapturingTransport::default();
+        let (inviter, mut invitee) = make_connections(&wallet).await;
+        let their_did_doc = invitee.their_did_doc().clone();
+
+        let rotate = inviter.rotate_keys(&wallet, &transport).await.unwrap();
+        let other_vk = invitee.pairwise_info().pw_vk.clone();
+
+        invitee
+            .handle_did_rotate(&wallet, rotate, &other_vk, &transport)
+            .await
+            .unwrap_err();
+        assert_eq!(invitee.their_did_doc(), &their_did_doc);
+    }
+
+    #[tokio::test]
+    async fn test_did_rotate_to_unresolvable_did_sends_problem_report() {
+        let wallet = in_memory_wallet_profile().inject_wallet();
+        let transport = CapturingTransport::default();
+        let (inviter, mut invitee) = make_connections(&wallet).await;
+        let their_did_doc = invitee.their_did_doc().clone();
+
+        let rotate = Rotate::with_decorators(
+            Uuid::new_v4().to_string(),
+            RotateContent::new("did:sov:2wJPyULfLLnYTEFYzByfUR".to_owned()),
+            RotateDecorators::default(),
+        );
+        let sender_vk = inviter.pairwise_info().pw_vk.clone();
+
+        invitee
+            .handle_did_rotate(&wallet, rotate.clone(), &sender_vk, &transport)
+            .await
+            .unwrap_err();
+        assert_eq!(invitee.their_did_doc(), &their_did_doc);
+
+        let (DidRotate::ProblemReport(problem_report), _) = transport.take_message(&wallet).await else {
+            panic!("Expected a DID rotate problem report");
+        };
+        assert_eq!(problem_report.content.0.description.code, DID_UNRESOLVABLE);
+        assert_eq!(problem_report.decorators.thread.unwrap().thid, rotate.id);
+    }
+}
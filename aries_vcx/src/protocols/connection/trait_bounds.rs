@@ -26,6 +26,9 @@ pub trait CompletedState {
     fn remote_protocols(&self) -> Option<&[ProtocolDescriptor]>;
 
     fn handle_disclose(&mut self, disclose: Disclose);
+
+    /// Replaces the [`AriesDidDoc`] of the counterparty once it rotated its keys.
+    fn handle_did_rotate(&mut self, did_doc: AriesDidDoc);
 }
 
 /// Marker trait used for implementing [`messages::protocols::connection::problem_report::ProblemReport`]
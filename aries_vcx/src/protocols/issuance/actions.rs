@@ -10,6 +10,7 @@ use messages::msg_parts::MsgParts;
 use messages::AriesMessage;
 
 use crate::handlers::util::{matches_opt_thread_id, matches_thread_id};
+use crate::protocols::common::ThreadedAction;
 
 type OptionalComment = Option<String>;
 
@@ -28,8 +29,8 @@ pub enum CredentialIssuanceAction {
     Unknown,
 }
 
-impl CredentialIssuanceAction {
-    pub fn thread_id_matches(&self, thread_id: &str) -> bool {
+impl ThreadedAction for CredentialIssuanceAction {
+    fn thread_id_matches(&self, thread_id: &str) -> bool {
         match self {
             Self::CredentialOffer(msg) => matches_opt_thread_id!(msg, thread_id),
             Self::CredentialProposal(msg) => matches_opt_thread_id!(msg, thread_id),
@@ -19,6 +19,9 @@ use messages::msg_fields::protocols::report_problem::ProblemReport;
 use messages::AriesMessage;
 use uuid::Uuid;
 
+use crate::common::credentials::encoding::AttributeEncodingRegistry;
+use crate::common::credentials::revocation_status::unmark_credential_revoked;
+use crate::common::credentials::storage_time::{forget_credential_stored, record_credential_stored};
 use crate::common::credentials::{get_cred_rev_id, is_cred_revoked};
 use crate::core::profile::profile::Profile;
 use crate::errors::error::prelude::*;
@@ -116,8 +119,12 @@ impl HolderSM {
     }
 
     pub fn from_offer(offer: OfferCredential, source_id: String) -> Self {
+        let thread_id = match &offer.decorators.thread {
+            Some(thread) => thread.thid.clone(),
+            None => offer.id.clone(),
+        };
         HolderSM {
-            thread_id: offer.id.clone(),
+            thread_id,
             state: HolderFullState::OfferReceived(OfferReceivedState::new(offer)),
             source_id,
         }
@@ -159,12 +166,31 @@ impl HolderSM {
         );
         for (uid, message) in messages {
             match self.state {
-                HolderFullState::ProposalSent(_) => {
-                    if let AriesMessage::CredentialIssuance(CredentialIssuance::OfferCredential(offer)) = &message {
+                HolderFullState::ProposalSent(_) => match &message {
+                    AriesMessage::CredentialIssuance(CredentialIssuance::OfferCredential(offer)) => {
                         if matches_opt_thread_id!(offer, self.thread_id.as_str()) {
                             return Some((uid, message));
                         }
                     }
+                    AriesMessage::ReportProblem(problem_report) => {
+                        if matches_opt_thread_id!(problem_report, self.thread_id.as_str()) {
+                            return Some((uid, message));
+                        }
+                    }
+                    AriesMessage::Notification(Notification::ProblemReport(msg)) => {
+                        if matches_opt_thread_id!(msg, self.thread_id.as_str()) {
+                            return Some((uid, message));
+                        }
+                    }
+                    _ => {}
+                },
+                // the issuer may replace its offer before receiving a request
+                HolderFullState::OfferReceived(_) => {
+                    if let AriesMessage::CredentialIssuance(CredentialIssuance::OfferCredential(offer)) = &message {
+                        if offer.decorators.thread.is_some() && matches_opt_thread_id!(offer, self.thread_id.as_str()) {
+                            return Some((uid, message));
+                        }
+                    }
                 }
                 HolderFullState::RequestSent(_) => match &message {
                     AriesMessage::CredentialIssuance(CredentialIssuance::IssueCredential(credential)) => {
@@ -248,8 +274,10 @@ impl HolderSM {
                 HolderFullState::ProposalSent(ProposalSentState::new(proposal))
             }
             HolderFullState::OfferReceived(_) => {
+                // counter proposal, answering the received offer
                 let mut proposal = proposal_data;
-                proposal.id = self.thread_id.clone();
+                proposal.id = Uuid::new_v4().to_string();
+                proposal.decorators.thread = Some(Thread::new(self.thread_id.clone()));
                 send_message(proposal.clone().into()).await?;
                 HolderFullState::ProposalSent(ProposalSentState::new(proposal))
             }
@@ -267,7 +295,9 @@ impl HolderSM {
             &CredentialIssuanceAction::CredentialOffer(offer.clone()),
         )?;
         let state = match self.state {
-            HolderFullState::ProposalSent(_) => HolderFullState::OfferReceived(OfferReceivedState::new(offer)),
+            HolderFullState::ProposalSent(_) | HolderFullState::OfferReceived(_) => {
+                HolderFullState::OfferReceived(OfferReceivedState::new(offer))
+            }
             s => {
                 warn!("Unable to receive credential offer in state {}", s);
                 s
@@ -560,6 +590,40 @@ fn _parse_rev_reg_id_from_credential(credential: &str) -> VcxResult<Option<Strin
     Ok(rev_reg_id)
 }
 
+// checks the values of attributes with a declared encoding were encoded with it by the issuer
+async fn _check_credential_encodings(profile: &Arc<dyn Profile>, credential: &str) -> VcxResult<()> {
+    let parsed_credential: serde_json::Value = serde_json::from_str(credential).map_err(|err| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidJson,
+            format!("Invalid Credential Json: {}, err: {:?}", credential, err),
+        )
+    })?;
+    let schema_id = match parsed_credential["schema_id"].as_str() {
+        Some(schema_id) => schema_id,
+        None => return Ok(()),
+    };
+
+    let registry = AttributeEncodingRegistry::load(profile, &[schema_id]).await?;
+    let values = parsed_credential["values"].as_object().into_iter().flatten();
+    for (attribute, value) in values.filter(|(attribute, _)| registry.has_encoding(schema_id, attribute)) {
+        let raw = value["raw"].as_str().unwrap_or_default();
+        let encoded = value["encoded"].as_str().unwrap_or_default();
+        if !registry.matches(schema_id, attribute, raw, encoded) {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidAttributesStructure,
+                format!(
+                    "Encoded value {} of attribute \"{}\" doesn't match its raw value \"{}\" encoded as {:?}",
+                    encoded,
+                    attribute,
+                    raw,
+                    registry.encoding(schema_id, attribute)
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
 async fn _store_credential(
     profile: &Arc<dyn Profile>,
     credential: &IssueCredential,
@@ -573,12 +637,25 @@ async fn _store_credential(
         cred_def_json
     );
 
+    let credential_json = get_attach_as_string!(&credential.content.credentials_attach);
+    store_anoncreds_credential(profile, &credential_json, req_meta, cred_def_json).await
+}
+
+/// Checks the attribute encodings of the AnonCreds credential `credential_json` and stores it in the wallet, returns
+/// the id of the stored credential and the definition of its revocation registry. The time the credential was stored
+/// at is recorded, for [crate::common::proofs::prover::credential_selection::SelectionCriterion::Newest].
+pub(crate) async fn store_anoncreds_credential(
+    profile: &Arc<dyn Profile>,
+    credential_json: &str,
+    req_meta: &str,
+    cred_def_json: &str,
+) -> VcxResult<(String, Option<String>)> {
     let ledger = Arc::clone(profile).inject_ledger();
     let anoncreds = Arc::clone(profile).inject_anoncreds();
 
-    let credential_json = get_attach_as_string!(&credential.content.credentials_attach);
+    _check_credential_encodings(profile, credential_json).await?;
 
-    let rev_reg_id = _parse_rev_reg_id_from_credential(&credential_json)?;
+    let rev_reg_id = _parse_rev_reg_id_from_credential(credential_json)?;
     let rev_reg_def_json = if let Some(rev_reg_id) = rev_reg_id {
         let json = ledger.get_rev_reg_def_json(&rev_reg_id).await?;
         Some(json)
@@ -590,11 +667,12 @@ async fn _store_credential(
         .prover_store_credential(
             None,
             req_meta,
-            &credential_json,
+            credential_json,
             cred_def_json,
             rev_reg_def_json.as_deref(),
         )
         .await?;
+    record_credential_stored(profile, &cred_id).await?;
     Ok((cred_id, rev_reg_def_json))
 }
 
@@ -602,10 +680,9 @@ async fn _delete_credential(profile: &Arc<dyn Profile>, cred_id: &str) -> VcxRes
     trace!("Holder::_delete_credential >>> cred_id: {}", cred_id);
 
     let anoncreds = Arc::clone(profile).inject_anoncreds();
-    anoncreds
-        .prover_delete_credential(cred_id)
-        .await
-        .map_err(|err| err.into())
+    anoncreds.prover_delete_credential(cred_id).await?;
+    unmark_credential_revoked(profile, cred_id).await?;
+    forget_credential_stored(profile, cred_id).await
 }
 
 pub async fn create_credential_request(
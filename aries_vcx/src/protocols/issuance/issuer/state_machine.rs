@@ -26,7 +26,9 @@ use messages::msg_fields::protocols::report_problem::ProblemReport;
 use messages::AriesMessage;
 use uuid::Uuid;
 
-use crate::common::credentials::encoding::encode_attributes;
+use crate::common::credentials::encoding::{
+    encode_attributes, encode_attributes_for_schema, AttributeEncodingRegistry,
+};
 use crate::common::credentials::is_cred_revoked;
 use crate::errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult};
 use crate::protocols::common::build_problem_report_msg;
@@ -115,13 +117,20 @@ fn build_credential_message(libindy_credential: String) -> VcxResult<IssueCreden
     Ok(IssueCredential::with_decorators(id, content, decorators))
 }
 
+// an offer opening the thread has the thread id as its id, an offer answering a proposal or replacing a sent offer
+// refers to the thread instead
 fn build_credential_offer(
     thread_id: &str,
+    opens_thread: bool,
     credential_offer: &str,
     credential_preview: CredentialPreview,
     comment: Option<String>,
 ) -> VcxResult<OfferCredential> {
-    let id = thread_id.to_owned();
+    let id = if opens_thread {
+        thread_id.to_owned()
+    } else {
+        Uuid::new_v4().to_string()
+    };
 
     let mut content = OfferCredentialContent::new(
         credential_preview,
@@ -136,6 +145,9 @@ fn build_credential_offer(
     let mut timing = Timing::default();
     timing.out_time = Some(Utc::now());
     decorators.timing = Some(timing);
+    if !opens_thread {
+        decorators.thread = Some(Thread::new(thread_id.to_owned()));
+    }
 
     Ok(OfferCredential::with_decorators(id, content, decorators))
 }
@@ -151,7 +163,7 @@ impl IssuerSM {
 
     pub fn from_proposal(source_id: &str, credential_proposal: &ProposeCredential) -> Self {
         Self {
-            thread_id: credential_proposal.id.clone(),
+            thread_id: proposal_thread_id(credential_proposal),
             source_id: source_id.to_string(),
             state: IssuerFullState::ProposalReceived(ProposalReceivedState::new(credential_proposal.clone(), None)),
         }
@@ -356,9 +368,18 @@ impl IssuerSM {
             source_id,
             thread_id,
         } = self;
+        let opens_thread = match &state {
+            IssuerFullState::Initial(_) => true,
+            IssuerFullState::OfferSet(state) => state.offer.decorators.thread.is_none(),
+            _ => false,
+        };
         let state = match state {
-            IssuerFullState::Initial(_) | IssuerFullState::OfferSet(_) | IssuerFullState::ProposalReceived(_) => {
-                let cred_offer_msg = build_credential_offer(&thread_id, credential_offer, credential_preview, comment)?;
+            IssuerFullState::Initial(_)
+            | IssuerFullState::OfferSet(_)
+            | IssuerFullState::ProposalReceived(_)
+            | IssuerFullState::OfferSent(_) => {
+                let cred_offer_msg =
+                    build_credential_offer(&thread_id, opens_thread, credential_offer, credential_preview, comment)?;
                 IssuerFullState::OfferSet(OfferSetState::new(
                     cred_offer_msg,
                     &offer_info.credential_json,
@@ -414,15 +435,11 @@ impl IssuerSM {
         )?;
         let (state, thread_id) = match self.state {
             IssuerFullState::Initial(_) => {
-                let thread_id = proposal.id.to_string();
+                let thread_id = proposal_thread_id(&proposal);
                 let state = IssuerFullState::ProposalReceived(ProposalReceivedState::new(proposal, None));
                 (state, thread_id)
             }
             IssuerFullState::OfferSent(_) => {
-                verify_thread_id(
-                    &self.thread_id,
-                    &CredentialIssuanceAction::CredentialProposal(proposal.clone()),
-                )?;
                 let state = IssuerFullState::ProposalReceived(ProposalReceivedState::new(proposal, None));
                 (state, self.thread_id.clone())
             }
@@ -438,6 +455,25 @@ impl IssuerSM {
         })
     }
 
+    pub async fn decline_proposal(self, comment: Option<String>, send_message: SendClosure) -> VcxResult<Self> {
+        let state = match self.state {
+            IssuerFullState::ProposalReceived(_) => {
+                let problem_report = build_problem_report_msg(comment, &self.thread_id);
+                send_message(problem_report.clone().into()).await?;
+                IssuerFullState::Finished(FinishedState {
+                    cred_id: None,
+                    revocation_info_v1: None,
+                    status: Status::Declined(problem_report),
+                })
+            }
+            s => {
+                warn!("Unable to decline credential proposal in state {}", s);
+                s
+            }
+        };
+        Ok(Self { state, ..self })
+    }
+
     pub async fn send_credential_offer(self, send_message: SendClosure) -> VcxResult<Self> {
         Ok(match self.state {
             IssuerFullState::OfferSet(ref state_data) => {
@@ -585,8 +621,6 @@ async fn _create_credential(
     cred_data: &str,
     thread_id: &str,
 ) -> VcxResult<(IssueCredential, Option<String>)> {
-    let anoncreds = Arc::clone(profile).inject_anoncreds();
-
     let offer = get_attach_as_string!(&offer.content.offers_attach);
 
     trace!("Issuer::_create_credential >>> request: {:?}, rev_reg_id: {:?}, tails_file: {:?}, offer: {}, cred_data: {}, thread_id: {}", request, rev_reg_id, tails_file, offer, cred_data, thread_id);
@@ -599,14 +633,55 @@ async fn _create_credential(
 
     let request = get_attach_as_string!(&request.content.requests_attach);
 
-    let cred_data = encode_attributes(cred_data)?;
-    let (libindy_credential, cred_rev_id, _) = anoncreds
-        .issuer_create_credential(&offer, &request, &cred_data, rev_reg_id.clone(), tails_file.clone())
-        .await?;
+    let (libindy_credential, cred_rev_id) =
+        create_anoncreds_credential(profile, &offer, &request, cred_data, rev_reg_id, tails_file).await?;
     let credential = build_credential_message(libindy_credential)?;
     Ok((credential, cred_rev_id))
 }
 
+/// Creates the AnonCreds credential answering `request` with the values of `cred_data` encoded as declared for the
+/// schema of `offer`, returns the credential and its revocation id.
+pub(crate) async fn create_anoncreds_credential(
+    profile: &Arc<dyn Profile>,
+    offer: &str,
+    request: &str,
+    cred_data: &str,
+    rev_reg_id: &Option<String>,
+    tails_file: &Option<String>,
+) -> VcxResult<(String, Option<String>)> {
+    let anoncreds = Arc::clone(profile).inject_anoncreds();
+
+    let cred_data = match _parse_schema_id_from_offer(offer)? {
+        Some(schema_id) => {
+            let registry = AttributeEncodingRegistry::load(profile, &[schema_id.as_str()]).await?;
+            encode_attributes_for_schema(cred_data, &schema_id, &registry)?
+        }
+        None => encode_attributes(cred_data)?,
+    };
+    let (credential, cred_rev_id, _) = anoncreds
+        .issuer_create_credential(offer, request, &cred_data, rev_reg_id.clone(), tails_file.clone())
+        .await?;
+    Ok((credential, cred_rev_id))
+}
+
+// a proposal answering an offer refers to the thread of the offer, otherwise it opens a thread
+fn proposal_thread_id(proposal: &ProposeCredential) -> String {
+    match &proposal.decorators.thread {
+        Some(thread) => thread.thid.clone(),
+        None => proposal.id.clone(),
+    }
+}
+
+fn _parse_schema_id_from_offer(offer: &str) -> VcxResult<Option<String>> {
+    let parsed_offer: serde_json::Value = serde_json::from_str(offer).map_err(|err| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidJson,
+            format!("Invalid Credential Offer Json: {}, err: {:?}", offer, err),
+        )
+    })?;
+    Ok(parsed_offer["schema_id"].as_str().map(String::from))
+}
+
 // #[cfg(test)]
 // pub mod unit_tests {
 //     use crate::common::test_utils::mock_profile;
@@ -2,25 +2,13 @@ use std::sync::Arc;
 
 use crate::core::profile::profile::Profile;
 use crate::errors::error::prelude::*;
-use crate::global::settings;
-use crate::protocols::issuance::actions::CredentialIssuanceAction;
+pub use crate::protocols::common::verify_thread_id;
 
 pub mod actions;
 pub mod holder;
 pub mod issuer;
-
-pub fn verify_thread_id(thread_id: &str, message: &CredentialIssuanceAction) -> VcxResult<()> {
-    if !settings::indy_mocks_enabled() && !message.thread_id_matches(thread_id) {
-        return Err(AriesVcxError::from_msg(
-            AriesVcxErrorKind::InvalidJson,
-            format!(
-                "Cannot handle message {:?}: thread id does not match, expected {:?}",
-                message, thread_id
-            ),
-        ));
-    };
-    Ok(())
-}
+pub mod negotiation;
+pub mod v2;
 
 pub async fn is_cred_def_revokable(profile: &Arc<dyn Profile>, cred_def_id: &str) -> VcxResult<bool> {
     let ledger = Arc::clone(profile).inject_ledger();
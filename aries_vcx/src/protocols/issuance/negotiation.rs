@@ -0,0 +1,245 @@
+use messages::msg_fields::protocols::cred_issuance::offer_credential::OfferCredential;
+use messages::msg_fields::protocols::cred_issuance::propose_credential::ProposeCredential;
+
+use crate::errors::error::prelude::*;
+use crate::handlers::util::OfferInfo;
+
+/// Issuer's answer to a credential proposal.
+#[derive(Debug, Clone)]
+pub enum ProposalDecision {
+    /// Offer the credential with the values and credential definition of the proposal.
+    Accept {
+        rev_reg_id: Option<String>,
+        tails_file: Option<String>,
+        comment: Option<String>,
+    },
+    /// Offer the credential with changed values.
+    Counter {
+        offer_info: OfferInfo,
+        comment: Option<String>,
+    },
+    /// End the exchange with a problem report.
+    Decline { comment: Option<String> },
+}
+
+/// Holder's answer to a credential offer.
+#[derive(Debug, Clone)]
+pub enum OfferDecision {
+    /// Request the offered credential.
+    Accept { my_pw_did: String },
+    /// Propose a credential with changed values, the issuer may answer with a new offer.
+    Counter(ProposeCredential),
+    /// End the exchange with a problem report.
+    Decline { comment: Option<String> },
+}
+
+/// Decides how the issuer answers credential proposals, see `Issuer::negotiate_proposal`.
+pub trait IssuerNegotiationHook: Send + Sync {
+    fn on_proposal(&self, proposal: &ProposeCredential) -> ProposalDecision;
+}
+
+impl<F> IssuerNegotiationHook for F
+where
+    F: Fn(&ProposeCredential) -> ProposalDecision + Send + Sync,
+{
+    fn on_proposal(&self, proposal: &ProposeCredential) -> ProposalDecision {
+        self(proposal)
+    }
+}
+
+/// Decides how the holder answers credential offers, see `Holder::negotiate_offer`.
+pub trait HolderNegotiationHook: Send + Sync {
+    fn on_offer(&self, offer: &OfferCredential) -> OfferDecision;
+}
+
+impl<F> HolderNegotiationHook for F
+where
+    F: Fn(&OfferCredential) -> OfferDecision + Send + Sync,
+{
+    fn on_offer(&self, offer: &OfferCredential) -> OfferDecision {
+        self(offer)
+    }
+}
+
+/// Builds the offer info of a credential with the values and credential definition proposed in `proposal`.
+pub fn offer_info_from_proposal(
+    proposal: &ProposeCredential,
+    rev_reg_id: Option<String>,
+    tails_file: Option<String>,
+) -> VcxResult<OfferInfo> {
+    let credential_json: serde_json::Map<String, serde_json::Value> = proposal
+        .content
+        .credential_proposal
+        .attributes
+        .iter()
+        .map(|attr| (attr.name.clone(), serde_json::Value::String(attr.value.clone())))
+        .collect();
+    if credential_json.is_empty() {
+        return Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidAttributesStructure,
+            "Credential proposal has no attributes to offer",
+        ));
+    }
+    Ok(OfferInfo::new(
+        serde_json::Value::Object(credential_json).to_string(),
+        proposal.content.cred_def_id.clone(),
+        rev_reg_id,
+        tails_file,
+    ))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+pub mod unit_tests {
+    use messages::msg_fields::protocols::cred_issuance::propose_credential::{
+        ProposeCredentialContent, ProposeCredentialDecorators,
+    };
+    use messages::msg_fields::protocols::cred_issuance::{CredentialAttr, CredentialPreview};
+    use messages::AriesMessage;
+    use uuid::Uuid;
+
+    use crate::common::test_utils::{mock_profile, Outbox};
+    use crate::handlers::issuance::holder::Holder;
+    use crate::handlers::issuance::issuer::Issuer;
+    use crate::protocols::issuance::actions::CredentialIssuanceAction;
+    use crate::protocols::issuance::holder::state_machine::HolderState;
+    use crate::protocols::issuance::issuer::state_machine::IssuerState;
+    use crate::utils::devsetup::SetupMocks;
+
+    use super::*;
+
+    fn _proposal(age: &str) -> ProposeCredential {
+        let preview = CredentialPreview::new(vec![CredentialAttr::new("age".to_string(), age.to_string())]);
+        let content = ProposeCredentialContent::new(preview, "schema_id".to_string(), "cred_def_id".to_string());
+        ProposeCredential::with_decorators(
+            Uuid::new_v4().to_string(),
+            content,
+            ProposeCredentialDecorators::default(),
+        )
+    }
+
+    fn _last_offer(outbox: &Outbox) -> OfferCredential {
+        match outbox.pop::<CredentialIssuanceAction>() {
+            CredentialIssuanceAction::CredentialOffer(offer) => offer,
+            action => panic!("Expected credential offer, got {:?}", action),
+        }
+    }
+
+    fn _last_proposal(outbox: &Outbox) -> ProposeCredential {
+        match outbox.pop::<CredentialIssuanceAction>() {
+            CredentialIssuanceAction::CredentialProposal(proposal) => proposal,
+            action => panic!("Expected credential proposal, got {:?}", action),
+        }
+    }
+
+    #[test]
+    fn test_offer_info_from_proposal() {
+        let offer_info = offer_info_from_proposal(&_proposal("25"), Some("rev_reg_id".to_string()), None).unwrap();
+
+        assert_eq!(offer_info.credential_json, json!({ "age": "25" }).to_string());
+        assert_eq!(offer_info.cred_def_id, "cred_def_id");
+        assert_eq!(offer_info.rev_reg_id, Some("rev_reg_id".to_string()));
+
+        let mut proposal = _proposal("25");
+        proposal.content.credential_proposal.attributes.clear();
+        assert_eq!(
+            offer_info_from_proposal(&proposal, None, None).unwrap_err().kind(),
+            AriesVcxErrorKind::InvalidAttributesStructure
+        );
+    }
+
+    #[tokio::test]
+    async fn test_negotiation_loop_preserves_thread() {
+        let _setup = SetupMocks::init();
+        let profile = mock_profile();
+        let outbox = Outbox::default();
+
+        let mut holder = Holder::create("holder").unwrap();
+        holder
+            .send_proposal(_proposal("17"), outbox.send_closure())
+            .await
+            .unwrap();
+        let thread_id = holder.get_thread_id().unwrap();
+
+        // issuer counters with changed values
+        let mut issuer = Issuer::create_from_proposal("issuer", &_last_proposal(&outbox)).unwrap();
+        assert_eq!(issuer.get_thread_id().unwrap(), thread_id);
+        let counter_offer = |_: &ProposeCredential| ProposalDecision::Counter {
+            offer_info: OfferInfo::new(
+                json!({ "age": "18" }).to_string(),
+                "cred_def_id".to_string(),
+                None,
+                None,
+            ),
+            comment: Some("must be an adult".to_string()),
+        };
+        issuer
+            .negotiate_proposal(&profile, &counter_offer, outbox.send_closure())
+            .await
+            .unwrap();
+        assert_eq!(issuer.get_state(), IssuerState::OfferSent);
+        let offer = _last_offer(&outbox);
+        assert_eq!(offer.decorators.thread.as_ref().unwrap().thid, thread_id);
+
+        // holder counters the offer
+        holder
+            .step(&profile, CredentialIssuanceAction::CredentialOffer(offer), None)
+            .await
+            .unwrap();
+        assert_eq!(holder.get_state(), HolderState::OfferReceived);
+        let counter_proposal = |_: &OfferCredential| OfferDecision::Counter(_proposal("21"));
+        holder
+            .negotiate_offer(&profile, &counter_proposal, outbox.send_closure())
+            .await
+            .unwrap();
+        assert_eq!(holder.get_state(), HolderState::ProposalSent);
+        let proposal = _last_proposal(&outbox);
+        assert_eq!(proposal.decorators.thread.as_ref().unwrap().thid, thread_id);
+        assert_ne!(proposal.id, thread_id);
+
+        // issuer accepts the counter proposal
+        issuer
+            .step(&profile, CredentialIssuanceAction::CredentialProposal(proposal), None)
+            .await
+            .unwrap();
+        assert_eq!(issuer.get_state(), IssuerState::ProposalReceived);
+        let accept = |_: &ProposeCredential| ProposalDecision::Accept {
+            rev_reg_id: None,
+            tails_file: None,
+            comment: None,
+        };
+        issuer
+            .negotiate_proposal(&profile, &accept, outbox.send_closure())
+            .await
+            .unwrap();
+        let offer = _last_offer(&outbox);
+        assert_eq!(offer.decorators.thread.as_ref().unwrap().thid, thread_id);
+
+        holder
+            .step(&profile, CredentialIssuanceAction::CredentialOffer(offer), None)
+            .await
+            .unwrap();
+        assert_eq!(holder.get_attributes().unwrap(), json!({ "age": "21" }).to_string());
+        assert_eq!(holder.get_thread_id().unwrap(), thread_id);
+        assert_eq!(issuer.get_thread_id().unwrap(), thread_id);
+    }
+
+    #[tokio::test]
+    async fn test_issuer_declines_proposal() {
+        let _setup = SetupMocks::init();
+        let profile = mock_profile();
+        let outbox = Outbox::default();
+
+        let mut issuer = Issuer::create_from_proposal("issuer", &_proposal("17")).unwrap();
+        let decline = |_: &ProposeCredential| ProposalDecision::Decline {
+            comment: Some("not eligible".to_string()),
+        };
+        issuer
+            .negotiate_proposal(&profile, &decline, outbox.send_closure())
+            .await
+            .unwrap();
+
+        assert_eq!(issuer.get_state(), IssuerState::Failed);
+        assert!(matches!(outbox.pop::<AriesMessage>(), AriesMessage::ReportProblem(_)));
+    }
+}
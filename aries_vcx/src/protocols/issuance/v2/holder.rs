@@ -0,0 +1,458 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use chrono::Utc;
+use messages::decorators::thread::Thread;
+use messages::decorators::timing::Timing;
+use messages::msg_fields::protocols::cred_issuance::v2::ack::{AckCredentialV2, AckCredentialV2Content};
+use messages::msg_fields::protocols::cred_issuance::v2::issue_credential::IssueCredentialV2;
+use messages::msg_fields::protocols::cred_issuance::v2::offer_credential::OfferCredentialV2;
+use messages::msg_fields::protocols::cred_issuance::v2::propose_credential::ProposeCredentialV2;
+use messages::msg_fields::protocols::cred_issuance::v2::request_credential::{
+    RequestCredentialV2, RequestCredentialV2Content, RequestCredentialV2Decorators,
+};
+use messages::msg_fields::protocols::notification::ack::{AckDecorators, AckStatus};
+use messages::msg_fields::protocols::report_problem::ProblemReport;
+use messages::AriesMessage;
+use uuid::Uuid;
+
+use crate::core::profile::profile::Profile;
+use crate::errors::error::prelude::*;
+use crate::handlers::util::Status;
+use crate::protocols::common::{verify_thread_id, ThreadedAction};
+use crate::protocols::issuance::holder::state_machine::{
+    create_credential_request, parse_cred_def_id_from_cred_offer, store_anoncreds_credential, HolderState,
+};
+use crate::protocols::issuance::v2::{
+    build_problem_report_msg_v2, get_anoncreds_attachment, make_anoncreds_attachment, problem_report_from_v2,
+    AnoncredsAttachmentFormat, CredentialIssuanceV2Action,
+};
+use crate::protocols::SendClosure;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub enum HolderV2FullState {
+    #[default]
+    Initial,
+    ProposalSent {
+        proposal: ProposeCredentialV2,
+    },
+    OfferReceived {
+        offer: OfferCredentialV2,
+    },
+    RequestSent {
+        offer: OfferCredentialV2,
+        req_meta: String,
+        cred_def_json: String,
+    },
+    Finished {
+        offer: Option<OfferCredentialV2>,
+        credential: Option<IssueCredentialV2>,
+        cred_id: Option<String>,
+        status: Status,
+    },
+}
+
+impl fmt::Display for HolderV2FullState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            HolderV2FullState::Initial => f.write_str("Initial"),
+            HolderV2FullState::ProposalSent { .. } => f.write_str("ProposalSent"),
+            HolderV2FullState::OfferReceived { .. } => f.write_str("OfferReceived"),
+            HolderV2FullState::RequestSent { .. } => f.write_str("RequestSent"),
+            HolderV2FullState::Finished { .. } => f.write_str("Finished"),
+        }
+    }
+}
+
+/// Holder side of the issue-credential 2.0 protocol, answering in the AnonCreds format of the received offer.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct HolderV2SM {
+    source_id: String,
+    thread_id: String,
+    state: HolderV2FullState,
+}
+
+fn build_credential_request_msg_v2(
+    thread_id: &str,
+    format: AnoncredsAttachmentFormat,
+    credential_request: &str,
+) -> RequestCredentialV2 {
+    let (request_format, attachment) = make_anoncreds_attachment(format.request_format(), credential_request);
+    let content = RequestCredentialV2Content::new(vec![request_format], vec![attachment]);
+
+    let mut decorators = RequestCredentialV2Decorators::default();
+    decorators.thread = Some(Thread::new(thread_id.to_owned()));
+    let mut timing = Timing::default();
+    timing.out_time = Some(Utc::now());
+    decorators.timing = Some(timing);
+
+    RequestCredentialV2::with_decorators(Uuid::new_v4().to_string(), content, decorators)
+}
+
+fn build_credential_ack_v2(thread_id: &str) -> AckCredentialV2 {
+    let content = AckCredentialV2Content::new(AckStatus::Ok);
+    let mut decorators = AckDecorators::new(Thread::new(thread_id.to_owned()));
+    let mut timing = Timing::default();
+    timing.out_time = Some(Utc::now());
+    decorators.timing = Some(timing);
+
+    AckCredentialV2::with_decorators(Uuid::new_v4().to_string(), content, decorators)
+}
+
+// an offer answering a proposal refers to the thread of the proposal, otherwise it opens a thread
+fn offer_thread_id(offer: &OfferCredentialV2) -> String {
+    match &offer.decorators.thread {
+        Some(thread) => thread.thid.clone(),
+        None => offer.id.clone(),
+    }
+}
+
+impl HolderV2SM {
+    pub fn new(source_id: String) -> Self {
+        Self {
+            source_id,
+            thread_id: Uuid::new_v4().to_string(),
+            state: HolderV2FullState::Initial,
+        }
+    }
+
+    pub fn from_offer(offer: OfferCredentialV2, source_id: String) -> Self {
+        Self {
+            source_id,
+            thread_id: offer_thread_id(&offer),
+            state: HolderV2FullState::OfferReceived { offer },
+        }
+    }
+
+    pub fn get_source_id(&self) -> String {
+        self.source_id.clone()
+    }
+
+    pub fn get_thread_id(&self) -> VcxResult<String> {
+        Ok(self.thread_id.clone())
+    }
+
+    pub fn get_state(&self) -> HolderState {
+        match self.state {
+            HolderV2FullState::Initial => HolderState::Initial,
+            HolderV2FullState::ProposalSent { .. } => HolderState::ProposalSent,
+            HolderV2FullState::OfferReceived { .. } => HolderState::OfferReceived,
+            HolderV2FullState::RequestSent { .. } => HolderState::RequestSent,
+            HolderV2FullState::Finished { ref status, .. } => match status {
+                Status::Success => HolderState::Finished,
+                _ => HolderState::Failed,
+            },
+        }
+    }
+
+    pub fn get_offer(&self) -> VcxResult<OfferCredentialV2> {
+        match &self.state {
+            HolderV2FullState::OfferReceived { offer } | HolderV2FullState::RequestSent { offer, .. } => {
+                Ok(offer.clone())
+            }
+            HolderV2FullState::Finished { offer: Some(offer), .. } => Ok(offer.clone()),
+            _ => Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::NotReady,
+                "Cannot get credential offer: credential offer not received yet",
+            )),
+        }
+    }
+
+    pub fn get_attributes(&self) -> VcxResult<String> {
+        let attributes: serde_json::Map<String, serde_json::Value> = self
+            .get_offer()?
+            .content
+            .credential_preview
+            .attributes
+            .into_iter()
+            .map(|attr| (attr.name, serde_json::Value::String(attr.value)))
+            .collect();
+        Ok(serde_json::Value::Object(attributes).to_string())
+    }
+
+    pub fn get_credential(&self) -> VcxResult<(String, IssueCredentialV2)> {
+        match &self.state {
+            HolderV2FullState::Finished {
+                cred_id: Some(cred_id),
+                credential: Some(credential),
+                ..
+            } => Ok((cred_id.clone(), credential.clone())),
+            _ => Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::NotReady,
+                "Cannot get credential: Credential Issuance is not finished yet",
+            )),
+        }
+    }
+
+    pub fn get_cred_id(&self) -> VcxResult<String> {
+        self.get_credential().map(|(cred_id, _)| cred_id)
+    }
+
+    pub async fn send_proposal(self, proposal: ProposeCredentialV2, send_message: SendClosure) -> VcxResult<Self> {
+        let (state, thread_id) = match self.state {
+            HolderV2FullState::Initial => {
+                let thread_id = proposal.id.clone();
+                send_message(proposal.clone().into()).await?;
+                (HolderV2FullState::ProposalSent { proposal }, thread_id)
+            }
+            HolderV2FullState::OfferReceived { .. } => {
+                let mut proposal = proposal;
+                proposal.decorators.thread = Some(Thread::new(self.thread_id.clone()));
+                send_message(proposal.clone().into()).await?;
+                (HolderV2FullState::ProposalSent { proposal }, self.thread_id.clone())
+            }
+            s => {
+                warn!("Unable to send credential proposal in state {}", s);
+                (s, self.thread_id.clone())
+            }
+        };
+        Ok(Self {
+            state,
+            thread_id,
+            ..self
+        })
+    }
+
+    pub fn receive_offer(self, offer: OfferCredentialV2) -> VcxResult<Self> {
+        let state = match self.state {
+            HolderV2FullState::ProposalSent { .. } | HolderV2FullState::OfferReceived { .. } => {
+                HolderV2FullState::OfferReceived { offer }
+            }
+            s => {
+                warn!("Unable to receive credential offer in state {}", s);
+                s
+            }
+        };
+        Ok(Self { state, ..self })
+    }
+
+    pub async fn send_request(
+        self,
+        profile: &Arc<dyn Profile>,
+        my_pw_did: String,
+        send_message: SendClosure,
+    ) -> VcxResult<Self> {
+        let state = match self.state {
+            HolderV2FullState::OfferReceived { offer } => {
+                match _make_credential_request(profile, &self.thread_id, &my_pw_did, &offer).await {
+                    Ok((request, req_meta, cred_def_json)) => {
+                        send_message(request.into()).await?;
+                        HolderV2FullState::RequestSent {
+                            offer,
+                            req_meta,
+                            cred_def_json,
+                        }
+                    }
+                    Err(err) => {
+                        let problem_report = build_problem_report_msg_v2(Some(err.to_string()), &self.thread_id);
+                        error!(
+                            "Failed to create credential request, sending problem report: {:?}",
+                            problem_report
+                        );
+                        send_message(problem_report.clone().into()).await?;
+                        HolderV2FullState::Finished {
+                            offer: Some(offer),
+                            credential: None,
+                            cred_id: None,
+                            status: Status::Failed(problem_report_from_v2(problem_report)),
+                        }
+                    }
+                }
+            }
+            s => {
+                warn!("Unable to send credential request in state {}", s);
+                s
+            }
+        };
+        Ok(Self { state, ..self })
+    }
+
+    pub async fn decline_offer(self, comment: Option<String>, send_message: SendClosure) -> VcxResult<Self> {
+        let state = match self.state {
+            HolderV2FullState::OfferReceived { offer } => {
+                let problem_report = build_problem_report_msg_v2(comment, &self.thread_id);
+                send_message(problem_report.clone().into()).await?;
+                HolderV2FullState::Finished {
+                    offer: Some(offer),
+                    credential: None,
+                    cred_id: None,
+                    status: Status::Declined(problem_report_from_v2(problem_report)),
+                }
+            }
+            s => {
+                warn!("Unable to decline credential offer in state {}", s);
+                s
+            }
+        };
+        Ok(Self { state, ..self })
+    }
+
+    pub async fn receive_credential(
+        self,
+        profile: &Arc<dyn Profile>,
+        credential: IssueCredentialV2,
+        send_message: SendClosure,
+    ) -> VcxResult<Self> {
+        let state = match self.state {
+            HolderV2FullState::RequestSent {
+                offer,
+                req_meta,
+                cred_def_json,
+            } => match _store_credential(profile, &credential, &req_meta, &cred_def_json).await {
+                Ok(cred_id) => {
+                    if credential.decorators.please_ack.is_some() {
+                        let ack = build_credential_ack_v2(&self.thread_id);
+                        send_message(ack.into()).await?;
+                    }
+                    HolderV2FullState::Finished {
+                        offer: Some(offer),
+                        credential: Some(credential),
+                        cred_id: Some(cred_id),
+                        status: Status::Success,
+                    }
+                }
+                Err(err) => {
+                    let problem_report = build_problem_report_msg_v2(Some(err.to_string()), &self.thread_id);
+                    error!(
+                        "Failed to process or save received credential, sending problem report: {:?}",
+                        problem_report
+                    );
+                    send_message(problem_report.clone().into()).await?;
+                    HolderV2FullState::Finished {
+                        offer: Some(offer),
+                        credential: None,
+                        cred_id: None,
+                        status: Status::Failed(problem_report_from_v2(problem_report)),
+                    }
+                }
+            },
+            s => {
+                warn!("Unable to receive credential in state {}", s);
+                s
+            }
+        };
+        Ok(Self { state, ..self })
+    }
+
+    pub fn receive_problem_report(self, problem_report: ProblemReport) -> VcxResult<Self> {
+        let state = match self.state {
+            HolderV2FullState::ProposalSent { .. } => HolderV2FullState::Finished {
+                offer: None,
+                credential: None,
+                cred_id: None,
+                status: Status::Failed(problem_report),
+            },
+            HolderV2FullState::RequestSent { offer, .. } => HolderV2FullState::Finished {
+                offer: Some(offer),
+                credential: None,
+                cred_id: None,
+                status: Status::Failed(problem_report),
+            },
+            s => {
+                warn!("Unable to receive problem report in state {}", s);
+                s
+            }
+        };
+        Ok(Self { state, ..self })
+    }
+
+    pub fn find_message_to_handle(&self, messages: HashMap<String, AriesMessage>) -> Option<(String, AriesMessage)> {
+        trace!(
+            "HolderV2SM::find_message_to_handle >>> messages: {:?}, state: {:?}",
+            messages,
+            self.state
+        );
+
+        for (uid, message) in messages {
+            let action = CredentialIssuanceV2Action::from(message.clone());
+            let is_expected = match (&self.state, &action) {
+                (HolderV2FullState::ProposalSent { .. }, CredentialIssuanceV2Action::CredentialOffer(_))
+                | (HolderV2FullState::ProposalSent { .. }, CredentialIssuanceV2Action::ProblemReport(_))
+                | (HolderV2FullState::RequestSent { .. }, CredentialIssuanceV2Action::Credential(_))
+                | (HolderV2FullState::RequestSent { .. }, CredentialIssuanceV2Action::ProblemReport(_)) => true,
+                // the issuer may replace its offer before receiving a request
+                (HolderV2FullState::OfferReceived { .. }, CredentialIssuanceV2Action::CredentialOffer(offer)) => {
+                    offer.decorators.thread.is_some()
+                }
+                _ => false,
+            };
+            if is_expected && action.thread_id_matches(&self.thread_id) {
+                return Some((uid, message));
+            }
+        }
+
+        None
+    }
+
+    pub async fn handle_message(
+        self,
+        profile: &Arc<dyn Profile>,
+        action: CredentialIssuanceV2Action,
+        send_message: Option<SendClosure>,
+    ) -> VcxResult<Self> {
+        trace!(
+            "HolderV2SM::handle_message >>> action: {:?}, state: {:?}",
+            action,
+            self.state
+        );
+        verify_thread_id(&self.thread_id, &action)?;
+        let holder_sm = match action {
+            CredentialIssuanceV2Action::CredentialOffer(offer) => self.receive_offer(offer)?,
+            CredentialIssuanceV2Action::CredentialRequestSend(my_pw_did) => {
+                let send_message = send_message.ok_or(AriesVcxError::from_msg(
+                    AriesVcxErrorKind::InvalidState,
+                    "Attempted to call undefined send_message callback",
+                ))?;
+                self.send_request(profile, my_pw_did, send_message).await?
+            }
+            CredentialIssuanceV2Action::Credential(credential) => {
+                let send_message = send_message.ok_or(AriesVcxError::from_msg(
+                    AriesVcxErrorKind::InvalidState,
+                    "Attempted to call undefined send_message callback",
+                ))?;
+                self.receive_credential(profile, credential, send_message).await?
+            }
+            CredentialIssuanceV2Action::ProblemReport(problem_report) => self.receive_problem_report(problem_report)?,
+            _ => self,
+        };
+        Ok(holder_sm)
+    }
+
+    pub fn credential_status(&self) -> u32 {
+        match self.state {
+            HolderV2FullState::Finished { ref status, .. } => status.code(),
+            _ => Status::Undefined.code(),
+        }
+    }
+
+    pub fn is_terminal_state(&self) -> bool {
+        matches!(self.state, HolderV2FullState::Finished { .. })
+    }
+}
+
+async fn _make_credential_request(
+    profile: &Arc<dyn Profile>,
+    thread_id: &str,
+    my_pw_did: &str,
+    offer: &OfferCredentialV2,
+) -> VcxResult<(RequestCredentialV2, String, String)> {
+    let (format, cred_offer) = get_anoncreds_attachment(&offer.content.formats, &offer.content.offers_attach)?;
+    let cred_def_id = parse_cred_def_id_from_cred_offer(&cred_offer)?;
+    let (request, req_meta, _cred_def_id, cred_def_json) =
+        create_credential_request(profile, &cred_def_id, my_pw_did, &cred_offer).await?;
+    let request_msg = build_credential_request_msg_v2(thread_id, format, &request);
+    Ok((request_msg, req_meta, cred_def_json))
+}
+
+async fn _store_credential(
+    profile: &Arc<dyn Profile>,
+    credential: &IssueCredentialV2,
+    req_meta: &str,
+    cred_def_json: &str,
+) -> VcxResult<String> {
+    let (_, credential_json) =
+        get_anoncreds_attachment(&credential.content.formats, &credential.content.credentials_attach)?;
+    let (cred_id, _) = store_anoncreds_credential(profile, &credential_json, req_meta, cred_def_json).await?;
+    Ok(cred_id)
+}
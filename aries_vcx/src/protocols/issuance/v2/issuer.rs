@@ -0,0 +1,496 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::Arc;
+
+use chrono::Utc;
+use messages::decorators::please_ack::PleaseAck;
+use messages::decorators::thread::Thread;
+use messages::decorators::timing::Timing;
+use messages::maybe_known::MaybeKnown;
+use messages::msg_fields::protocols::cred_issuance::v2::issue_credential::{
+    IssueCredentialV2, IssueCredentialV2Content, IssueCredentialV2Decorators,
+};
+use messages::msg_fields::protocols::cred_issuance::v2::offer_credential::{
+    OfferCredentialV2, OfferCredentialV2Content, OfferCredentialV2Decorators,
+};
+use messages::msg_fields::protocols::cred_issuance::v2::propose_credential::ProposeCredentialV2;
+use messages::msg_fields::protocols::cred_issuance::v2::request_credential::RequestCredentialV2;
+use messages::msg_fields::protocols::cred_issuance::v2::CredentialPreviewV2;
+use messages::msg_fields::protocols::report_problem::ProblemReport;
+use messages::AriesMessage;
+use uuid::Uuid;
+
+use crate::core::profile::profile::Profile;
+use crate::errors::error::prelude::*;
+use crate::handlers::util::{OfferInfo, Status};
+use crate::protocols::common::{verify_thread_id, ThreadedAction};
+use crate::protocols::issuance::issuer::state_machine::{create_anoncreds_credential, IssuerState, RevocationInfoV1};
+use crate::protocols::issuance::v2::{
+    build_problem_report_msg_v2, get_anoncreds_attachment, make_anoncreds_attachment, problem_report_from_v2,
+    AnoncredsAttachmentFormat, AnoncredsFormatType, CredentialIssuanceV2Action,
+};
+use crate::protocols::SendClosure;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OfferDataV2 {
+    pub offer: OfferCredentialV2,
+    pub cred_data: String,
+    pub rev_reg_id: Option<String>,
+    pub tails_file: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub enum IssuerV2FullState {
+    #[default]
+    Initial,
+    ProposalReceived {
+        proposal: ProposeCredentialV2,
+    },
+    OfferSet(OfferDataV2),
+    OfferSent(OfferDataV2),
+    RequestReceived {
+        offer: OfferDataV2,
+        request: RequestCredentialV2,
+    },
+    CredentialSent {
+        offer: OfferDataV2,
+        cred_rev_id: Option<String>,
+    },
+    Finished {
+        revocation_info: Option<RevocationInfoV1>,
+        status: Status,
+    },
+}
+
+impl Display for IssuerV2FullState {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::result::Result<(), ::std::fmt::Error> {
+        match *self {
+            IssuerV2FullState::Initial => f.write_str("Initial"),
+            IssuerV2FullState::ProposalReceived { .. } => f.write_str("ProposalReceived"),
+            IssuerV2FullState::OfferSet(_) => f.write_str("OfferSet"),
+            IssuerV2FullState::OfferSent(_) => f.write_str("OfferSent"),
+            IssuerV2FullState::RequestReceived { .. } => f.write_str("RequestReceived"),
+            IssuerV2FullState::CredentialSent { .. } => f.write_str("CredentialSent"),
+            IssuerV2FullState::Finished { .. } => f.write_str("Finished"),
+        }
+    }
+}
+
+/// Issuer side of the issue-credential 2.0 protocol, exchanging AnonCreds credentials in `format`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct IssuerV2SM {
+    source_id: String,
+    thread_id: String,
+    format: AnoncredsAttachmentFormat,
+    state: IssuerV2FullState,
+}
+
+fn build_credential_offer_v2(
+    thread_id: &str,
+    opens_thread: bool,
+    format: AnoncredsAttachmentFormat,
+    credential_offer: &str,
+    credential_preview: CredentialPreviewV2,
+    comment: Option<String>,
+) -> OfferCredentialV2 {
+    let id = if opens_thread {
+        thread_id.to_owned()
+    } else {
+        Uuid::new_v4().to_string()
+    };
+
+    let (offer_format, attachment) = make_anoncreds_attachment(format.offer_format(), credential_offer);
+    let mut content = OfferCredentialV2Content::new(credential_preview, vec![offer_format], vec![attachment]);
+    content.comment = comment;
+
+    let mut decorators = OfferCredentialV2Decorators::default();
+    let mut timing = Timing::default();
+    timing.out_time = Some(Utc::now());
+    decorators.timing = Some(timing);
+    if !opens_thread {
+        decorators.thread = Some(Thread::new(thread_id.to_owned()));
+    }
+
+    OfferCredentialV2::with_decorators(id, content, decorators)
+}
+
+fn build_credential_message_v2(
+    thread_id: &str,
+    format: AnoncredsAttachmentFormat,
+    credential: &str,
+) -> IssueCredentialV2 {
+    let (credential_format, attachment) = make_anoncreds_attachment(format.credential_format(), credential);
+    let content = IssueCredentialV2Content::new(vec![credential_format], vec![attachment]);
+
+    let mut decorators = IssueCredentialV2Decorators::new(Thread::new(thread_id.to_owned()));
+    decorators.please_ack = Some(PleaseAck::new(vec![]));
+    let mut timing = Timing::default();
+    timing.out_time = Some(Utc::now());
+    decorators.timing = Some(timing);
+
+    IssueCredentialV2::with_decorators(Uuid::new_v4().to_string(), content, decorators)
+}
+
+// the AnonCreds format proposed by the holder, if any
+fn proposal_format(proposal: &ProposeCredentialV2) -> Option<AnoncredsAttachmentFormat> {
+    proposal
+        .content
+        .formats
+        .iter()
+        .find_map(|specifier| match &specifier.format {
+            MaybeKnown::Known(format) => format.anoncreds_format(),
+            MaybeKnown::Unknown(_) => None,
+        })
+}
+
+// a proposal answering an offer refers to the thread of the offer, otherwise it opens a thread
+fn proposal_thread_id(proposal: &ProposeCredentialV2) -> String {
+    match &proposal.decorators.thread {
+        Some(thread) => thread.thid.clone(),
+        None => proposal.id.clone(),
+    }
+}
+
+impl IssuerV2SM {
+    pub fn new(source_id: &str, format: AnoncredsAttachmentFormat) -> Self {
+        Self {
+            source_id: source_id.to_string(),
+            thread_id: Uuid::new_v4().to_string(),
+            format,
+            state: IssuerV2FullState::Initial,
+        }
+    }
+
+    pub fn from_proposal(source_id: &str, proposal: &ProposeCredentialV2) -> Self {
+        Self {
+            source_id: source_id.to_string(),
+            thread_id: proposal_thread_id(proposal),
+            format: proposal_format(proposal).unwrap_or_default(),
+            state: IssuerV2FullState::ProposalReceived {
+                proposal: proposal.clone(),
+            },
+        }
+    }
+
+    pub fn get_source_id(&self) -> String {
+        self.source_id.clone()
+    }
+
+    pub fn thread_id(&self) -> VcxResult<String> {
+        Ok(self.thread_id.clone())
+    }
+
+    pub fn get_format(&self) -> AnoncredsAttachmentFormat {
+        self.format
+    }
+
+    pub fn get_state(&self) -> IssuerState {
+        match self.state {
+            IssuerV2FullState::Initial => IssuerState::Initial,
+            IssuerV2FullState::ProposalReceived { .. } => IssuerState::ProposalReceived,
+            IssuerV2FullState::OfferSet(_) => IssuerState::OfferSet,
+            IssuerV2FullState::OfferSent(_) => IssuerState::OfferSent,
+            IssuerV2FullState::RequestReceived { .. } => IssuerState::RequestReceived,
+            IssuerV2FullState::CredentialSent { .. } => IssuerState::CredentialSent,
+            IssuerV2FullState::Finished { ref status, .. } => match status {
+                Status::Success => IssuerState::Finished,
+                _ => IssuerState::Failed,
+            },
+        }
+    }
+
+    pub fn get_proposal(&self) -> VcxResult<ProposeCredentialV2> {
+        match &self.state {
+            IssuerV2FullState::ProposalReceived { proposal } => Ok(proposal.clone()),
+            _ => Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidState,
+                "Proposal is only available in ProposalReceived state",
+            )),
+        }
+    }
+
+    pub fn get_revocation_info(&self) -> Option<RevocationInfoV1> {
+        match &self.state {
+            IssuerV2FullState::CredentialSent { offer, cred_rev_id } => Some(RevocationInfoV1 {
+                cred_rev_id: cred_rev_id.clone(),
+                rev_reg_id: offer.rev_reg_id.clone(),
+                tails_file: offer.tails_file.clone(),
+            }),
+            IssuerV2FullState::Finished { revocation_info, .. } => revocation_info.clone(),
+            _ => None,
+        }
+    }
+
+    pub fn build_credential_offer_msg(
+        self,
+        credential_offer: &str,
+        credential_preview: CredentialPreviewV2,
+        comment: Option<String>,
+        offer_info: &OfferInfo,
+    ) -> VcxResult<Self> {
+        let opens_thread = match &self.state {
+            IssuerV2FullState::Initial => true,
+            IssuerV2FullState::OfferSet(data) => data.offer.decorators.thread.is_none(),
+            _ => false,
+        };
+        let state = match self.state {
+            IssuerV2FullState::Initial
+            | IssuerV2FullState::OfferSet(_)
+            | IssuerV2FullState::ProposalReceived { .. }
+            | IssuerV2FullState::OfferSent(_) => IssuerV2FullState::OfferSet(OfferDataV2 {
+                offer: build_credential_offer_v2(
+                    &self.thread_id,
+                    opens_thread,
+                    self.format,
+                    credential_offer,
+                    credential_preview,
+                    comment,
+                ),
+                cred_data: offer_info.credential_json.clone(),
+                rev_reg_id: offer_info.rev_reg_id.clone(),
+                tails_file: offer_info.tails_file.clone(),
+            }),
+            s => {
+                return Err(AriesVcxError::from_msg(
+                    AriesVcxErrorKind::InvalidState,
+                    format!("Can not set_offer in current state {}.", s),
+                ));
+            }
+        };
+        Ok(Self { state, ..self })
+    }
+
+    pub fn get_credential_offer_msg(&self) -> VcxResult<OfferCredentialV2> {
+        match &self.state {
+            IssuerV2FullState::OfferSet(data) | IssuerV2FullState::OfferSent(data) => Ok(data.offer.clone()),
+            _ => Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidState,
+                format!("Can not get_credential_offer in current state {}.", self.state),
+            )),
+        }
+    }
+
+    pub fn mark_credential_offer_msg_sent(self) -> VcxResult<Self> {
+        let state = match self.state {
+            IssuerV2FullState::OfferSet(data) | IssuerV2FullState::OfferSent(data) => {
+                IssuerV2FullState::OfferSent(data)
+            }
+            s => {
+                return Err(AriesVcxError::from_msg(
+                    AriesVcxErrorKind::InvalidState,
+                    format!("Can not mark_as_offer_sent in current state {}.", s),
+                ))
+            }
+        };
+        Ok(Self { state, ..self })
+    }
+
+    pub async fn send_credential_offer(self, send_message: SendClosure) -> VcxResult<Self> {
+        let offer = self.get_credential_offer_msg()?;
+        send_message(offer.into()).await?;
+        self.mark_credential_offer_msg_sent()
+    }
+
+    pub fn receive_proposal(self, proposal: ProposeCredentialV2) -> VcxResult<Self> {
+        let thread_id = match self.state {
+            IssuerV2FullState::Initial => proposal_thread_id(&proposal),
+            IssuerV2FullState::OfferSent(_) => self.thread_id.clone(),
+            s => {
+                warn!("Unable to receive credential proposal in state {}", s);
+                return Ok(Self { state: s, ..self });
+            }
+        };
+        Ok(Self {
+            thread_id,
+            format: proposal_format(&proposal).unwrap_or(self.format),
+            state: IssuerV2FullState::ProposalReceived { proposal },
+            ..self
+        })
+    }
+
+    pub async fn decline_proposal(self, comment: Option<String>, send_message: SendClosure) -> VcxResult<Self> {
+        let state = match self.state {
+            IssuerV2FullState::ProposalReceived { .. } => {
+                let problem_report = build_problem_report_msg_v2(comment, &self.thread_id);
+                send_message(problem_report.clone().into()).await?;
+                IssuerV2FullState::Finished {
+                    revocation_info: None,
+                    status: Status::Declined(problem_report_from_v2(problem_report)),
+                }
+            }
+            s => {
+                warn!("Unable to decline credential proposal in state {}", s);
+                s
+            }
+        };
+        Ok(Self { state, ..self })
+    }
+
+    pub fn receive_request(self, request: RequestCredentialV2) -> VcxResult<Self> {
+        verify_thread_id(
+            &self.thread_id,
+            &CredentialIssuanceV2Action::CredentialRequest(request.clone()),
+        )?;
+        let state = match self.state {
+            IssuerV2FullState::OfferSent(offer) => IssuerV2FullState::RequestReceived { offer, request },
+            s => {
+                warn!("Unable to receive credential request in state {}", s);
+                s
+            }
+        };
+        Ok(Self { state, ..self })
+    }
+
+    pub async fn send_credential(self, profile: &Arc<dyn Profile>, send_message: SendClosure) -> VcxResult<Self> {
+        let state = match self.state {
+            IssuerV2FullState::RequestReceived { offer, request } => {
+                match _create_credential(profile, &offer, &request).await {
+                    Ok((credential, cred_rev_id)) => {
+                        let credential_msg = build_credential_message_v2(&self.thread_id, self.format, &credential);
+                        send_message(credential_msg.into()).await?;
+                        IssuerV2FullState::CredentialSent { offer, cred_rev_id }
+                    }
+                    Err(err) => {
+                        let problem_report = build_problem_report_msg_v2(Some(err.to_string()), &self.thread_id);
+                        error!(
+                            "Failed to create credential, sending problem report {:?}",
+                            problem_report
+                        );
+                        send_message(problem_report.clone().into()).await?;
+                        IssuerV2FullState::Finished {
+                            revocation_info: None,
+                            status: Status::Failed(problem_report_from_v2(problem_report)),
+                        }
+                    }
+                }
+            }
+            _ => {
+                return Err(AriesVcxError::from_msg(AriesVcxErrorKind::NotReady, "Invalid action"));
+            }
+        };
+        Ok(Self { state, ..self })
+    }
+
+    pub fn receive_ack(self) -> VcxResult<Self> {
+        let revocation_info = self.get_revocation_info();
+        let state = match self.state {
+            IssuerV2FullState::CredentialSent { .. } => IssuerV2FullState::Finished {
+                revocation_info,
+                status: Status::Success,
+            },
+            s => {
+                warn!("Unable to receive credential ack in state {}", s);
+                s
+            }
+        };
+        Ok(Self { state, ..self })
+    }
+
+    pub fn receive_problem_report(self, problem_report: ProblemReport) -> VcxResult<Self> {
+        let revocation_info = self.get_revocation_info();
+        let state = match self.state {
+            IssuerV2FullState::OfferSent(_) => IssuerV2FullState::Finished {
+                revocation_info: None,
+                status: Status::Failed(problem_report),
+            },
+            // the credential was issued, the holder may still fail to store it
+            IssuerV2FullState::CredentialSent { .. } => IssuerV2FullState::Finished {
+                revocation_info,
+                status: Status::Failed(problem_report),
+            },
+            s => {
+                warn!("Unable to receive problem report in state {}", s);
+                s
+            }
+        };
+        Ok(Self { state, ..self })
+    }
+
+    pub fn find_message_to_handle(&self, messages: HashMap<String, AriesMessage>) -> Option<(String, AriesMessage)> {
+        trace!(
+            "IssuerV2SM::find_message_to_handle >>> messages: {:?}, state: {:?}",
+            messages,
+            self.state
+        );
+
+        for (uid, message) in messages {
+            let action = CredentialIssuanceV2Action::from(message.clone());
+            let is_expected = match (&self.state, &action) {
+                (IssuerV2FullState::Initial, CredentialIssuanceV2Action::CredentialProposal(_)) => {
+                    return Some((uid, message))
+                }
+                (IssuerV2FullState::OfferSent(_), CredentialIssuanceV2Action::CredentialRequest(_))
+                | (IssuerV2FullState::OfferSent(_), CredentialIssuanceV2Action::CredentialProposal(_))
+                | (IssuerV2FullState::OfferSent(_), CredentialIssuanceV2Action::ProblemReport(_))
+                | (IssuerV2FullState::CredentialSent { .. }, CredentialIssuanceV2Action::CredentialAck(_))
+                | (IssuerV2FullState::CredentialSent { .. }, CredentialIssuanceV2Action::ProblemReport(_)) => true,
+                _ => false,
+            };
+            if is_expected && action.thread_id_matches(&self.thread_id) {
+                return Some((uid, message));
+            }
+        }
+
+        None
+    }
+
+    pub async fn handle_message(
+        self,
+        profile: &Arc<dyn Profile>,
+        action: CredentialIssuanceV2Action,
+        send_message: Option<SendClosure>,
+    ) -> VcxResult<Self> {
+        trace!(
+            "IssuerV2SM::handle_message >>> action: {:?}, state: {:?}",
+            action,
+            self.state
+        );
+        if !matches!(self.state, IssuerV2FullState::Initial) {
+            verify_thread_id(&self.thread_id, &action)?;
+        }
+        let issuer_sm = match action {
+            CredentialIssuanceV2Action::CredentialProposal(proposal) => self.receive_proposal(proposal)?,
+            CredentialIssuanceV2Action::CredentialRequest(request) => self.receive_request(request)?,
+            CredentialIssuanceV2Action::CredentialSend() => {
+                let send_message = send_message.ok_or(AriesVcxError::from_msg(
+                    AriesVcxErrorKind::InvalidState,
+                    "Attempted to call undefined send_message callback",
+                ))?;
+                self.send_credential(profile, send_message).await?
+            }
+            CredentialIssuanceV2Action::CredentialAck(_) => self.receive_ack()?,
+            CredentialIssuanceV2Action::ProblemReport(problem_report) => self.receive_problem_report(problem_report)?,
+            _ => self,
+        };
+        Ok(issuer_sm)
+    }
+
+    pub fn credential_status(&self) -> u32 {
+        match self.state {
+            IssuerV2FullState::Finished { ref status, .. } => status.code(),
+            _ => Status::Undefined.code(),
+        }
+    }
+
+    pub fn is_terminal_state(&self) -> bool {
+        matches!(self.state, IssuerV2FullState::Finished { .. })
+    }
+}
+
+async fn _create_credential(
+    profile: &Arc<dyn Profile>,
+    offer: &OfferDataV2,
+    request: &RequestCredentialV2,
+) -> VcxResult<(String, Option<String>)> {
+    let (_, offer_json) = get_anoncreds_attachment(&offer.offer.content.formats, &offer.offer.content.offers_attach)?;
+    let (_, request_json) = get_anoncreds_attachment(&request.content.formats, &request.content.requests_attach)?;
+    create_anoncreds_credential(
+        profile,
+        &offer_json,
+        &request_json,
+        &offer.cred_data,
+        &offer.rev_reg_id,
+        &offer.tails_file,
+    )
+    .await
+}
@@ -0,0 +1,378 @@
+use messages::decorators::attachment::{Attachment, AttachmentType};
+use messages::maybe_known::MaybeKnown;
+use messages::msg_fields::protocols::cred_issuance::v2::ack::{AckCredentialV2, AckCredentialV2Content};
+use messages::msg_fields::protocols::cred_issuance::v2::issue_credential::{
+    IssueCredentialAttachmentFormatType, IssueCredentialV2,
+};
+use messages::msg_fields::protocols::cred_issuance::v2::offer_credential::{
+    OfferCredentialAttachmentFormatType, OfferCredentialV2,
+};
+use messages::msg_fields::protocols::cred_issuance::v2::problem_report::{
+    CredIssuanceProblemReportV2, CredIssuanceProblemReportV2Content,
+};
+use messages::msg_fields::protocols::cred_issuance::v2::propose_credential::{
+    ProposeCredentialAttachmentFormatType, ProposeCredentialV2,
+};
+use messages::msg_fields::protocols::cred_issuance::v2::request_credential::{
+    RequestCredentialAttachmentFormatType, RequestCredentialV2,
+};
+use messages::msg_fields::protocols::cred_issuance::v2::{AttachmentFormatSpecifier, CredentialIssuanceV2};
+use messages::msg_fields::protocols::cred_issuance::CredentialIssuance;
+use messages::msg_fields::protocols::report_problem::ProblemReport;
+use messages::msg_parts::MsgParts;
+use messages::AriesMessage;
+use uuid::Uuid;
+
+use crate::errors::error::prelude::*;
+use crate::handlers::util::{make_attach_from_str, matches_opt_thread_id, matches_thread_id};
+use crate::protocols::common::{build_problem_report_msg, GenericMessage, ThreadedAction};
+
+pub mod holder;
+pub mod issuer;
+
+/// Attachment formats of the AnonCreds payloads exchanged with issue-credential 2.0.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum AnoncredsAttachmentFormat {
+    /// The `hlindy/*@v2.0` formats, understood by all agents supporting Indy credentials.
+    #[default]
+    HyperledgerIndy,
+    /// The `anoncreds/*@v1.0` formats.
+    Anoncreds,
+}
+
+impl AnoncredsAttachmentFormat {
+    pub fn offer_format(self) -> OfferCredentialAttachmentFormatType {
+        match self {
+            Self::HyperledgerIndy => OfferCredentialAttachmentFormatType::HyperledgerIndyCredentialAbstract2_0,
+            Self::Anoncreds => OfferCredentialAttachmentFormatType::AnoncredsCredentialOffer1_0,
+        }
+    }
+
+    pub fn request_format(self) -> RequestCredentialAttachmentFormatType {
+        match self {
+            Self::HyperledgerIndy => RequestCredentialAttachmentFormatType::HyperledgerIndyCredentialRequest2_0,
+            Self::Anoncreds => RequestCredentialAttachmentFormatType::AnoncredsCredentialRequest1_0,
+        }
+    }
+
+    pub fn credential_format(self) -> IssueCredentialAttachmentFormatType {
+        match self {
+            Self::HyperledgerIndy => IssueCredentialAttachmentFormatType::HyperledgerIndyCredential2_0,
+            Self::Anoncreds => IssueCredentialAttachmentFormatType::AnoncredsCredential1_0,
+        }
+    }
+}
+
+/// Attachment format types carrying AnonCreds payloads.
+pub(crate) trait AnoncredsFormatType {
+    fn anoncreds_format(&self) -> Option<AnoncredsAttachmentFormat>;
+}
+
+impl AnoncredsFormatType for ProposeCredentialAttachmentFormatType {
+    fn anoncreds_format(&self) -> Option<AnoncredsAttachmentFormat> {
+        match self {
+            Self::HyperledgerIndyCredentialFilter2_0 => Some(AnoncredsAttachmentFormat::HyperledgerIndy),
+            Self::AnoncredsCredentialFilter1_0 => Some(AnoncredsAttachmentFormat::Anoncreds),
+            Self::AriesLdProofVcDetail1_0 => None,
+        }
+    }
+}
+
+impl AnoncredsFormatType for OfferCredentialAttachmentFormatType {
+    fn anoncreds_format(&self) -> Option<AnoncredsAttachmentFormat> {
+        match self {
+            Self::HyperledgerIndyCredentialAbstract2_0 => Some(AnoncredsAttachmentFormat::HyperledgerIndy),
+            Self::AnoncredsCredentialOffer1_0 => Some(AnoncredsAttachmentFormat::Anoncreds),
+        }
+    }
+}
+
+impl AnoncredsFormatType for RequestCredentialAttachmentFormatType {
+    fn anoncreds_format(&self) -> Option<AnoncredsAttachmentFormat> {
+        match self {
+            Self::HyperledgerIndyCredentialRequest2_0 => Some(AnoncredsAttachmentFormat::HyperledgerIndy),
+            Self::AnoncredsCredentialRequest1_0 => Some(AnoncredsAttachmentFormat::Anoncreds),
+        }
+    }
+}
+
+impl AnoncredsFormatType for IssueCredentialAttachmentFormatType {
+    fn anoncreds_format(&self) -> Option<AnoncredsAttachmentFormat> {
+        match self {
+            Self::HyperledgerIndyCredential2_0 => Some(AnoncredsAttachmentFormat::HyperledgerIndy),
+            Self::AnoncredsCredential1_0 => Some(AnoncredsAttachmentFormat::Anoncreds),
+        }
+    }
+}
+
+/// Returns the first AnonCreds format listed in `formats` together with its decoded attachment.
+pub(crate) fn get_anoncreds_attachment<F: AnoncredsFormatType>(
+    formats: &[AttachmentFormatSpecifier<F>],
+    attachments: &[Attachment],
+) -> VcxResult<(AnoncredsAttachmentFormat, String)> {
+    let (format, attach_id) = formats
+        .iter()
+        .find_map(|specifier| match &specifier.format {
+            MaybeKnown::Known(format) => format
+                .anoncreds_format()
+                .map(|format| (format, specifier.attach_id.as_str())),
+            MaybeKnown::Unknown(_) => None,
+        })
+        .ok_or_else(|| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::ActionNotSupported,
+                "Message has no attachment in a supported AnonCreds format",
+            )
+        })?;
+    let attachment = attachments
+        .iter()
+        .find(|attachment| attachment.id.as_deref() == Some(attach_id))
+        .ok_or_else(|| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidMessageFormat,
+                format!("Message has no attachment with id {}", attach_id),
+            )
+        })?;
+    let content = match &attachment.data.content {
+        AttachmentType::Base64(encoded) => base64::decode(encoded)
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok()),
+        AttachmentType::Json(value) => Some(value.to_string()),
+        AttachmentType::Links(_) => None,
+    };
+    let content = content.ok_or_else(|| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::SerializationError,
+            format!("Attachment is not base 64 encoded JSON: {:?}", attachment),
+        )
+    })?;
+    Ok((format, content))
+}
+
+/// Builds the `formats` entry and the attachment of an AnonCreds payload.
+pub(crate) fn make_anoncreds_attachment<F>(format: F, payload: &str) -> (AttachmentFormatSpecifier<F>, Attachment) {
+    let attach_id = Uuid::new_v4().to_string();
+    let attachment = make_attach_from_str!(payload, attach_id.clone());
+    (AttachmentFormatSpecifier::new(attach_id, format), attachment)
+}
+
+pub(crate) fn build_problem_report_msg_v2(comment: Option<String>, thread_id: &str) -> CredIssuanceProblemReportV2 {
+    let MsgParts {
+        id,
+        content,
+        decorators,
+    } = build_problem_report_msg(comment, thread_id);
+    CredIssuanceProblemReportV2::with_decorators(id, CredIssuanceProblemReportV2Content(content), decorators)
+}
+
+pub(crate) fn problem_report_from_v2(problem_report: CredIssuanceProblemReportV2) -> ProblemReport {
+    let MsgParts {
+        id,
+        content,
+        decorators,
+    } = problem_report;
+    ProblemReport::with_decorators(id, content.0, decorators)
+}
+
+#[derive(Debug, Clone)]
+pub enum CredentialIssuanceV2Action {
+    CredentialSend(),
+    CredentialRequestSend(String),
+    CredentialProposal(ProposeCredentialV2),
+    CredentialOffer(OfferCredentialV2),
+    CredentialRequest(RequestCredentialV2),
+    Credential(IssueCredentialV2),
+    CredentialAck(AckCredentialV2),
+    ProblemReport(ProblemReport),
+    Unknown,
+}
+
+impl ThreadedAction for CredentialIssuanceV2Action {
+    fn thread_id_matches(&self, thread_id: &str) -> bool {
+        match self {
+            Self::CredentialProposal(msg) => matches_opt_thread_id!(msg, thread_id),
+            Self::CredentialOffer(msg) => matches_opt_thread_id!(msg, thread_id),
+            Self::CredentialRequest(msg) => matches_opt_thread_id!(msg, thread_id),
+            Self::Credential(msg) => matches_thread_id!(msg, thread_id),
+            Self::CredentialAck(msg) => matches_thread_id!(msg, thread_id),
+            Self::ProblemReport(msg) => matches_opt_thread_id!(msg, thread_id),
+            _ => true,
+        }
+    }
+}
+
+impl From<AriesMessage> for CredentialIssuanceV2Action {
+    fn from(msg: AriesMessage) -> Self {
+        match msg {
+            AriesMessage::CredentialIssuance(CredentialIssuance::V2(msg)) => match msg {
+                CredentialIssuanceV2::ProposeCredential(proposal) => Self::CredentialProposal(proposal),
+                CredentialIssuanceV2::OfferCredential(offer) => Self::CredentialOffer(offer),
+                CredentialIssuanceV2::RequestCredential(request) => Self::CredentialRequest(request),
+                CredentialIssuanceV2::IssueCredential(credential) => Self::Credential(credential),
+                CredentialIssuanceV2::Ack(ack) => Self::CredentialAck(ack),
+                CredentialIssuanceV2::ProblemReport(report) => Self::ProblemReport(problem_report_from_v2(report)),
+            },
+            msg => match GenericMessage::from_message(msg) {
+                Some(GenericMessage::Ack(ack)) => {
+                    let MsgParts {
+                        id,
+                        content,
+                        decorators,
+                    } = ack;
+                    Self::CredentialAck(AckCredentialV2::with_decorators(
+                        id,
+                        AckCredentialV2Content(content),
+                        decorators,
+                    ))
+                }
+                Some(GenericMessage::ProblemReport(report)) => Self::ProblemReport(report),
+                None => Self::Unknown,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+pub mod unit_tests {
+    use messages::decorators::attachment::AttachmentData;
+    use messages::msg_fields::protocols::cred_issuance::v2::CredentialPreviewV2;
+    use messages::msg_fields::protocols::cred_issuance::CredentialAttr;
+
+    use crate::common::test_utils::{mock_profile, Outbox};
+    use crate::handlers::util::OfferInfo;
+    use crate::protocols::issuance::holder::state_machine::HolderState;
+    use crate::protocols::issuance::issuer::state_machine::IssuerState;
+    use crate::protocols::issuance::v2::holder::HolderV2SM;
+    use crate::protocols::issuance::v2::issuer::IssuerV2SM;
+    use crate::utils::devsetup::SetupMocks;
+
+    use super::*;
+
+    fn _offered_issuer(format: AnoncredsAttachmentFormat) -> IssuerV2SM {
+        let offer_json = json!({ "schema_id": "schema_id", "cred_def_id": "cred_def_id" }).to_string();
+        let preview = CredentialPreviewV2::new(vec![CredentialAttr::new("age".to_string(), "25".to_string())]);
+        let offer_info = OfferInfo::new(
+            json!({ "age": "25" }).to_string(),
+            "cred_def_id".to_string(),
+            None,
+            None,
+        );
+        IssuerV2SM::new("issuer", format)
+            .build_credential_offer_msg(&offer_json, preview, None, &offer_info)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_get_anoncreds_attachment() {
+        let (format, attachment) = make_anoncreds_attachment(
+            AnoncredsAttachmentFormat::Anoncreds.offer_format(),
+            r#"{"cred_def_id":"cred_def_id"}"#,
+        );
+        let mut json_attachment = Attachment::new(AttachmentData::new(AttachmentType::Json(json!({ "key": "value" }))));
+        json_attachment.id = Some("json".to_string());
+        let json_format = AttachmentFormatSpecifier::new(
+            "json".to_string(),
+            AnoncredsAttachmentFormat::HyperledgerIndy.offer_format(),
+        );
+
+        let attachments = vec![json_attachment, attachment];
+        let (found_format, payload) = get_anoncreds_attachment(&[format.clone()], &attachments).unwrap();
+        assert_eq!(found_format, AnoncredsAttachmentFormat::Anoncreds);
+        assert_eq!(payload, r#"{"cred_def_id":"cred_def_id"}"#);
+
+        let (found_format, payload) = get_anoncreds_attachment(&[json_format, format], &attachments).unwrap();
+        assert_eq!(found_format, AnoncredsAttachmentFormat::HyperledgerIndy);
+        assert_eq!(payload, json!({ "key": "value" }).to_string());
+
+        let ld_proof = AttachmentFormatSpecifier::new(
+            "json".to_string(),
+            ProposeCredentialAttachmentFormatType::AriesLdProofVcDetail1_0,
+        );
+        assert_eq!(
+            get_anoncreds_attachment(&[ld_proof], &attachments).unwrap_err().kind(),
+            AriesVcxErrorKind::ActionNotSupported
+        );
+    }
+
+    #[tokio::test]
+    async fn test_issue_credential_v2() {
+        let _setup = SetupMocks::init();
+        let profile = mock_profile();
+        let outbox = Outbox::default();
+
+        let issuer = _offered_issuer(AnoncredsAttachmentFormat::Anoncreds)
+            .send_credential_offer(outbox.send_closure())
+            .await
+            .unwrap();
+        assert_eq!(issuer.get_state(), IssuerState::OfferSent);
+        let thread_id = issuer.thread_id().unwrap();
+
+        let offer = match outbox.pop::<CredentialIssuanceV2Action>() {
+            CredentialIssuanceV2Action::CredentialOffer(offer) => offer,
+            action => panic!("Expected credential offer, got {:?}", action),
+        };
+        let holder = HolderV2SM::from_offer(offer, "holder".to_string());
+        assert_eq!(holder.get_thread_id().unwrap(), thread_id);
+        assert_eq!(holder.get_attributes().unwrap(), json!({ "age": "25" }).to_string());
+
+        let holder = holder
+            .send_request(&profile, "my_pw_did".to_string(), outbox.send_closure())
+            .await
+            .unwrap();
+        assert_eq!(holder.get_state(), HolderState::RequestSent);
+        let request: CredentialIssuanceV2Action = outbox.pop();
+        match &request {
+            CredentialIssuanceV2Action::CredentialRequest(request) => assert_eq!(
+                request.content.formats[0].format,
+                MaybeKnown::Known(RequestCredentialAttachmentFormatType::AnoncredsCredentialRequest1_0)
+            ),
+            action => panic!("Expected credential request, got {:?}", action),
+        };
+
+        let issuer = issuer.handle_message(&profile, request, None).await.unwrap();
+        assert_eq!(issuer.get_state(), IssuerState::RequestReceived);
+        let issuer = issuer
+            .handle_message(
+                &profile,
+                CredentialIssuanceV2Action::CredentialSend(),
+                Some(outbox.send_closure()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(issuer.get_state(), IssuerState::CredentialSent);
+
+        let credential: CredentialIssuanceV2Action = outbox.pop();
+        let holder = holder
+            .handle_message(&profile, credential, Some(outbox.send_closure()))
+            .await
+            .unwrap();
+        assert_eq!(holder.get_state(), HolderState::Finished);
+        assert_eq!(holder.get_cred_id().unwrap(), "cred_id");
+
+        let ack: CredentialIssuanceV2Action = outbox.pop();
+        assert!(ack.thread_id_matches(&thread_id));
+        let issuer = issuer.handle_message(&profile, ack, None).await.unwrap();
+        assert_eq!(issuer.get_state(), IssuerState::Finished);
+    }
+
+    #[tokio::test]
+    async fn test_holder_declines_offer_v2() {
+        let _setup = SetupMocks::init();
+        let outbox = Outbox::default();
+
+        let offer = _offered_issuer(AnoncredsAttachmentFormat::HyperledgerIndy)
+            .get_credential_offer_msg()
+            .unwrap();
+        let holder = HolderV2SM::from_offer(offer, "holder".to_string())
+            .decline_offer(Some("not interested".to_string()), outbox.send_closure())
+            .await
+            .unwrap();
+
+        assert_eq!(holder.get_state(), HolderState::Failed);
+        assert!(matches!(
+            outbox.pop::<AriesMessage>(),
+            AriesMessage::CredentialIssuance(CredentialIssuance::V2(CredentialIssuanceV2::ProblemReport(_)))
+        ));
+    }
+}
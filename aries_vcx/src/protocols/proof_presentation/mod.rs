@@ -1,2 +1,3 @@
 pub mod prover;
+pub mod v2;
 pub mod verifier;
@@ -0,0 +1,303 @@
+use messages::maybe_known::MaybeKnown;
+use messages::msg_fields::protocols::discover_features::ProtocolDescriptor;
+use messages::msg_fields::protocols::present_proof::v2::ack::{AckPresentationV2, AckPresentationV2Content};
+use messages::msg_fields::protocols::present_proof::v2::present::{PresentationAttachmentFormatType, PresentationV2};
+use messages::msg_fields::protocols::present_proof::v2::problem_report::{
+    PresentProofProblemReportV2, PresentProofProblemReportV2Content,
+};
+use messages::msg_fields::protocols::present_proof::v2::propose::{
+    ProposePresentationAttachmentFormatType, ProposePresentationV2,
+};
+use messages::msg_fields::protocols::present_proof::v2::request::{
+    RequestPresentationAttachmentFormatType, RequestPresentationV2,
+};
+use messages::msg_fields::protocols::present_proof::v2::PresentProofV2;
+use messages::msg_fields::protocols::present_proof::PresentProof;
+use messages::msg_fields::protocols::report_problem::ProblemReport;
+use messages::msg_parts::MsgParts;
+use messages::msg_types::protocols::present_proof::PresentProofType;
+use messages::msg_types::Protocol;
+use messages::AriesMessage;
+
+use crate::handlers::util::{matches_opt_thread_id, matches_thread_id};
+use crate::protocols::common::{build_problem_report_msg, GenericMessage, ThreadedAction};
+use crate::protocols::issuance::v2::{AnoncredsAttachmentFormat, AnoncredsFormatType};
+
+pub mod prover;
+pub mod verifier;
+
+impl AnoncredsAttachmentFormat {
+    pub fn proof_request_format(self) -> RequestPresentationAttachmentFormatType {
+        match self {
+            Self::HyperledgerIndy => RequestPresentationAttachmentFormatType::HyperledgerIndyProofRequest2_0,
+            Self::Anoncreds => RequestPresentationAttachmentFormatType::AnoncredsProofRequest1_0,
+        }
+    }
+
+    pub fn presentation_format(self) -> PresentationAttachmentFormatType {
+        match self {
+            Self::HyperledgerIndy => PresentationAttachmentFormatType::HyperledgerIndyProof2_0,
+            Self::Anoncreds => PresentationAttachmentFormatType::AnoncredsProof1_0,
+        }
+    }
+}
+
+impl AnoncredsFormatType for ProposePresentationAttachmentFormatType {
+    fn anoncreds_format(&self) -> Option<AnoncredsAttachmentFormat> {
+        match self {
+            Self::HyperledgerIndyProofRequest2_0 => Some(AnoncredsAttachmentFormat::HyperledgerIndy),
+            Self::AnoncredsProofRequest1_0 => Some(AnoncredsAttachmentFormat::Anoncreds),
+        }
+    }
+}
+
+impl AnoncredsFormatType for RequestPresentationAttachmentFormatType {
+    fn anoncreds_format(&self) -> Option<AnoncredsAttachmentFormat> {
+        match self {
+            Self::HyperledgerIndyProofRequest2_0 => Some(AnoncredsAttachmentFormat::HyperledgerIndy),
+            Self::AnoncredsProofRequest1_0 => Some(AnoncredsAttachmentFormat::Anoncreds),
+        }
+    }
+}
+
+impl AnoncredsFormatType for PresentationAttachmentFormatType {
+    fn anoncreds_format(&self) -> Option<AnoncredsAttachmentFormat> {
+        match self {
+            Self::HyperledgerIndyProof2_0 => Some(AnoncredsAttachmentFormat::HyperledgerIndy),
+            Self::AnoncredsProof1_0 => Some(AnoncredsAttachmentFormat::Anoncreds),
+        }
+    }
+}
+
+/// Version of the present-proof protocol to run with a counterparty.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum PresentProofVersion {
+    V1,
+    V2,
+}
+
+impl PresentProofVersion {
+    /// Picks present-proof 2.0 if the counterparty disclosed support for it, otherwise falls back to 1.0, which is
+    /// also used while the protocols of the counterparty are not yet discovered.
+    pub fn negotiate(remote_protocols: Option<&[ProtocolDescriptor]>) -> Self {
+        let supports_v2 = remote_protocols.unwrap_or_default().iter().any(|descriptor| {
+            matches!(
+                descriptor.pid,
+                MaybeKnown::Known(Protocol::PresentProofType(PresentProofType::V2(_)))
+            )
+        });
+        if supports_v2 {
+            Self::V2
+        } else {
+            Self::V1
+        }
+    }
+}
+
+pub(crate) fn build_problem_report_msg_v2(comment: Option<String>, thread_id: &str) -> PresentProofProblemReportV2 {
+    let MsgParts {
+        id,
+        content,
+        decorators,
+    } = build_problem_report_msg(comment, thread_id);
+    PresentProofProblemReportV2::with_decorators(id, PresentProofProblemReportV2Content(content), decorators)
+}
+
+pub(crate) fn problem_report_from_v2(problem_report: PresentProofProblemReportV2) -> ProblemReport {
+    let MsgParts {
+        id,
+        content,
+        decorators,
+    } = problem_report;
+    ProblemReport::with_decorators(id, content.0, decorators)
+}
+
+#[derive(Debug, Clone)]
+pub enum PresentProofV2Action {
+    PresentationProposal(ProposePresentationV2),
+    PresentationRequest(RequestPresentationV2),
+    Presentation(PresentationV2),
+    PresentationAck(AckPresentationV2),
+    ProblemReport(ProblemReport),
+    Unknown,
+}
+
+impl ThreadedAction for PresentProofV2Action {
+    fn thread_id_matches(&self, thread_id: &str) -> bool {
+        match self {
+            Self::PresentationProposal(msg) => matches_opt_thread_id!(msg, thread_id),
+            Self::PresentationRequest(msg) => matches_opt_thread_id!(msg, thread_id),
+            Self::Presentation(msg) => matches_thread_id!(msg, thread_id),
+            Self::PresentationAck(msg) => matches_thread_id!(msg, thread_id),
+            Self::ProblemReport(msg) => matches_opt_thread_id!(msg, thread_id),
+            Self::Unknown => true,
+        }
+    }
+}
+
+impl From<AriesMessage> for PresentProofV2Action {
+    fn from(msg: AriesMessage) -> Self {
+        match msg {
+            AriesMessage::PresentProof(PresentProof::V2(msg)) => match msg {
+                PresentProofV2::ProposePresentation(proposal) => Self::PresentationProposal(proposal),
+                PresentProofV2::RequestPresentation(request) => Self::PresentationRequest(request),
+                PresentProofV2::Presentation(presentation) => Self::Presentation(presentation),
+                PresentProofV2::Ack(ack) => Self::PresentationAck(ack),
+                PresentProofV2::ProblemReport(report) => Self::ProblemReport(problem_report_from_v2(report)),
+            },
+            msg => match GenericMessage::from_message(msg) {
+                Some(GenericMessage::Ack(ack)) => {
+                    let MsgParts {
+                        id,
+                        content,
+                        decorators,
+                    } = ack;
+                    Self::PresentationAck(AckPresentationV2::with_decorators(
+                        id,
+                        AckPresentationV2Content(content),
+                        decorators,
+                    ))
+                }
+                Some(GenericMessage::ProblemReport(report)) => Self::ProblemReport(report),
+                None => Self::Unknown,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+pub mod unit_tests {
+    use messages::msg_types::protocols::present_proof::{PresentProofTypeV1, PresentProofTypeV2};
+    use messages::msg_types::protocols::trust_ping::TrustPingTypeV1;
+
+    use crate::common::proofs::proof_request::test_utils::_presentation_request_data;
+    use crate::common::test_utils::{mock_profile, Outbox};
+    use crate::protocols::proof_presentation::prover::state_machine::ProverState;
+    use crate::protocols::proof_presentation::v2::prover::ProverV2SM;
+    use crate::protocols::proof_presentation::v2::verifier::VerifierV2SM;
+    use crate::protocols::proof_presentation::verifier::state_machine::VerifierState;
+    use crate::protocols::proof_presentation::verifier::verification_status::PresentationVerificationStatus;
+    use crate::utils::devsetup::SetupMocks;
+    use crate::utils::mockdata::mock_settings::MockBuilder;
+
+    use super::*;
+
+    #[test]
+    fn test_negotiate_present_proof_version() {
+        let descriptor = |protocol: Protocol| ProtocolDescriptor::new(MaybeKnown::Known(protocol));
+
+        assert_eq!(PresentProofVersion::negotiate(None), PresentProofVersion::V1);
+        assert_eq!(PresentProofVersion::negotiate(Some(&[][..])), PresentProofVersion::V1);
+        let v1_only = vec![
+            descriptor(PresentProofTypeV1::new_v1_0().into()),
+            descriptor(TrustPingTypeV1::new_v1_0().into()),
+        ];
+        assert_eq!(
+            PresentProofVersion::negotiate(Some(v1_only.as_slice())),
+            PresentProofVersion::V1
+        );
+        let with_v2 = vec![
+            descriptor(PresentProofTypeV1::new_v1_0().into()),
+            descriptor(PresentProofTypeV2::new_v2_0().into()),
+        ];
+        assert_eq!(
+            PresentProofVersion::negotiate(Some(with_v2.as_slice())),
+            PresentProofVersion::V2
+        );
+    }
+
+    async fn _run_presentation(will_confirm: bool) -> (ProverV2SM, VerifierV2SM, Outbox) {
+        let profile = mock_profile();
+        let outbox = Outbox::default();
+
+        let verifier = VerifierV2SM::new("verifier", AnoncredsAttachmentFormat::Anoncreds)
+            .send_presentation_request(&_presentation_request_data(), None, will_confirm, outbox.send_closure())
+            .await
+            .unwrap();
+        assert_eq!(verifier.get_state(), VerifierState::PresentationRequestSent);
+
+        let request = match outbox.pop::<PresentProofV2Action>() {
+            PresentProofV2Action::PresentationRequest(request) => request,
+            action => panic!("Expected presentation request, got {:?}", action),
+        };
+        assert_eq!(request.content.will_confirm, Some(will_confirm));
+        let prover = ProverV2SM::from_request(request, "prover".to_string());
+        assert_eq!(prover.get_thread_id().unwrap(), verifier.thread_id());
+
+        let prover = prover
+            .send_presentation(&profile, "{}".to_string(), "{}".to_string(), outbox.send_closure())
+            .await
+            .unwrap();
+        let presentation: PresentProofV2Action = outbox.pop();
+        match &presentation {
+            PresentProofV2Action::Presentation(presentation) => assert_eq!(
+                presentation.content.formats[0].format,
+                MaybeKnown::Known(PresentationAttachmentFormatType::AnoncredsProof1_0)
+            ),
+            action => panic!("Expected presentation, got {:?}", action),
+        };
+
+        let verifier = verifier
+            .handle_message(&profile, presentation, Some(outbox.send_closure()))
+            .await
+            .unwrap();
+        (prover, verifier, outbox)
+    }
+
+    #[tokio::test]
+    async fn test_present_proof_v2_will_confirm() {
+        let _setup = SetupMocks::init();
+        let _mock_builder = MockBuilder::init()
+            .set_mock_generate_indy_proof("{}")
+            .set_mock_result_for_validate_indy_proof(Ok(true));
+        let profile = mock_profile();
+
+        // the prover waits for the confirmation the verifier committed to
+        let (prover, verifier, outbox) = _run_presentation(true).await;
+        assert_eq!(verifier.get_state(), VerifierState::Finished);
+        assert_eq!(
+            verifier.get_verification_status(),
+            PresentationVerificationStatus::Valid
+        );
+        assert_eq!(prover.get_state(), ProverState::PresentationSent);
+        let ack: PresentProofV2Action = outbox.pop();
+        assert!(matches!(ack, PresentProofV2Action::PresentationAck(_)));
+        let prover = prover.handle_message(&profile, ack, None).await.unwrap();
+        assert_eq!(prover.get_state(), ProverState::Finished);
+
+        // without confirmation, the exchange ends once the presentation is sent
+        let (prover, verifier, outbox) = _run_presentation(false).await;
+        assert_eq!(verifier.get_state(), VerifierState::Finished);
+        assert_eq!(prover.get_state(), ProverState::Finished);
+        assert!(outbox.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_prover_declines_request_v2() {
+        let _setup = SetupMocks::init();
+        let outbox = Outbox::default();
+
+        let verifier = VerifierV2SM::new("verifier", AnoncredsAttachmentFormat::HyperledgerIndy)
+            .send_presentation_request(&_presentation_request_data(), None, true, outbox.send_closure())
+            .await
+            .unwrap();
+        let request = match outbox.pop::<PresentProofV2Action>() {
+            PresentProofV2Action::PresentationRequest(request) => request,
+            action => panic!("Expected presentation request, got {:?}", action),
+        };
+        let prover = ProverV2SM::from_request(request, "prover".to_string())
+            .decline_presentation_request(Some("not interested".to_string()), outbox.send_closure())
+            .await
+            .unwrap();
+        assert_eq!(prover.get_state(), ProverState::Failed);
+
+        let problem_report: PresentProofV2Action = outbox.pop();
+        assert!(matches!(problem_report, PresentProofV2Action::ProblemReport(_)));
+        let verifier = verifier
+            .handle_message(&mock_profile(), problem_report, None)
+            .await
+            .unwrap();
+        assert_eq!(verifier.get_state(), VerifierState::Failed);
+    }
+}
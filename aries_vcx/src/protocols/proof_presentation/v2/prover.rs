@@ -0,0 +1,386 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use chrono::Utc;
+use messages::decorators::thread::Thread;
+use messages::decorators::timing::Timing;
+use messages::msg_fields::protocols::present_proof::v2::ack::AckPresentationV2;
+use messages::msg_fields::protocols::present_proof::v2::present::{
+    PresentationV2, PresentationV2Content, PresentationV2Decorators,
+};
+use messages::msg_fields::protocols::present_proof::v2::propose::ProposePresentationV2;
+use messages::msg_fields::protocols::present_proof::v2::request::RequestPresentationV2;
+use messages::msg_fields::protocols::report_problem::ProblemReport;
+use messages::AriesMessage;
+use uuid::Uuid;
+
+use crate::common::proofs::prover::prover::generate_indy_proof;
+use crate::core::profile::profile::Profile;
+use crate::errors::error::prelude::*;
+use crate::handlers::util::Status;
+use crate::protocols::common::{verify_thread_id, ThreadedAction};
+use crate::protocols::issuance::v2::{get_anoncreds_attachment, make_anoncreds_attachment, AnoncredsAttachmentFormat};
+use crate::protocols::proof_presentation::prover::state_machine::ProverState;
+use crate::protocols::proof_presentation::v2::{
+    build_problem_report_msg_v2, problem_report_from_v2, PresentProofV2Action,
+};
+use crate::protocols::SendClosure;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub enum ProverV2FullState {
+    #[default]
+    Initial,
+    ProposalSent {
+        proposal: ProposePresentationV2,
+    },
+    RequestReceived {
+        request: RequestPresentationV2,
+    },
+    PresentationSent {
+        request: RequestPresentationV2,
+        presentation: PresentationV2,
+    },
+    Finished {
+        request: Option<RequestPresentationV2>,
+        presentation: Option<PresentationV2>,
+        status: Status,
+    },
+}
+
+impl fmt::Display for ProverV2FullState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            ProverV2FullState::Initial => f.write_str("Initial"),
+            ProverV2FullState::ProposalSent { .. } => f.write_str("ProposalSent"),
+            ProverV2FullState::RequestReceived { .. } => f.write_str("RequestReceived"),
+            ProverV2FullState::PresentationSent { .. } => f.write_str("PresentationSent"),
+            ProverV2FullState::Finished { .. } => f.write_str("Finished"),
+        }
+    }
+}
+
+/// Prover side of the present-proof 2.0 protocol, answering in the AnonCreds format of the received request.
+///
+/// Once the presentation is sent, the prover waits for the ack of the verifier only if the request set `will_confirm`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ProverV2SM {
+    source_id: String,
+    thread_id: String,
+    state: ProverV2FullState,
+}
+
+fn build_presentation_msg_v2(thread_id: &str, format: AnoncredsAttachmentFormat, proof: &str) -> PresentationV2 {
+    let (presentation_format, attachment) = make_anoncreds_attachment(format.presentation_format(), proof);
+    let content = PresentationV2Content::new(vec![presentation_format], vec![attachment]);
+
+    let mut decorators = PresentationV2Decorators::new(Thread::new(thread_id.to_owned()));
+    let mut timing = Timing::default();
+    timing.out_time = Some(Utc::now());
+    decorators.timing = Some(timing);
+
+    PresentationV2::with_decorators(Uuid::new_v4().to_string(), content, decorators)
+}
+
+// a request answering a proposal refers to the thread of the proposal, otherwise it opens a thread
+fn request_thread_id(request: &RequestPresentationV2) -> String {
+    match &request.decorators.thread {
+        Some(thread) => thread.thid.clone(),
+        None => request.id.clone(),
+    }
+}
+
+impl ProverV2SM {
+    pub fn new(source_id: String) -> Self {
+        Self {
+            source_id,
+            thread_id: Uuid::new_v4().to_string(),
+            state: ProverV2FullState::Initial,
+        }
+    }
+
+    pub fn from_request(request: RequestPresentationV2, source_id: String) -> Self {
+        Self {
+            source_id,
+            thread_id: request_thread_id(&request),
+            state: ProverV2FullState::RequestReceived { request },
+        }
+    }
+
+    pub fn get_source_id(&self) -> String {
+        self.source_id.clone()
+    }
+
+    pub fn get_thread_id(&self) -> VcxResult<String> {
+        Ok(self.thread_id.clone())
+    }
+
+    pub fn get_state(&self) -> ProverState {
+        match self.state {
+            ProverV2FullState::Initial => ProverState::Initial,
+            ProverV2FullState::ProposalSent { .. } => ProverState::PresentationProposalSent,
+            ProverV2FullState::RequestReceived { .. } => ProverState::PresentationRequestReceived,
+            ProverV2FullState::PresentationSent { .. } => ProverState::PresentationSent,
+            ProverV2FullState::Finished { ref status, .. } => match status {
+                Status::Success => ProverState::Finished,
+                _ => ProverState::Failed,
+            },
+        }
+    }
+
+    pub fn get_presentation_request(&self) -> VcxResult<RequestPresentationV2> {
+        match &self.state {
+            ProverV2FullState::RequestReceived { request } | ProverV2FullState::PresentationSent { request, .. } => {
+                Ok(request.clone())
+            }
+            ProverV2FullState::Finished {
+                request: Some(request), ..
+            } => Ok(request.clone()),
+            _ => Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::NotReady,
+                "Presentation request is not available",
+            )),
+        }
+    }
+
+    /// Returns the AnonCreds proof request carried by the presentation request.
+    pub fn get_presentation_request_data(&self) -> VcxResult<String> {
+        let request = self.get_presentation_request()?;
+        let (_, proof_request) =
+            get_anoncreds_attachment(&request.content.formats, &request.content.request_presentations_attach)?;
+        Ok(proof_request)
+    }
+
+    pub fn get_presentation_msg(&self) -> VcxResult<PresentationV2> {
+        match &self.state {
+            ProverV2FullState::PresentationSent { presentation, .. } => Ok(presentation.clone()),
+            ProverV2FullState::Finished {
+                presentation: Some(presentation),
+                ..
+            } => Ok(presentation.clone()),
+            _ => Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::NotReady,
+                "Presentation is not created yet",
+            )),
+        }
+    }
+
+    pub async fn send_proposal(self, proposal: ProposePresentationV2, send_message: SendClosure) -> VcxResult<Self> {
+        let (state, thread_id) = match self.state {
+            ProverV2FullState::Initial => {
+                let thread_id = proposal.id.clone();
+                send_message(proposal.clone().into()).await?;
+                (ProverV2FullState::ProposalSent { proposal }, thread_id)
+            }
+            ProverV2FullState::RequestReceived { .. } => {
+                let mut proposal = proposal;
+                proposal.decorators.thread = Some(Thread::new(self.thread_id.clone()));
+                send_message(proposal.clone().into()).await?;
+                (ProverV2FullState::ProposalSent { proposal }, self.thread_id.clone())
+            }
+            s => {
+                warn!("Unable to send presentation proposal in state {}", s);
+                (s, self.thread_id.clone())
+            }
+        };
+        Ok(Self {
+            state,
+            thread_id,
+            ..self
+        })
+    }
+
+    pub fn receive_request(self, request: RequestPresentationV2) -> VcxResult<Self> {
+        let state = match self.state {
+            ProverV2FullState::ProposalSent { .. } => ProverV2FullState::RequestReceived { request },
+            s => {
+                warn!("Unable to receive presentation request in state {}", s);
+                s
+            }
+        };
+        Ok(Self { state, ..self })
+    }
+
+    /// Builds the presentation from the selected credentials and sends it. If it cannot be built, a problem report is
+    /// sent instead.
+    pub async fn send_presentation(
+        self,
+        profile: &Arc<dyn Profile>,
+        credentials: String,
+        self_attested_attrs: String,
+        send_message: SendClosure,
+    ) -> VcxResult<Self> {
+        let state = match self.state {
+            ProverV2FullState::RequestReceived { request } => {
+                match _make_presentation(profile, &self.thread_id, &request, &credentials, &self_attested_attrs).await {
+                    Ok(presentation) => {
+                        send_message(presentation.clone().into()).await?;
+                        if request.content.will_confirm.unwrap_or(false) {
+                            ProverV2FullState::PresentationSent { request, presentation }
+                        } else {
+                            ProverV2FullState::Finished {
+                                request: Some(request),
+                                presentation: Some(presentation),
+                                status: Status::Success,
+                            }
+                        }
+                    }
+                    Err(err) => {
+                        let problem_report = build_problem_report_msg_v2(Some(err.to_string()), &self.thread_id);
+                        error!(
+                            "Failed to build presentation, sending problem report: {:?}",
+                            problem_report
+                        );
+                        send_message(problem_report.clone().into()).await?;
+                        ProverV2FullState::Finished {
+                            request: Some(request),
+                            presentation: None,
+                            status: Status::Failed(problem_report_from_v2(problem_report)),
+                        }
+                    }
+                }
+            }
+            s => {
+                warn!("Unable to send presentation in state {}", s);
+                s
+            }
+        };
+        Ok(Self { state, ..self })
+    }
+
+    pub async fn decline_presentation_request(
+        self,
+        comment: Option<String>,
+        send_message: SendClosure,
+    ) -> VcxResult<Self> {
+        let state = match self.state {
+            ProverV2FullState::RequestReceived { request } => {
+                let problem_report = build_problem_report_msg_v2(comment, &self.thread_id);
+                send_message(problem_report.clone().into()).await?;
+                ProverV2FullState::Finished {
+                    request: Some(request),
+                    presentation: None,
+                    status: Status::Declined(problem_report_from_v2(problem_report)),
+                }
+            }
+            s => {
+                warn!("Unable to decline presentation request in state {}", s);
+                s
+            }
+        };
+        Ok(Self { state, ..self })
+    }
+
+    pub fn receive_ack(self, _ack: AckPresentationV2) -> VcxResult<Self> {
+        let state = match self.state {
+            ProverV2FullState::PresentationSent { request, presentation } => ProverV2FullState::Finished {
+                request: Some(request),
+                presentation: Some(presentation),
+                status: Status::Success,
+            },
+            s => {
+                warn!("Unable to receive presentation ack in state {}", s);
+                s
+            }
+        };
+        Ok(Self { state, ..self })
+    }
+
+    pub fn receive_problem_report(self, problem_report: ProblemReport) -> VcxResult<Self> {
+        let state = match self.state {
+            ProverV2FullState::ProposalSent { .. } => ProverV2FullState::Finished {
+                request: None,
+                presentation: None,
+                status: Status::Failed(problem_report),
+            },
+            ProverV2FullState::PresentationSent { request, presentation } => ProverV2FullState::Finished {
+                request: Some(request),
+                presentation: Some(presentation),
+                status: Status::Failed(problem_report),
+            },
+            s => {
+                warn!("Unable to receive problem report in state {}", s);
+                s
+            }
+        };
+        Ok(Self { state, ..self })
+    }
+
+    pub fn find_message_to_handle(&self, messages: HashMap<String, AriesMessage>) -> Option<(String, AriesMessage)> {
+        trace!(
+            "ProverV2SM::find_message_to_handle >>> messages: {:?}, state: {:?}",
+            messages,
+            self.state
+        );
+
+        for (uid, message) in messages {
+            let action = PresentProofV2Action::from(message.clone());
+            let is_expected = matches!(
+                (&self.state, &action),
+                (
+                    ProverV2FullState::ProposalSent { .. },
+                    PresentProofV2Action::PresentationRequest(_)
+                ) | (
+                    ProverV2FullState::ProposalSent { .. },
+                    PresentProofV2Action::ProblemReport(_)
+                ) | (
+                    ProverV2FullState::PresentationSent { .. },
+                    PresentProofV2Action::PresentationAck(_)
+                ) | (
+                    ProverV2FullState::PresentationSent { .. },
+                    PresentProofV2Action::ProblemReport(_)
+                )
+            );
+            if is_expected && action.thread_id_matches(&self.thread_id) {
+                return Some((uid, message));
+            }
+        }
+
+        None
+    }
+
+    pub async fn handle_message(
+        self,
+        _profile: &Arc<dyn Profile>,
+        action: PresentProofV2Action,
+        _send_message: Option<SendClosure>,
+    ) -> VcxResult<Self> {
+        trace!(
+            "ProverV2SM::handle_message >>> action: {:?}, state: {:?}",
+            action,
+            self.state
+        );
+        verify_thread_id(&self.thread_id, &action)?;
+        let prover_sm = match action {
+            PresentProofV2Action::PresentationRequest(request) => self.receive_request(request)?,
+            PresentProofV2Action::PresentationAck(ack) => self.receive_ack(ack)?,
+            PresentProofV2Action::ProblemReport(problem_report) => self.receive_problem_report(problem_report)?,
+            _ => self,
+        };
+        Ok(prover_sm)
+    }
+
+    pub fn presentation_status(&self) -> u32 {
+        match self.state {
+            ProverV2FullState::Finished { ref status, .. } => status.code(),
+            _ => Status::Undefined.code(),
+        }
+    }
+
+    pub fn is_terminal_state(&self) -> bool {
+        matches!(self.state, ProverV2FullState::Finished { .. })
+    }
+}
+
+async fn _make_presentation(
+    profile: &Arc<dyn Profile>,
+    thread_id: &str,
+    request: &RequestPresentationV2,
+    credentials: &str,
+    self_attested_attrs: &str,
+) -> VcxResult<PresentationV2> {
+    let (format, proof_request) =
+        get_anoncreds_attachment(&request.content.formats, &request.content.request_presentations_attach)?;
+    let proof = generate_indy_proof(profile, credentials, self_attested_attrs, &proof_request).await?;
+    Ok(build_presentation_msg_v2(thread_id, format, &proof))
+}
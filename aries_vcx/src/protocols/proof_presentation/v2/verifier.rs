@@ -0,0 +1,442 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use chrono::Utc;
+use messages::decorators::thread::Thread;
+use messages::decorators::timing::Timing;
+use messages::maybe_known::MaybeKnown;
+use messages::msg_fields::protocols::notification::ack::{AckDecorators, AckStatus};
+use messages::msg_fields::protocols::present_proof::v2::ack::{AckPresentationV2, AckPresentationV2Content};
+use messages::msg_fields::protocols::present_proof::v2::present::PresentationV2;
+use messages::msg_fields::protocols::present_proof::v2::propose::ProposePresentationV2;
+use messages::msg_fields::protocols::present_proof::v2::request::{
+    RequestPresentationV2, RequestPresentationV2Content, RequestPresentationV2Decorators,
+};
+use messages::msg_fields::protocols::report_problem::ProblemReport;
+use messages::AriesMessage;
+use uuid::Uuid;
+
+use crate::common::proofs::proof_request::PresentationRequestData;
+use crate::common::proofs::verifier::verifier::validate_indy_proof;
+use crate::core::profile::profile::Profile;
+use crate::errors::error::prelude::*;
+use crate::handlers::util::Status;
+use crate::protocols::common::{verify_thread_id, ThreadedAction};
+use crate::protocols::issuance::v2::{
+    get_anoncreds_attachment, make_anoncreds_attachment, AnoncredsAttachmentFormat, AnoncredsFormatType,
+};
+use crate::protocols::proof_presentation::v2::{
+    build_problem_report_msg_v2, problem_report_from_v2, PresentProofV2Action,
+};
+use crate::protocols::proof_presentation::verifier::state_machine::VerifierState;
+use crate::protocols::proof_presentation::verifier::verification_status::PresentationVerificationStatus;
+use crate::protocols::SendClosure;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub enum VerifierV2FullState {
+    #[default]
+    Initial,
+    ProposalReceived {
+        proposal: ProposePresentationV2,
+    },
+    RequestSent {
+        request: RequestPresentationV2,
+    },
+    Finished {
+        request: Option<RequestPresentationV2>,
+        presentation: Option<PresentationV2>,
+        status: Status,
+        verification_status: PresentationVerificationStatus,
+    },
+}
+
+impl fmt::Display for VerifierV2FullState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            VerifierV2FullState::Initial => f.write_str("Initial"),
+            VerifierV2FullState::ProposalReceived { .. } => f.write_str("ProposalReceived"),
+            VerifierV2FullState::RequestSent { .. } => f.write_str("RequestSent"),
+            VerifierV2FullState::Finished { .. } => f.write_str("Finished"),
+        }
+    }
+}
+
+/// Verifier side of the present-proof 2.0 protocol, requesting AnonCreds proofs in the configured attachment format.
+///
+/// The presentation is acknowledged only if the request was sent with `will_confirm`, failed verifications are always
+/// answered with a problem report.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct VerifierV2SM {
+    source_id: String,
+    thread_id: String,
+    format: AnoncredsAttachmentFormat,
+    state: VerifierV2FullState,
+}
+
+fn build_presentation_request_msg_v2(
+    id: String,
+    thread_id: Option<&str>,
+    format: AnoncredsAttachmentFormat,
+    request_data: &PresentationRequestData,
+    comment: Option<String>,
+    will_confirm: bool,
+) -> RequestPresentationV2 {
+    let (request_format, attachment) =
+        make_anoncreds_attachment(format.proof_request_format(), &json!(request_data).to_string());
+    let mut content = RequestPresentationV2Content::new(vec![request_format], vec![attachment]);
+    content.comment = comment;
+    content.will_confirm = Some(will_confirm);
+
+    let mut decorators = RequestPresentationV2Decorators::default();
+    decorators.thread = thread_id.map(|thread_id| Thread::new(thread_id.to_owned()));
+    let mut timing = Timing::default();
+    timing.out_time = Some(Utc::now());
+    decorators.timing = Some(timing);
+
+    RequestPresentationV2::with_decorators(id, content, decorators)
+}
+
+fn build_verification_ack_v2(thread_id: &str) -> AckPresentationV2 {
+    let content = AckPresentationV2Content::new(AckStatus::Ok);
+    let mut decorators = AckDecorators::new(Thread::new(thread_id.to_owned()));
+    let mut timing = Timing::default();
+    timing.out_time = Some(Utc::now());
+    decorators.timing = Some(timing);
+
+    AckPresentationV2::with_decorators(Uuid::new_v4().to_string(), content, decorators)
+}
+
+// a proposal answering a request refers to the thread of the request, otherwise it opens a thread
+fn proposal_thread_id(proposal: &ProposePresentationV2) -> String {
+    match &proposal.decorators.thread {
+        Some(thread) => thread.thid.clone(),
+        None => proposal.id.clone(),
+    }
+}
+
+impl VerifierV2SM {
+    pub fn new(source_id: &str, format: AnoncredsAttachmentFormat) -> Self {
+        Self {
+            source_id: source_id.to_string(),
+            thread_id: Uuid::new_v4().to_string(),
+            format,
+            state: VerifierV2FullState::Initial,
+        }
+    }
+
+    /// Starts the exchange from a proposal, the request will be sent in the first AnonCreds format of the proposal.
+    pub fn from_proposal(source_id: &str, proposal: &ProposePresentationV2) -> Self {
+        let format = proposal
+            .content
+            .formats
+            .iter()
+            .find_map(|specifier| match &specifier.format {
+                MaybeKnown::Known(format) => format.anoncreds_format(),
+                MaybeKnown::Unknown(_) => None,
+            })
+            .unwrap_or_default();
+        Self {
+            source_id: source_id.to_string(),
+            thread_id: proposal_thread_id(proposal),
+            format,
+            state: VerifierV2FullState::ProposalReceived {
+                proposal: proposal.clone(),
+            },
+        }
+    }
+
+    pub fn source_id(&self) -> String {
+        self.source_id.clone()
+    }
+
+    pub fn thread_id(&self) -> String {
+        self.thread_id.clone()
+    }
+
+    pub fn get_format(&self) -> AnoncredsAttachmentFormat {
+        self.format
+    }
+
+    pub fn get_state(&self) -> VerifierState {
+        match self.state {
+            VerifierV2FullState::Initial => VerifierState::Initial,
+            VerifierV2FullState::ProposalReceived { .. } => VerifierState::PresentationProposalReceived,
+            VerifierV2FullState::RequestSent { .. } => VerifierState::PresentationRequestSent,
+            VerifierV2FullState::Finished { ref status, .. } => match status {
+                Status::Success => VerifierState::Finished,
+                _ => VerifierState::Failed,
+            },
+        }
+    }
+
+    pub fn get_verification_status(&self) -> PresentationVerificationStatus {
+        match self.state {
+            VerifierV2FullState::Finished {
+                ref verification_status,
+                ..
+            } => verification_status.clone(),
+            _ => PresentationVerificationStatus::Unavailable,
+        }
+    }
+
+    pub fn get_presentation_proposal(&self) -> VcxResult<ProposePresentationV2> {
+        match &self.state {
+            VerifierV2FullState::ProposalReceived { proposal } => Ok(proposal.clone()),
+            _ => Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidState,
+                "Presentation proposal not received yet",
+            )),
+        }
+    }
+
+    pub fn get_presentation_request_msg(&self) -> VcxResult<RequestPresentationV2> {
+        match &self.state {
+            VerifierV2FullState::RequestSent { request } => Ok(request.clone()),
+            VerifierV2FullState::Finished {
+                request: Some(request), ..
+            } => Ok(request.clone()),
+            _ => Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidState,
+                "Presentation request not sent yet",
+            )),
+        }
+    }
+
+    pub fn get_presentation_msg(&self) -> VcxResult<PresentationV2> {
+        match &self.state {
+            VerifierV2FullState::Finished {
+                presentation: Some(presentation),
+                ..
+            } => Ok(presentation.clone()),
+            _ => Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidState,
+                "Presentation not received yet",
+            )),
+        }
+    }
+
+    /// Sends the presentation request, `will_confirm` commits the verifier to acknowledge the presentation.
+    pub async fn send_presentation_request(
+        self,
+        request_data: &PresentationRequestData,
+        comment: Option<String>,
+        will_confirm: bool,
+        send_message: SendClosure,
+    ) -> VcxResult<Self> {
+        let state = match self.state {
+            VerifierV2FullState::Initial => {
+                let request = build_presentation_request_msg_v2(
+                    self.thread_id.clone(),
+                    None,
+                    self.format,
+                    request_data,
+                    comment,
+                    will_confirm,
+                );
+                send_message(request.clone().into()).await?;
+                VerifierV2FullState::RequestSent { request }
+            }
+            VerifierV2FullState::ProposalReceived { .. } => {
+                let request = build_presentation_request_msg_v2(
+                    Uuid::new_v4().to_string(),
+                    Some(&self.thread_id),
+                    self.format,
+                    request_data,
+                    comment,
+                    will_confirm,
+                );
+                send_message(request.clone().into()).await?;
+                VerifierV2FullState::RequestSent { request }
+            }
+            s => {
+                warn!("Unable to send presentation request in state {}", s);
+                s
+            }
+        };
+        Ok(Self { state, ..self })
+    }
+
+    pub async fn decline_proposal(self, comment: Option<String>, send_message: SendClosure) -> VcxResult<Self> {
+        let state = match self.state {
+            VerifierV2FullState::ProposalReceived { .. } => {
+                let problem_report = build_problem_report_msg_v2(comment, &self.thread_id);
+                send_message(problem_report.clone().into()).await?;
+                VerifierV2FullState::Finished {
+                    request: None,
+                    presentation: None,
+                    status: Status::Declined(problem_report_from_v2(problem_report)),
+                    verification_status: PresentationVerificationStatus::Unavailable,
+                }
+            }
+            s => {
+                warn!("Unable to decline presentation proposal in state {}", s);
+                s
+            }
+        };
+        Ok(Self { state, ..self })
+    }
+
+    pub fn receive_proposal(self, proposal: ProposePresentationV2) -> VcxResult<Self> {
+        let state = match self.state {
+            VerifierV2FullState::RequestSent { .. } => VerifierV2FullState::ProposalReceived { proposal },
+            s => {
+                warn!("Unable to receive presentation proposal in state {}", s);
+                s
+            }
+        };
+        Ok(Self { state, ..self })
+    }
+
+    pub async fn verify_presentation(
+        self,
+        profile: &Arc<dyn Profile>,
+        presentation: PresentationV2,
+        send_message: SendClosure,
+    ) -> VcxResult<Self> {
+        let state = match self.state {
+            VerifierV2FullState::RequestSent { request } => {
+                match _verify_presentation(profile, &request, &presentation).await {
+                    Ok(()) => {
+                        if request.content.will_confirm.unwrap_or(false) {
+                            send_message(build_verification_ack_v2(&self.thread_id).into()).await?;
+                        }
+                        VerifierV2FullState::Finished {
+                            request: Some(request),
+                            presentation: Some(presentation),
+                            status: Status::Success,
+                            verification_status: PresentationVerificationStatus::Valid,
+                        }
+                    }
+                    Err(err) => {
+                        let problem_report = build_problem_report_msg_v2(Some(err.to_string()), &self.thread_id);
+                        send_message(problem_report.clone().into()).await?;
+                        match err.kind() {
+                            AriesVcxErrorKind::InvalidProof => VerifierV2FullState::Finished {
+                                request: Some(request),
+                                presentation: Some(presentation),
+                                status: Status::Success,
+                                verification_status: PresentationVerificationStatus::Invalid,
+                            },
+                            _ => VerifierV2FullState::Finished {
+                                request: Some(request),
+                                presentation: None,
+                                status: Status::Failed(problem_report_from_v2(problem_report)),
+                                verification_status: PresentationVerificationStatus::Unavailable,
+                            },
+                        }
+                    }
+                }
+            }
+            s => {
+                warn!("Unable to verify presentation in state {}", s);
+                s
+            }
+        };
+        Ok(Self { state, ..self })
+    }
+
+    pub fn receive_problem_report(self, problem_report: ProblemReport) -> VcxResult<Self> {
+        let state = match self.state {
+            VerifierV2FullState::RequestSent { request } => VerifierV2FullState::Finished {
+                request: Some(request),
+                presentation: None,
+                status: Status::Failed(problem_report),
+                verification_status: PresentationVerificationStatus::Unavailable,
+            },
+            s => {
+                warn!("Unable to receive problem report in state {}", s);
+                s
+            }
+        };
+        Ok(Self { state, ..self })
+    }
+
+    pub fn find_message_to_handle(&self, messages: HashMap<String, AriesMessage>) -> Option<(String, AriesMessage)> {
+        trace!(
+            "VerifierV2SM::find_message_to_handle >>> messages: {:?}, state: {:?}",
+            messages,
+            self.state
+        );
+
+        for (uid, message) in messages {
+            let action = PresentProofV2Action::from(message.clone());
+            let is_expected = matches!(
+                (&self.state, &action),
+                (
+                    VerifierV2FullState::RequestSent { .. },
+                    PresentProofV2Action::Presentation(_)
+                ) | (
+                    VerifierV2FullState::RequestSent { .. },
+                    PresentProofV2Action::PresentationProposal(_)
+                ) | (
+                    VerifierV2FullState::RequestSent { .. },
+                    PresentProofV2Action::ProblemReport(_)
+                )
+            );
+            if is_expected && action.thread_id_matches(&self.thread_id) {
+                return Some((uid, message));
+            }
+        }
+
+        None
+    }
+
+    pub async fn handle_message(
+        self,
+        profile: &Arc<dyn Profile>,
+        action: PresentProofV2Action,
+        send_message: Option<SendClosure>,
+    ) -> VcxResult<Self> {
+        trace!(
+            "VerifierV2SM::handle_message >>> action: {:?}, state: {:?}",
+            action,
+            self.state
+        );
+        verify_thread_id(&self.thread_id, &action)?;
+        let verifier_sm = match action {
+            PresentProofV2Action::PresentationProposal(proposal) => self.receive_proposal(proposal)?,
+            PresentProofV2Action::Presentation(presentation) => {
+                let send_message = send_message.ok_or(AriesVcxError::from_msg(
+                    AriesVcxErrorKind::InvalidState,
+                    "Attempted to call undefined send_message callback",
+                ))?;
+                self.verify_presentation(profile, presentation, send_message).await?
+            }
+            PresentProofV2Action::ProblemReport(problem_report) => self.receive_problem_report(problem_report)?,
+            _ => self,
+        };
+        Ok(verifier_sm)
+    }
+
+    pub fn presentation_status(&self) -> u32 {
+        match self.state {
+            VerifierV2FullState::Finished { ref status, .. } => status.code(),
+            _ => Status::Undefined.code(),
+        }
+    }
+
+    pub fn is_terminal_state(&self) -> bool {
+        matches!(self.state, VerifierV2FullState::Finished { .. })
+    }
+}
+
+async fn _verify_presentation(
+    profile: &Arc<dyn Profile>,
+    request: &RequestPresentationV2,
+    presentation: &PresentationV2,
+) -> VcxResult<()> {
+    let (_, proof_request) =
+        get_anoncreds_attachment(&request.content.formats, &request.content.request_presentations_attach)?;
+    let (_, proof) = get_anoncreds_attachment(
+        &presentation.content.formats,
+        &presentation.content.presentations_attach,
+    )?;
+    if !validate_indy_proof(profile, &proof, &proof_request).await? {
+        return Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidProof,
+            "Presentation verification failed",
+        ));
+    }
+    Ok(())
+}
@@ -3,6 +3,7 @@ use std::fmt::Display;
 use std::sync::Arc;
 
 use crate::common::proofs::proof_request::PresentationRequestData;
+use crate::common::proofs::verifier::policy::{PolicyEvaluation, PresentationPolicies};
 use crate::core::profile::profile::Profile;
 use crate::errors::error::prelude::*;
 use crate::handlers::util::{make_attach_from_str, matches_opt_thread_id, matches_thread_id, AttachmentId, Status};
@@ -88,6 +89,16 @@ fn build_verification_ack(thread_id: &str) -> AckPresentation {
     AckPresentation::with_decorators(Uuid::new_v4().to_string(), content, decorators)
 }
 
+fn to_notification_problem_report(problem_report: ProblemReport) -> AriesMessage {
+    let MsgParts {
+        id,
+        content,
+        decorators,
+    } = problem_report;
+
+    NotificationProblemReport::with_decorators(id, NotificationProblemReportContent(content), decorators).into()
+}
+
 fn build_starting_presentation_request(
     thread_id: &str,
     request_data: &PresentationRequestData,
@@ -224,6 +235,19 @@ impl VerifierSM {
         profile: &Arc<dyn Profile>,
         presentation: Presentation,
         send_message: SendClosure,
+    ) -> VcxResult<Self> {
+        self.verify_presentation_with_policies(profile, presentation, None, send_message)
+            .await
+    }
+
+    /// Verifies the presentation, then evaluates the policies over it, if any. A presentation violating the policies
+    /// is rejected with a problem report, and the evaluation is recorded in the finished state.
+    pub async fn verify_presentation_with_policies(
+        self,
+        profile: &Arc<dyn Profile>,
+        presentation: Presentation,
+        policies: Option<&PresentationPolicies>,
+        send_message: SendClosure,
     ) -> VcxResult<Self> {
         verify_thread_id(
             &self.thread_id,
@@ -232,15 +256,30 @@ impl VerifierSM {
         let state = match self.state {
             VerifierFullState::PresentationRequestSent(state) => {
                 let verification_result = state.verify_presentation(profile, &presentation, &self.thread_id).await;
+                let policy_evaluation = match (&verification_result, policies) {
+                    (Ok(()), Some(policies)) => Some(state.evaluate_policies(profile, &presentation, policies).await?),
+                    _ => None,
+                };
 
                 let (sm, message) = match verification_result {
-                    Ok(()) => {
-                        let sm = VerifierFullState::Finished(
-                            (state, presentation, PresentationVerificationStatus::Valid).into(),
-                        );
-                        let ack = build_verification_ack(&self.thread_id).into();
-                        (sm, ack)
-                    }
+                    Ok(()) => match policy_evaluation {
+                        Some(evaluation) if !evaluation.is_accepted() => {
+                            let problem_report = build_problem_report_msg(Some(evaluation.describe()), &self.thread_id);
+                            let finished: FinishedState =
+                                (state, presentation, evaluation, problem_report.clone()).into();
+                            (
+                                VerifierFullState::Finished(finished),
+                                to_notification_problem_report(problem_report),
+                            )
+                        }
+                        policy_evaluation => {
+                            let mut finished: FinishedState =
+                                (state, presentation, PresentationVerificationStatus::Valid).into();
+                            finished.policy_evaluation = policy_evaluation;
+                            let ack = build_verification_ack(&self.thread_id).into();
+                            (VerifierFullState::Finished(finished), ack)
+                        }
+                    },
                     Err(err) => {
                         let problem_report = build_problem_report_msg(Some(err.to_string()), &self.thread_id);
 
@@ -251,19 +290,7 @@ impl VerifierSM {
                             _ => VerifierFullState::Finished((state, problem_report.clone()).into()),
                         };
 
-                        let MsgParts {
-                            id,
-                            content,
-                            decorators,
-                        } = problem_report;
-
-                        let problem_report = NotificationProblemReport::with_decorators(
-                            id,
-                            NotificationProblemReportContent(content),
-                            decorators,
-                        );
-
-                        (sm, AriesMessage::from(problem_report))
+                        (sm, to_notification_problem_report(problem_report))
                     }
                 };
                 send_message(message).await?;
@@ -460,6 +487,13 @@ impl VerifierSM {
         }
     }
 
+    pub fn get_policy_evaluation(&self) -> Option<PolicyEvaluation> {
+        match self.state {
+            VerifierFullState::Finished(ref state) => state.policy_evaluation.clone(),
+            _ => None,
+        }
+    }
+
     pub fn presentation_request_msg(&self) -> VcxResult<RequestPresentation> {
         match self.state {
             VerifierFullState::Initial(_) => Err(AriesVcxError::from_msg(
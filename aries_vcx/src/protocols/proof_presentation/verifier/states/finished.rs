@@ -5,7 +5,7 @@ use messages::msg_fields::protocols::{
 use serde::Deserialize;
 
 use crate::{
-    handlers::util::Status,
+    common::proofs::verifier::policy::PolicyEvaluation, handlers::util::Status,
     protocols::proof_presentation::verifier::verification_status::PresentationVerificationStatus,
 };
 
@@ -15,6 +15,8 @@ pub struct FinishedState {
     pub presentation: Option<Presentation>,
     pub status: Status,
     pub verification_status: PresentationVerificationStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy_evaluation: Option<PolicyEvaluation>,
 }
 
 impl FinishedState {
@@ -25,6 +27,7 @@ impl FinishedState {
             presentation: None,
             status: Status::Declined(problem_report),
             verification_status: PresentationVerificationStatus::Unavailable,
+            policy_evaluation: None,
         }
     }
 }
@@ -41,6 +44,7 @@ pub mod unit_tests {
             presentation: None,
             status: Status::Success,
             verification_status: PresentationVerificationStatus::Valid,
+            policy_evaluation: None,
         };
         let serialized = serde_json::to_string(&state).unwrap();
         let expected =
@@ -57,6 +61,7 @@ pub mod unit_tests {
             presentation: None,
             status: Status::Success,
             verification_status: PresentationVerificationStatus::Unavailable,
+            policy_evaluation: None,
         };
         let serialized = serde_json::to_string(&state).unwrap();
         let expected = r#"{"presentation_request":null,"presentation":null,"status":"Success","verification_status":"Unavailable"}"#;
@@ -72,6 +77,7 @@ pub mod unit_tests {
             presentation: None,
             status: Status::Success,
             verification_status: PresentationVerificationStatus::Invalid,
+            policy_evaluation: None,
         };
         let serialized = serde_json::to_string(&state).unwrap();
         let expected =
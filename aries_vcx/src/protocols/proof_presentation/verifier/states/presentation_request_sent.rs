@@ -1,9 +1,13 @@
 use std::sync::Arc;
 
+use aries_vcx_core::anoncreds::types::{AnonCredsObject, Proof};
 use messages::msg_fields::protocols::present_proof::present::Presentation;
 use messages::msg_fields::protocols::present_proof::request::RequestPresentation;
 use messages::msg_fields::protocols::report_problem::ProblemReport;
 
+use crate::common::credentials::encoding::AttributeEncodingRegistry;
+use crate::common::proofs::verifier::policy::{PolicyEvaluation, PresentationPolicies};
+use crate::common::proofs::verifier::verification_report::build_verification_report;
 use crate::common::proofs::verifier::verifier::validate_indy_proof;
 use crate::core::profile::profile::Profile;
 use crate::errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult};
@@ -48,6 +52,26 @@ impl PresentationRequestSentState {
 
         Ok(())
     }
+
+    pub async fn evaluate_policies(
+        &self,
+        profile: &Arc<dyn Profile>,
+        presentation: &Presentation,
+        policies: &PresentationPolicies,
+    ) -> VcxResult<PolicyEvaluation> {
+        let proof_json = get_attach_as_string!(&presentation.content.presentations_attach);
+        let proof_req_json = get_attach_as_string!(&self.presentation_request.content.request_presentations_attach);
+
+        let proof = Proof::from_json(&proof_json)?;
+        let schema_ids: Vec<&str> = proof
+            .identifiers
+            .iter()
+            .map(|identifier| identifier.schema_id.as_str())
+            .collect();
+        let registry = AttributeEncodingRegistry::load(profile, &schema_ids).await?;
+        let report = build_verification_report(&proof_json, &proof_req_json, &registry, true)?;
+        Ok(policies.evaluate_now(&report))
+    }
 }
 
 impl
@@ -70,6 +94,7 @@ impl
             presentation: Some(presentation),
             status: Status::Success,
             verification_status,
+            policy_evaluation: None,
         }
     }
 }
@@ -82,6 +107,80 @@ impl From<(PresentationRequestSentState, ProblemReport)> for FinishedState {
             presentation: None,
             status: Status::Failed(problem_report),
             verification_status: PresentationVerificationStatus::Unavailable,
+            policy_evaluation: None,
         }
     }
 }
+
+impl
+    From<(
+        PresentationRequestSentState,
+        Presentation,
+        PolicyEvaluation,
+        ProblemReport,
+    )> for FinishedState
+{
+    fn from(
+        (state, presentation, policy_evaluation, problem_report): (
+            PresentationRequestSentState,
+            Presentation,
+            PolicyEvaluation,
+            ProblemReport,
+        ),
+    ) -> Self {
+        trace!("transit state from PresentationRequestSentState to FinishedState due to a policy violation");
+        FinishedState {
+            presentation_request: Some(state.presentation_request),
+            presentation: Some(presentation),
+            status: Status::Failed(problem_report),
+            verification_status: PresentationVerificationStatus::Invalid,
+            policy_evaluation: Some(policy_evaluation),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+pub mod unit_tests {
+    use messages::decorators::thread::Thread;
+    use messages::msg_fields::protocols::present_proof::present::{PresentationContent, PresentationDecorators};
+    use messages::msg_fields::protocols::present_proof::request::{
+        RequestPresentationContent, RequestPresentationDecorators,
+    };
+
+    use crate::common::proofs::verifier::policy::PolicyViolation;
+    use crate::protocols::common::build_problem_report_msg;
+
+    use super::*;
+
+    #[test]
+    fn test_verifier_policy_rejection_finishes_as_failed() {
+        let state = PresentationRequestSentState {
+            presentation_request: RequestPresentation::with_decorators(
+                "request".to_owned(),
+                RequestPresentationContent::new(vec![]),
+                RequestPresentationDecorators::default(),
+            ),
+        };
+        let presentation = Presentation::with_decorators(
+            "presentation".to_owned(),
+            PresentationContent::new(vec![]),
+            PresentationDecorators::new(Thread::new("thread".to_owned())),
+        );
+        let evaluation = PolicyEvaluation {
+            evaluated_at: 0,
+            violations: vec![PolicyViolation {
+                rule: "trusted_issuers".to_owned(),
+                referent: None,
+                reason: "untrusted issuer".to_owned(),
+            }],
+        };
+        let problem_report = build_problem_report_msg(Some(evaluation.describe()), "thread");
+
+        let finished: FinishedState = (state, presentation, evaluation.clone(), problem_report.clone()).into();
+
+        assert_eq!(finished.status, Status::Failed(problem_report));
+        assert_eq!(finished.verification_status, PresentationVerificationStatus::Invalid);
+        assert_eq!(finished.policy_evaluation, Some(evaluation));
+    }
+}
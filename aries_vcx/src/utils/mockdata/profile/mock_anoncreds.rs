@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use aries_vcx_core::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult};
 use async_trait::async_trait;
 
@@ -10,9 +12,19 @@ use crate::{
     },
 };
 use aries_vcx_core::anoncreds::base_anoncreds::BaseAnonCreds;
+use aries_vcx_core::wallet::base_wallet::{BaseWallet, WalletTransaction};
 
-#[derive(Debug)]
-pub(crate) struct MockAnoncreds;
+#[derive(Debug, Default)]
+pub(crate) struct MockAnoncreds {
+    // wallet the transactions given along with revocations are committed to, if any
+    wallet: Option<Arc<dyn BaseWallet>>,
+}
+
+impl MockAnoncreds {
+    pub(crate) fn with_wallet(wallet: Arc<dyn BaseWallet>) -> Self {
+        MockAnoncreds { wallet: Some(wallet) }
+    }
+}
 
 // NOTE : currently matches the expected results if indy_mocks are enabled
 /// Implementation of [BaseAnoncreds] which responds with mock data
@@ -186,6 +198,19 @@ impl BaseAnonCreds for MockAnoncreds {
         Ok(())
     }
 
+    async fn revoke_credential_local_in_transaction(
+        &self,
+        _tails_dir: &str,
+        _rev_reg_id: &str,
+        _cred_rev_id: &str,
+        transaction: WalletTransaction,
+    ) -> VcxCoreResult<()> {
+        match &self.wallet {
+            Some(wallet) => wallet.commit_transaction(transaction).await,
+            None => Ok(()),
+        }
+    }
+
     async fn publish_local_revocations(&self, _submitter_did: &str, _rev_reg_id: &str) -> VcxCoreResult<()> {
         Ok(())
     }
@@ -212,7 +237,7 @@ mod unit_tests {
             assert_eq!(result.unwrap_err().kind(), AriesVcxCoreErrorKind::UnimplementedFeature)
         }
 
-        let anoncreds: Box<dyn BaseAnonCreds> = Box::new(MockAnoncreds);
+        let anoncreds: Box<dyn BaseAnonCreds> = Box::new(MockAnoncreds::default());
 
         assert_unimplemented(anoncreds.verifier_verify_proof("", "", "", "", "", "").await);
         assert_unimplemented(anoncreds.issuer_create_and_store_revoc_reg("", "", "", 0, "").await);
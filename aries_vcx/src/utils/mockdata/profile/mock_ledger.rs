@@ -4,7 +4,10 @@ use async_trait::async_trait;
 
 use crate::utils::{
     self,
-    constants::{rev_def_json, CRED_DEF_JSON, REV_REG_DELTA_JSON, REV_REG_ID, REV_REG_JSON, SCHEMA_JSON, SCHEMA_TXN},
+    constants::{
+        rev_def_json, CRED_DEF_JSON, CRED_DEF_REQ, REV_REG_DELTA_JSON, REV_REG_ID, REV_REG_JSON, SCHEMA_JSON,
+        SCHEMA_TXN,
+    },
 };
 
 #[derive(Debug)]
@@ -99,6 +102,34 @@ impl BaseLedger for MockLedger {
         Ok(SCHEMA_TXN.to_string())
     }
 
+    async fn build_cred_def_request(&self, submitter_did: &str, cred_def_json: &str) -> VcxCoreResult<String> {
+        Ok(CRED_DEF_REQ.to_string())
+    }
+
+    async fn build_rev_reg_def_request(&self, submitter_did: &str, rev_reg_def_json: &str) -> VcxCoreResult<String> {
+        Ok("".to_string())
+    }
+
+    async fn build_rev_reg_delta_request(
+        &self,
+        submitter_did: &str,
+        rev_reg_id: &str,
+        rev_reg_entry_json: &str,
+    ) -> VcxCoreResult<String> {
+        Ok("".to_string())
+    }
+
+    async fn build_nym_request(
+        &self,
+        submitter_did: &str,
+        target_did: &str,
+        verkey: Option<&str>,
+        data: Option<&str>,
+        role: Option<&str>,
+    ) -> VcxCoreResult<String> {
+        Ok("".to_string())
+    }
+
     async fn publish_schema(
         &self,
         schema_json: &str,
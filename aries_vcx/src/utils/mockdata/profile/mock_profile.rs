@@ -1,7 +1,9 @@
 use std::sync::Arc;
 
 use aries_vcx_core::{
-    anoncreds::base_anoncreds::BaseAnonCreds, ledger::base_ledger::BaseLedger, wallet::base_wallet::BaseWallet,
+    anoncreds::base_anoncreds::BaseAnonCreds,
+    ledger::base_ledger::BaseLedger,
+    wallet::{base_wallet::BaseWallet, in_memory_wallet::InMemoryWallet},
 };
 
 use crate::core::profile::profile::Profile;
@@ -19,10 +21,52 @@ impl Profile for MockProfile {
     }
 
     fn inject_anoncreds(self: Arc<Self>) -> Arc<dyn BaseAnonCreds> {
-        Arc::new(MockAnoncreds {})
+        Arc::new(MockAnoncreds::default())
     }
 
     fn inject_wallet(&self) -> Arc<dyn BaseWallet> {
         Arc::new(MockWallet {})
     }
 }
+
+/// Implementation of a [Profile] which uses [MockLedger] and [MockAnoncreds], but a real [InMemoryWallet],
+/// so that protocols can be unit tested with working cryptography. Only for unit testing purposes
+#[derive(Debug)]
+pub struct InMemoryWalletProfile {
+    wallet: Arc<dyn BaseWallet>,
+    ledger: Arc<dyn BaseLedger>,
+}
+
+impl InMemoryWalletProfile {
+    pub fn new() -> Self {
+        Self::with_ledger(Arc::new(MockLedger {}))
+    }
+
+    /// Uses `ledger` instead of [MockLedger], e.g. a ledger shared with the profiles of other parties.
+    pub fn with_ledger(ledger: Arc<dyn BaseLedger>) -> Self {
+        InMemoryWalletProfile {
+            wallet: Arc::new(InMemoryWallet::new()),
+            ledger,
+        }
+    }
+}
+
+impl Default for InMemoryWalletProfile {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Profile for InMemoryWalletProfile {
+    fn inject_ledger(self: Arc<Self>) -> Arc<dyn BaseLedger> {
+        Arc::clone(&self.ledger)
+    }
+
+    fn inject_anoncreds(self: Arc<Self>) -> Arc<dyn BaseAnonCreds> {
+        Arc::new(MockAnoncreds::with_wallet(Arc::clone(&self.wallet)))
+    }
+
+    fn inject_wallet(&self) -> Arc<dyn BaseWallet> {
+        Arc::clone(&self.wallet)
+    }
+}
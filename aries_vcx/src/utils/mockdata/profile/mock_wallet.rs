@@ -1,6 +1,6 @@
 use aries_vcx_core::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult};
 use aries_vcx_core::utils::async_fn_iterator::AsyncFnIterator;
-use aries_vcx_core::wallet::base_wallet::BaseWallet;
+use aries_vcx_core::wallet::base_wallet::{BaseWallet, WalletEntry, WalletTransaction};
 use async_trait::async_trait;
 
 use crate::utils::{self};
@@ -80,6 +80,10 @@ impl BaseWallet for MockWallet {
         ))
     }
 
+    async fn commit_transaction(&self, transaction: WalletTransaction) -> VcxCoreResult<()> {
+        Ok(())
+    }
+
     async fn sign(&self, my_vk: &str, msg: &[u8]) -> VcxCoreResult<Vec<u8>> {
         Ok(Vec::from(msg))
     }
@@ -95,4 +99,16 @@ impl BaseWallet for MockWallet {
     async fn unpack_message(&self, msg: &[u8]) -> VcxCoreResult<Vec<u8>> {
         Ok(msg.to_vec())
     }
+
+    async fn export_entries(&self) -> VcxCoreResult<Box<dyn AsyncFnIterator<Item = VcxCoreResult<WalletEntry>>>> {
+        // not needed yet
+        Err(AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::UnimplementedFeature,
+            "unimplemented mock method: export_entries",
+        ))
+    }
+
+    async fn import_entry(&self, entry: WalletEntry) -> VcxCoreResult<()> {
+        Ok(())
+    }
 }
@@ -48,7 +48,6 @@ pub mod file;
 pub mod mockdata;
 pub mod openssl;
 pub mod provision;
-pub mod qualifier;
 pub mod random;
 pub mod uuid;
 
@@ -59,6 +58,8 @@ pub mod filters;
 pub mod serialization;
 pub mod validation;
 
+pub use aries_vcx_core::utils::qualifier;
+
 pub fn get_temp_dir_path(filename: &str) -> PathBuf {
     let mut path = env::temp_dir();
     path.push(filename);
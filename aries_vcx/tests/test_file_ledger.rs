@@ -0,0 +1,63 @@
+#[cfg(feature = "vdrtools")]
+mod integration_tests {
+    use std::collections::HashMap;
+    use std::fs;
+    use std::sync::Arc;
+
+    use aries_vcx::common::test_utils::create_indy_proof;
+    use aries_vcx::core::profile::profile::Profile;
+    use aries_vcx::core::profile::vdrtools_profile::VdrtoolsProfile;
+    use aries_vcx::utils::devsetup::SetupInstitutionWallet;
+    use aries_vcx::utils::get_temp_dir_path;
+    use aries_vcx_core::ledger::base_ledger::BaseLedger;
+    use aries_vcx_core::ledger::file_ledger::FileLedger;
+    use serde_json::{Map, Value};
+
+    #[tokio::test]
+    async fn test_file_ledger_issue_and_verify_proof() {
+        let setup = SetupInstitutionWallet::init().await;
+        let ledger_path = get_temp_dir_path(&format!("file_ledger_{}.json", uuid::Uuid::new_v4()));
+
+        let ledger: Arc<dyn BaseLedger> = Arc::new(FileLedger::open(&ledger_path).unwrap());
+        let profile: Arc<dyn Profile> = Arc::new(VdrtoolsProfile::new_with_ledger(setup.wallet_handle, ledger));
+
+        // the issuer publishes its schema and credential definition to the ledger file,
+        // the holder then proves the issued credential
+        let (schemas, cred_defs, proof_req, proof) =
+            create_indy_proof(&profile, &profile, &setup.institution_did).await;
+
+        // the verifier only relies on what it reads from the ledger file
+        let verifier_ledger = FileLedger::open(&ledger_path).unwrap();
+        let schemas: HashMap<String, Value> = serde_json::from_str(&schemas).unwrap();
+        let cred_defs: HashMap<String, Value> = serde_json::from_str(&cred_defs).unwrap();
+
+        let mut ledger_schemas = Map::new();
+        for schema_id in schemas.keys() {
+            let schema = verifier_ledger.get_schema(schema_id, None).await.unwrap();
+            ledger_schemas.insert(schema_id.clone(), serde_json::from_str(&schema).unwrap());
+        }
+        let mut ledger_cred_defs = Map::new();
+        for (cred_def_id, cred_def) in cred_defs {
+            let ledger_cred_def: Value =
+                serde_json::from_str(&verifier_ledger.get_cred_def(&cred_def_id, None).await.unwrap()).unwrap();
+            assert_eq!(ledger_cred_def, cred_def);
+            ledger_cred_defs.insert(cred_def_id, ledger_cred_def);
+        }
+
+        let proof_validation = Arc::clone(&profile)
+            .inject_anoncreds()
+            .verifier_verify_proof(
+                &proof_req,
+                &proof,
+                &Value::from(ledger_schemas).to_string(),
+                &Value::from(ledger_cred_defs).to_string(),
+                "{}",
+                "{}",
+            )
+            .await
+            .unwrap();
+        assert!(proof_validation);
+
+        fs::remove_file(ledger_path).unwrap();
+    }
+}
@@ -0,0 +1,1595 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::Arc;
+
+use anoncreds::data_types::{
+    cred_def::CredentialDefinitionId, issuer_id::IssuerId, rev_reg_def::RevocationRegistryDefinitionId,
+    schema::SchemaId,
+};
+use anoncreds::tails::{TailsFileReader, TailsFileWriter};
+use anoncreds::types::{
+    AttributeNames, Credential, CredentialDefinition, CredentialDefinitionConfig, CredentialDefinitionPrivate,
+    CredentialKeyCorrectnessProof, CredentialOffer, CredentialRequest, CredentialRequestMetadata,
+    CredentialRevocationConfig, CredentialRevocationState, CredentialValues, LinkSecret, PresentCredentials,
+    Presentation, PresentationRequest, RegistryType, RevocationRegistryDefinition, RevocationRegistryDefinitionPrivate,
+    RevocationStatusList, Schema, SignatureType,
+};
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::ledger::base_ledger::BaseLedger;
+use crate::utils::{
+    constants::ATTRS,
+    json::{AsTypeOrDeserializationError, TryGetIndex},
+    qualifier,
+};
+use crate::wallet::base_wallet::AsyncFnIteratorCollect;
+use crate::{
+    errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult},
+    wallet::base_wallet::{BaseWallet, WalletTransaction},
+};
+
+use super::base_anoncreds::BaseAnonCreds;
+
+// same record layout as IndyCredxAnonCreds, so that wallets populated by either implementation can be used by the other
+const CATEGORY_CREDENTIAL: &str = "VCX_CREDENTIAL";
+const CATEGORY_LINK_SECRET: &str = "VCX_LINK_SECRET";
+const CATEGORY_REV_REG_DEF: &str = "VCX_REV_REG_DEF";
+
+// issuer records, keyed by credential definition or revocation registry ID
+const CATEGORY_CRED_DEF: &str = "VCX_CRED_DEF";
+const CATEGORY_CRED_DEF_PRIV: &str = "VCX_CRED_DEF_PRIV";
+const CATEGORY_CRED_KEY_CORRECTNESS_PROOF: &str = "VCX_CRED_KEY_CORRECTNESS_PROOF";
+const CATEGORY_REV_REG_DEF_PRIV: &str = "VCX_REV_REG_DEF_PRIV";
+const CATEGORY_REV_REG: &str = "VCX_REV_REG";
+const CATEGORY_REV_REG_INFO: &str = "VCX_REV_REG_INFO";
+const CATEGORY_REV_REG_DELTA: &str = "VCX_REV_REG_DELTA";
+// marks the credential revocation IDs already used in a registry, keyed by `<rev_reg_id>:<cred_rev_id>`
+const CATEGORY_REV_REG_IDX: &str = "VCX_REV_REG_IDX";
+
+/// Implementation of [BaseAnonCreds] based on anoncreds-rs, the implementation of the AnonCreds v1 specification.
+/// Inputs and outputs keep the indy anoncreds JSON formats of [BaseAnonCreds], identifiers can be either legacy
+/// indy identifiers or fully qualified ones (e.g. `did:indy:sovrin:WgWxqztrNooG92RXvxSTWv/anoncreds/v0/SCHEMA/gvt/1.0`).
+/// Revocation status lists are accepted wherever a revocation registry (delta) is expected.
+///
+/// Issuers get registries with issuance by default, as with vdrtools. Revocations are recorded locally, and
+/// [BaseAnonCreds::publish_local_revocations] writes them to the ledger given in
+/// [AnoncredsRsAnonCreds::new_with_ledger]. Without a ledger, publishing is not supported.
+#[derive(Debug)]
+pub struct AnoncredsRsAnonCreds {
+    wallet: Arc<dyn BaseWallet>,
+    ledger: Option<Arc<dyn BaseLedger>>,
+}
+
+impl AnoncredsRsAnonCreds {
+    pub fn new(wallet: Arc<dyn BaseWallet>) -> Self {
+        AnoncredsRsAnonCreds { wallet, ledger: None }
+    }
+
+    pub fn new_with_ledger(wallet: Arc<dyn BaseWallet>, ledger: Arc<dyn BaseLedger>) -> Self {
+        AnoncredsRsAnonCreds {
+            wallet,
+            ledger: Some(ledger),
+        }
+    }
+
+    async fn get_link_secret(&self, link_secret_id: &str) -> VcxCoreResult<LinkSecret> {
+        let record = self
+            .wallet
+            .get_wallet_record(CATEGORY_LINK_SECRET, link_secret_id, "{}")
+            .await?;
+
+        let record: Value = serde_json::from_str(&record)?;
+
+        let ms_value = (&record).try_get("value")?;
+        let ms_decimal = ms_value.try_as_str()?;
+
+        LinkSecret::try_from(ms_decimal).map_err(|err| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::UrsaError,
+                format!("Failed to create link secret from decimal string: {}", err),
+            )
+        })
+    }
+
+    async fn get_wallet_record_value<T: DeserializeOwned>(&self, category: &str, id: &str) -> VcxCoreResult<T> {
+        let record = self.wallet.get_wallet_record(category, id, "{}").await?;
+        let record: Value = serde_json::from_str(&record)?;
+        let value = (&record).try_get("value")?.try_as_str()?;
+
+        Ok(serde_json::from_str(value)?)
+    }
+
+    // Takes the next free credential revocation ID of the registry. The ID is marked as used in the same transaction
+    // as the registry info is updated, so that concurrent issuances conflict on the marker instead of sharing an ID.
+    // An ID stays used if the issuance fails afterwards.
+    async fn reserve_cred_rev_id(&self, rev_reg_id: &str, max_cred_num: u32) -> VcxCoreResult<u32> {
+        loop {
+            let mut rev_reg_info: RevocationRegistryInfo =
+                self.get_wallet_record_value(CATEGORY_REV_REG_INFO, rev_reg_id).await?;
+            let cred_rev_id = rev_reg_info.curr_id + 1;
+            if cred_rev_id > max_cred_num {
+                return Err(AriesVcxCoreError::from_msg(
+                    AriesVcxCoreErrorKind::InvalidRevocationDetails,
+                    format!("Revocation registry {} is full", rev_reg_id),
+                ));
+            }
+            rev_reg_info.curr_id = cred_rev_id;
+
+            let mut transaction = WalletTransaction::new();
+            transaction
+                .add_wallet_record(
+                    CATEGORY_REV_REG_IDX,
+                    &format!("{}:{}", rev_reg_id, cred_rev_id),
+                    "{}",
+                    None,
+                )
+                .update_wallet_record_value(
+                    CATEGORY_REV_REG_INFO,
+                    rev_reg_id,
+                    &serde_json::to_string(&rev_reg_info)?,
+                );
+            match self.wallet.commit_transaction(transaction).await {
+                Err(err) if err.kind() == AriesVcxCoreErrorKind::DuplicationWalletRecord => continue,
+                res => return res.map(|_| cred_rev_id),
+            }
+        }
+    }
+
+    async fn _get_credential(&self, credential_id: &str) -> VcxCoreResult<Credential> {
+        let cred_record = self
+            .wallet
+            .get_wallet_record(CATEGORY_CREDENTIAL, credential_id, "{}")
+            .await?;
+        let cred_record: Value = serde_json::from_str(&cred_record)?;
+        let cred_record_value = (&cred_record).try_get("value")?;
+
+        let cred_json = cred_record_value.try_as_str()?;
+
+        let credential: Credential = serde_json::from_str(cred_json)?;
+
+        Ok(credential)
+    }
+
+    async fn _get_credentials(&self, wql: &str) -> VcxCoreResult<Vec<(String, Credential)>> {
+        let mut record_iterator = self
+            .wallet
+            .iterate_wallet_records(CATEGORY_CREDENTIAL, wql, "{}")
+            .await?;
+        let records = record_iterator.collect().await?;
+
+        records
+            .iter()
+            .map(|record| {
+                let cred_record: Value = serde_json::from_str(record)?;
+
+                let cred_record_id = (&cred_record).try_get("id")?.try_as_str()?.to_string();
+                let cred_record_value = (&cred_record).try_get("value")?;
+
+                let cred_json = cred_record_value.try_as_str()?;
+
+                let credential: Credential = serde_json::from_str(cred_json)?;
+
+                Ok((cred_record_id, credential))
+            })
+            .collect()
+    }
+
+    async fn _get_credentials_for_proof_req_for_attr_name(
+        &self,
+        restrictions: Option<&Value>,
+        attr_name: &str,
+    ) -> VcxCoreResult<Vec<(String, Credential)>> {
+        let attr_marker_tag_name = _format_attribute_as_marker_tag_name(attr_name);
+
+        let wql_attr_query = json!({
+            attr_marker_tag_name: "1"
+        });
+
+        let wql_query = match restrictions.map(|x| x.to_owned()) {
+            Some(Value::Array(mut arr)) => {
+                arr.push(wql_attr_query);
+                json!({ "$and": arr })
+            }
+            Some(Value::Object(obj)) => json!({ "$and": vec![wql_attr_query, Value::Object(obj)] }),
+            _ => wql_attr_query,
+        };
+
+        let wql_query = serde_json::to_string(&wql_query)?;
+
+        self._get_credentials(&wql_query).await
+    }
+}
+
+#[async_trait]
+impl BaseAnonCreds for AnoncredsRsAnonCreds {
+    async fn verifier_verify_proof(
+        &self,
+        proof_req_json: &str,
+        proof_json: &str,
+        schemas_json: &str,
+        credential_defs_json: &str,
+        rev_reg_defs_json: &str,
+        rev_regs_json: &str,
+    ) -> VcxCoreResult<bool> {
+        let presentation: Presentation = serde_json::from_str(proof_json)?;
+        let pres_req: PresentationRequest = serde_json::from_str(proof_req_json)?;
+
+        let schemas: HashMap<SchemaId, Schema> = _parse_objects(schemas_json)?
+            .into_iter()
+            .map(|(id, schema)| (SchemaId::new_unchecked(id), schema))
+            .collect();
+        let cred_defs: HashMap<CredentialDefinitionId, CredentialDefinition> = _parse_objects(credential_defs_json)?
+            .into_iter()
+            .map(|(id, cred_def)| (CredentialDefinitionId::new_unchecked(id), cred_def))
+            .collect();
+
+        let rev_reg_defs: Option<HashMap<String, Value>> = serde_json::from_str(rev_reg_defs_json)?;
+        let rev_reg_defs = rev_reg_defs.unwrap_or_default();
+        let rev_regs: Option<HashMap<String, HashMap<u64, Value>>> = serde_json::from_str(rev_regs_json)?;
+
+        // the specification replaces revocation registries by revocation status lists
+        let mut rev_status_lists = vec![];
+        for (rev_reg_id, rev_regs_by_timestamp) in rev_regs.unwrap_or_default() {
+            let rev_reg_def = rev_reg_defs.get(&rev_reg_id).ok_or(AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidRevocationDetails,
+                format!("No revocation registry definition provided for '{}'", rev_reg_id),
+            ))?;
+            for (timestamp, rev_reg) in rev_regs_by_timestamp {
+                rev_status_lists.push(_to_rev_status_list(&rev_reg_id, rev_reg_def, rev_reg, timestamp)?);
+            }
+        }
+
+        let rev_reg_defs: HashMap<RevocationRegistryDefinitionId, RevocationRegistryDefinition> = rev_reg_defs
+            .into_iter()
+            .map(|(id, rev_reg_def)| {
+                let rev_reg_def = serde_json::from_value(_with_issuer_id(rev_reg_def, &id))?;
+                Ok((RevocationRegistryDefinitionId::new_unchecked(id), rev_reg_def))
+            })
+            .collect::<VcxCoreResult<_>>()?;
+
+        let (rev_reg_defs, rev_status_lists) = if rev_status_lists.is_empty() {
+            (None, None)
+        } else {
+            (Some(hashmap_as_ref(&rev_reg_defs)), Some(rev_status_lists))
+        };
+
+        Ok(anoncreds::verifier::verify_presentation(
+            &presentation,
+            &pres_req,
+            &hashmap_as_ref(&schemas),
+            &hashmap_as_ref(&cred_defs),
+            rev_reg_defs.as_ref(),
+            rev_status_lists,
+            None,
+        )?)
+    }
+
+    async fn issuer_create_and_store_revoc_reg(
+        &self,
+        issuer_did: &str,
+        cred_def_id: &str,
+        tails_dir: &str,
+        max_creds: u32,
+        tag: &str,
+    ) -> VcxCoreResult<(String, String, String)> {
+        let cred_def: CredentialDefinition = self.get_wallet_record_value(CATEGORY_CRED_DEF, cred_def_id).await?;
+        let rev_reg_id = _rev_reg_id(issuer_did, cred_def_id, tag);
+
+        let mut tails_writer = TailsFileWriter::new(Some(tails_dir.to_string()));
+        let (rev_reg_def, rev_reg_def_priv) = anoncreds::issuer::create_revocation_registry_def(
+            &cred_def,
+            CredentialDefinitionId::new_unchecked(cred_def_id),
+            IssuerId::new_unchecked(issuer_did),
+            tag,
+            RegistryType::CL_ACCUM,
+            max_creds,
+            &mut tails_writer,
+        )?;
+        let rev_status_list = anoncreds::issuer::create_revocation_status_list(
+            RevocationRegistryDefinitionId::new_unchecked(rev_reg_id.clone()),
+            &rev_reg_def,
+            IssuerId::new_unchecked(issuer_did),
+            None,
+            true,
+        )?;
+
+        let mut transaction = WalletTransaction::new();
+        transaction
+            .add_wallet_record(
+                CATEGORY_REV_REG_DEF,
+                &rev_reg_id,
+                &serde_json::to_string(&rev_reg_def)?,
+                None,
+            )
+            .add_wallet_record(
+                CATEGORY_REV_REG_DEF_PRIV,
+                &rev_reg_id,
+                &serde_json::to_string(&rev_reg_def_priv)?,
+                None,
+            )
+            .add_wallet_record(
+                CATEGORY_REV_REG,
+                &rev_reg_id,
+                &serde_json::to_string(&rev_status_list)?,
+                None,
+            )
+            .add_wallet_record(
+                CATEGORY_REV_REG_INFO,
+                &rev_reg_id,
+                &serde_json::to_string(&RevocationRegistryInfo::default())?,
+                None,
+            );
+        self.wallet.commit_transaction(transaction).await?;
+
+        let mut rev_reg_def_json = _to_legacy_object(&rev_reg_def, &rev_reg_id)?;
+        rev_reg_def_json["value"]["issuanceType"] = Value::String("ISSUANCE_BY_DEFAULT".to_string());
+        let rev_reg_entry_json = json!({
+            "ver": "1.0",
+            "value": { "accum": _accumulator(&rev_status_list)? }
+        });
+
+        Ok((
+            rev_reg_id,
+            serde_json::to_string(&rev_reg_def_json)?,
+            serde_json::to_string(&rev_reg_entry_json)?,
+        ))
+    }
+
+    async fn issuer_create_and_store_credential_def(
+        &self,
+        issuer_did: &str,
+        schema_json: &str,
+        tag: &str,
+        sig_type: Option<&str>,
+        config_json: &str,
+    ) -> VcxCoreResult<(String, String)> {
+        let schema_v: Value = serde_json::from_str(schema_json)?;
+        let schema_id = (&schema_v).try_get("id")?.try_as_str()?.to_string();
+        // legacy credential definition IDs reference their schema by its ledger sequence number, when known
+        let schema_ref = schema_v
+            .get("seqNo")
+            .and_then(Value::as_u64)
+            .map_or_else(|| schema_id.clone(), |seq_no| seq_no.to_string());
+        let schema: Schema = serde_json::from_value(_with_issuer_id(schema_v, &schema_id))?;
+        let cred_def_id = _cred_def_id(issuer_did, &schema_ref, tag);
+
+        let signature_type = match sig_type.unwrap_or("CL") {
+            "CL" => SignatureType::CL,
+            sig_type => {
+                return Err(AriesVcxCoreError::from_msg(
+                    AriesVcxCoreErrorKind::InvalidInput,
+                    format!("Unsupported signature type: {}", sig_type),
+                ))
+            }
+        };
+        let config: Value = serde_json::from_str(config_json)?;
+        let support_revocation = config
+            .get("support_revocation")
+            .and_then(Value::as_bool)
+            .unwrap_or_default();
+
+        let (cred_def, cred_def_priv, key_correctness_proof) = anoncreds::issuer::create_credential_definition(
+            SchemaId::new_unchecked(schema_id),
+            &schema,
+            IssuerId::new_unchecked(issuer_did),
+            tag,
+            signature_type,
+            CredentialDefinitionConfig { support_revocation },
+        )?;
+
+        let mut transaction = WalletTransaction::new();
+        transaction
+            .add_wallet_record(
+                CATEGORY_CRED_DEF,
+                &cred_def_id,
+                &serde_json::to_string(&cred_def)?,
+                None,
+            )
+            .add_wallet_record(
+                CATEGORY_CRED_DEF_PRIV,
+                &cred_def_id,
+                &serde_json::to_string(&cred_def_priv)?,
+                None,
+            )
+            .add_wallet_record(
+                CATEGORY_CRED_KEY_CORRECTNESS_PROOF,
+                &cred_def_id,
+                &serde_json::to_string(&key_correctness_proof)?,
+                None,
+            );
+        self.wallet.commit_transaction(transaction).await?;
+
+        let cred_def_json = _to_legacy_object(&cred_def, &cred_def_id)?;
+
+        Ok((cred_def_id, serde_json::to_string(&cred_def_json)?))
+    }
+
+    async fn issuer_create_credential_offer(&self, cred_def_id: &str) -> VcxCoreResult<String> {
+        let cred_def: CredentialDefinition = self.get_wallet_record_value(CATEGORY_CRED_DEF, cred_def_id).await?;
+        let key_correctness_proof: CredentialKeyCorrectnessProof = self
+            .get_wallet_record_value(CATEGORY_CRED_KEY_CORRECTNESS_PROOF, cred_def_id)
+            .await?;
+
+        let cred_offer = anoncreds::issuer::create_credential_offer(
+            cred_def.schema_id,
+            CredentialDefinitionId::new_unchecked(cred_def_id),
+            &key_correctness_proof,
+        )?;
+
+        Ok(serde_json::to_string(&cred_offer)?)
+    }
+
+    async fn issuer_create_credential(
+        &self,
+        cred_offer_json: &str,
+        cred_req_json: &str,
+        cred_values_json: &str,
+        rev_reg_id: Option<String>,
+        tails_dir: Option<String>,
+    ) -> VcxCoreResult<(String, Option<String>, Option<String>)> {
+        let cred_offer: CredentialOffer = serde_json::from_str(cred_offer_json)?;
+        let cred_request: CredentialRequest = serde_json::from_str(cred_req_json)?;
+        let cred_values: CredentialValues = serde_json::from_str(cred_values_json)?;
+
+        let cred_def_id = &cred_offer.cred_def_id.0;
+        let cred_def: CredentialDefinition = self.get_wallet_record_value(CATEGORY_CRED_DEF, cred_def_id).await?;
+        let cred_def_priv: CredentialDefinitionPrivate = self
+            .get_wallet_record_value(CATEGORY_CRED_DEF_PRIV, cred_def_id)
+            .await?;
+
+        let Some(rev_reg_id) = rev_reg_id else {
+            let credential = anoncreds::issuer::create_credential(
+                &cred_def,
+                &cred_def_priv,
+                &cred_offer,
+                &cred_request,
+                cred_values,
+                None,
+                None,
+                None,
+            )?;
+            return Ok((serde_json::to_string(&credential)?, None, None));
+        };
+
+        let tails_dir = tails_dir.ok_or(AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::InvalidOption,
+            "Missing tails directory for a revocable credential",
+        ))?;
+        let rev_reg_def: RevocationRegistryDefinition =
+            self.get_wallet_record_value(CATEGORY_REV_REG_DEF, &rev_reg_id).await?;
+        let rev_reg_def_priv: RevocationRegistryDefinitionPrivate = self
+            .get_wallet_record_value(CATEGORY_REV_REG_DEF_PRIV, &rev_reg_id)
+            .await?;
+        let rev_status_list: RevocationStatusList = self.get_wallet_record_value(CATEGORY_REV_REG, &rev_reg_id).await?;
+        let cred_rev_id = self
+            .reserve_cred_rev_id(&rev_reg_id, rev_reg_def.value.max_cred_num)
+            .await?;
+
+        let tails_file_path = format!("{}/{}", tails_dir, rev_reg_def.value.tails_hash);
+        let revocation_config = CredentialRevocationConfig {
+            reg_def: &rev_reg_def,
+            reg_def_private: &rev_reg_def_priv,
+            registry_idx: cred_rev_id,
+            tails_reader: TailsFileReader::new_tails_reader(&tails_file_path),
+        };
+        let credential = anoncreds::issuer::create_credential(
+            &cred_def,
+            &cred_def_priv,
+            &cred_offer,
+            &cred_request,
+            cred_values,
+            Some(RevocationRegistryDefinitionId::new_unchecked(rev_reg_id.clone())),
+            Some(&rev_status_list),
+            Some(revocation_config),
+        )?;
+
+        // with issuance by default, issuing a credential doesn't change the registry, so there is no delta
+        Ok((serde_json::to_string(&credential)?, Some(cred_rev_id.to_string()), None))
+    }
+
+    /// * `requested_credentials_json`: same format as in [IndyCredxAnonCreds](super::credx_anoncreds::IndyCredxAnonCreds)
+    /// * `revoc_states_json`: revocation states by revocation registry ID and timestamp
+    async fn prover_create_proof(
+        &self,
+        proof_req_json: &str,
+        requested_credentials_json: &str,
+        link_secret_id: &str,
+        schemas_json: &str,
+        credential_defs_json: &str,
+        revoc_states_json: Option<&str>,
+    ) -> VcxCoreResult<String> {
+        let pres_req: PresentationRequest = serde_json::from_str(proof_req_json)?;
+
+        let requested_credentials: Value = serde_json::from_str(requested_credentials_json)?;
+        let requested_attributes = (&requested_credentials).try_get("requested_attributes")?;
+
+        let requested_predicates = (&requested_credentials).try_get("requested_predicates")?;
+        let self_attested_attributes = requested_credentials.get("self_attested_attributes");
+
+        let rev_states: Option<Value> = if let Some(revoc_states_json) = revoc_states_json {
+            Some(serde_json::from_str(revoc_states_json)?)
+        } else {
+            None
+        };
+
+        let schemas: HashMap<SchemaId, Schema> = _parse_objects(schemas_json)?
+            .into_iter()
+            .map(|(id, schema)| (SchemaId::new_unchecked(id), schema))
+            .collect();
+        let cred_defs: HashMap<CredentialDefinitionId, CredentialDefinition> = _parse_objects(credential_defs_json)?
+            .into_iter()
+            .map(|(id, cred_def)| (CredentialDefinitionId::new_unchecked(id), cred_def))
+            .collect();
+
+        let mut present_credentials: PresentCredentials = PresentCredentials::default();
+
+        let mut proof_details_by_cred_id: HashMap<
+            String,
+            (
+                Credential,
+                Option<u64>,
+                Option<CredentialRevocationState>,
+                Vec<(String, bool)>,
+                Vec<String>,
+            ),
+        > = HashMap::new();
+
+        // add cred data and referent details for each requested attribute
+        for (reft, detail) in requested_attributes.try_as_object()?.iter() {
+            let _cred_id = detail.try_get("cred_id")?;
+            let cred_id = _cred_id.try_as_str()?;
+
+            let revealed = detail.try_get("revealed")?.try_as_bool()?;
+
+            if let Some((_, _, _, req_attr_refts_revealed, _)) = proof_details_by_cred_id.get_mut(cred_id) {
+                // mapping made for this credential already, add reft and its revealed status
+                req_attr_refts_revealed.push((reft.to_string(), revealed));
+            } else {
+                let credential = self._get_credential(cred_id).await?;
+
+                let (timestamp, rev_state) = get_rev_state(cred_id, &credential, detail, rev_states.as_ref())?;
+
+                proof_details_by_cred_id.insert(
+                    cred_id.to_string(),
+                    (
+                        credential,
+                        timestamp,
+                        rev_state,
+                        vec![(reft.to_string(), revealed)],
+                        vec![],
+                    ),
+                );
+            }
+        }
+
+        // add cred data and referent details for each requested predicate
+        for (reft, detail) in requested_predicates.try_as_object()?.iter() {
+            let _cred_id = detail.try_get("cred_id")?;
+            let cred_id = _cred_id.try_as_str()?;
+
+            if let Some((_, _, _, _, req_preds_refts)) = proof_details_by_cred_id.get_mut(cred_id) {
+                // mapping made for this credential already, add reft
+                req_preds_refts.push(reft.to_string());
+            } else {
+                let credential = self._get_credential(cred_id).await?;
+
+                let (timestamp, rev_state) = get_rev_state(cred_id, &credential, detail, rev_states.as_ref())?;
+
+                proof_details_by_cred_id.insert(
+                    cred_id.to_string(),
+                    (credential, timestamp, rev_state, vec![], vec![reft.to_string()]),
+                );
+            }
+        }
+
+        // add all accumulated requested attributes and requested predicates to the [PresentCredentials] object
+        for (_cred_id, (credential, timestamp, rev_state, req_attr_refts_revealed, req_preds_refts)) in
+            proof_details_by_cred_id.iter()
+        {
+            let mut add_cred = present_credentials.add_credential(credential, *timestamp, rev_state.as_ref());
+
+            for (referent, revealed) in req_attr_refts_revealed {
+                add_cred.add_requested_attribute(referent, *revealed);
+            }
+
+            for referent in req_preds_refts {
+                add_cred.add_requested_predicate(referent);
+            }
+        }
+
+        let self_attested = match self_attested_attributes {
+            Some(self_attested_value) => {
+                let mut self_attested_map: HashMap<String, String> = HashMap::new();
+                for (k, v) in self_attested_value.try_as_object()?.iter() {
+                    self_attested_map.insert(k.to_string(), v.try_as_str()?.to_string());
+                }
+                Some(self_attested_map).filter(|map| !map.is_empty())
+            }
+            None => None,
+        };
+
+        let link_secret = self.get_link_secret(link_secret_id).await?;
+
+        let presentation = anoncreds::prover::create_presentation(
+            &pres_req,
+            present_credentials,
+            self_attested,
+            &link_secret,
+            &hashmap_as_ref(&schemas),
+            &hashmap_as_ref(&cred_defs),
+        )?;
+
+        Ok(serde_json::to_string(&presentation)?)
+    }
+
+    async fn prover_get_credential(&self, cred_id: &str) -> VcxCoreResult<String> {
+        let cred = self._get_credential(cred_id).await?;
+
+        let cred_info = _make_cred_info(cred_id, &cred)?;
+
+        Ok(serde_json::to_string(&cred_info)?)
+    }
+
+    async fn prover_get_credentials(&self, filter_json: Option<&str>) -> VcxCoreResult<String> {
+        // filter_json should map to WQL query directly
+        let creds_wql = filter_json.map_or("{}", |x| x);
+        let creds = self._get_credentials(creds_wql).await?;
+
+        let cred_info_list = creds
+            .iter()
+            .map(|(credential_id, cred)| _make_cred_info(credential_id, cred))
+            .collect::<VcxCoreResult<Vec<Value>>>()?;
+
+        Ok(serde_json::to_string(&cred_info_list)?)
+    }
+
+    async fn prover_get_credentials_for_proof_req(&self, proof_req: &str) -> VcxCoreResult<String> {
+        let proof_req_v: Value = serde_json::from_str(proof_req)
+            .map_err(|e| AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::InvalidProofRequest, e))?;
+
+        let requested_attributes = match proof_req_v.get("requested_attributes") {
+            Some(requested_attributes) => Some(requested_attributes.try_as_object()?.clone()),
+            None => None,
+        };
+        let requested_predicates = match proof_req_v.get("requested_predicates") {
+            Some(requested_predicates) => Some(requested_predicates.try_as_object()?.clone()),
+            None => None,
+        };
+
+        // handle special case of "empty because json is bad" vs "empty because no attributes sepected"
+        if requested_attributes.is_none() && requested_predicates.is_none() {
+            return Err(AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidAttributesStructure,
+                "Invalid Json Parsing of Requested Attributes Retrieved From Libindy",
+            ));
+        }
+
+        let mut referents: HashSet<String> = HashSet::new();
+        for requested in [&requested_attributes, &requested_predicates].into_iter().flatten() {
+            referents.extend(requested.keys().cloned());
+        }
+
+        let mut cred_by_attr: Value = json!({});
+
+        for reft in referents {
+            let requested_val = requested_attributes
+                .as_ref()
+                .and_then(|req_attrs| req_attrs.get(&reft))
+                .or_else(|| requested_predicates.as_ref().and_then(|req_preds| req_preds.get(&reft)))
+                .ok_or(AriesVcxCoreError::from_msg(
+                    // should not happen
+                    AriesVcxCoreErrorKind::InvalidState,
+                    format!("Unknown referent: {}", reft),
+                ))?;
+
+            let _attr_name = requested_val.try_get("name")?;
+            let _attr_name = _attr_name.try_as_str()?;
+            let attr_name = _normalize_attr_name(_attr_name);
+
+            let non_revoked = requested_val.get("non_revoked");
+            let restrictions = requested_val.get("restrictions");
+
+            let creds = self
+                ._get_credentials_for_proof_req_for_attr_name(restrictions, &attr_name)
+                .await?;
+
+            let mut credentials_json = vec![];
+
+            for (cred_id, cred) in creds {
+                credentials_json.push(json!({
+                    "cred_info": _make_cred_info(&cred_id, &cred)?,
+                    "interval": non_revoked
+                }))
+            }
+
+            cred_by_attr[ATTRS][reft] = Value::Array(credentials_json);
+        }
+
+        Ok(serde_json::to_string(&cred_by_attr)?)
+    }
+
+    async fn prover_create_credential_req(
+        &self,
+        prover_did: &str,
+        credential_offer_json: &str,
+        credential_def_json: &str,
+        link_secret_id: &str,
+    ) -> VcxCoreResult<(String, String)> {
+        let credential_offer: CredentialOffer = serde_json::from_str(credential_offer_json)?;
+        let cred_def: CredentialDefinition = _parse_object(credential_def_json, &credential_offer.cred_def_id.0)?;
+        let link_secret = self.get_link_secret(link_secret_id).await?;
+
+        // the specification only allows a legacy DID as prover DID, fully qualified ones are used as entropy instead
+        let (entropy, prover_did) = if qualifier::is_fully_qualified(prover_did) {
+            (Some(prover_did), None)
+        } else {
+            (None, Some(prover_did))
+        };
+
+        let (cred_req, cred_req_metadata) = anoncreds::prover::create_credential_request(
+            entropy,
+            prover_did,
+            &cred_def,
+            &link_secret,
+            link_secret_id,
+            &credential_offer,
+        )?;
+
+        Ok((
+            serde_json::to_string(&cred_req)?,
+            serde_json::to_string(&cred_req_metadata)?,
+        ))
+    }
+
+    /// * `rev_reg_delta_json`: either a revocation status list, or a legacy revocation registry delta
+    /// accumulated since the creation of the registry
+    async fn create_revocation_state(
+        &self,
+        tails_dir: &str,
+        rev_reg_def_json: &str,
+        rev_reg_delta_json: &str,
+        timestamp: u64,
+        cred_rev_id: &str,
+    ) -> VcxCoreResult<String> {
+        let rev_reg_def_v: Value = serde_json::from_str(rev_reg_def_json)?;
+        let rev_reg_def_id = (&rev_reg_def_v).try_get("id")?.try_as_str()?.to_string();
+        let rev_status_list = _to_rev_status_list(
+            &rev_reg_def_id,
+            &rev_reg_def_v,
+            serde_json::from_str(rev_reg_delta_json)?,
+            timestamp,
+        )?;
+        let rev_reg_def: RevocationRegistryDefinition =
+            serde_json::from_value(_with_issuer_id(rev_reg_def_v, &rev_reg_def_id))?;
+
+        let tails_file_path = format!("{}/{}", tails_dir, rev_reg_def.value.tails_hash);
+        let rev_reg_idx: u32 = cred_rev_id
+            .parse()
+            .map_err(|e| AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::ParsingError, e))?;
+
+        let rev_state = anoncreds::prover::create_or_update_revocation_state(
+            &tails_file_path,
+            &rev_reg_def,
+            &rev_status_list,
+            rev_reg_idx,
+            None,
+            None,
+        )?;
+
+        Ok(serde_json::to_string(&rev_state)?)
+    }
+
+    async fn prover_store_credential(
+        &self,
+        cred_id: Option<&str>,
+        cred_req_meta: &str,
+        cred_json: &str,
+        cred_def_json: &str,
+        rev_reg_def_json: Option<&str>,
+    ) -> VcxCoreResult<String> {
+        let mut credential: Credential = serde_json::from_str(cred_json)?;
+        let cred_request_metadata: CredentialRequestMetadata = serde_json::from_str(cred_req_meta)?;
+        let link_secret = self.get_link_secret(&cred_request_metadata.link_secret_name).await?;
+        let cred_def: CredentialDefinition = _parse_object(cred_def_json, &credential.cred_def_id.0)?;
+        let rev_reg_def: Option<RevocationRegistryDefinition> = match (&credential.rev_reg_id, rev_reg_def_json) {
+            (Some(rev_reg_id), Some(rev_reg_def_json)) => Some(_parse_object(rev_reg_def_json, &rev_reg_id.0)?),
+            _ => None,
+        };
+
+        anoncreds::prover::process_credential(
+            &mut credential,
+            &cred_request_metadata,
+            &link_secret,
+            &cred_def,
+            rev_reg_def.as_ref(),
+        )?;
+
+        let schema_id = &credential.schema_id.0;
+        let (schema_issuer_did, schema_name, schema_version) =
+            _parse_schema_id(schema_id).ok_or(AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidSchema,
+                "Could not process credential.schema_id as parts.",
+            ))?;
+
+        let cred_def_id = &credential.cred_def_id.0;
+
+        let mut tags = json!({
+            "schema_id": schema_id,
+            "schema_issuer_did": schema_issuer_did,
+            "schema_name": schema_name,
+            "schema_version": schema_version,
+            "issuer_did": qualifier::issuer_did(cred_def_id),
+            "cred_def_id": cred_def_id
+        });
+
+        if let Some(rev_reg_id) = &credential.rev_reg_id {
+            tags["rev_reg_id"] = Value::String(rev_reg_id.0.to_string())
+        }
+
+        for (raw_attr_name, attr_value) in credential.values.0.iter() {
+            let attr_name = _normalize_attr_name(raw_attr_name);
+            // add attribute name and raw value pair
+            let value_tag_name = _format_attribute_as_value_tag_name(&attr_name);
+            tags[value_tag_name] = Value::String(attr_value.raw.to_string());
+
+            // add attribute name and marker (used for checking existent)
+            let marker_tag_name = _format_attribute_as_marker_tag_name(&attr_name);
+            tags[marker_tag_name] = Value::String("1".to_string());
+        }
+
+        let credential_id = cred_id.map_or(Uuid::new_v4().to_string(), String::from);
+
+        let record_value = serde_json::to_string(&credential)?;
+        let tags_json = serde_json::to_string(&tags)?;
+
+        let mut transaction = WalletTransaction::new();
+        transaction.add_wallet_record(CATEGORY_CREDENTIAL, &credential_id, &record_value, Some(&tags_json));
+
+        // keep the revocation registry definition next to the credential, so both land in the wallet or neither does
+        if let (Some(rev_reg_id), Some(rev_reg_def_json)) = (&credential.rev_reg_id, rev_reg_def_json) {
+            let existing_rev_reg_def = self
+                .wallet
+                .get_wallet_record(CATEGORY_REV_REG_DEF, &rev_reg_id.0, "{}")
+                .await
+                .ok(); // ignore error, as we only care about whether it exists or not
+
+            if existing_rev_reg_def.is_none() {
+                transaction.add_wallet_record(CATEGORY_REV_REG_DEF, &rev_reg_id.0, rev_reg_def_json, None);
+            }
+        }
+
+        self.wallet.commit_transaction(transaction).await?;
+
+        Ok(credential_id)
+    }
+
+    async fn prover_create_link_secret(&self, link_secret_id: &str) -> VcxCoreResult<String> {
+        let existing_record = self
+            .wallet
+            .get_wallet_record(CATEGORY_LINK_SECRET, link_secret_id, "{}")
+            .await
+            .ok(); // ignore error, as we only care about whether it exists or not
+
+        if existing_record.is_some() {
+            return Err(AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::DuplicationMasterSecret,
+                format!("Master secret id: {} already exists in wallet.", link_secret_id),
+            ));
+        }
+
+        // stored as a decimal string, as done by IndyCredxAnonCreds
+        let ms_decimal: String = anoncreds::prover::create_link_secret()?.try_into().map_err(|err| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::UrsaError,
+                format!("Failed convert link secret to decimal string: {}", err),
+            )
+        })?;
+
+        self.wallet
+            .add_wallet_record(CATEGORY_LINK_SECRET, link_secret_id, &ms_decimal, None)
+            .await?;
+
+        Ok(link_secret_id.to_string())
+    }
+
+    async fn prover_delete_credential(&self, cred_id: &str) -> VcxCoreResult<()> {
+        self.wallet.delete_wallet_record(CATEGORY_CREDENTIAL, cred_id).await
+    }
+
+    async fn issuer_create_schema(
+        &self,
+        issuer_did: &str,
+        name: &str,
+        version: &str,
+        attrs: &str,
+    ) -> VcxCoreResult<(String, String)> {
+        let attr_names: AttributeNames = serde_json::from_str(attrs)?;
+
+        let schema = anoncreds::issuer::create_schema(name, version, IssuerId::new_unchecked(issuer_did), attr_names)?;
+        let schema_id = _schema_id(issuer_did, name, version);
+        let schema_json = _to_legacy_object(&schema, &schema_id)?;
+
+        Ok((schema_id, serde_json::to_string(&schema_json)?))
+    }
+
+    /// Revokes the credential in the local revocation status list, the tails file is the one referenced by the
+    /// registry definition. Revocations not published yet are kept as a single revocation registry delta.
+    async fn revoke_credential_local(&self, tails_dir: &str, rev_reg_id: &str, cred_rev_id: &str) -> VcxCoreResult<()> {
+        self.revoke_credential_local_in_transaction(tails_dir, rev_reg_id, cred_rev_id, WalletTransaction::new())
+            .await
+    }
+
+    async fn revoke_credential_local_in_transaction(
+        &self,
+        _tails_dir: &str,
+        rev_reg_id: &str,
+        cred_rev_id: &str,
+        mut transaction: WalletTransaction,
+    ) -> VcxCoreResult<()> {
+        let rev_reg_def: RevocationRegistryDefinition =
+            self.get_wallet_record_value(CATEGORY_REV_REG_DEF, rev_reg_id).await?;
+        let rev_status_list: RevocationStatusList = self.get_wallet_record_value(CATEGORY_REV_REG, rev_reg_id).await?;
+        let cred_rev_id: u32 = cred_rev_id
+            .parse()
+            .map_err(|e| AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::ParsingError, e))?;
+
+        let updated_status_list = anoncreds::issuer::update_revocation_status_list(
+            None,
+            None,
+            Some(BTreeSet::from([cred_rev_id])),
+            &rev_reg_def,
+            &rev_status_list,
+        )?;
+
+        let pending_delta: Option<Value> = self
+            .get_wallet_record_value(CATEGORY_REV_REG_DELTA, rev_reg_id)
+            .await
+            .ok();
+        let delta = _merge_pending_delta(
+            pending_delta.as_ref(),
+            _accumulator(&rev_status_list)?,
+            _accumulator(&updated_status_list)?,
+            cred_rev_id,
+        );
+        let delta_json = serde_json::to_string(&delta)?;
+
+        transaction.update_wallet_record_value(
+            CATEGORY_REV_REG,
+            rev_reg_id,
+            &serde_json::to_string(&updated_status_list)?,
+        );
+        if pending_delta.is_some() {
+            transaction.update_wallet_record_value(CATEGORY_REV_REG_DELTA, rev_reg_id, &delta_json);
+        } else {
+            transaction.add_wallet_record(CATEGORY_REV_REG_DELTA, rev_reg_id, &delta_json, None);
+        }
+        self.wallet.commit_transaction(transaction).await
+    }
+
+    /// Publishes the revocation registry delta kept by [BaseAnonCreds::revoke_credential_local] and forgets it, so
+    /// that later revocations start a new delta.
+    async fn publish_local_revocations(&self, submitter_did: &str, rev_reg_id: &str) -> VcxCoreResult<()> {
+        let ledger = self.ledger.as_ref().ok_or(AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::ActionNotSupported,
+            format!(
+                "anoncreds-rs can't publish the revocations of registry {}, it has no access to a ledger",
+                rev_reg_id
+            ),
+        ))?;
+        let delta: Value = match self.get_wallet_record_value(CATEGORY_REV_REG_DELTA, rev_reg_id).await {
+            Err(err) if err.kind() == AriesVcxCoreErrorKind::WalletRecordNotFound => {
+                return Err(AriesVcxCoreError::from_msg(
+                    AriesVcxCoreErrorKind::RevDeltaNotFound,
+                    format!(
+                        "Failed to publish revocation delta for revocation registry {}, no delta found. Possibly \
+                         already published?",
+                        rev_reg_id
+                    ),
+                ))
+            }
+            res => res?,
+        };
+
+        ledger
+            .publish_rev_reg_delta(rev_reg_id, &serde_json::to_string(&delta)?, submitter_did)
+            .await?;
+        self.wallet
+            .delete_wallet_record(CATEGORY_REV_REG_DELTA, rev_reg_id)
+            .await
+    }
+
+    async fn generate_nonce(&self) -> VcxCoreResult<String> {
+        let nonce = anoncreds::verifier::generate_nonce()?.to_string();
+        Ok(nonce)
+    }
+}
+
+fn get_rev_state(
+    cred_id: &str,
+    credential: &Credential,
+    detail: &Value,
+    rev_states: Option<&Value>,
+) -> VcxCoreResult<(Option<u64>, Option<CredentialRevocationState>)> {
+    let timestamp = detail.get("timestamp").and_then(|timestamp| timestamp.as_u64());
+    let cred_rev_reg_id = credential.rev_reg_id.as_ref().map(|id| id.0.to_string());
+    let rev_state = if let (Some(timestamp), Some(cred_rev_reg_id)) = (timestamp, cred_rev_reg_id) {
+        let rev_state = rev_states
+            .and_then(|_rev_states| _rev_states.get(&cred_rev_reg_id))
+            .and_then(|rev_states_by_timestamp| rev_states_by_timestamp.get(timestamp.to_string()))
+            .ok_or(AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidJson,
+                format!(
+                    "No revocation states provided for credential '{}' with rev_reg_id '{}' at timestamp '{}'",
+                    cred_id, cred_rev_reg_id, timestamp
+                ),
+            ))?;
+
+        let rev_state: CredentialRevocationState = serde_json::from_value(rev_state.clone())?;
+        Some(rev_state)
+    } else {
+        None
+    };
+
+    Ok((timestamp, rev_state))
+}
+
+fn _schema_id(issuer_did: &str, name: &str, version: &str) -> String {
+    if qualifier::is_fully_qualified(issuer_did) {
+        format!("{}/anoncreds/v0/SCHEMA/{}/{}", issuer_did, name, version)
+    } else {
+        format!("{}:2:{}:{}", issuer_did, name, version)
+    }
+}
+
+// returns issuer ID, name and version of the schema
+fn _parse_schema_id(schema_id: &str) -> Option<(String, String, String)> {
+    if qualifier::is_fully_qualified(schema_id) {
+        let (issuer_id, path) = schema_id.split_once('/')?;
+        match path.split('/').collect::<Vec<_>>()[..] {
+            ["anoncreds", "v0", "SCHEMA", name, version] => {
+                Some((issuer_id.to_string(), name.to_string(), version.to_string()))
+            }
+            _ => None,
+        }
+    } else {
+        match schema_id.split(':').collect::<Vec<_>>()[..] {
+            [issuer_id, "2", name, version] => Some((issuer_id.to_string(), name.to_string(), version.to_string())),
+            _ => None,
+        }
+    }
+}
+
+fn _cred_def_id(issuer_did: &str, schema_ref: &str, tag: &str) -> String {
+    if qualifier::is_fully_qualified(issuer_did) {
+        format!("{}/anoncreds/v0/CLAIM_DEF/{}/{}", issuer_did, schema_ref, tag)
+    } else {
+        format!("{}:3:CL:{}:{}", issuer_did, schema_ref, tag)
+    }
+}
+
+fn _rev_reg_id(issuer_did: &str, cred_def_id: &str, tag: &str) -> String {
+    if qualifier::is_fully_qualified(cred_def_id) {
+        format!("{}/{}", cred_def_id.replacen("/CLAIM_DEF/", "/REV_REG_DEF/", 1), tag)
+    } else {
+        format!("{}:4:{}:CL_ACCUM:{}", issuer_did, cred_def_id, tag)
+    }
+}
+
+// Issuance state of a revocation registry, `curr_id` being the last revocation ID given to a credential.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RevocationRegistryInfo {
+    curr_id: u32,
+}
+
+// Indy formatted objects carry their own ID, while specification objects carry the ID of their issuer instead.
+fn _with_issuer_id(mut object: Value, id: &str) -> Value {
+    if let Value::Object(map) = &mut object {
+        map.entry("issuerId")
+            .or_insert_with(|| Value::String(qualifier::issuer_did(id).to_string()));
+    }
+    object
+}
+
+// Adds the ID and version of the indy format to a specification object.
+fn _to_legacy_object<T: Serialize>(object: &T, id: &str) -> VcxCoreResult<Value> {
+    let mut object = serde_json::to_value(object)?;
+    object["id"] = Value::String(id.to_string());
+    object["ver"] = Value::String("1.0".to_string());
+    if let Value::Object(map) = &mut object {
+        map.remove("issuerId");
+    }
+    Ok(object)
+}
+
+fn _parse_object<T: DeserializeOwned>(object_json: &str, id: &str) -> VcxCoreResult<T> {
+    let object: Value = serde_json::from_str(object_json)?;
+    Ok(serde_json::from_value(_with_issuer_id(object, id))?)
+}
+
+// parses a JSON map of objects by their ID
+fn _parse_objects<T: DeserializeOwned>(objects_json: &str) -> VcxCoreResult<Vec<(String, T)>> {
+    let objects: HashMap<String, Value> = serde_json::from_str(objects_json)?;
+    objects
+        .into_iter()
+        .map(|(id, object)| {
+            let object = serde_json::from_value(_with_issuer_id(object, &id))?;
+            Ok((id, object))
+        })
+        .collect()
+}
+
+// Converts a legacy revocation registry (delta) at `timestamp` to a revocation status list, status lists are
+// returned as they are.
+fn _to_rev_status_list(
+    rev_reg_def_id: &str,
+    rev_reg_def: &Value,
+    rev_reg: Value,
+    timestamp: u64,
+) -> VcxCoreResult<RevocationStatusList> {
+    if rev_reg.get("revocationList").is_some() {
+        return Ok(serde_json::from_value(rev_reg)?);
+    }
+
+    let rev_reg_def_value = rev_reg_def.try_get("value")?;
+    let max_cred_num = rev_reg_def_value.try_get("maxCredNum")?.as_u64().ok_or_else(|| {
+        AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::InvalidRevocationDetails,
+            format!(
+                "Invalid maxCredNum in revocation registry definition '{}'",
+                rev_reg_def_id
+            ),
+        )
+    })?;
+    let issuance_by_default =
+        rev_reg_def_value.get("issuanceType").and_then(Value::as_str) != Some("ISSUANCE_ON_DEMAND");
+
+    let rev_reg_value = (&rev_reg).try_get("value")?;
+    let accum = rev_reg_value.try_get("accum")?.try_as_str()?;
+    let issued = _revocation_indices(rev_reg_value.get("issued"))?;
+    let revoked = _revocation_indices(rev_reg_value.get("revoked"))?;
+
+    let rev_status_list = json!({
+        "revRegDefId": rev_reg_def_id,
+        "issuerId": qualifier::issuer_did(rev_reg_def_id),
+        "revocationList": _revocation_list(max_cred_num, issuance_by_default, &issued, &revoked),
+        "currentAccumulator": accum,
+        "timestamp": timestamp,
+    });
+
+    Ok(serde_json::from_value(rev_status_list)?)
+}
+
+fn _accumulator(rev_status_list: &RevocationStatusList) -> VcxCoreResult<Value> {
+    let rev_status_list = serde_json::to_value(rev_status_list)?;
+    Ok((&rev_status_list).try_get("currentAccumulator")?.clone())
+}
+
+// Legacy revocation registry delta revoking `cred_rev_id` on top of the `pending` one, if any.
+fn _merge_pending_delta(pending: Option<&Value>, prev_accum: Value, accum: Value, cred_rev_id: u32) -> Value {
+    let pending_value = pending.and_then(|pending| pending.get("value"));
+    let prev_accum = pending_value
+        .and_then(|value| value.get("prevAccum"))
+        .cloned()
+        .unwrap_or(prev_accum);
+    let mut revoked: Vec<Value> = pending_value
+        .and_then(|value| value.get("revoked"))
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    revoked.push(json!(cred_rev_id));
+
+    json!({
+        "ver": "1.0",
+        "value": {
+            "prevAccum": prev_accum,
+            "accum": accum,
+            "issued": [],
+            "revoked": revoked
+        }
+    })
+}
+
+fn _revocation_indices(indices: Option<&Value>) -> VcxCoreResult<Vec<u64>> {
+    let Some(indices) = indices else {
+        return Ok(vec![]);
+    };
+    indices
+        .try_as_array()?
+        .iter()
+        .map(|index| {
+            index.as_u64().ok_or_else(|| {
+                AriesVcxCoreError::from_msg(
+                    AriesVcxCoreErrorKind::InvalidRevocationEntry,
+                    format!("Invalid revocation index: {}", index),
+                )
+            })
+        })
+        .collect()
+}
+
+// Status of every credential of the registry, 1 meaning revoked. Legacy revocation indices start at 1.
+fn _revocation_list(max_cred_num: u64, issuance_by_default: bool, issued: &[u64], revoked: &[u64]) -> Vec<u8> {
+    let mut revocation_list = vec![u8::from(!issuance_by_default); max_cred_num as usize];
+    for (indices, status) in [(issued, 0), (revoked, 1)] {
+        for index in indices {
+            if let Some(entry) = (*index as usize)
+                .checked_sub(1)
+                .and_then(|index| revocation_list.get_mut(index))
+            {
+                *entry = status;
+            }
+        }
+    }
+    revocation_list
+}
+
+fn _normalize_attr_name(name: &str) -> String {
+    // "name": string, // attribute name, (case insensitive and ignore spaces)
+    name.replace(' ', "").to_lowercase()
+}
+
+fn _make_cred_info(credential_id: &str, cred: &Credential) -> VcxCoreResult<Value> {
+    let cred_sig = serde_json::to_value(&cred.signature)?;
+
+    let rev_info = cred_sig.get("r_credential");
+
+    let schema_id = &cred.schema_id.0;
+    let cred_def_id = &cred.cred_def_id.0;
+    let rev_reg_id = cred.rev_reg_id.as_ref().map(|x| x.0.to_string());
+    let cred_rev_id = rev_info.and_then(|x| x.get("i")).and_then(|i| {
+        i.as_str()
+            .map(|str_i| str_i.to_string())
+            .or(i.as_i64().map(|int_i| int_i.to_string()))
+    });
+
+    let mut attrs = json!({});
+    for (x, y) in cred.values.0.iter() {
+        attrs[x] = Value::String(y.raw.to_string());
+    }
+
+    let val = json!({
+        "referent": credential_id,
+        "schema_id": schema_id,
+        "cred_def_id": cred_def_id,
+        "rev_reg_id": rev_reg_id,
+        "cred_rev_id": cred_rev_id,
+        "attrs": attrs
+    });
+
+    Ok(val)
+}
+
+fn _format_attribute_as_value_tag_name(attribute_name: &str) -> String {
+    format!("attr::{attribute_name}::value")
+}
+
+fn _format_attribute_as_marker_tag_name(attribute_name: &str) -> String {
+    format!("attr::{attribute_name}::marker")
+}
+
+// anoncreds-rs takes maps of references
+fn hashmap_as_ref<T, U>(map: &HashMap<T, U>) -> HashMap<&T, &U>
+where
+    T: std::hash::Hash,
+    T: std::cmp::Eq,
+{
+    map.iter().collect()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use crate::ledger::file_ledger::FileLedger;
+    use crate::wallet::in_memory_wallet::InMemoryWallet;
+
+    use super::*;
+
+    const ISSUER_DID: &str = "V4SGRU86Z58d6TV7PBUe6f";
+    const PROVER_DID: &str = "2hoqvcwupRTUNkXn6ArYzs";
+    const LINK_SECRET_ID: &str = "main";
+
+    fn _cred_values() -> String {
+        json!({
+            "name": { "raw": "Alice", "encoded": "1139481716457488690172217916278103335" },
+            "age": { "raw": "28", "encoded": "28" }
+        })
+        .to_string()
+    }
+
+    // issues a credential of a new credential definition, returns the schema, the credential definition and the
+    // credential along with its revocation ID
+    async fn _issue_credential(
+        anoncreds: &AnoncredsRsAnonCreds,
+        tails_dir: Option<&str>,
+    ) -> ((String, String), (String, String), (String, String), Option<String>) {
+        let (schema_id, schema_json) = anoncreds
+            .issuer_create_schema(ISSUER_DID, "gvt", "1.0", r#"["name", "age"]"#)
+            .await
+            .unwrap();
+        let (cred_def_id, cred_def_json) = anoncreds
+            .issuer_create_and_store_credential_def(
+                ISSUER_DID,
+                &schema_json,
+                "tag",
+                None,
+                &json!({ "support_revocation": tails_dir.is_some() }).to_string(),
+            )
+            .await
+            .unwrap();
+        let rev_reg_id = match tails_dir {
+            Some(tails_dir) => Some(
+                anoncreds
+                    .issuer_create_and_store_revoc_reg(ISSUER_DID, &cred_def_id, tails_dir, 10, "tag")
+                    .await
+                    .unwrap()
+                    .0,
+            ),
+            None => None,
+        };
+
+        let cred_offer = anoncreds.issuer_create_credential_offer(&cred_def_id).await.unwrap();
+        anoncreds.prover_create_link_secret(LINK_SECRET_ID).await.unwrap();
+        let (cred_req, cred_req_meta) = anoncreds
+            .prover_create_credential_req(PROVER_DID, &cred_offer, &cred_def_json, LINK_SECRET_ID)
+            .await
+            .unwrap();
+        let (cred_json, cred_rev_id, _) = anoncreds
+            .issuer_create_credential(
+                &cred_offer,
+                &cred_req,
+                &_cred_values(),
+                rev_reg_id,
+                tails_dir.map(String::from),
+            )
+            .await
+            .unwrap();
+
+        (
+            (schema_id, schema_json),
+            (cred_def_id, cred_def_json),
+            (cred_req_meta, cred_json),
+            cred_rev_id,
+        )
+    }
+
+    #[test]
+    fn test_identifiers() {
+        let legacy_schema_id = _schema_id("WgWxqztrNooG92RXvxSTWv", "gvt", "1.0");
+        assert_eq!(legacy_schema_id, "WgWxqztrNooG92RXvxSTWv:2:gvt:1.0");
+        assert_eq!(
+            _parse_schema_id(&legacy_schema_id).unwrap(),
+            (
+                "WgWxqztrNooG92RXvxSTWv".to_string(),
+                "gvt".to_string(),
+                "1.0".to_string()
+            )
+        );
+
+        let schema_id = _schema_id("did:indy:sovrin:WgWxqztrNooG92RXvxSTWv", "gvt", "1.0");
+        assert_eq!(
+            schema_id,
+            "did:indy:sovrin:WgWxqztrNooG92RXvxSTWv/anoncreds/v0/SCHEMA/gvt/1.0"
+        );
+        assert_eq!(
+            _parse_schema_id(&schema_id).unwrap(),
+            (
+                "did:indy:sovrin:WgWxqztrNooG92RXvxSTWv".to_string(),
+                "gvt".to_string(),
+                "1.0".to_string()
+            )
+        );
+        assert_eq!(_parse_schema_id("WgWxqztrNooG92RXvxSTWv:3:CL:2471:tag"), None);
+    }
+
+    #[test]
+    fn test_revocation_list_from_legacy_delta() {
+        assert_eq!(_revocation_list(4, true, &[1, 2, 3], &[2]), vec![0, 1, 0, 0]);
+        assert_eq!(_revocation_list(4, false, &[1, 2, 3], &[2]), vec![0, 1, 0, 1]);
+        // out of range indices are ignored
+        assert_eq!(_revocation_list(2, true, &[], &[0, 3]), vec![0, 0]);
+    }
+
+    #[tokio::test]
+    async fn test_holder_round_trip() {
+        let wallet: Arc<dyn BaseWallet> = Arc::new(InMemoryWallet::new());
+        let anoncreds = AnoncredsRsAnonCreds::new(wallet.clone());
+        let ((schema_id, schema_json), (cred_def_id, cred_def_json), (cred_req_meta, cred_json), cred_rev_id) =
+            _issue_credential(&anoncreds, None).await;
+        assert_eq!(cred_rev_id, None);
+
+        let cred_id = anoncreds
+            .prover_store_credential(Some("cred_1"), &cred_req_meta, &cred_json, &cred_def_json, None)
+            .await
+            .unwrap();
+
+        // same record layout as IndyCredxAnonCreds
+        let record = wallet
+            .get_wallet_record(CATEGORY_CREDENTIAL, &cred_id, r#"{"retrieveTags": true}"#)
+            .await
+            .unwrap();
+        let record: Value = serde_json::from_str(&record).unwrap();
+        let _: Credential = serde_json::from_str(record["value"].as_str().unwrap()).unwrap();
+        assert_eq!(
+            record["tags"],
+            json!({
+                "schema_id": schema_id,
+                "schema_issuer_did": ISSUER_DID,
+                "schema_name": "gvt",
+                "schema_version": "1.0",
+                "issuer_did": ISSUER_DID,
+                "cred_def_id": cred_def_id,
+                "attr::name::value": "Alice",
+                "attr::name::marker": "1",
+                "attr::age::value": "28",
+                "attr::age::marker": "1"
+            })
+        );
+
+        let cred_info: Value = serde_json::from_str(&anoncreds.prover_get_credential(&cred_id).await.unwrap()).unwrap();
+        assert_eq!(cred_info["referent"], "cred_1");
+        assert_eq!(cred_info["attrs"], json!({ "name": "Alice", "age": "28" }));
+        let creds = anoncreds
+            .prover_get_credentials(Some(&json!({ "attr::name::value": "Alice" }).to_string()))
+            .await
+            .unwrap();
+        assert_eq!(serde_json::from_str::<Vec<Value>>(&creds).unwrap(), vec![cred_info]);
+
+        let proof_req = json!({
+            "nonce": anoncreds.generate_nonce().await.unwrap(),
+            "name": "proof",
+            "version": "1.0",
+            "requested_attributes": {
+                "attr_1": { "name": "name", "restrictions": { "cred_def_id": cred_def_id } }
+            },
+            "requested_predicates": {
+                "pred_1": { "name": "age", "p_type": ">=", "p_value": 18 }
+            }
+        })
+        .to_string();
+        let creds_for_proof_req: Value = serde_json::from_str(
+            &anoncreds
+                .prover_get_credentials_for_proof_req(&proof_req)
+                .await
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            creds_for_proof_req["attrs"]["attr_1"][0]["cred_info"]["referent"],
+            "cred_1"
+        );
+        assert_eq!(
+            creds_for_proof_req["attrs"]["pred_1"][0]["cred_info"]["referent"],
+            "cred_1"
+        );
+
+        let requested_credentials = json!({
+            "self_attested_attributes": {},
+            "requested_attributes": { "attr_1": { "cred_id": cred_id, "revealed": true } },
+            "requested_predicates": { "pred_1": { "cred_id": cred_id } }
+        })
+        .to_string();
+        let schemas = json!({ schema_id: serde_json::from_str::<Value>(&schema_json).unwrap() }).to_string();
+        let cred_defs = json!({ cred_def_id: serde_json::from_str::<Value>(&cred_def_json).unwrap() }).to_string();
+        let proof = anoncreds
+            .prover_create_proof(
+                &proof_req,
+                &requested_credentials,
+                LINK_SECRET_ID,
+                &schemas,
+                &cred_defs,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let proof_v: Value = serde_json::from_str(&proof).unwrap();
+        assert_eq!(proof_v["requested_proof"]["revealed_attrs"]["attr_1"]["raw"], "Alice");
+        assert!(anoncreds
+            .verifier_verify_proof(&proof_req, &proof, &schemas, &cred_defs, "{}", "{}")
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_revoke_credential_local() {
+        let wallet: Arc<dyn BaseWallet> = Arc::new(InMemoryWallet::new());
+        let anoncreds = AnoncredsRsAnonCreds::new(wallet.clone());
+        let tails_dir = std::env::temp_dir().join(Uuid::new_v4().to_string());
+        std::fs::create_dir_all(&tails_dir).unwrap();
+        let tails_dir = tails_dir.to_str().unwrap();
+
+        let (_, (cred_def_id, _), (_, cred_json), cred_rev_id) = _issue_credential(&anoncreds, Some(tails_dir)).await;
+        assert_eq!(cred_rev_id.as_deref(), Some("1"));
+        let rev_reg_id = _rev_reg_id(ISSUER_DID, &cred_def_id, "tag");
+        let credential: Value = serde_json::from_str(&cred_json).unwrap();
+        assert_eq!(credential["rev_reg_id"], rev_reg_id);
+
+        let mut transaction = WalletTransaction::new();
+        transaction.add_wallet_record("pending", "1", "1", None);
+        anoncreds
+            .revoke_credential_local_in_transaction(tails_dir, &rev_reg_id, "1", transaction)
+            .await
+            .unwrap();
+        wallet.get_wallet_record("pending", "1", "{}").await.unwrap();
+
+        let delta: Value = anoncreds
+            .get_wallet_record_value(CATEGORY_REV_REG_DELTA, &rev_reg_id)
+            .await
+            .unwrap();
+        assert_eq!(delta["value"]["revoked"], json!([1]));
+        assert_ne!(delta["value"]["prevAccum"], delta["value"]["accum"]);
+        assert_eq!(
+            anoncreds
+                .publish_local_revocations(ISSUER_DID, &rev_reg_id)
+                .await
+                .unwrap_err()
+                .kind(),
+            AriesVcxCoreErrorKind::ActionNotSupported
+        );
+    }
+
+    #[tokio::test]
+    async fn test_publish_local_revocations() {
+        let wallet: Arc<dyn BaseWallet> = Arc::new(InMemoryWallet::new());
+        let ledger = Arc::new(FileLedger::in_memory());
+        let anoncreds = AnoncredsRsAnonCreds::new_with_ledger(wallet.clone(), ledger.clone());
+        let tails_dir = std::env::temp_dir().join(Uuid::new_v4().to_string());
+        std::fs::create_dir_all(&tails_dir).unwrap();
+        let tails_dir = tails_dir.to_str().unwrap();
+
+        let (_, (cred_def_id, _), _, _) = _issue_credential(&anoncreds, Some(tails_dir)).await;
+        let rev_reg_id = _rev_reg_id(ISSUER_DID, &cred_def_id, "tag");
+        let rev_reg_def: RevocationRegistryDefinition = anoncreds
+            .get_wallet_record_value(CATEGORY_REV_REG_DEF, &rev_reg_id)
+            .await
+            .unwrap();
+        let rev_reg_def_json = _to_legacy_object(&rev_reg_def, &rev_reg_id).unwrap().to_string();
+        ledger.publish_rev_reg_def(&rev_reg_def_json, ISSUER_DID).await.unwrap();
+
+        anoncreds
+            .revoke_credential_local(tails_dir, &rev_reg_id, "1")
+            .await
+            .unwrap();
+        let delta: Value = anoncreds
+            .get_wallet_record_value(CATEGORY_REV_REG_DELTA, &rev_reg_id)
+            .await
+            .unwrap();
+        anoncreds
+            .publish_local_revocations(ISSUER_DID, &rev_reg_id)
+            .await
+            .unwrap();
+
+        let (_, published_delta, _) = ledger.get_rev_reg_delta_json(&rev_reg_id, None, None).await.unwrap();
+        let published_delta: Value = serde_json::from_str(&published_delta).unwrap();
+        assert_eq!(published_delta["value"]["accum"], delta["value"]["accum"]);
+        assert_eq!(published_delta["value"]["revoked"], json!([1]));
+        let err = wallet
+            .get_wallet_record(CATEGORY_REV_REG_DELTA, &rev_reg_id, "{}")
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxCoreErrorKind::WalletRecordNotFound);
+
+        // nothing left to publish
+        assert_eq!(
+            anoncreds
+                .publish_local_revocations(ISSUER_DID, &rev_reg_id)
+                .await
+                .unwrap_err()
+                .kind(),
+            AriesVcxCoreErrorKind::RevDeltaNotFound
+        );
+    }
+
+    #[test]
+    fn test_merge_pending_delta() {
+        let delta = _merge_pending_delta(None, json!("accum_0"), json!("accum_1"), 2);
+        let delta = _merge_pending_delta(Some(&delta), json!("accum_1"), json!("accum_2"), 5);
+        assert_eq!(
+            delta["value"],
+            json!({ "prevAccum": "accum_0", "accum": "accum_2", "issued": [], "revoked": [2, 5] })
+        );
+    }
+}
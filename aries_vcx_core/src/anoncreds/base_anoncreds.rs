@@ -1,6 +1,13 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
 
-use crate::errors::error::VcxCoreResult;
+use crate::anoncreds::types::{
+    AnonCredsObject, CredentialDefinition, CredentialOffer, CredentialRequest, Proof, ProofRequest, RevocationRegistry,
+    RevocationRegistryDefinition, Schema,
+};
+use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult};
+use crate::wallet::base_wallet::WalletTransaction;
 
 /// Trait defining standard 'anoncreds' related functionality. The APIs, including
 /// input and output types are based off the indy Anoncreds API:
@@ -103,8 +110,140 @@ pub trait BaseAnonCreds: std::fmt::Debug + Send + Sync {
     // TODO - FUTURE - think about moving this to somewhere else, as it aggregates other calls (not PURE Anoncreds)
     async fn revoke_credential_local(&self, tails_dir: &str, rev_reg_id: &str, cred_rev_id: &str) -> VcxCoreResult<()>;
 
+    /// Revokes the credential like [BaseAnonCreds::revoke_credential_local], committing the writes collected in
+    /// `transaction` in the same wallet transaction as the local revocation registry delta, e.g. to keep track of
+    /// the revocations not published yet.
+    async fn revoke_credential_local_in_transaction(
+        &self,
+        tails_dir: &str,
+        rev_reg_id: &str,
+        cred_rev_id: &str,
+        transaction: WalletTransaction,
+    ) -> VcxCoreResult<()>;
+
     // TODO - FUTURE - think about moving this to somewhere else, as it aggregates other calls (not PURE Anoncreds)
     async fn publish_local_revocations(&self, submitter_did: &str, rev_reg_id: &str) -> VcxCoreResult<()>;
 
     async fn generate_nonce(&self) -> VcxCoreResult<String>;
+
+    // Typed variants of the methods above. Inputs and outputs are anoncreds objects, so malformed data is
+    // rejected when it is parsed rather than by the underlying anoncreds implementation.
+
+    async fn verifier_verify_proof_object(
+        &self,
+        proof_request: &ProofRequest,
+        proof: &Proof,
+        schemas: &HashMap<String, Schema>,
+        credential_defs: &HashMap<String, CredentialDefinition>,
+        rev_reg_defs: &HashMap<String, RevocationRegistryDefinition>,
+        rev_regs: &HashMap<String, HashMap<u64, RevocationRegistry>>,
+    ) -> VcxCoreResult<bool> {
+        self.verifier_verify_proof(
+            &proof_request.to_json()?,
+            &proof.to_json()?,
+            &to_json(schemas)?,
+            &to_json(credential_defs)?,
+            &to_json(rev_reg_defs)?,
+            &to_json(rev_regs)?,
+        )
+        .await
+    }
+
+    async fn issuer_create_and_store_credential_def_object(
+        &self,
+        issuer_did: &str,
+        schema: &Schema,
+        tag: &str,
+        signature_type: Option<&str>,
+        config_json: &str,
+    ) -> VcxCoreResult<CredentialDefinition> {
+        let (_, cred_def_json) = self
+            .issuer_create_and_store_credential_def(issuer_did, &schema.to_json()?, tag, signature_type, config_json)
+            .await?;
+        CredentialDefinition::from_json(&cred_def_json)
+    }
+
+    async fn issuer_create_and_store_revoc_reg_object(
+        &self,
+        issuer_did: &str,
+        cred_def_id: &str,
+        tails_dir: &str,
+        max_creds: u32,
+        tag: &str,
+    ) -> VcxCoreResult<(RevocationRegistryDefinition, String)> {
+        let (_, rev_reg_def_json, rev_reg_entry_json) = self
+            .issuer_create_and_store_revoc_reg(issuer_did, cred_def_id, tails_dir, max_creds, tag)
+            .await?;
+        Ok((
+            RevocationRegistryDefinition::from_json(&rev_reg_def_json)?,
+            rev_reg_entry_json,
+        ))
+    }
+
+    async fn issuer_create_credential_offer_object(&self, cred_def_id: &str) -> VcxCoreResult<CredentialOffer> {
+        CredentialOffer::from_json(&self.issuer_create_credential_offer(cred_def_id).await?)
+    }
+
+    // returns the credential request and its metadata JSON
+    async fn prover_create_credential_req_object(
+        &self,
+        prover_did: &str,
+        cred_offer: &CredentialOffer,
+        cred_def: &CredentialDefinition,
+        master_secret_id: &str,
+    ) -> VcxCoreResult<(CredentialRequest, String)> {
+        let (cred_req_json, cred_req_meta_json) = self
+            .prover_create_credential_req(
+                prover_did,
+                &cred_offer.to_json()?,
+                &cred_def.to_json()?,
+                master_secret_id,
+            )
+            .await?;
+        Ok((CredentialRequest::from_json(&cred_req_json)?, cred_req_meta_json))
+    }
+
+    async fn prover_create_proof_object(
+        &self,
+        proof_request: &ProofRequest,
+        requested_credentials_json: &str,
+        master_secret_id: &str,
+        schemas: &HashMap<String, Schema>,
+        credential_defs: &HashMap<String, CredentialDefinition>,
+        revoc_states_json: Option<&str>,
+    ) -> VcxCoreResult<Proof> {
+        let proof_json = self
+            .prover_create_proof(
+                &proof_request.to_json()?,
+                requested_credentials_json,
+                master_secret_id,
+                &to_json(schemas)?,
+                &to_json(credential_defs)?,
+                revoc_states_json,
+            )
+            .await?;
+        Proof::from_json(&proof_json)
+    }
+
+    async fn issuer_create_schema_object(
+        &self,
+        issuer_did: &str,
+        name: &str,
+        version: &str,
+        attrs: &[String],
+    ) -> VcxCoreResult<Schema> {
+        let (_, schema_json) = self
+            .issuer_create_schema(issuer_did, name, version, &to_json(&attrs)?)
+            .await?;
+        Schema::from_json(&schema_json)
+    }
+}
+
+fn to_json<T: serde::Serialize + ?Sized>(value: &T) -> VcxCoreResult<String> {
+    serde_json::to_string(value).map_err(|err| {
+        AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::SerializationError,
+            format!("Cannot serialize anoncreds objects: {}", err),
+        )
+    })
 }
@@ -8,7 +8,7 @@ use crate::utils::{
     constants::ATTRS,
     json::{AsTypeOrDeserializationError, TryGetIndex},
 };
-use crate::wallet::base_wallet::AsyncFnIteratorCollect;
+use crate::wallet::base_wallet::{AsyncFnIteratorCollect, WalletTransaction};
 use crate::{
     errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult},
     wallet::base_wallet::BaseWallet,
@@ -35,6 +35,7 @@ use super::base_anoncreds::BaseAnonCreds;
 
 const CATEGORY_CREDENTIAL: &str = "VCX_CREDENTIAL";
 const CATEGORY_LINK_SECRET: &str = "VCX_LINK_SECRET";
+const CATEGORY_REV_REG_DEF: &str = "VCX_REV_REG_DEF";
 
 #[derive(Debug)]
 pub struct IndyCredxAnonCreds {
@@ -608,9 +609,23 @@ impl BaseAnonCreds for IndyCredxAnonCreds {
         let record_value = serde_json::to_string(&credential)?;
         let tags_json = serde_json::to_string(&tags)?;
 
-        self.wallet
-            .add_wallet_record(CATEGORY_CREDENTIAL, &credential_id, &record_value, Some(&tags_json))
-            .await?;
+        let mut transaction = WalletTransaction::new();
+        transaction.add_wallet_record(CATEGORY_CREDENTIAL, &credential_id, &record_value, Some(&tags_json));
+
+        // keep the revocation registry definition next to the credential, so both land in the wallet or neither does
+        if let (Some(rev_reg_id), Some(rev_reg_def_json)) = (&credential.rev_reg_id, rev_reg_def_json) {
+            let existing_rev_reg_def = self
+                .wallet
+                .get_wallet_record(CATEGORY_REV_REG_DEF, &rev_reg_id.0, "{}")
+                .await
+                .ok(); // ignore error, as we only care about whether it exists or not
+
+            if existing_rev_reg_def.is_none() {
+                transaction.add_wallet_record(CATEGORY_REV_REG_DEF, &rev_reg_id.0, rev_reg_def_json, None);
+            }
+        }
+
+        self.wallet.commit_transaction(transaction).await?;
 
         Ok(credential_id)
     }
@@ -688,6 +703,17 @@ impl BaseAnonCreds for IndyCredxAnonCreds {
         Err(unimplemented_method_err("credx revoke_credential_local"))
     }
 
+    async fn revoke_credential_local_in_transaction(
+        &self,
+        tails_dir: &str,
+        rev_reg_id: &str,
+        cred_rev_id: &str,
+        transaction: WalletTransaction,
+    ) -> VcxCoreResult<()> {
+        let _ = (tails_dir, rev_reg_id, cred_rev_id, transaction);
+        Err(unimplemented_method_err("credx revoke_credential_local_in_transaction"))
+    }
+
     async fn publish_local_revocations(&self, submitter_did: &str, rev_reg_id: &str) -> VcxCoreResult<()> {
         let _ = (submitter_did, rev_reg_id);
         Err(unimplemented_method_err("credx publish_local_revocations"))
@@ -830,6 +856,11 @@ mod unit_tests {
         assert_unimplemented(anoncreds.issuer_create_credential_offer("").await);
         assert_unimplemented(anoncreds.issuer_create_credential("", "", "", None, None).await);
         assert_unimplemented(anoncreds.revoke_credential_local("", "", "").await);
+        assert_unimplemented(
+            anoncreds
+                .revoke_credential_local_in_transaction("", "", "", WalletTransaction::new())
+                .await,
+        );
         assert_unimplemented(anoncreds.publish_local_revocations("", "").await);
     }
 }
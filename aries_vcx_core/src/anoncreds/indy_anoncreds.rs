@@ -1,6 +1,7 @@
 use async_trait::async_trait;
 
 use crate::errors::error::VcxCoreResult;
+use crate::wallet::base_wallet::WalletTransaction;
 use crate::{indy, PoolHandle, WalletHandle};
 
 use super::base_anoncreds::BaseAnonCreds;
@@ -219,6 +220,23 @@ impl BaseAnonCreds for IndySdkAnonCreds {
         .await
     }
 
+    async fn revoke_credential_local_in_transaction(
+        &self,
+        tails_dir: &str,
+        rev_reg_id: &str,
+        cred_rev_id: &str,
+        transaction: WalletTransaction,
+    ) -> VcxCoreResult<()> {
+        indy::primitives::revocation_registry::revoke_credential_local_in_transaction(
+            self.indy_wallet_handle,
+            tails_dir,
+            rev_reg_id,
+            cred_rev_id,
+            transaction,
+        )
+        .await
+    }
+
     async fn publish_local_revocations(&self, submitter_did: &str, rev_reg_id: &str) -> VcxCoreResult<()> {
         indy::primitives::revocation_registry::publish_local_revocations(
             self.indy_wallet_handle,
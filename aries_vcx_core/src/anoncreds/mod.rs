@@ -1,5 +1,8 @@
+#[cfg(feature = "anoncreds_rs")]
+pub mod anoncreds_rs_anoncreds;
 pub mod base_anoncreds;
 #[cfg(feature = "modular_libs")]
 pub mod credx_anoncreds;
 #[cfg(feature = "vdrtools")]
 pub mod indy_anoncreds;
+pub mod types;
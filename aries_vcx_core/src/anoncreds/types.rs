@@ -0,0 +1,403 @@
+//! Typed representations of the anoncreds objects exchanged through [BaseLedger](crate::ledger::base_ledger::BaseLedger)
+//! and [BaseAnonCreds](super::base_anoncreds::BaseAnonCreds). The JSON formats follow the indy anoncreds API.
+//! Cryptographic material (keys, proofs, accumulators internals) is kept as opaque JSON, only the structure
+//! callers rely on is typed.
+
+use std::collections::HashMap;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult};
+
+/// Conversion between anoncreds objects and their JSON representation. Parsing is the single boundary where
+/// malformed data is rejected, so that it does not fail later deep inside vdrtools or credx.
+pub trait AnonCredsObject: Serialize + DeserializeOwned {
+    const NAME: &'static str;
+    const ERROR_KIND: AriesVcxCoreErrorKind;
+
+    // checks invariants which can't be expressed by the structure itself
+    fn validate(&self) -> VcxCoreResult<()> {
+        Ok(())
+    }
+
+    fn from_json(json: &str) -> VcxCoreResult<Self> {
+        let object: Self = serde_json::from_str(json)
+            .map_err(|err| AriesVcxCoreError::from_msg(Self::ERROR_KIND, format!("Invalid {}: {}", Self::NAME, err)))?;
+        object.validate()?;
+        Ok(object)
+    }
+
+    fn to_json(&self) -> VcxCoreResult<String> {
+        serde_json::to_string(self).map_err(|err| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::SerializationError,
+                format!("Cannot serialize {}: {}", Self::NAME, err),
+            )
+        })
+    }
+}
+
+fn invalid<T: AnonCredsObject>(msg: String) -> AriesVcxCoreError {
+    AriesVcxCoreError::from_msg(T::ERROR_KIND, format!("Invalid {}: {}", T::NAME, msg))
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Schema {
+    pub ver: String,
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    pub attr_names: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seq_no: Option<u32>,
+}
+
+impl AnonCredsObject for Schema {
+    const NAME: &'static str = "schema";
+    const ERROR_KIND: AriesVcxCoreErrorKind = AriesVcxCoreErrorKind::InvalidSchema;
+
+    fn validate(&self) -> VcxCoreResult<()> {
+        if self.attr_names.is_empty() {
+            return Err(invalid::<Self>(format!("schema {} has no attributes", self.id)));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CredentialDefinition {
+    pub ver: String,
+    pub id: String,
+    pub schema_id: String,
+    #[serde(rename = "type")]
+    pub signature_type: String,
+    pub tag: String,
+    pub value: serde_json::Value,
+}
+
+impl AnonCredsObject for CredentialDefinition {
+    const NAME: &'static str = "credential definition";
+    const ERROR_KIND: AriesVcxCoreErrorKind = AriesVcxCoreErrorKind::InvalidJson;
+
+    fn validate(&self) -> VcxCoreResult<()> {
+        if self.value.get("primary").is_none() {
+            return Err(invalid::<Self>(format!(
+                "credential definition {} has no primary key",
+                self.id
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Deserialize, Debug, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct RevocationRegistryDefinitionValue {
+    pub issuance_type: String,
+    pub max_cred_num: u32,
+    pub public_keys: serde_json::Value,
+    pub tails_hash: String,
+    pub tails_location: String,
+}
+
+#[derive(Clone, Deserialize, Debug, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct RevocationRegistryDefinition {
+    pub id: String,
+    pub revoc_def_type: String,
+    pub tag: String,
+    pub cred_def_id: String,
+    pub value: RevocationRegistryDefinitionValue,
+    pub ver: String,
+}
+
+impl AnonCredsObject for RevocationRegistryDefinition {
+    const NAME: &'static str = "revocation registry definition";
+    const ERROR_KIND: AriesVcxCoreErrorKind = AriesVcxCoreErrorKind::InvalidRevocationDetails;
+
+    fn validate(&self) -> VcxCoreResult<()> {
+        if self.revoc_def_type != "CL_ACCUM" {
+            return Err(invalid::<Self>(format!(
+                "unsupported revocation registry type {}",
+                self.revoc_def_type
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Deserialize, Debug, Serialize, PartialEq, Eq, Default)]
+pub struct RevocationRegistryDelta {
+    pub value: RevocationRegistryDeltaValue,
+    #[serde(rename = "ver")]
+    pub version: String,
+}
+
+#[derive(Clone, Deserialize, Debug, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct RevocationRegistryDeltaValue {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prev_accum: Option<String>,
+    pub accum: String,
+    #[serde(default)]
+    pub issued: Vec<u32>,
+    #[serde(default)]
+    pub revoked: Vec<u32>,
+}
+
+impl AnonCredsObject for RevocationRegistryDelta {
+    const NAME: &'static str = "revocation registry delta";
+    const ERROR_KIND: AriesVcxCoreErrorKind = AriesVcxCoreErrorKind::InvalidRevocationEntry;
+}
+
+#[derive(Clone, Deserialize, Debug, Serialize, PartialEq, Eq, Default)]
+pub struct RevocationRegistry {
+    pub value: RevocationRegistryValue,
+    pub ver: String,
+}
+
+#[derive(Clone, Deserialize, Debug, Serialize, PartialEq, Eq, Default)]
+pub struct RevocationRegistryValue {
+    pub accum: String,
+}
+
+impl AnonCredsObject for RevocationRegistry {
+    const NAME: &'static str = "revocation registry";
+    const ERROR_KIND: AriesVcxCoreErrorKind = AriesVcxCoreErrorKind::InvalidRevocationEntry;
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CredentialOffer {
+    pub schema_id: String,
+    pub cred_def_id: String,
+    pub key_correctness_proof: serde_json::Value,
+    pub nonce: String,
+}
+
+impl AnonCredsObject for CredentialOffer {
+    const NAME: &'static str = "credential offer";
+    const ERROR_KIND: AriesVcxCoreErrorKind = AriesVcxCoreErrorKind::InvalidJson;
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CredentialRequest {
+    pub prover_did: String,
+    pub cred_def_id: String,
+    pub blinded_ms: serde_json::Value,
+    pub blinded_ms_correctness_proof: serde_json::Value,
+    pub nonce: String,
+}
+
+impl AnonCredsObject for CredentialRequest {
+    const NAME: &'static str = "credential request";
+    const ERROR_KIND: AriesVcxCoreErrorKind = AriesVcxCoreErrorKind::InvalidJson;
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct NonRevokedInterval {
+    pub from: Option<u64>,
+    pub to: Option<u64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct RequestedAttribute {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub names: Option<Vec<String>>,
+    // WQL query over the credential tags, or a list of filters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restrictions: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub non_revoked: Option<NonRevokedInterval>,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum PredicateType {
+    #[serde(rename = ">=")]
+    GreaterOrEqual,
+    #[serde(rename = ">")]
+    Greater,
+    #[serde(rename = "<=")]
+    LessOrEqual,
+    #[serde(rename = "<")]
+    Less,
+}
+
+impl PredicateType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PredicateType::GreaterOrEqual => ">=",
+            PredicateType::Greater => ">",
+            PredicateType::LessOrEqual => "<=",
+            PredicateType::Less => "<",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RequestedPredicate {
+    pub name: String,
+    pub p_type: PredicateType,
+    pub p_value: i32,
+    // WQL query over the credential tags, or a list of filters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restrictions: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub non_revoked: Option<NonRevokedInterval>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProofRequest {
+    pub nonce: String,
+    pub name: String,
+    pub version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ver: Option<String>,
+    #[serde(default)]
+    pub requested_attributes: HashMap<String, RequestedAttribute>,
+    #[serde(default)]
+    pub requested_predicates: HashMap<String, RequestedPredicate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub non_revoked: Option<NonRevokedInterval>,
+    // fields unknown to this representation, kept so that the request survives a round trip unchanged
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl AnonCredsObject for ProofRequest {
+    const NAME: &'static str = "proof request";
+    const ERROR_KIND: AriesVcxCoreErrorKind = AriesVcxCoreErrorKind::InvalidProofRequest;
+
+    fn validate(&self) -> VcxCoreResult<()> {
+        for (referent, attribute) in self.requested_attributes.iter() {
+            match (&attribute.name, &attribute.names) {
+                (Some(_), None) => {}
+                (None, Some(names)) if !names.is_empty() => {}
+                _ => {
+                    return Err(invalid::<Self>(format!(
+                        "requested attribute {} must contain either 'name' or a non-empty 'names'",
+                        referent
+                    )))
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProofIdentifier {
+    pub schema_id: String,
+    pub cred_def_id: String,
+    pub rev_reg_id: Option<String>,
+    pub timestamp: Option<u64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Proof {
+    pub proof: serde_json::Value,
+    pub requested_proof: serde_json::Value,
+    pub identifiers: Vec<ProofIdentifier>,
+}
+
+impl AnonCredsObject for Proof {
+    const NAME: &'static str = "proof";
+    const ERROR_KIND: AriesVcxCoreErrorKind = AriesVcxCoreErrorKind::InvalidProof;
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use crate::utils::constants::{rev_def_json, SCHEMA_JSON};
+
+    use super::*;
+
+    #[test]
+    fn test_schema_roundtrip() {
+        let schema = Schema::from_json(SCHEMA_JSON).unwrap();
+        assert_eq!(schema.attr_names, vec!["height", "name", "sex", "age"]);
+        assert_eq!(schema.seq_no, Some(2471));
+
+        let json: serde_json::Value = serde_json::from_str(&schema.to_json().unwrap()).unwrap();
+        assert_eq!(json, serde_json::from_str::<serde_json::Value>(SCHEMA_JSON).unwrap());
+    }
+
+    #[test]
+    fn test_malformed_objects_are_rejected() {
+        let err = Schema::from_json(r#"{"ver":"1.0","id":"id","name":"name","version":"1.0"}"#).unwrap_err();
+        assert_eq!(err.kind(), AriesVcxCoreErrorKind::InvalidSchema);
+        assert!(err.to_string().contains("attrNames"));
+
+        let err =
+            Schema::from_json(r#"{"ver":"1.0","id":"id","name":"name","version":"1.0","attrNames":[]}"#).unwrap_err();
+        assert_eq!(err.kind(), AriesVcxCoreErrorKind::InvalidSchema);
+
+        let mut rev_reg_def = RevocationRegistryDefinition::from_json(&rev_def_json()).unwrap();
+        rev_reg_def.revoc_def_type = "UNKNOWN".to_string();
+        assert_eq!(
+            RevocationRegistryDefinition::from_json(&rev_reg_def.to_json().unwrap())
+                .unwrap_err()
+                .kind(),
+            AriesVcxCoreErrorKind::InvalidRevocationDetails
+        );
+
+        let proof_request = json!({
+            "nonce": "123",
+            "name": "proof",
+            "version": "1.0",
+            "requested_attributes": { "attribute_0": { "name": "age", "names": ["age"] } }
+        });
+        assert_eq!(
+            ProofRequest::from_json(&proof_request.to_string()).unwrap_err().kind(),
+            AriesVcxCoreErrorKind::InvalidProofRequest
+        );
+
+        let proof_request = json!({
+            "nonce": "123",
+            "name": "proof",
+            "version": "1.0",
+            "requested_predicates": { "predicate_0": { "name": "age", "p_type": "=", "p_value": 18 } }
+        });
+        assert_eq!(
+            ProofRequest::from_json(&proof_request.to_string()).unwrap_err().kind(),
+            AriesVcxCoreErrorKind::InvalidProofRequest
+        );
+    }
+
+    #[test]
+    fn test_proof_request_roundtrip() {
+        let proof_request = json!({
+            "nonce": "123",
+            "name": "proof",
+            "version": "1.0",
+            "ver": "2.0",
+            "requested_attributes": {
+                "attribute_0": { "names": ["name", "age"], "restrictions": { "cred_def_id": "cred_def_id" } }
+            },
+            "requested_predicates": {
+                "predicate_0": { "name": "age", "p_type": ">=", "p_value": 18, "non_revoked": { "from": 1, "to": 2 } }
+            },
+            "non_revoked": { "from": null, "to": 100 },
+            "extension": { "key": "value" }
+        });
+
+        let parsed = ProofRequest::from_json(&proof_request.to_string()).unwrap();
+        assert_eq!(parsed.ver.as_deref(), Some("2.0"));
+        assert_eq!(
+            parsed.requested_attributes["attribute_0"].names,
+            Some(vec!["name".to_string(), "age".to_string()])
+        );
+        assert_eq!(
+            parsed.requested_predicates["predicate_0"].p_type,
+            PredicateType::GreaterOrEqual
+        );
+        assert_eq!(parsed.extra["extension"], json!({ "key": "value" }));
+
+        let json: serde_json::Value = serde_json::from_str(&parsed.to_json().unwrap()).unwrap();
+        assert_eq!(json, proof_request);
+    }
+}
@@ -0,0 +1,25 @@
+use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind};
+use anoncreds::Error as AnoncredsError;
+
+impl From<AnoncredsError> for AriesVcxCoreError {
+    fn from(err: AnoncredsError) -> Self {
+        match err.kind() {
+            anoncreds::ErrorKind::Input => AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::InvalidInput, err),
+            anoncreds::ErrorKind::IOError => AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::IOError, err),
+            anoncreds::ErrorKind::InvalidState => AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::InvalidState, err),
+            anoncreds::ErrorKind::Unexpected => AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::UnknownError, err),
+            anoncreds::ErrorKind::CredentialRevoked => {
+                AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::InvalidState, err)
+            }
+            anoncreds::ErrorKind::InvalidUserRevocId => {
+                AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::InvalidInput, err)
+            }
+            anoncreds::ErrorKind::ProofRejected => {
+                AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::ProofRejected, err)
+            }
+            anoncreds::ErrorKind::RevocationRegistryFull => {
+                AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::InvalidState, err)
+            }
+        }
+    }
+}
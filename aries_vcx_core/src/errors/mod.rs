@@ -1,5 +1,7 @@
 pub mod error;
 mod mapping_agency_client;
+#[cfg(feature = "anoncreds_rs")]
+mod mapping_anoncreds;
 #[cfg(feature = "modular_libs")]
 mod mapping_credx;
 #[cfg(feature = "modular_libs")]
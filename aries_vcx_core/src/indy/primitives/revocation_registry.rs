@@ -7,7 +7,9 @@ use crate::indy::ledger::transactions::{
     build_rev_reg_delta_request, build_rev_reg_request, check_response, sign_and_submit_to_ledger,
 };
 use crate::indy::utils::parse_and_validate;
-use crate::indy::wallet_non_secrets::{clear_rev_reg_delta, get_rev_reg_delta, set_rev_reg_delta};
+use crate::indy::wallet::commit_wallet_transaction;
+use crate::indy::wallet_non_secrets::{clear_rev_reg_delta, get_rev_reg_delta, set_rev_reg_delta_in_transaction};
+use crate::wallet::base_wallet::WalletTransaction;
 use crate::{PoolHandle, WalletHandle};
 
 pub const BLOB_STORAGE_TYPE: &str = "default";
@@ -131,6 +133,24 @@ pub async fn revoke_credential_local(
     tails_file: &str,
     rev_reg_id: &str,
     cred_rev_id: &str,
+) -> VcxCoreResult<()> {
+    revoke_credential_local_in_transaction(
+        wallet_handle,
+        tails_file,
+        rev_reg_id,
+        cred_rev_id,
+        WalletTransaction::new(),
+    )
+    .await
+}
+
+// the revocation itself is stored by vdrtools, only the delta is written along with `transaction`
+pub async fn revoke_credential_local_in_transaction(
+    wallet_handle: WalletHandle,
+    tails_file: &str,
+    rev_reg_id: &str,
+    cred_rev_id: &str,
+    mut transaction: WalletTransaction,
 ) -> VcxCoreResult<()> {
     if settings::indy_mocks_enabled() {
         return Ok(());
@@ -141,14 +161,16 @@ pub async fn revoke_credential_local(
 
     debug!("revoke_credential_local >>> new_delta_json: {}", new_delta_json);
 
-    if let Some(old_delta_json) = get_rev_reg_delta(wallet_handle, rev_reg_id).await {
+    let old_delta_json = get_rev_reg_delta(wallet_handle, rev_reg_id).await;
+    if let Some(old_delta_json) = &old_delta_json {
         debug!("revoke_credential_local >>> old_delta_json: {}", old_delta_json);
         new_delta_json =
             libindy_issuer_merge_revocation_registry_deltas(old_delta_json.as_str(), new_delta_json.as_str()).await?;
         debug!("revoke_credential_local >>> merged_delta_json: {}", new_delta_json);
     }
 
-    set_rev_reg_delta(wallet_handle, rev_reg_id, &new_delta_json).await
+    set_rev_reg_delta_in_transaction(&mut transaction, rev_reg_id, &new_delta_json, old_delta_json.is_some())?;
+    commit_wallet_transaction(wallet_handle, transaction).await
 }
 
 // consider moving out of indy dir as this aggregates multiple calls
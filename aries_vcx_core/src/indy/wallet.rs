@@ -1,14 +1,17 @@
+use std::collections::HashMap;
+
 use crate::secret;
 use serde::{Deserialize, Serialize};
 use vdrtools::{
     types::domain::wallet::{default_key_derivation_method, KeyDerivationMethod},
     types::errors::IndyErrorKind,
-    Locator,
+    Locator, WalletOperation,
 };
 
 use crate::{
     errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult},
     indy::credentials::holder,
+    wallet::base_wallet::{WalletRecordOperation, WalletTransaction},
 };
 use crate::{global::settings, WalletHandle};
 use crate::{indy::keys, SearchHandle};
@@ -442,6 +445,69 @@ pub(crate) async fn delete_wallet_record_tags(
     Ok(())
 }
 
+pub(crate) async fn commit_wallet_transaction(
+    wallet_handle: WalletHandle,
+    transaction: WalletTransaction,
+) -> VcxCoreResult<()> {
+    trace!("commit_wallet_transaction >>> transaction: {:?}", secret!(&transaction));
+
+    if settings::indy_mocks_enabled() {
+        return Ok(());
+    }
+
+    let operations = transaction
+        .into_operations()
+        .into_iter()
+        .map(|operation| {
+            Ok(match operation {
+                WalletRecordOperation::Add {
+                    xtype,
+                    id,
+                    value,
+                    tags_json,
+                } => WalletOperation::Add {
+                    type_: xtype,
+                    name: id,
+                    value,
+                    tags: tags_json
+                        .as_deref()
+                        .map(serde_json::from_str)
+                        .transpose()?
+                        .unwrap_or_default(),
+                },
+                WalletRecordOperation::UpdateValue { xtype, id, value } => WalletOperation::UpdateValue {
+                    type_: xtype,
+                    name: id,
+                    value,
+                },
+                WalletRecordOperation::AddTags { xtype, id, tags_json } => WalletOperation::AddTags {
+                    type_: xtype,
+                    name: id,
+                    tags: serde_json::from_str(&tags_json)?,
+                },
+                WalletRecordOperation::UpdateTags { xtype, id, tags_json } => WalletOperation::UpdateTags {
+                    type_: xtype,
+                    name: id,
+                    tags: serde_json::from_str(&tags_json)?,
+                },
+                WalletRecordOperation::DeleteTags { xtype, id, tag_names } => WalletOperation::DeleteTags {
+                    type_: xtype,
+                    name: id,
+                    tag_names: serde_json::from_str(&tag_names)?,
+                },
+                WalletRecordOperation::Delete { xtype, id } => WalletOperation::Delete { type_: xtype, name: id },
+            })
+        })
+        .collect::<VcxCoreResult<Vec<WalletOperation>>>()?;
+
+    Locator::instance()
+        .non_secret_controller
+        .batch_records(wallet_handle, operations)
+        .await?;
+
+    Ok(())
+}
+
 // TODO - FUTURE - revert to pub(crate) after libvcx dependency is fixed
 pub async fn open_search_wallet(
     wallet_handle: WalletHandle,
@@ -508,6 +574,46 @@ pub async fn close_search_wallet(search_handle: SearchHandle) -> VcxCoreResult<(
     Ok(())
 }
 
+pub(crate) async fn open_search_all_wallet(wallet_handle: WalletHandle) -> VcxCoreResult<SearchHandle> {
+    trace!("open_search_all >>>");
+
+    if settings::indy_mocks_enabled() {
+        return Ok(SearchHandle(1));
+    }
+
+    let res = Locator::instance()
+        .non_secret_controller
+        .open_search_all(wallet_handle)
+        .await?;
+
+    Ok(res)
+}
+
+pub(crate) async fn restore_wallet_record(
+    wallet_handle: WalletHandle,
+    xtype: &str,
+    id: &str,
+    value: &str,
+    tags: HashMap<String, String>,
+) -> VcxCoreResult<()> {
+    trace!(
+        "restore_wallet_record >>> xtype: {}, id: {}",
+        secret!(&xtype),
+        secret!(&id)
+    );
+
+    if settings::indy_mocks_enabled() {
+        return Ok(());
+    }
+
+    Locator::instance()
+        .non_secret_controller
+        .restore_record(wallet_handle, xtype.into(), id.into(), value.into(), tags)
+        .await?;
+
+    Ok(())
+}
+
 // TODO - FUTURE - can this be moved externally - move to a generic setup util?
 pub async fn wallet_configure_issuer(
     wallet_handle: WalletHandle,
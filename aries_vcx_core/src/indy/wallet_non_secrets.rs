@@ -1,7 +1,8 @@
 use serde_json;
 
 use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult};
-use crate::indy::wallet::{add_wallet_record, delete_wallet_record, get_wallet_record, update_wallet_record_value};
+use crate::indy::wallet::{delete_wallet_record, get_wallet_record};
+use crate::wallet::base_wallet::WalletTransaction;
 use crate::WalletHandle;
 
 static WALLET_RECORD_TYPE: &str = "cache";
@@ -58,33 +59,36 @@ pub async fn get_rev_reg_delta(wallet_handle: WalletHandle, rev_reg_id: &str) ->
     }
 }
 
-/// Rewrites or creates revocation registry delta record
+/// Adds the rewrite or the creation of the revocation registry delta record to `transaction`
 ///
 /// # Arguments
 /// `rev_reg_id`: revocation registry id.
 /// `cache`: Cache object.
+/// `exists`: whether a delta is already stored for the revocation registry.
 ///
-pub async fn set_rev_reg_delta(wallet_handle: WalletHandle, rev_reg_id: &str, cache: &str) -> VcxCoreResult<()> {
+pub fn set_rev_reg_delta_in_transaction(
+    transaction: &mut WalletTransaction,
+    rev_reg_id: &str,
+    cache: &str,
+    exists: bool,
+) -> VcxCoreResult<()> {
     debug!(
         "set_rev_reg_delta >> Setting store revocation registry delta for revocation registry {} to new value: {}",
         rev_reg_id, cache
     );
-    match serde_json::to_string(cache) {
-        Ok(json) => {
-            let wallet_id = format!("{RECORD_ID_PREFIX}{rev_reg_id}");
-            match update_wallet_record_value(wallet_handle, WALLET_RECORD_TYPE, &wallet_id, &json)
-                .await
-                .or(add_wallet_record(wallet_handle, WALLET_RECORD_TYPE, &wallet_id, &json, None).await)
-            {
-                Ok(_) => Ok(()),
-                Err(err) => Err(err),
-            }
-        }
-        Err(_) => Err(AriesVcxCoreError::from_msg(
+    let json = serde_json::to_string(cache).map_err(|_| {
+        AriesVcxCoreError::from_msg(
             AriesVcxCoreErrorKind::SerializationError,
             format!("Expected cache argument to be valid json. Found instead: {cache}"),
-        )),
+        )
+    })?;
+    let wallet_id = format!("{RECORD_ID_PREFIX}{rev_reg_id}");
+    if exists {
+        transaction.update_wallet_record_value(WALLET_RECORD_TYPE, &wallet_id, &json);
+    } else {
+        transaction.add_wallet_record(WALLET_RECORD_TYPE, &wallet_id, &json, None);
     }
+    Ok(())
 }
 
 /// Clears the stored revocation registry delta record
@@ -1,5 +1,9 @@
 use async_trait::async_trait;
 
+use crate::anoncreds::types::{
+    AnonCredsObject, CredentialDefinition, RevocationRegistry, RevocationRegistryDefinition, RevocationRegistryDelta,
+    Schema,
+};
 use crate::errors::error::VcxCoreResult;
 
 /// Trait defining standard 'ledger' related functionality.
@@ -107,6 +111,30 @@ pub trait BaseLedger: std::fmt::Debug + Send + Sync {
     // returns request as JSON
     async fn build_schema_request(&self, submitter_did: &str, schema_json: &str) -> VcxCoreResult<String>;
 
+    // returns request as JSON
+    async fn build_cred_def_request(&self, submitter_did: &str, cred_def_json: &str) -> VcxCoreResult<String>;
+
+    // returns request as JSON
+    async fn build_rev_reg_def_request(&self, submitter_did: &str, rev_reg_def_json: &str) -> VcxCoreResult<String>;
+
+    // returns request as JSON
+    async fn build_rev_reg_delta_request(
+        &self,
+        submitter_did: &str,
+        rev_reg_id: &str,
+        rev_reg_entry_json: &str,
+    ) -> VcxCoreResult<String>;
+
+    // returns request as JSON
+    async fn build_nym_request(
+        &self,
+        submitter_did: &str,
+        target_did: &str,
+        verkey: Option<&str>,
+        data: Option<&str>,
+        role: Option<&str>,
+    ) -> VcxCoreResult<String>;
+
     async fn publish_schema(
         &self,
         schema_json: &str,
@@ -124,4 +152,41 @@ pub trait BaseLedger: std::fmt::Debug + Send + Sync {
         rev_reg_entry_json: &str,
         submitter_did: &str,
     ) -> VcxCoreResult<()>;
+
+    // Typed variants of the getters above, parsing the ledger data into anoncreds objects.
+
+    async fn get_schema_object(&self, schema_id: &str, submitter_did: Option<&str>) -> VcxCoreResult<Schema> {
+        Schema::from_json(&self.get_schema(schema_id, submitter_did).await?)
+    }
+
+    async fn get_cred_def_object(
+        &self,
+        cred_def_id: &str,
+        submitter_did: Option<&str>,
+    ) -> VcxCoreResult<CredentialDefinition> {
+        CredentialDefinition::from_json(&self.get_cred_def(cred_def_id, submitter_did).await?)
+    }
+
+    async fn get_rev_reg_def_object(&self, rev_reg_id: &str) -> VcxCoreResult<RevocationRegistryDefinition> {
+        RevocationRegistryDefinition::from_json(&self.get_rev_reg_def_json(rev_reg_id).await?)
+    }
+
+    async fn get_rev_reg_delta_object(
+        &self,
+        rev_reg_id: &str,
+        from: Option<u64>,
+        to: Option<u64>,
+    ) -> VcxCoreResult<(String, RevocationRegistryDelta, u64)> {
+        let (id, rev_reg_delta_json, timestamp) = self.get_rev_reg_delta_json(rev_reg_id, from, to).await?;
+        Ok((id, RevocationRegistryDelta::from_json(&rev_reg_delta_json)?, timestamp))
+    }
+
+    async fn get_rev_reg_object(
+        &self,
+        rev_reg_id: &str,
+        timestamp: u64,
+    ) -> VcxCoreResult<(String, RevocationRegistry, u64)> {
+        let (id, rev_reg_json, timestamp) = self.get_rev_reg(rev_reg_id, timestamp).await?;
+        Ok((id, RevocationRegistry::from_json(&rev_reg_json)?, timestamp))
+    }
 }
@@ -0,0 +1,681 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+
+use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult};
+
+use super::base_ledger::BaseLedger;
+
+const DEFAULT_CACHE_CAPACITY: usize = 1000;
+const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(60);
+const NYM_TXN_TYPE: &str = "1";
+const REVOC_REG_ENTRY_TXN_TYPE: &str = "114";
+
+#[derive(Debug, Clone)]
+pub struct LedgerCacheConfig {
+    // maximal number of entries kept by each of the caches, least recently used entries are evicted first
+    pub capacity: usize,
+    // lifetime of entries which can change on the ledger (NYMs and revocation registry deltas)
+    pub ttl: Duration,
+}
+
+impl Default for LedgerCacheConfig {
+    fn default() -> Self {
+        LedgerCacheConfig {
+            capacity: DEFAULT_CACHE_CAPACITY,
+            ttl: DEFAULT_CACHE_TTL,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct CacheEntry<V> {
+    value: V,
+    expires_at: Option<Instant>,
+    last_used: u64,
+}
+
+/// Size-bounded LRU cache, entries inserted with a TTL are dropped once expired.
+#[derive(Debug)]
+struct LedgerCache<V> {
+    entries: HashMap<String, CacheEntry<V>>,
+    capacity: usize,
+    tick: u64,
+}
+
+impl<V: Clone> LedgerCache<V> {
+    fn new(capacity: usize) -> Self {
+        LedgerCache {
+            entries: HashMap::new(),
+            capacity,
+            tick: 0,
+        }
+    }
+
+    fn get(&mut self, key: &str) -> Option<V> {
+        let now = Instant::now();
+        if self
+            .entries
+            .get(key)?
+            .expires_at
+            .map_or(false, |expires_at| expires_at <= now)
+        {
+            self.entries.remove(key);
+            return None;
+        }
+
+        self.tick += 1;
+        let entry = self.entries.get_mut(key)?;
+        entry.last_used = self.tick;
+        Some(entry.value.clone())
+    }
+
+    fn insert(&mut self, key: String, value: V, ttl: Option<Duration>) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if !self.entries.contains_key(&key) && self.entries.len() >= self.capacity {
+            let now = Instant::now();
+            self.entries
+                .retain(|_, entry| entry.expires_at.map_or(true, |expires_at| expires_at > now));
+        }
+        if !self.entries.contains_key(&key) && self.entries.len() >= self.capacity {
+            let lru_key = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(lru_key) = lru_key {
+                self.entries.remove(&lru_key);
+            }
+        }
+
+        self.tick += 1;
+        let entry = CacheEntry {
+            value,
+            expires_at: ttl.map(|ttl| Instant::now() + ttl),
+            last_used: self.tick,
+        };
+        self.entries.insert(key, entry);
+    }
+
+    fn remove(&mut self, key: &str) {
+        self.entries.remove(key);
+    }
+
+    fn remove_prefixed(&mut self, prefix: &str) {
+        self.entries.retain(|key, _| !key.starts_with(prefix));
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+type RevRegDelta = (String, String, u64);
+
+/// Implementation of [BaseLedger] caching the reads of a wrapped ledger.
+/// Schemas, credential definitions and revocation registry definitions never change once written,
+/// so they are kept until evicted. NYMs and revocation registry deltas are only kept for the configured TTL.
+/// Publishing or endorsing a NYM or a revocation registry entry evicts the entries it changes.
+/// Failed reads are never cached. All other methods are passed through to the wrapped ledger.
+#[derive(Debug)]
+pub struct CachingLedger {
+    inner: Arc<dyn BaseLedger>,
+    ttl: Duration,
+    immutable: Mutex<LedgerCache<String>>,
+    nyms: Mutex<LedgerCache<String>>,
+    rev_reg_deltas: Mutex<LedgerCache<RevRegDelta>>,
+}
+
+impl CachingLedger {
+    pub fn new(inner: Arc<dyn BaseLedger>, config: LedgerCacheConfig) -> Self {
+        CachingLedger {
+            inner,
+            ttl: config.ttl,
+            immutable: Mutex::new(LedgerCache::new(config.capacity)),
+            nyms: Mutex::new(LedgerCache::new(config.capacity)),
+            rev_reg_deltas: Mutex::new(LedgerCache::new(config.capacity)),
+        }
+    }
+
+    fn lock<V>(cache: &Mutex<LedgerCache<V>>) -> VcxCoreResult<std::sync::MutexGuard<'_, LedgerCache<V>>> {
+        cache
+            .lock()
+            .map_err(|_| AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::InvalidState, "Ledger cache is poisoned"))
+    }
+
+    async fn get_immutable<F>(&self, key: String, fetch: F) -> VcxCoreResult<String>
+    where
+        F: std::future::Future<Output = VcxCoreResult<String>> + Send,
+    {
+        let cached = Self::lock(&self.immutable)?.get(&key);
+        if let Some(value) = cached {
+            trace!("CachingLedger::get_immutable >>> cache hit for {}", key);
+            return Ok(value);
+        }
+
+        let value = fetch.await?;
+        Self::lock(&self.immutable)?.insert(key, value.clone(), None);
+        Ok(value)
+    }
+
+    fn remove_rev_reg_deltas(&self, rev_reg_id: &str) -> VcxCoreResult<()> {
+        // deltas of any interval may now include the new entry
+        Self::lock(&self.rev_reg_deltas)?.remove_prefixed(&format!("{}:", rev_reg_id));
+        Ok(())
+    }
+
+    // evicts the cached NYM or revocation registry deltas the ledger request `request_json` writes, everything which
+    // may have changed if the request can't be parsed
+    fn remove_written(&self, request_json: &str) -> VcxCoreResult<()> {
+        let operation = serde_json::from_str::<serde_json::Value>(request_json)
+            .ok()
+            .map(|request| request["operation"].clone());
+        let txn_type = operation.as_ref().and_then(|operation| operation["type"].as_str());
+        match (operation.as_ref(), txn_type) {
+            (Some(operation), Some(NYM_TXN_TYPE)) => match operation["dest"].as_str() {
+                Some(did) => Self::lock(&self.nyms)?.remove(did),
+                None => Self::lock(&self.nyms)?.clear(),
+            },
+            (Some(operation), Some(REVOC_REG_ENTRY_TXN_TYPE)) => match operation["revocRegDefId"].as_str() {
+                Some(rev_reg_id) => self.remove_rev_reg_deltas(rev_reg_id)?,
+                None => Self::lock(&self.rev_reg_deltas)?.clear(),
+            },
+            (Some(_), Some(_)) => {}
+            _ => {
+                Self::lock(&self.nyms)?.clear();
+                Self::lock(&self.rev_reg_deltas)?.clear();
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl BaseLedger for CachingLedger {
+    async fn sign_and_submit_request(&self, submitter_did: &str, request_json: &str) -> VcxCoreResult<String> {
+        self.inner.sign_and_submit_request(submitter_did, request_json).await
+    }
+
+    async fn submit_request(&self, request_json: &str) -> VcxCoreResult<String> {
+        self.inner.submit_request(request_json).await
+    }
+
+    async fn endorse_transaction(&self, endorser_did: &str, request_json: &str) -> VcxCoreResult<()> {
+        let res = self.inner.endorse_transaction(endorser_did, request_json).await;
+        self.remove_written(request_json)?;
+        res
+    }
+
+    async fn set_endorser(&self, submitter_did: &str, request: &str, endorser: &str) -> VcxCoreResult<String> {
+        self.inner.set_endorser(submitter_did, request, endorser).await
+    }
+
+    async fn get_txn_author_agreement(&self) -> VcxCoreResult<String> {
+        self.inner.get_txn_author_agreement().await
+    }
+
+    async fn get_nym(&self, did: &str) -> VcxCoreResult<String> {
+        let cached = Self::lock(&self.nyms)?.get(did);
+        if let Some(nym) = cached {
+            trace!("CachingLedger::get_nym >>> cache hit for {}", did);
+            return Ok(nym);
+        }
+
+        let nym = self.inner.get_nym(did).await?;
+        Self::lock(&self.nyms)?.insert(did.to_string(), nym.clone(), Some(self.ttl));
+        Ok(nym)
+    }
+
+    async fn publish_nym(
+        &self,
+        submitter_did: &str,
+        target_did: &str,
+        verkey: Option<&str>,
+        data: Option<&str>,
+        role: Option<&str>,
+    ) -> VcxCoreResult<String> {
+        let res = self
+            .inner
+            .publish_nym(submitter_did, target_did, verkey, data, role)
+            .await;
+        Self::lock(&self.nyms)?.remove(target_did);
+        res
+    }
+
+    async fn get_schema(&self, schema_id: &str, submitter_did: Option<&str>) -> VcxCoreResult<String> {
+        self.get_immutable(
+            format!("schema:{}", schema_id),
+            self.inner.get_schema(schema_id, submitter_did),
+        )
+        .await
+    }
+
+    async fn get_cred_def(&self, cred_def_id: &str, submitter_did: Option<&str>) -> VcxCoreResult<String> {
+        self.get_immutable(
+            format!("cred_def:{}", cred_def_id),
+            self.inner.get_cred_def(cred_def_id, submitter_did),
+        )
+        .await
+    }
+
+    async fn get_attr(&self, target_did: &str, attr_name: &str) -> VcxCoreResult<String> {
+        self.inner.get_attr(target_did, attr_name).await
+    }
+
+    async fn add_attr(&self, target_did: &str, attrib_json: &str) -> VcxCoreResult<String> {
+        self.inner.add_attr(target_did, attrib_json).await
+    }
+
+    async fn get_rev_reg_def_json(&self, rev_reg_id: &str) -> VcxCoreResult<String> {
+        self.get_immutable(
+            format!("rev_reg_def:{}", rev_reg_id),
+            self.inner.get_rev_reg_def_json(rev_reg_id),
+        )
+        .await
+    }
+
+    async fn get_rev_reg_delta_json(
+        &self,
+        rev_reg_id: &str,
+        from: Option<u64>,
+        to: Option<u64>,
+    ) -> VcxCoreResult<(String, String, u64)> {
+        let key = format!("{}:{:?}:{:?}", rev_reg_id, from, to);
+        let cached = Self::lock(&self.rev_reg_deltas)?.get(&key);
+        if let Some(delta) = cached {
+            trace!("CachingLedger::get_rev_reg_delta_json >>> cache hit for {}", key);
+            return Ok(delta);
+        }
+
+        let delta = self.inner.get_rev_reg_delta_json(rev_reg_id, from, to).await?;
+        Self::lock(&self.rev_reg_deltas)?.insert(key, delta.clone(), Some(self.ttl));
+        Ok(delta)
+    }
+
+    async fn get_rev_reg(&self, rev_reg_id: &str, timestamp: u64) -> VcxCoreResult<(String, String, u64)> {
+        self.inner.get_rev_reg(rev_reg_id, timestamp).await
+    }
+
+    async fn get_ledger_txn(&self, seq_no: i32, submitter_did: Option<&str>) -> VcxCoreResult<String> {
+        self.inner.get_ledger_txn(seq_no, submitter_did).await
+    }
+
+    async fn build_schema_request(&self, submitter_did: &str, schema_json: &str) -> VcxCoreResult<String> {
+        self.inner.build_schema_request(submitter_did, schema_json).await
+    }
+
+    async fn build_cred_def_request(&self, submitter_did: &str, cred_def_json: &str) -> VcxCoreResult<String> {
+        self.inner.build_cred_def_request(submitter_did, cred_def_json).await
+    }
+
+    async fn build_rev_reg_def_request(&self, submitter_did: &str, rev_reg_def_json: &str) -> VcxCoreResult<String> {
+        self.inner
+            .build_rev_reg_def_request(submitter_did, rev_reg_def_json)
+            .await
+    }
+
+    async fn build_rev_reg_delta_request(
+        &self,
+        submitter_did: &str,
+        rev_reg_id: &str,
+        rev_reg_entry_json: &str,
+    ) -> VcxCoreResult<String> {
+        self.inner
+            .build_rev_reg_delta_request(submitter_did, rev_reg_id, rev_reg_entry_json)
+            .await
+    }
+
+    async fn build_nym_request(
+        &self,
+        submitter_did: &str,
+        target_did: &str,
+        verkey: Option<&str>,
+        data: Option<&str>,
+        role: Option<&str>,
+    ) -> VcxCoreResult<String> {
+        self.inner
+            .build_nym_request(submitter_did, target_did, verkey, data, role)
+            .await
+    }
+
+    async fn publish_schema(
+        &self,
+        schema_json: &str,
+        submitter_did: &str,
+        endorser_did: Option<String>,
+    ) -> VcxCoreResult<()> {
+        self.inner
+            .publish_schema(schema_json, submitter_did, endorser_did)
+            .await
+    }
+
+    async fn publish_cred_def(&self, cred_def_json: &str, submitter_did: &str) -> VcxCoreResult<()> {
+        self.inner.publish_cred_def(cred_def_json, submitter_did).await
+    }
+
+    async fn publish_rev_reg_def(&self, rev_reg_def: &str, submitter_did: &str) -> VcxCoreResult<()> {
+        self.inner.publish_rev_reg_def(rev_reg_def, submitter_did).await
+    }
+
+    async fn publish_rev_reg_delta(
+        &self,
+        rev_reg_id: &str,
+        rev_reg_entry_json: &str,
+        submitter_did: &str,
+    ) -> VcxCoreResult<()> {
+        let res = self
+            .inner
+            .publish_rev_reg_delta(rev_reg_id, rev_reg_entry_json, submitter_did)
+            .await;
+        self.remove_rev_reg_deltas(rev_reg_id)?;
+        res
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct CountingLedger {
+        reads: AtomicUsize,
+    }
+
+    fn unimplemented_method_err(method_name: &str) -> AriesVcxCoreError {
+        AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::UnimplementedFeature,
+            format!("method '{}' is not implemented by the counting ledger", method_name),
+        )
+    }
+
+    impl CountingLedger {
+        fn read(&self, value: &str) -> VcxCoreResult<String> {
+            self.reads.fetch_add(1, Ordering::SeqCst);
+            if value.starts_with("missing") {
+                return Err(AriesVcxCoreError::from_msg(
+                    AriesVcxCoreErrorKind::LedgerItemNotFound,
+                    "not found",
+                ));
+            }
+            Ok(value.to_string())
+        }
+    }
+
+    #[async_trait]
+    impl BaseLedger for CountingLedger {
+        async fn sign_and_submit_request(&self, _submitter_did: &str, _request_json: &str) -> VcxCoreResult<String> {
+            Err(unimplemented_method_err("sign_and_submit_request"))
+        }
+
+        async fn submit_request(&self, _request_json: &str) -> VcxCoreResult<String> {
+            Err(unimplemented_method_err("submit_request"))
+        }
+
+        async fn endorse_transaction(&self, _endorser_did: &str, _request_json: &str) -> VcxCoreResult<()> {
+            Ok(())
+        }
+
+        async fn set_endorser(&self, _submitter_did: &str, _request: &str, _endorser: &str) -> VcxCoreResult<String> {
+            Err(unimplemented_method_err("set_endorser"))
+        }
+
+        async fn get_txn_author_agreement(&self) -> VcxCoreResult<String> {
+            Err(unimplemented_method_err("get_txn_author_agreement"))
+        }
+
+        async fn get_nym(&self, did: &str) -> VcxCoreResult<String> {
+            self.read(did)
+        }
+
+        async fn publish_nym(
+            &self,
+            _submitter_did: &str,
+            target_did: &str,
+            _verkey: Option<&str>,
+            _data: Option<&str>,
+            _role: Option<&str>,
+        ) -> VcxCoreResult<String> {
+            Ok(target_did.to_string())
+        }
+
+        async fn get_schema(&self, schema_id: &str, _submitter_did: Option<&str>) -> VcxCoreResult<String> {
+            self.read(schema_id)
+        }
+
+        async fn get_cred_def(&self, cred_def_id: &str, _submitter_did: Option<&str>) -> VcxCoreResult<String> {
+            self.read(cred_def_id)
+        }
+
+        async fn get_attr(&self, _target_did: &str, _attr_name: &str) -> VcxCoreResult<String> {
+            Err(unimplemented_method_err("get_attr"))
+        }
+
+        async fn add_attr(&self, _target_did: &str, _attrib_json: &str) -> VcxCoreResult<String> {
+            Err(unimplemented_method_err("add_attr"))
+        }
+
+        async fn get_rev_reg_def_json(&self, rev_reg_id: &str) -> VcxCoreResult<String> {
+            self.read(rev_reg_id)
+        }
+
+        async fn get_rev_reg_delta_json(
+            &self,
+            rev_reg_id: &str,
+            _from: Option<u64>,
+            to: Option<u64>,
+        ) -> VcxCoreResult<(String, String, u64)> {
+            let delta = self.read(rev_reg_id)?;
+            Ok((rev_reg_id.to_string(), delta, to.unwrap_or_default()))
+        }
+
+        async fn get_rev_reg(&self, _rev_reg_id: &str, _timestamp: u64) -> VcxCoreResult<(String, String, u64)> {
+            Err(unimplemented_method_err("get_rev_reg"))
+        }
+
+        async fn get_ledger_txn(&self, _seq_no: i32, _submitter_did: Option<&str>) -> VcxCoreResult<String> {
+            Err(unimplemented_method_err("get_ledger_txn"))
+        }
+
+        async fn build_schema_request(&self, _submitter_did: &str, _schema_json: &str) -> VcxCoreResult<String> {
+            Err(unimplemented_method_err("build_schema_request"))
+        }
+
+        async fn build_cred_def_request(&self, _submitter_did: &str, _cred_def_json: &str) -> VcxCoreResult<String> {
+            Err(unimplemented_method_err("build_cred_def_request"))
+        }
+
+        async fn build_rev_reg_def_request(
+            &self,
+            _submitter_did: &str,
+            _rev_reg_def_json: &str,
+        ) -> VcxCoreResult<String> {
+            Err(unimplemented_method_err("build_rev_reg_def_request"))
+        }
+
+        async fn build_rev_reg_delta_request(
+            &self,
+            _submitter_did: &str,
+            _rev_reg_id: &str,
+            _rev_reg_entry_json: &str,
+        ) -> VcxCoreResult<String> {
+            Err(unimplemented_method_err("build_rev_reg_delta_request"))
+        }
+
+        async fn build_nym_request(
+            &self,
+            _submitter_did: &str,
+            _target_did: &str,
+            _verkey: Option<&str>,
+            _data: Option<&str>,
+            _role: Option<&str>,
+        ) -> VcxCoreResult<String> {
+            Err(unimplemented_method_err("build_nym_request"))
+        }
+
+        async fn publish_schema(
+            &self,
+            _schema_json: &str,
+            _submitter_did: &str,
+            _endorser_did: Option<String>,
+        ) -> VcxCoreResult<()> {
+            Err(unimplemented_method_err("publish_schema"))
+        }
+
+        async fn publish_cred_def(&self, _cred_def_json: &str, _submitter_did: &str) -> VcxCoreResult<()> {
+            Err(unimplemented_method_err("publish_cred_def"))
+        }
+
+        async fn publish_rev_reg_def(&self, _rev_reg_def: &str, _submitter_did: &str) -> VcxCoreResult<()> {
+            Err(unimplemented_method_err("publish_rev_reg_def"))
+        }
+
+        async fn publish_rev_reg_delta(
+            &self,
+            _rev_reg_id: &str,
+            _rev_reg_entry_json: &str,
+            _submitter_did: &str,
+        ) -> VcxCoreResult<()> {
+            Ok(())
+        }
+    }
+
+    fn setup(config: LedgerCacheConfig) -> (Arc<CountingLedger>, CachingLedger) {
+        let inner = Arc::new(CountingLedger::default());
+        let ledger = CachingLedger::new(inner.clone(), config);
+        (inner, ledger)
+    }
+
+    #[tokio::test]
+    async fn test_caching_ledger_caches_immutable_objects() {
+        let (inner, ledger) = setup(LedgerCacheConfig::default());
+
+        for _ in 0..3 {
+            assert_eq!(ledger.get_schema("schema", None).await.unwrap(), "schema");
+            assert_eq!(ledger.get_cred_def("cred_def", None).await.unwrap(), "cred_def");
+            assert_eq!(ledger.get_rev_reg_def_json("rev_reg_def").await.unwrap(), "rev_reg_def");
+        }
+
+        assert_eq!(inner.reads.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_caching_ledger_does_not_cache_errors() {
+        let (inner, ledger) = setup(LedgerCacheConfig::default());
+
+        ledger.get_schema("missing_schema", None).await.unwrap_err();
+        ledger.get_schema("missing_schema", None).await.unwrap_err();
+
+        assert_eq!(inner.reads.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_caching_ledger_expires_mutable_objects() {
+        let config = LedgerCacheConfig {
+            ttl: Duration::from_millis(50),
+            ..Default::default()
+        };
+        let (inner, ledger) = setup(config);
+
+        ledger.get_nym("did").await.unwrap();
+        ledger.get_rev_reg_delta_json("rev_reg", None, Some(1)).await.unwrap();
+        ledger.get_nym("did").await.unwrap();
+        ledger.get_rev_reg_delta_json("rev_reg", None, Some(1)).await.unwrap();
+        assert_eq!(inner.reads.load(Ordering::SeqCst), 2);
+
+        // a different interval is a different delta
+        ledger.get_rev_reg_delta_json("rev_reg", None, Some(2)).await.unwrap();
+        assert_eq!(inner.reads.load(Ordering::SeqCst), 3);
+
+        std::thread::sleep(Duration::from_millis(100));
+        ledger.get_nym("did").await.unwrap();
+        ledger.get_rev_reg_delta_json("rev_reg", None, Some(1)).await.unwrap();
+        assert_eq!(inner.reads.load(Ordering::SeqCst), 5);
+    }
+
+    #[tokio::test]
+    async fn test_caching_ledger_publish_nym_invalidates_nym() {
+        let (inner, ledger) = setup(LedgerCacheConfig::default());
+
+        ledger.get_nym("did").await.unwrap();
+        ledger.publish_nym("submitter", "did", None, None, None).await.unwrap();
+        ledger.get_nym("did").await.unwrap();
+
+        assert_eq!(inner.reads.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_caching_ledger_publish_rev_reg_delta_invalidates_deltas() {
+        let (inner, ledger) = setup(LedgerCacheConfig::default());
+
+        ledger.get_rev_reg_delta_json("rev_reg", None, Some(1)).await.unwrap();
+        ledger.get_rev_reg_delta_json("rev_reg", Some(1), None).await.unwrap();
+        ledger.get_rev_reg_delta_json("rev_reg_2", None, None).await.unwrap();
+        ledger
+            .publish_rev_reg_delta("rev_reg", "{}", "submitter")
+            .await
+            .unwrap();
+        ledger.get_rev_reg_delta_json("rev_reg", None, Some(1)).await.unwrap();
+        ledger.get_rev_reg_delta_json("rev_reg", Some(1), None).await.unwrap();
+        ledger.get_rev_reg_delta_json("rev_reg_2", None, None).await.unwrap();
+
+        assert_eq!(inner.reads.load(Ordering::SeqCst), 5);
+    }
+
+    #[tokio::test]
+    async fn test_caching_ledger_endorse_transaction_invalidates_written_objects() {
+        let (inner, ledger) = setup(LedgerCacheConfig::default());
+
+        ledger.get_nym("did").await.unwrap();
+        ledger.get_nym("did_2").await.unwrap();
+        ledger.get_rev_reg_delta_json("rev_reg", None, Some(1)).await.unwrap();
+        ledger.get_rev_reg_delta_json("rev_reg_2", None, None).await.unwrap();
+        let nym_request = json!({ "operation": { "type": "1", "dest": "did" } }).to_string();
+        ledger.endorse_transaction("endorser", &nym_request).await.unwrap();
+        let rev_reg_entry_request = json!({ "operation": { "type": "114", "revocRegDefId": "rev_reg" } }).to_string();
+        ledger
+            .endorse_transaction("endorser", &rev_reg_entry_request)
+            .await
+            .unwrap();
+        ledger.get_nym("did").await.unwrap();
+        ledger.get_nym("did_2").await.unwrap();
+        ledger.get_rev_reg_delta_json("rev_reg", None, Some(1)).await.unwrap();
+        ledger.get_rev_reg_delta_json("rev_reg_2", None, None).await.unwrap();
+        assert_eq!(inner.reads.load(Ordering::SeqCst), 6);
+
+        // a request which can't be parsed may have written anything
+        ledger.endorse_transaction("endorser", "not a request").await.unwrap();
+        ledger.get_nym("did_2").await.unwrap();
+        ledger.get_rev_reg_delta_json("rev_reg_2", None, None).await.unwrap();
+        assert_eq!(inner.reads.load(Ordering::SeqCst), 8);
+    }
+
+    #[test]
+    fn test_ledger_cache_evicts_least_recently_used() {
+        let mut cache = LedgerCache::new(2);
+
+        cache.insert("a".to_string(), 1, None);
+        cache.insert("b".to_string(), 2, None);
+        assert_eq!(cache.get("a"), Some(1));
+
+        cache.insert("c".to_string(), 3, None);
+        assert_eq!(cache.get("a"), Some(1));
+        assert_eq!(cache.get("b"), None);
+        assert_eq!(cache.get("c"), Some(3));
+    }
+
+    #[test]
+    fn test_ledger_cache_with_zero_capacity_is_disabled() {
+        let mut cache = LedgerCache::new(0);
+
+        cache.insert("a".to_string(), 1, None);
+        assert_eq!(cache.get("a"), None);
+    }
+}
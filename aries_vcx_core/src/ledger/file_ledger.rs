@@ -0,0 +1,664 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use time::OffsetDateTime;
+
+use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult};
+
+use super::base_ledger::BaseLedger;
+
+const NYM_TXN_TYPE: &str = "105";
+const ATTRIB_TXN_TYPE: &str = "104";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct NymTxn {
+    identifier: String,
+    dest: String,
+    verkey: Option<String>,
+    role: Option<String>,
+    alias: Option<String>,
+    seq_no: u64,
+    txn_time: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct AttribTxn {
+    value: Value,
+    seq_no: u64,
+    txn_time: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RevRegEntryTxn {
+    accum: Value,
+    #[serde(default)]
+    issued: Vec<u32>,
+    #[serde(default)]
+    revoked: Vec<u32>,
+    txn_time: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct LedgerStore {
+    last_seq_no: u64,
+    nyms: HashMap<String, NymTxn>,
+    // target DID -> attribute name -> attribute
+    attribs: HashMap<String, HashMap<String, AttribTxn>>,
+    schemas: HashMap<String, Value>,
+    cred_defs: HashMap<String, Value>,
+    rev_reg_defs: HashMap<String, Value>,
+    // ordered by transaction time
+    rev_reg_entries: HashMap<String, Vec<RevRegEntryTxn>>,
+}
+
+impl LedgerStore {
+    fn next_seq_no(&mut self) -> u64 {
+        self.last_seq_no += 1;
+        self.last_seq_no
+    }
+}
+
+/// Implementation of [BaseLedger] serving ledger objects from a local JSON file instead of an indy pool,
+/// useful for air-gapped and test environments.
+/// The write methods append to the same file, so objects published through the ledger can be read back
+/// by any other [FileLedger] opened on the file later. Get responses of NYMs and attributes mimic the
+/// replies of an indy pool so they can be consumed by the same parsers, e.g. the `did_resolver_sov` resolver.
+/// Endorsement, raw requests and transaction author agreements are not supported.
+#[derive(Debug)]
+pub struct FileLedger {
+    path: Option<PathBuf>,
+    store: RwLock<LedgerStore>,
+}
+
+impl FileLedger {
+    /// Opens the ledger stored at `path`, the file is created on the first write if it does not exist.
+    pub fn open(path: impl AsRef<Path>) -> VcxCoreResult<Self> {
+        let path = path.as_ref().to_path_buf();
+        let store = if path.exists() {
+            let content = fs::read_to_string(&path).map_err(|err| {
+                AriesVcxCoreError::from_msg(
+                    AriesVcxCoreErrorKind::IOError,
+                    format!("Cannot read ledger file {}: {}", path.display(), err),
+                )
+            })?;
+            serde_json::from_str(&content).map_err(|err| {
+                AriesVcxCoreError::from_msg(
+                    AriesVcxCoreErrorKind::InvalidJson,
+                    format!("Cannot deserialize ledger file {}: {}", path.display(), err),
+                )
+            })?
+        } else {
+            LedgerStore::default()
+        };
+
+        Ok(FileLedger {
+            path: Some(path),
+            store: RwLock::new(store),
+        })
+    }
+
+    /// Creates an empty ledger which is never persisted.
+    pub fn in_memory() -> Self {
+        FileLedger {
+            path: None,
+            store: RwLock::new(LedgerStore::default()),
+        }
+    }
+
+    fn read<T>(&self, f: impl FnOnce(&LedgerStore) -> VcxCoreResult<T>) -> VcxCoreResult<T> {
+        f(&*self.store.read()?)
+    }
+
+    // applies `f` to the store and persists the result, the store is left untouched when `f` fails
+    fn write<T>(&self, f: impl FnOnce(&mut LedgerStore) -> VcxCoreResult<T>) -> VcxCoreResult<T> {
+        let mut store = self.store.write()?;
+        let backup = store.clone();
+
+        let res = f(&mut store).and_then(|res| self.persist(&store).map(|_| res));
+        if res.is_err() {
+            *store = backup;
+        }
+        res
+    }
+
+    fn persist(&self, store: &LedgerStore) -> VcxCoreResult<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        let io_err = |err: std::io::Error| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::IOError,
+                format!("Cannot write ledger file {}: {}", path.display(), err),
+            )
+        };
+
+        // write into a temporary file first, so the ledger file is never left half-written
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, serde_json::to_vec_pretty(store)?).map_err(io_err)?;
+        fs::rename(&tmp_path, path).map_err(io_err)
+    }
+
+    fn get_object(objects: &HashMap<String, Value>, id: &str, object_name: &str) -> VcxCoreResult<String> {
+        let object = objects.get(id).ok_or_else(|| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::LedgerItemNotFound,
+                format!("{} {} not found on the ledger", object_name, id),
+            )
+        })?;
+        Ok(serde_json::to_string(object)?)
+    }
+
+    // inserts a ledger object keyed by its "id" field, ledger objects can not be overwritten
+    fn insert_object(objects: &mut HashMap<String, Value>, object_json: &str, object_name: &str) -> VcxCoreResult<()> {
+        let object: Value = serde_json::from_str(object_json)?;
+        let id = object["id"].as_str().ok_or_else(|| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidJson,
+                format!("{} is missing the \"id\" field: {}", object_name, object_json),
+            )
+        })?;
+
+        if objects.contains_key(id) {
+            return Err(AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidLedgerResponse,
+                format!("{} {} is already written on the ledger", object_name, id),
+            ));
+        }
+        objects.insert(id.to_string(), object);
+        Ok(())
+    }
+
+    fn rev_reg_entries<'a>(store: &'a LedgerStore, rev_reg_id: &str) -> VcxCoreResult<&'a [RevRegEntryTxn]> {
+        store
+            .rev_reg_entries
+            .get(rev_reg_id)
+            .map(Vec::as_slice)
+            .filter(|entries| !entries.is_empty())
+            .ok_or_else(|| {
+                AriesVcxCoreError::from_msg(
+                    AriesVcxCoreErrorKind::LedgerItemNotFound,
+                    format!("No entries of revocation registry {} found on the ledger", rev_reg_id),
+                )
+            })
+    }
+}
+
+#[async_trait]
+impl BaseLedger for FileLedger {
+    async fn sign_and_submit_request(&self, submitter_did: &str, request_json: &str) -> VcxCoreResult<String> {
+        let _ = (submitter_did, request_json);
+        Err(unsupported_method_err("sign_and_submit_request"))
+    }
+
+    async fn submit_request(&self, request_json: &str) -> VcxCoreResult<String> {
+        let _ = request_json;
+        Err(unsupported_method_err("submit_request"))
+    }
+
+    async fn endorse_transaction(&self, endorser_did: &str, request_json: &str) -> VcxCoreResult<()> {
+        let _ = (endorser_did, request_json);
+        Err(unsupported_method_err("endorse_transaction"))
+    }
+
+    async fn set_endorser(&self, submitter_did: &str, request: &str, endorser: &str) -> VcxCoreResult<String> {
+        let _ = (submitter_did, request, endorser);
+        Err(unsupported_method_err("set_endorser"))
+    }
+
+    async fn get_txn_author_agreement(&self) -> VcxCoreResult<String> {
+        Err(unsupported_method_err("get_txn_author_agreement"))
+    }
+
+    async fn get_nym(&self, did: &str) -> VcxCoreResult<String> {
+        self.read(|store| {
+            let nym = store.nyms.get(did);
+            let data = nym
+                .map(|nym| {
+                    serde_json::to_string(&json!({
+                        "identifier": nym.identifier,
+                        "dest": nym.dest,
+                        "verkey": nym.verkey,
+                        "role": nym.role,
+                        "alias": nym.alias,
+                        "seqNo": nym.seq_no,
+                        "txnTime": nym.txn_time,
+                    }))
+                })
+                .transpose()?;
+
+            Ok(reply(json!({
+                "type": NYM_TXN_TYPE,
+                "dest": did,
+                "data": data,
+                "seqNo": nym.map(|nym| nym.seq_no),
+                "txnTime": nym.map(|nym| nym.txn_time),
+            })))
+        })
+    }
+
+    async fn publish_nym(
+        &self,
+        submitter_did: &str,
+        target_did: &str,
+        verkey: Option<&str>,
+        data: Option<&str>,
+        role: Option<&str>,
+    ) -> VcxCoreResult<String> {
+        self.write(|store| {
+            let seq_no = store.next_seq_no();
+            let previous = store.nyms.get(target_did);
+
+            let nym = NymTxn {
+                identifier: submitter_did.to_string(),
+                dest: target_did.to_string(),
+                verkey: verkey
+                    .map(String::from)
+                    .or_else(|| previous.and_then(|nym| nym.verkey.clone())),
+                role: role
+                    .map(String::from)
+                    .or_else(|| previous.and_then(|nym| nym.role.clone())),
+                alias: data
+                    .map(String::from)
+                    .or_else(|| previous.and_then(|nym| nym.alias.clone())),
+                seq_no,
+                txn_time: current_epoch_time(),
+            };
+            let result = json!({
+                "type": "1",
+                "dest": nym.dest,
+                "verkey": nym.verkey,
+                "seqNo": nym.seq_no,
+                "txnTime": nym.txn_time,
+            });
+            store.nyms.insert(target_did.to_string(), nym);
+
+            Ok(reply(result))
+        })
+    }
+
+    async fn get_schema(&self, schema_id: &str, submitter_did: Option<&str>) -> VcxCoreResult<String> {
+        let _ = submitter_did;
+        self.read(|store| Self::get_object(&store.schemas, schema_id, "Schema"))
+    }
+
+    async fn get_cred_def(&self, cred_def_id: &str, submitter_did: Option<&str>) -> VcxCoreResult<String> {
+        let _ = submitter_did;
+        self.read(|store| Self::get_object(&store.cred_defs, cred_def_id, "Credential definition"))
+    }
+
+    async fn get_attr(&self, target_did: &str, attr_name: &str) -> VcxCoreResult<String> {
+        self.read(|store| {
+            let attrib = store.attribs.get(target_did).and_then(|attribs| attribs.get(attr_name));
+            let data = attrib
+                .map(|attrib| serde_json::to_string(&json!({ attr_name: attrib.value })))
+                .transpose()?;
+
+            Ok(reply(json!({
+                "type": ATTRIB_TXN_TYPE,
+                "dest": target_did,
+                "raw": attr_name,
+                "data": data,
+                "seqNo": attrib.map(|attrib| attrib.seq_no),
+                "txnTime": attrib.map(|attrib| attrib.txn_time),
+            })))
+        })
+    }
+
+    async fn add_attr(&self, target_did: &str, attrib_json: &str) -> VcxCoreResult<String> {
+        let attribs: HashMap<String, Value> = serde_json::from_str(attrib_json)?;
+
+        self.write(|store| {
+            let seq_no = store.next_seq_no();
+            let txn_time = current_epoch_time();
+
+            let did_attribs = store.attribs.entry(target_did.to_string()).or_default();
+            for (name, value) in attribs {
+                did_attribs.insert(
+                    name,
+                    AttribTxn {
+                        value,
+                        seq_no,
+                        txn_time,
+                    },
+                );
+            }
+
+            Ok(reply(json!({
+                "type": "100",
+                "dest": target_did,
+                "seqNo": seq_no,
+                "txnTime": txn_time,
+            })))
+        })
+    }
+
+    async fn get_rev_reg_def_json(&self, rev_reg_id: &str) -> VcxCoreResult<String> {
+        self.read(|store| Self::get_object(&store.rev_reg_defs, rev_reg_id, "Revocation registry definition"))
+    }
+
+    async fn get_rev_reg_delta_json(
+        &self,
+        rev_reg_id: &str,
+        from: Option<u64>,
+        to: Option<u64>,
+    ) -> VcxCoreResult<(String, String, u64)> {
+        self.read(|store| {
+            let entries = Self::rev_reg_entries(store, rev_reg_id)?;
+            let to = to.unwrap_or_else(current_epoch_time);
+
+            let accum_to = entries.iter().rev().find(|entry| entry.txn_time <= to).ok_or_else(|| {
+                AriesVcxCoreError::from_msg(
+                    AriesVcxCoreErrorKind::LedgerItemNotFound,
+                    format!("Revocation registry {} has no entries before {}", rev_reg_id, to),
+                )
+            })?;
+            let accum_from = from.and_then(|from| entries.iter().rev().find(|entry| entry.txn_time <= from));
+
+            let mut issued = BTreeSet::new();
+            let mut revoked = BTreeSet::new();
+            for entry in entries
+                .iter()
+                .filter(|entry| from.map_or(true, |from| entry.txn_time > from) && entry.txn_time <= to)
+            {
+                for idx in &entry.issued {
+                    revoked.remove(idx);
+                    issued.insert(*idx);
+                }
+                for idx in &entry.revoked {
+                    issued.remove(idx);
+                    revoked.insert(*idx);
+                }
+            }
+
+            let mut delta_value = json!({
+                "accum": accum_to.accum,
+                "issued": issued,
+                "revoked": revoked,
+            });
+            if let Some(accum_from) = accum_from {
+                delta_value["prevAccum"] = accum_from.accum.clone();
+            }
+            let reg_delta = json!({"ver": "1.0", "value": delta_value});
+
+            Ok((
+                rev_reg_id.to_string(),
+                serde_json::to_string(&reg_delta)?,
+                accum_to.txn_time,
+            ))
+        })
+    }
+
+    async fn get_rev_reg(&self, rev_reg_id: &str, timestamp: u64) -> VcxCoreResult<(String, String, u64)> {
+        self.read(|store| {
+            let entry = Self::rev_reg_entries(store, rev_reg_id)?
+                .iter()
+                .rev()
+                .find(|entry| entry.txn_time <= timestamp)
+                .ok_or_else(|| {
+                    AriesVcxCoreError::from_msg(
+                        AriesVcxCoreErrorKind::LedgerItemNotFound,
+                        format!("Revocation registry {} has no entries before {}", rev_reg_id, timestamp),
+                    )
+                })?;
+            let rev_reg = json!({"ver": "1.0", "value": {"accum": entry.accum}});
+
+            Ok((rev_reg_id.to_string(), serde_json::to_string(&rev_reg)?, entry.txn_time))
+        })
+    }
+
+    async fn get_ledger_txn(&self, seq_no: i32, submitter_did: Option<&str>) -> VcxCoreResult<String> {
+        let _ = (seq_no, submitter_did);
+        Err(unsupported_method_err("get_ledger_txn"))
+    }
+
+    async fn build_schema_request(&self, submitter_did: &str, schema_json: &str) -> VcxCoreResult<String> {
+        let _ = (submitter_did, schema_json);
+        Err(unsupported_method_err("build_schema_request"))
+    }
+
+    async fn build_cred_def_request(&self, submitter_did: &str, cred_def_json: &str) -> VcxCoreResult<String> {
+        let _ = (submitter_did, cred_def_json);
+        Err(unsupported_method_err("build_cred_def_request"))
+    }
+
+    async fn build_rev_reg_def_request(&self, submitter_did: &str, rev_reg_def_json: &str) -> VcxCoreResult<String> {
+        let _ = (submitter_did, rev_reg_def_json);
+        Err(unsupported_method_err("build_rev_reg_def_request"))
+    }
+
+    async fn build_rev_reg_delta_request(
+        &self,
+        submitter_did: &str,
+        rev_reg_id: &str,
+        rev_reg_entry_json: &str,
+    ) -> VcxCoreResult<String> {
+        let _ = (submitter_did, rev_reg_id, rev_reg_entry_json);
+        Err(unsupported_method_err("build_rev_reg_delta_request"))
+    }
+
+    async fn build_nym_request(
+        &self,
+        submitter_did: &str,
+        target_did: &str,
+        verkey: Option<&str>,
+        data: Option<&str>,
+        role: Option<&str>,
+    ) -> VcxCoreResult<String> {
+        let _ = (submitter_did, target_did, verkey, data, role);
+        Err(unsupported_method_err("build_nym_request"))
+    }
+
+    async fn publish_schema(
+        &self,
+        schema_json: &str,
+        submitter_did: &str,
+        endorser_did: Option<String>,
+    ) -> VcxCoreResult<()> {
+        let _ = submitter_did;
+        if endorser_did.is_some() {
+            return Err(unsupported_method_err("publish_schema with endorser"));
+        }
+
+        let mut schema: Value = serde_json::from_str(schema_json)?;
+        self.write(|store| {
+            // the sequence number is referenced by the IDs of credential definitions created for the schema
+            schema["seqNo"] = json!(store.next_seq_no());
+            Self::insert_object(&mut store.schemas, &serde_json::to_string(&schema)?, "Schema")
+        })
+    }
+
+    async fn publish_cred_def(&self, cred_def_json: &str, submitter_did: &str) -> VcxCoreResult<()> {
+        let _ = submitter_did;
+        self.write(|store| {
+            store.next_seq_no();
+            Self::insert_object(&mut store.cred_defs, cred_def_json, "Credential definition")
+        })
+    }
+
+    async fn publish_rev_reg_def(&self, rev_reg_def: &str, submitter_did: &str) -> VcxCoreResult<()> {
+        let _ = submitter_did;
+        self.write(|store| {
+            store.next_seq_no();
+            Self::insert_object(&mut store.rev_reg_defs, rev_reg_def, "Revocation registry definition")
+        })
+    }
+
+    async fn publish_rev_reg_delta(
+        &self,
+        rev_reg_id: &str,
+        rev_reg_entry_json: &str,
+        submitter_did: &str,
+    ) -> VcxCoreResult<()> {
+        let _ = submitter_did;
+        let entry: Value = serde_json::from_str(rev_reg_entry_json)?;
+        let mut entry_value = entry["value"].clone();
+        entry_value["txn_time"] = json!(current_epoch_time());
+        let entry: RevRegEntryTxn = serde_json::from_value(entry_value).map_err(|err| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidJson,
+                format!("Cannot deserialize revocation registry entry: {}", err),
+            )
+        })?;
+
+        self.write(|store| {
+            if !store.rev_reg_defs.contains_key(rev_reg_id) {
+                return Err(AriesVcxCoreError::from_msg(
+                    AriesVcxCoreErrorKind::LedgerItemNotFound,
+                    format!("Revocation registry definition {} not found on the ledger", rev_reg_id),
+                ));
+            }
+            store.next_seq_no();
+            store
+                .rev_reg_entries
+                .entry(rev_reg_id.to_string())
+                .or_default()
+                .push(entry);
+            Ok(())
+        })
+    }
+}
+
+// wraps the result of a transaction into a reply, as received from an indy pool
+fn reply(result: Value) -> String {
+    json!({"op": "REPLY", "result": result}).to_string()
+}
+
+fn unsupported_method_err(method_name: &str) -> AriesVcxCoreError {
+    AriesVcxCoreError::from_msg(
+        AriesVcxCoreErrorKind::UnimplementedFeature,
+        format!("method '{}' is not supported by the file ledger", method_name),
+    )
+}
+
+fn current_epoch_time() -> u64 {
+    OffsetDateTime::now_utc().unix_timestamp() as u64
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use super::*;
+
+    const DID: &str = "V4SGRU86Z58d6TV7PBUe6f";
+    const VERKEY: &str = "GJ1SzoWzavQYfNL9XkaJdrQejfztN4XqdsiV4ct3LXKL";
+    const SCHEMA_ID: &str = "V4SGRU86Z58d6TV7PBUe6f:2:test-schema:1.0";
+    const REV_REG_ID: &str = "V4SGRU86Z58d6TV7PBUe6f:4:V4SGRU86Z58d6TV7PBUe6f:3:CL:1:tag:CL_ACCUM:tag";
+
+    fn schema_json() -> String {
+        json!({
+            "ver": "1.0",
+            "id": SCHEMA_ID,
+            "name": "test-schema",
+            "version": "1.0",
+            "attrNames": ["name", "age"],
+            "seqNo": null
+        })
+        .to_string()
+    }
+
+    fn result_data(response: &str) -> Value {
+        let response: Value = serde_json::from_str(response).unwrap();
+        assert_eq!(response["op"], "REPLY");
+        serde_json::from_str(response["result"]["data"].as_str().unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_file_ledger_publish_and_get_schema() {
+        let ledger = FileLedger::in_memory();
+
+        ledger.publish_schema(&schema_json(), DID, None).await.unwrap();
+        let schema: Value = serde_json::from_str(&ledger.get_schema(SCHEMA_ID, None).await.unwrap()).unwrap();
+
+        assert_eq!(schema["id"], SCHEMA_ID);
+        assert_eq!(schema["seqNo"], 1);
+        assert_eq!(
+            ledger
+                .publish_schema(&schema_json(), DID, None)
+                .await
+                .unwrap_err()
+                .kind(),
+            AriesVcxCoreErrorKind::InvalidLedgerResponse
+        );
+        assert_eq!(
+            ledger.get_cred_def("unknown", None).await.unwrap_err().kind(),
+            AriesVcxCoreErrorKind::LedgerItemNotFound
+        );
+    }
+
+    #[tokio::test]
+    async fn test_file_ledger_nym_and_attr_responses() {
+        let ledger = FileLedger::in_memory();
+
+        let nym = ledger.get_nym(DID).await.unwrap();
+        let nym: Value = serde_json::from_str(&nym).unwrap();
+        assert!(nym["result"]["data"].is_null());
+
+        ledger.publish_nym(DID, DID, Some(VERKEY), None, None).await.unwrap();
+        ledger
+            .add_attr(
+                DID,
+                &json!({"endpoint": {"endpoint": "http://localhost:8080"}}).to_string(),
+            )
+            .await
+            .unwrap();
+
+        let nym = result_data(&ledger.get_nym(DID).await.unwrap());
+        assert_eq!(nym["verkey"], VERKEY);
+        let attr = result_data(&ledger.get_attr(DID, "endpoint").await.unwrap());
+        assert_eq!(attr["endpoint"]["endpoint"], "http://localhost:8080");
+    }
+
+    #[tokio::test]
+    async fn test_file_ledger_rev_reg_delta_merges_entries() {
+        let ledger = FileLedger::in_memory();
+        ledger
+            .publish_rev_reg_def(&json!({"ver": "1.0", "id": REV_REG_ID}).to_string(), DID)
+            .await
+            .unwrap();
+
+        let entries = [
+            json!({"ver": "1.0", "value": {"accum": "accum_1"}}),
+            json!({"ver": "1.0", "value": {"prevAccum": "accum_1", "accum": "accum_2", "revoked": [1, 2]}}),
+            json!({"ver": "1.0", "value": {"prevAccum": "accum_2", "accum": "accum_3", "issued": [2]}}),
+        ];
+        for entry in entries {
+            ledger
+                .publish_rev_reg_delta(REV_REG_ID, &entry.to_string(), DID)
+                .await
+                .unwrap();
+        }
+
+        let (id, delta, timestamp) = ledger.get_rev_reg_delta_json(REV_REG_ID, None, None).await.unwrap();
+        let delta: Value = serde_json::from_str(&delta).unwrap();
+        assert_eq!(id, REV_REG_ID);
+        assert_eq!(delta["value"]["accum"], "accum_3");
+        assert_eq!(delta["value"]["revoked"], json!([1]));
+        assert_eq!(delta["value"]["issued"], json!([2]));
+
+        let (_, rev_reg, _) = ledger.get_rev_reg(REV_REG_ID, timestamp).await.unwrap();
+        let rev_reg: Value = serde_json::from_str(&rev_reg).unwrap();
+        assert_eq!(rev_reg["value"]["accum"], "accum_3");
+    }
+
+    #[tokio::test]
+    async fn test_file_ledger_is_persisted() {
+        let path = std::env::temp_dir().join(format!("file_ledger_{}.json", uuid::Uuid::new_v4()));
+
+        let ledger = FileLedger::open(&path).unwrap();
+        ledger.publish_schema(&schema_json(), DID, None).await.unwrap();
+        ledger.publish_nym(DID, DID, Some(VERKEY), None, None).await.unwrap();
+
+        let ledger = FileLedger::open(&path).unwrap();
+        ledger.get_schema(SCHEMA_ID, None).await.unwrap();
+        assert_eq!(result_data(&ledger.get_nym(DID).await.unwrap())["verkey"], VERKEY);
+
+        fs::remove_file(path).unwrap();
+    }
+}
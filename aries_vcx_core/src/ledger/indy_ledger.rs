@@ -147,6 +147,37 @@ impl BaseLedger for IndySdkLedger {
         indy::ledger::transactions::build_schema_request(submitter_did, schema_json).await
     }
 
+    async fn build_cred_def_request(&self, submitter_did: &str, cred_def_json: &str) -> VcxCoreResult<String> {
+        indy::ledger::transactions::build_cred_def_request(submitter_did, cred_def_json).await
+    }
+
+    async fn build_rev_reg_def_request(&self, submitter_did: &str, rev_reg_def_json: &str) -> VcxCoreResult<String> {
+        indy::ledger::transactions::build_rev_reg_request(submitter_did, rev_reg_def_json).await
+    }
+
+    async fn build_rev_reg_delta_request(
+        &self,
+        submitter_did: &str,
+        rev_reg_id: &str,
+        rev_reg_entry_json: &str,
+    ) -> VcxCoreResult<String> {
+        indy::ledger::transactions::build_rev_reg_delta_request(submitter_did, rev_reg_id, rev_reg_entry_json).await
+    }
+
+    async fn build_nym_request(
+        &self,
+        submitter_did: &str,
+        target_did: &str,
+        verkey: Option<&str>,
+        data: Option<&str>,
+        role: Option<&str>,
+    ) -> VcxCoreResult<String> {
+        let nym_request =
+            indy::ledger::transactions::libindy_build_nym_request(submitter_did, target_did, verkey, data, role)
+                .await?;
+        indy::ledger::transactions::append_txn_author_agreement_to_request(&nym_request).await
+    }
+
     async fn publish_schema(
         &self,
         schema_json: &str,
@@ -446,6 +446,38 @@ impl BaseLedger for IndyVdrLedger {
         Err(unimplemented_method_err("indy_vdr build_schema_request"))
     }
 
+    async fn build_cred_def_request(&self, submitter_did: &str, cred_def_json: &str) -> VcxCoreResult<String> {
+        let _ = (submitter_did, cred_def_json);
+        Err(unimplemented_method_err("indy_vdr build_cred_def_request"))
+    }
+
+    async fn build_rev_reg_def_request(&self, submitter_did: &str, rev_reg_def_json: &str) -> VcxCoreResult<String> {
+        let _ = (submitter_did, rev_reg_def_json);
+        Err(unimplemented_method_err("indy_vdr build_rev_reg_def_request"))
+    }
+
+    async fn build_rev_reg_delta_request(
+        &self,
+        submitter_did: &str,
+        rev_reg_id: &str,
+        rev_reg_entry_json: &str,
+    ) -> VcxCoreResult<String> {
+        let _ = (submitter_did, rev_reg_id, rev_reg_entry_json);
+        Err(unimplemented_method_err("indy_vdr build_rev_reg_delta_request"))
+    }
+
+    async fn build_nym_request(
+        &self,
+        submitter_did: &str,
+        target_did: &str,
+        verkey: Option<&str>,
+        data: Option<&str>,
+        role: Option<&str>,
+    ) -> VcxCoreResult<String> {
+        let _ = (submitter_did, target_did, verkey, data, role);
+        Err(unimplemented_method_err("indy_vdr build_nym_request"))
+    }
+
     async fn publish_schema(
         &self,
         schema_json: &str,
@@ -542,6 +574,10 @@ mod unit_tests {
         assert_unimplemented(ledger.get_rev_reg("", 0).await);
         assert_unimplemented(ledger.get_ledger_txn(0, None).await);
         assert_unimplemented(ledger.build_schema_request("", "").await);
+        assert_unimplemented(ledger.build_cred_def_request("", "").await);
+        assert_unimplemented(ledger.build_rev_reg_def_request("", "").await);
+        assert_unimplemented(ledger.build_rev_reg_delta_request("", "", "").await);
+        assert_unimplemented(ledger.build_nym_request("", "", None, None, None).await);
         assert_unimplemented(ledger.publish_schema("", "", None).await);
         assert_unimplemented(ledger.publish_cred_def("", "").await);
         assert_unimplemented(ledger.publish_rev_reg_def("", "").await);
@@ -1,5 +1,8 @@
 pub mod base_ledger;
+pub mod caching_ledger;
+pub mod file_ledger;
 #[cfg(feature = "vdrtools")]
 pub mod indy_ledger;
 #[cfg(feature = "modular_libs")]
 pub mod indy_vdr_ledger;
+pub mod multi_ledger;
@@ -0,0 +1,305 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult};
+use crate::utils::qualifier;
+
+use super::base_ledger::BaseLedger;
+
+/// Implementation of [BaseLedger] dispatching to one of several ledgers, based on the namespace of
+/// the DID or identifier a method is called with (see [qualifier::namespace]), e.g. `indy:sovrin:staging`
+/// for `did:indy:sovrin:staging:WgWxqztrNooG92RXvxSTWv`.
+/// Unqualified identifiers, as well as methods not taking any identifier, are dispatched to the default
+/// ledger. Identifiers are passed to the selected ledger unchanged.
+#[derive(Debug)]
+pub struct MultiLedger {
+    ledgers: HashMap<String, Arc<dyn BaseLedger>>,
+    default_ledger: Option<Arc<dyn BaseLedger>>,
+}
+
+impl MultiLedger {
+    pub fn new(ledgers: HashMap<String, Arc<dyn BaseLedger>>, default_ledger: Option<Arc<dyn BaseLedger>>) -> Self {
+        MultiLedger {
+            ledgers,
+            default_ledger,
+        }
+    }
+
+    fn default_ledger(&self) -> VcxCoreResult<&dyn BaseLedger> {
+        self.default_ledger.as_deref().ok_or_else(|| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidConfiguration,
+                "No default ledger is configured",
+            )
+        })
+    }
+
+    fn ledger_for(&self, identifier: &str) -> VcxCoreResult<&dyn BaseLedger> {
+        match qualifier::namespace(identifier) {
+            Some(namespace) => self.ledgers.get(&namespace).map(Arc::as_ref).ok_or_else(|| {
+                AriesVcxCoreError::from_msg(
+                    AriesVcxCoreErrorKind::InvalidConfiguration,
+                    format!(
+                        "No ledger is configured for namespace {} of identifier {}",
+                        namespace, identifier
+                    ),
+                )
+            }),
+            None => self.default_ledger().map_err(|_| {
+                AriesVcxCoreError::from_msg(
+                    AriesVcxCoreErrorKind::InvalidConfiguration,
+                    format!(
+                        "Identifier {} is not fully qualified and no default ledger is configured",
+                        identifier
+                    ),
+                )
+            }),
+        }
+    }
+}
+
+#[async_trait]
+impl BaseLedger for MultiLedger {
+    async fn sign_and_submit_request(&self, submitter_did: &str, request_json: &str) -> VcxCoreResult<String> {
+        self.ledger_for(submitter_did)?
+            .sign_and_submit_request(submitter_did, request_json)
+            .await
+    }
+
+    async fn submit_request(&self, request_json: &str) -> VcxCoreResult<String> {
+        self.default_ledger()?.submit_request(request_json).await
+    }
+
+    async fn endorse_transaction(&self, endorser_did: &str, request_json: &str) -> VcxCoreResult<()> {
+        self.ledger_for(endorser_did)?
+            .endorse_transaction(endorser_did, request_json)
+            .await
+    }
+
+    async fn set_endorser(&self, submitter_did: &str, request: &str, endorser: &str) -> VcxCoreResult<String> {
+        self.ledger_for(submitter_did)?
+            .set_endorser(submitter_did, request, endorser)
+            .await
+    }
+
+    async fn get_txn_author_agreement(&self) -> VcxCoreResult<String> {
+        self.default_ledger()?.get_txn_author_agreement().await
+    }
+
+    async fn get_nym(&self, did: &str) -> VcxCoreResult<String> {
+        self.ledger_for(did)?.get_nym(did).await
+    }
+
+    async fn publish_nym(
+        &self,
+        submitter_did: &str,
+        target_did: &str,
+        verkey: Option<&str>,
+        data: Option<&str>,
+        role: Option<&str>,
+    ) -> VcxCoreResult<String> {
+        self.ledger_for(target_did)?
+            .publish_nym(submitter_did, target_did, verkey, data, role)
+            .await
+    }
+
+    async fn get_schema(&self, schema_id: &str, submitter_did: Option<&str>) -> VcxCoreResult<String> {
+        self.ledger_for(schema_id)?.get_schema(schema_id, submitter_did).await
+    }
+
+    async fn get_cred_def(&self, cred_def_id: &str, submitter_did: Option<&str>) -> VcxCoreResult<String> {
+        self.ledger_for(cred_def_id)?
+            .get_cred_def(cred_def_id, submitter_did)
+            .await
+    }
+
+    async fn get_attr(&self, target_did: &str, attr_name: &str) -> VcxCoreResult<String> {
+        self.ledger_for(target_did)?.get_attr(target_did, attr_name).await
+    }
+
+    async fn add_attr(&self, target_did: &str, attrib_json: &str) -> VcxCoreResult<String> {
+        self.ledger_for(target_did)?.add_attr(target_did, attrib_json).await
+    }
+
+    async fn get_rev_reg_def_json(&self, rev_reg_id: &str) -> VcxCoreResult<String> {
+        self.ledger_for(rev_reg_id)?.get_rev_reg_def_json(rev_reg_id).await
+    }
+
+    async fn get_rev_reg_delta_json(
+        &self,
+        rev_reg_id: &str,
+        from: Option<u64>,
+        to: Option<u64>,
+    ) -> VcxCoreResult<(String, String, u64)> {
+        self.ledger_for(rev_reg_id)?
+            .get_rev_reg_delta_json(rev_reg_id, from, to)
+            .await
+    }
+
+    async fn get_rev_reg(&self, rev_reg_id: &str, timestamp: u64) -> VcxCoreResult<(String, String, u64)> {
+        self.ledger_for(rev_reg_id)?.get_rev_reg(rev_reg_id, timestamp).await
+    }
+
+    async fn get_ledger_txn(&self, seq_no: i32, submitter_did: Option<&str>) -> VcxCoreResult<String> {
+        match submitter_did {
+            Some(did) => self.ledger_for(did)?.get_ledger_txn(seq_no, submitter_did).await,
+            None => self.default_ledger()?.get_ledger_txn(seq_no, submitter_did).await,
+        }
+    }
+
+    async fn build_schema_request(&self, submitter_did: &str, schema_json: &str) -> VcxCoreResult<String> {
+        self.ledger_for(submitter_did)?
+            .build_schema_request(submitter_did, schema_json)
+            .await
+    }
+
+    async fn build_cred_def_request(&self, submitter_did: &str, cred_def_json: &str) -> VcxCoreResult<String> {
+        self.ledger_for(submitter_did)?
+            .build_cred_def_request(submitter_did, cred_def_json)
+            .await
+    }
+
+    async fn build_rev_reg_def_request(&self, submitter_did: &str, rev_reg_def_json: &str) -> VcxCoreResult<String> {
+        self.ledger_for(submitter_did)?
+            .build_rev_reg_def_request(submitter_did, rev_reg_def_json)
+            .await
+    }
+
+    async fn build_rev_reg_delta_request(
+        &self,
+        submitter_did: &str,
+        rev_reg_id: &str,
+        rev_reg_entry_json: &str,
+    ) -> VcxCoreResult<String> {
+        self.ledger_for(rev_reg_id)?
+            .build_rev_reg_delta_request(submitter_did, rev_reg_id, rev_reg_entry_json)
+            .await
+    }
+
+    async fn build_nym_request(
+        &self,
+        submitter_did: &str,
+        target_did: &str,
+        verkey: Option<&str>,
+        data: Option<&str>,
+        role: Option<&str>,
+    ) -> VcxCoreResult<String> {
+        self.ledger_for(target_did)?
+            .build_nym_request(submitter_did, target_did, verkey, data, role)
+            .await
+    }
+
+    async fn publish_schema(
+        &self,
+        schema_json: &str,
+        submitter_did: &str,
+        endorser_did: Option<String>,
+    ) -> VcxCoreResult<()> {
+        self.ledger_for(submitter_did)?
+            .publish_schema(schema_json, submitter_did, endorser_did)
+            .await
+    }
+
+    async fn publish_cred_def(&self, cred_def_json: &str, submitter_did: &str) -> VcxCoreResult<()> {
+        self.ledger_for(submitter_did)?
+            .publish_cred_def(cred_def_json, submitter_did)
+            .await
+    }
+
+    async fn publish_rev_reg_def(&self, rev_reg_def: &str, submitter_did: &str) -> VcxCoreResult<()> {
+        self.ledger_for(submitter_did)?
+            .publish_rev_reg_def(rev_reg_def, submitter_did)
+            .await
+    }
+
+    async fn publish_rev_reg_delta(
+        &self,
+        rev_reg_id: &str,
+        rev_reg_entry_json: &str,
+        submitter_did: &str,
+    ) -> VcxCoreResult<()> {
+        self.ledger_for(rev_reg_id)?
+            .publish_rev_reg_delta(rev_reg_id, rev_reg_entry_json, submitter_did)
+            .await
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use serde_json::json;
+
+    use crate::ledger::file_ledger::FileLedger;
+
+    use super::*;
+
+    const STAGING_DID: &str = "did:indy:sovrin:staging:WgWxqztrNooG92RXvxSTWv";
+    const BUILDER_DID: &str = "did:indy:sovrin:builder:WgWxqztrNooG92RXvxSTWv";
+
+    async fn ledger_with_nym(did: &str, verkey: &str) -> Arc<dyn BaseLedger> {
+        let ledger = FileLedger::in_memory();
+        ledger.publish_nym(did, did, Some(verkey), None, None).await.unwrap();
+        Arc::new(ledger)
+    }
+
+    fn verkey(nym_response: &str) -> String {
+        let response: serde_json::Value = serde_json::from_str(nym_response).unwrap();
+        let data: serde_json::Value = serde_json::from_str(response["result"]["data"].as_str().unwrap()).unwrap();
+        data["verkey"].as_str().unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn test_multi_ledger_dispatches_by_namespace() {
+        let ledgers = HashMap::from([
+            (
+                "indy:sovrin:staging".to_string(),
+                ledger_with_nym(STAGING_DID, "staging_vk").await,
+            ),
+            (
+                "indy:sovrin:builder".to_string(),
+                ledger_with_nym(BUILDER_DID, "builder_vk").await,
+            ),
+        ]);
+        let default_ledger = ledger_with_nym("WgWxqztrNooG92RXvxSTWv", "default_vk").await;
+        let ledger = MultiLedger::new(ledgers, Some(default_ledger));
+
+        assert_eq!(verkey(&ledger.get_nym(STAGING_DID).await.unwrap()), "staging_vk");
+        assert_eq!(verkey(&ledger.get_nym(BUILDER_DID).await.unwrap()), "builder_vk");
+        assert_eq!(
+            verkey(&ledger.get_nym("WgWxqztrNooG92RXvxSTWv").await.unwrap()),
+            "default_vk"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_multi_ledger_fails_for_unknown_namespace() {
+        let ledgers = HashMap::from([(
+            "indy:sovrin:staging".to_string(),
+            ledger_with_nym(STAGING_DID, "staging_vk").await,
+        )]);
+        let ledger = MultiLedger::new(ledgers, None);
+
+        assert_eq!(
+            ledger.get_nym(BUILDER_DID).await.unwrap_err().kind(),
+            AriesVcxCoreErrorKind::InvalidConfiguration
+        );
+        assert_eq!(
+            ledger
+                .get_schema("NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0", None)
+                .await
+                .unwrap_err()
+                .kind(),
+            AriesVcxCoreErrorKind::InvalidConfiguration
+        );
+        assert_eq!(
+            ledger
+                .publish_schema(&json!({"id": "schema"}).to_string(), BUILDER_DID, None)
+                .await
+                .unwrap_err()
+                .kind(),
+            AriesVcxCoreErrorKind::InvalidConfiguration
+        );
+    }
+}
@@ -3,3 +3,4 @@ use std::{env, path::PathBuf};
 pub mod async_fn_iterator;
 pub(crate) mod constants;
 pub(crate) mod json;
+pub mod qualifier;
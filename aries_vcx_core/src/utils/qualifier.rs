@@ -0,0 +1,87 @@
+use regex::Regex;
+
+lazy_static! {
+    pub static ref REGEX: Regex =
+        Regex::new("did:([a-z0-9]+):([a-zA-Z0-9:.-_]*)").expect("Unexpected regex error occurred.");
+}
+
+pub fn is_fully_qualified(entity: &str) -> bool {
+    REGEX.is_match(entity)
+}
+
+/// Returns the ledger namespace of a fully qualified DID, or of an identifier containing one (schema,
+/// credential definition or revocation registry ID), e.g. `indy:sovrin:staging` for
+/// `did:indy:sovrin:staging:WgWxqztrNooG92RXvxSTWv` and `sov` for `did:sov:WgWxqztrNooG92RXvxSTWv`.
+/// Returns `None` for unqualified entities.
+pub fn namespace(entity: &str) -> Option<String> {
+    let caps = REGEX.captures(entity)?;
+    let method = caps.get(1)?.as_str();
+    // the method specific id ends either at the end of the DID or where a path of a did:indy object starts
+    let method_specific_id = caps.get(2)?.as_str().split('/').next().unwrap_or_default();
+
+    // for did:indy, every segment of the method specific id except the last one (the DID itself)
+    // identifies the network, other methods are bound to a single network
+    let (network, _) = method_specific_id.rsplit_once(':').unwrap_or_default();
+    match (method, network) {
+        ("indy", "") => None,
+        ("indy", network) => Some(format!("{}:{}", method, network)),
+        (method, _) => Some(method.to_string()),
+    }
+}
+
+/// Returns the DID of the issuer of the object identified by `id` (schema, credential definition or revocation
+/// registry ID), e.g. `WgWxqztrNooG92RXvxSTWv` for `WgWxqztrNooG92RXvxSTWv:2:gvt:1.0` and
+/// `did:indy:sovrin:WgWxqztrNooG92RXvxSTWv` for `did:indy:sovrin:WgWxqztrNooG92RXvxSTWv/anoncreds/v0/SCHEMA/gvt/1.0`.
+pub fn issuer_did(id: &str) -> &str {
+    let issuer_did = if is_fully_qualified(id) {
+        id.split('/').next()
+    } else {
+        id.split(':').next()
+    };
+    issuer_did.unwrap_or(id)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn is_fully_qualified_works() {
+        assert!(is_fully_qualified("did:indy:some"));
+        assert!(!is_fully_qualified("did:indy"));
+        assert!(!is_fully_qualified("indy:some"));
+    }
+
+    #[test]
+    fn namespace_works() {
+        assert_eq!(
+            namespace("did:indy:sovrin:staging:WgWxqztrNooG92RXvxSTWv").as_deref(),
+            Some("indy:sovrin:staging")
+        );
+        assert_eq!(
+            namespace("did:indy:sovrin:WgWxqztrNooG92RXvxSTWv/anoncreds/v0/SCHEMA/gvt/1.0").as_deref(),
+            Some("indy:sovrin")
+        );
+        assert_eq!(namespace("did:sov:WgWxqztrNooG92RXvxSTWv").as_deref(), Some("sov"));
+        assert_eq!(
+            namespace("schema:sov:did:sov:NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0").as_deref(),
+            Some("sov")
+        );
+        assert_eq!(namespace("did:indy:WgWxqztrNooG92RXvxSTWv"), None);
+        assert_eq!(namespace("NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0"), None);
+        assert_eq!(namespace("WgWxqztrNooG92RXvxSTWv"), None);
+    }
+
+    #[test]
+    fn issuer_did_works() {
+        assert_eq!(issuer_did("WgWxqztrNooG92RXvxSTWv:2:gvt:1.0"), "WgWxqztrNooG92RXvxSTWv");
+        assert_eq!(
+            issuer_did("WgWxqztrNooG92RXvxSTWv:4:WgWxqztrNooG92RXvxSTWv:3:CL:2471:tag:CL_ACCUM:tag"),
+            "WgWxqztrNooG92RXvxSTWv"
+        );
+        assert_eq!(
+            issuer_did("did:indy:sovrin:WgWxqztrNooG92RXvxSTWv/anoncreds/v0/CLAIM_DEF/2471/tag"),
+            "did:indy:sovrin:WgWxqztrNooG92RXvxSTWv"
+        );
+    }
+}
@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use crate::utils::async_fn_iterator::AsyncFnIterator;
 
-use super::base_wallet::BaseWallet;
+use super::base_wallet::{BaseWallet, WalletEntry, WalletTransaction};
 use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult};
 use agency_client::errors::error::{AgencyClientError, AgencyClientErrorKind, AgencyClientResult};
 use agency_client::wallet::base_agency_client_wallet::BaseAgencyClientWallet;
@@ -81,6 +81,10 @@ impl BaseWallet for AgencyClientWallet {
         Err(unimplemented_agency_client_wallet_method("iterate_wallet_records"))
     }
 
+    async fn commit_transaction(&self, transaction: WalletTransaction) -> VcxCoreResult<()> {
+        Err(unimplemented_agency_client_wallet_method("commit_transaction"))
+    }
+
     async fn sign(&self, my_vk: &str, msg: &[u8]) -> VcxCoreResult<Vec<u8>> {
         Err(unimplemented_agency_client_wallet_method("sign"))
     }
@@ -96,6 +100,14 @@ impl BaseWallet for AgencyClientWallet {
     async fn unpack_message(&self, msg: &[u8]) -> VcxCoreResult<Vec<u8>> {
         Ok(self.inner.unpack_message(msg).await?)
     }
+
+    async fn export_entries(&self) -> VcxCoreResult<Box<dyn AsyncFnIterator<Item = VcxCoreResult<WalletEntry>>>> {
+        Err(unimplemented_agency_client_wallet_method("export_entries"))
+    }
+
+    async fn import_entry(&self, entry: WalletEntry) -> VcxCoreResult<()> {
+        Err(unimplemented_agency_client_wallet_method("import_entry"))
+    }
 }
 
 pub trait ToBaseWallet {
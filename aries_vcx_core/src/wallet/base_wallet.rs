@@ -1,4 +1,8 @@
+use std::collections::HashMap;
+use std::fmt;
+
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 
 use crate::errors::error::VcxCoreResult;
 use crate::utils::async_fn_iterator::AsyncFnIterator;
@@ -48,6 +52,10 @@ pub trait BaseWallet: std::fmt::Debug + Send + Sync {
         options: &str,
     ) -> VcxCoreResult<Box<dyn AsyncFnIterator<Item = VcxCoreResult<String>>>>;
 
+    // applies every record write collected in `transaction` atomically: either all of them are
+    // persisted, or on any failure none of them are
+    async fn commit_transaction(&self, transaction: WalletTransaction) -> VcxCoreResult<()>;
+
     // ---- crypto
 
     async fn sign(&self, my_vk: &str, msg: &[u8]) -> VcxCoreResult<Vec<u8>>;
@@ -57,6 +65,169 @@ pub trait BaseWallet: std::fmt::Debug + Send + Sync {
     async fn pack_message(&self, sender_vk: Option<&str>, receiver_keys: &str, msg: &[u8]) -> VcxCoreResult<Vec<u8>>;
 
     async fn unpack_message(&self, msg: &[u8]) -> VcxCoreResult<Vec<u8>>;
+
+    // ---- migration
+
+    // streams every DID, key (including its secret part) and record held by the wallet, see
+    // [crate::wallet::migration] for moving them between wallets
+    async fn export_entries(&self) -> VcxCoreResult<Box<dyn AsyncFnIterator<Item = VcxCoreResult<WalletEntry>>>>;
+
+    // stores a single entry exported by `export_entries` of this or any other wallet implementation
+    async fn import_entry(&self, entry: WalletEntry) -> VcxCoreResult<()>;
+}
+
+/// A single DID, key or record exported from a [BaseWallet], in a form independent of the
+/// wallet implementation it was exported from.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum WalletEntry {
+    Did {
+        did: String,
+        verkey: String,
+    },
+    Key {
+        verkey: String,
+        // base58 encoded 64 byte ed25519 secret key, the same encoding vdrtools stores keys with
+        signkey: String,
+    },
+    Record {
+        xtype: String,
+        id: String,
+        value: String,
+        tags: HashMap<String, String>,
+    },
+}
+
+impl fmt::Debug for WalletEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WalletEntry::Did { did, verkey } => {
+                f.debug_struct("Did").field("did", did).field("verkey", verkey).finish()
+            }
+            WalletEntry::Key { verkey, .. } => f.debug_struct("Key").field("verkey", verkey).finish_non_exhaustive(),
+            WalletEntry::Record { xtype, id, .. } => f
+                .debug_struct("Record")
+                .field("xtype", xtype)
+                .field("id", id)
+                .finish_non_exhaustive(),
+        }
+    }
+}
+
+/// A single record write collected into a [WalletTransaction]. Arguments mirror the
+/// corresponding single-record methods of [BaseWallet].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WalletRecordOperation {
+    Add {
+        xtype: String,
+        id: String,
+        value: String,
+        tags_json: Option<String>,
+    },
+    UpdateValue {
+        xtype: String,
+        id: String,
+        value: String,
+    },
+    AddTags {
+        xtype: String,
+        id: String,
+        tags_json: String,
+    },
+    UpdateTags {
+        xtype: String,
+        id: String,
+        tags_json: String,
+    },
+    DeleteTags {
+        xtype: String,
+        id: String,
+        tag_names: String,
+    },
+    Delete {
+        xtype: String,
+        id: String,
+    },
+}
+
+/// Groups several record writes so they can be committed atomically via
+/// [BaseWallet::commit_transaction]. Nothing is written to the wallet until the transaction
+/// is committed; dropping it discards the collected writes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WalletTransaction {
+    operations: Vec<WalletRecordOperation>,
+}
+
+impl WalletTransaction {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_wallet_record(&mut self, xtype: &str, id: &str, value: &str, tags_json: Option<&str>) -> &mut Self {
+        self.operations.push(WalletRecordOperation::Add {
+            xtype: xtype.to_string(),
+            id: id.to_string(),
+            value: value.to_string(),
+            tags_json: tags_json.map(String::from),
+        });
+        self
+    }
+
+    pub fn update_wallet_record_value(&mut self, xtype: &str, id: &str, value: &str) -> &mut Self {
+        self.operations.push(WalletRecordOperation::UpdateValue {
+            xtype: xtype.to_string(),
+            id: id.to_string(),
+            value: value.to_string(),
+        });
+        self
+    }
+
+    pub fn add_wallet_record_tags(&mut self, xtype: &str, id: &str, tags_json: &str) -> &mut Self {
+        self.operations.push(WalletRecordOperation::AddTags {
+            xtype: xtype.to_string(),
+            id: id.to_string(),
+            tags_json: tags_json.to_string(),
+        });
+        self
+    }
+
+    pub fn update_wallet_record_tags(&mut self, xtype: &str, id: &str, tags_json: &str) -> &mut Self {
+        self.operations.push(WalletRecordOperation::UpdateTags {
+            xtype: xtype.to_string(),
+            id: id.to_string(),
+            tags_json: tags_json.to_string(),
+        });
+        self
+    }
+
+    pub fn delete_wallet_record_tags(&mut self, xtype: &str, id: &str, tag_names: &str) -> &mut Self {
+        self.operations.push(WalletRecordOperation::DeleteTags {
+            xtype: xtype.to_string(),
+            id: id.to_string(),
+            tag_names: tag_names.to_string(),
+        });
+        self
+    }
+
+    pub fn delete_wallet_record(&mut self, xtype: &str, id: &str) -> &mut Self {
+        self.operations.push(WalletRecordOperation::Delete {
+            xtype: xtype.to_string(),
+            id: id.to_string(),
+        });
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    pub fn operations(&self) -> &[WalletRecordOperation] {
+        &self.operations
+    }
+
+    pub fn into_operations(self) -> Vec<WalletRecordOperation> {
+        self.operations
+    }
 }
 
 #[async_trait]
@@ -0,0 +1,149 @@
+use std::sync::Once;
+
+use sodiumoxide::crypto::{box_, sign};
+
+use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult};
+use crate::wallet::sodium::{crypto_sign_ed25519_pk_to_curve25519, crypto_sign_ed25519_sk_to_curve25519};
+
+const DEFAULT_CRYPTO_TYPE_SUFFIX: &str = ":ed25519";
+
+static SODIUM_INIT: Once = Once::new();
+
+pub(super) fn init() {
+    SODIUM_INIT.call_once(|| {
+        // safe to ignore, libsodium only fails to initialize when no entropy source is available
+        sodiumoxide::init().ok();
+    });
+}
+
+pub(super) fn create_key(seed: Option<&str>) -> VcxCoreResult<(sign::PublicKey, sign::SecretKey)> {
+    match seed {
+        Some(seed) => Ok(sign::keypair_from_seed(&convert_seed(seed)?)),
+        None => Ok(sign::gen_keypair()),
+    }
+}
+
+pub(super) fn encode_verkey(verkey: &sign::PublicKey) -> String {
+    bs58::encode(&verkey.0[..]).into_string()
+}
+
+pub(super) fn decode_verkey(verkey: &str) -> VcxCoreResult<sign::PublicKey> {
+    let verkey = verkey.strip_suffix(DEFAULT_CRYPTO_TYPE_SUFFIX).unwrap_or(verkey);
+
+    let bytes = bs58::decode(verkey).into_vec().map_err(|err| {
+        AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::InvalidVerkey,
+            format!("Verkey {} is not a valid base58 string: {}", verkey, err),
+        )
+    })?;
+
+    sign::PublicKey::from_slice(&bytes).ok_or_else(|| {
+        AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::InvalidVerkey,
+            format!("Verkey {} does not have {} bytes", verkey, sign::PUBLICKEYBYTES),
+        )
+    })
+}
+
+pub(super) fn encode_signkey(signkey: &sign::SecretKey) -> String {
+    bs58::encode(&signkey.0[..]).into_string()
+}
+
+// decodes a signing key exported together with `verkey`, making sure the two belong together
+pub(super) fn decode_signkey(verkey: &str, signkey: &str) -> VcxCoreResult<sign::SecretKey> {
+    let invalid_signkey = || {
+        AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::InvalidInput,
+            format!("Signing key of verkey {} is invalid", verkey),
+        )
+    };
+
+    let bytes = bs58::decode(signkey).into_vec().map_err(|_| invalid_signkey())?;
+    let signkey = sign::SecretKey::from_slice(&bytes).ok_or_else(invalid_signkey)?;
+
+    // the second half of an ed25519 secret key is its public key
+    if signkey.0[sign::SEEDBYTES..] != decode_verkey(verkey)?.0[..] {
+        return Err(invalid_signkey());
+    }
+
+    Ok(signkey)
+}
+
+pub(super) fn sign(secret_key: &sign::SecretKey, msg: &[u8]) -> Vec<u8> {
+    sign::sign_detached(msg, secret_key).0.to_vec()
+}
+
+pub(super) fn verify(verkey: &str, msg: &[u8], signature: &[u8]) -> VcxCoreResult<bool> {
+    let verkey = decode_verkey(verkey)?;
+
+    let signature = sign::Signature::from_slice(signature).ok_or_else(|| {
+        AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::InvalidInput,
+            format!("Signature does not have {} bytes", sign::SIGNATUREBYTES),
+        )
+    })?;
+
+    Ok(sign::verify_detached(&signature, msg, &verkey))
+}
+
+pub(super) fn verkey_to_curve25519(verkey: &sign::PublicKey) -> VcxCoreResult<box_::PublicKey> {
+    let mut curve25519_pk = [0u8; box_::PUBLICKEYBYTES];
+    let res = unsafe { crypto_sign_ed25519_pk_to_curve25519(&mut curve25519_pk, &verkey.0) };
+    if res != 0 {
+        return Err(AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::InvalidVerkey,
+            format!("Not a valid Ed25519 verkey: {}", encode_verkey(verkey)),
+        ));
+    }
+    Ok(box_::PublicKey(curve25519_pk))
+}
+
+pub(super) fn signkey_to_curve25519(signkey: &sign::SecretKey) -> VcxCoreResult<box_::SecretKey> {
+    let mut curve25519_sk = [0u8; box_::SECRETKEYBYTES];
+    let res = unsafe { crypto_sign_ed25519_sk_to_curve25519(&mut curve25519_sk, &signkey.0) };
+    if res != 0 {
+        return Err(AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::InvalidInput,
+            "Signing key can't be converted to a Curve25519 key",
+        ));
+    }
+    Ok(box_::SecretKey(curve25519_sk))
+}
+
+// accepts the same seed formats as vdrtools: a 32 byte string, a base64 string or a 64 character hex string
+fn convert_seed(seed: &str) -> VcxCoreResult<sign::Seed> {
+    let bytes = if seed.as_bytes().len() == sign::SEEDBYTES {
+        seed.as_bytes().to_vec()
+    } else if seed.ends_with('=') {
+        base64::decode(seed).map_err(|err| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidInput,
+                format!("Can't deserialize seed from base64 string: {}", err),
+            )
+        })?
+    } else if seed.len() == sign::SEEDBYTES * 2 && seed.is_ascii() {
+        (0..seed.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&seed[i..i + 2], 16))
+            .collect::<Result<Vec<u8>, _>>()
+            .map_err(|err| {
+                AriesVcxCoreError::from_msg(
+                    AriesVcxCoreErrorKind::InvalidInput,
+                    format!("Seed is invalid hex: {}", err),
+                )
+            })?
+    } else {
+        vec![]
+    };
+
+    sign::Seed::from_slice(&bytes).ok_or_else(|| {
+        AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::InvalidInput,
+            format!(
+                "Invalid seed, it can be either a {} bytes string, a base64 string or a {} bytes hex string",
+                sign::SEEDBYTES,
+                sign::SEEDBYTES * 2
+            ),
+        )
+    })
+}
@@ -0,0 +1,733 @@
+mod crypto;
+mod pack;
+mod wql;
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt;
+use std::sync::RwLock;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use sodiumoxide::crypto::sign;
+
+use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult};
+use crate::utils::async_fn_iterator::AsyncFnIterator;
+
+use self::wql::Query;
+
+use super::base_wallet::{BaseWallet, WalletEntry, WalletRecordOperation, WalletTransaction};
+
+type Tags = HashMap<String, String>;
+
+#[derive(Clone, Debug)]
+struct Record {
+    value: String,
+    tags: Tags,
+}
+
+#[derive(Clone, Debug)]
+struct DidEntry {
+    verkey: String,
+    next_verkey: Option<String>,
+}
+
+#[derive(Default)]
+struct WalletState {
+    dids: HashMap<String, DidEntry>,
+    // verkey -> ed25519 signing key
+    keys: HashMap<String, sign::SecretKey>,
+    // record type -> record id -> record
+    records: HashMap<String, BTreeMap<String, Record>>,
+}
+
+/// Implementation of [BaseWallet] which keeps DIDs, keys and records in memory only.
+/// Cryptography (ed25519 signatures, authcrypt/anoncrypt message packing) is real and compatible
+/// with [crate::wallet::indy_wallet::IndySdkWallet], nothing is persisted once the wallet is dropped.
+/// Useful for tests and short-lived agents.
+pub struct InMemoryWallet {
+    state: RwLock<WalletState>,
+}
+
+impl InMemoryWallet {
+    pub fn new() -> Self {
+        crypto::init();
+        InMemoryWallet {
+            state: RwLock::new(WalletState::default()),
+        }
+    }
+
+    fn read_state(&self) -> VcxCoreResult<std::sync::RwLockReadGuard<'_, WalletState>> {
+        self.state.read().map_err(|_| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidState,
+                "In-memory wallet state is poisoned",
+            )
+        })
+    }
+
+    fn write_state(&self) -> VcxCoreResult<std::sync::RwLockWriteGuard<'_, WalletState>> {
+        self.state.write().map_err(|_| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidState,
+                "In-memory wallet state is poisoned",
+            )
+        })
+    }
+
+    fn signkey(&self, verkey: &str) -> VcxCoreResult<sign::SecretKey> {
+        self.read_state()?.keys.get(verkey).cloned().ok_or_else(|| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::WalletRecordNotFound,
+                format!("Key {} not found in wallet", verkey),
+            )
+        })
+    }
+
+    fn apply_operation(
+        records: &mut HashMap<String, BTreeMap<String, Record>>,
+        operation: WalletRecordOperation,
+    ) -> VcxCoreResult<()> {
+        match operation {
+            WalletRecordOperation::Add {
+                xtype,
+                id,
+                value,
+                tags_json,
+            } => {
+                let tags = tags_json.as_deref().map(parse_tags).transpose()?.unwrap_or_default();
+                let records_of_type = records.entry(xtype.clone()).or_default();
+                if records_of_type.contains_key(&id) {
+                    return Err(AriesVcxCoreError::from_msg(
+                        AriesVcxCoreErrorKind::DuplicationWalletRecord,
+                        format!("Record of type {} with id {} already exists", xtype, id),
+                    ));
+                }
+                records_of_type.insert(id, Record { value, tags });
+            }
+            WalletRecordOperation::UpdateValue { xtype, id, value } => {
+                find_record(records, &xtype, &id)?.value = value;
+            }
+            WalletRecordOperation::AddTags { xtype, id, tags_json } => {
+                let tags = parse_tags(&tags_json)?;
+                find_record(records, &xtype, &id)?.tags.extend(tags);
+            }
+            WalletRecordOperation::UpdateTags { xtype, id, tags_json } => {
+                let tags = parse_tags(&tags_json)?;
+                find_record(records, &xtype, &id)?.tags = tags;
+            }
+            WalletRecordOperation::DeleteTags { xtype, id, tag_names } => {
+                let tag_names: Vec<String> = serde_json::from_str(&tag_names)?;
+                let record = find_record(records, &xtype, &id)?;
+                for tag_name in tag_names {
+                    record.tags.remove(&tag_name);
+                }
+            }
+            WalletRecordOperation::Delete { xtype, id } => {
+                records
+                    .get_mut(&xtype)
+                    .and_then(|records_of_type| records_of_type.remove(&id))
+                    .ok_or_else(|| record_not_found(&xtype, &id))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn apply_operations(&self, operations: Vec<WalletRecordOperation>) -> VcxCoreResult<()> {
+        let mut state = self.write_state()?;
+
+        // work on a copy, so a failing operation leaves the wallet untouched
+        let mut records = state.records.clone();
+        for operation in operations {
+            Self::apply_operation(&mut records, operation)?;
+        }
+        state.records = records;
+
+        Ok(())
+    }
+}
+
+impl Default for InMemoryWallet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for InMemoryWallet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InMemoryWallet").finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl BaseWallet for InMemoryWallet {
+    async fn create_and_store_my_did(
+        &self,
+        seed: Option<&str>,
+        method_name: Option<&str>,
+    ) -> VcxCoreResult<(String, String)> {
+        let (verkey, signkey) = crypto::create_key(seed)?;
+
+        let unqualified_did = bs58::encode(&verkey.0[..16]).into_string();
+        let did = match method_name {
+            Some(method_name) => format!("did:{}:{}", method_name, unqualified_did),
+            None => unqualified_did,
+        };
+        let verkey = crypto::encode_verkey(&verkey);
+
+        let mut state = self.write_state()?;
+        if let Some(existing) = state.dids.get(&did) {
+            // same as vdrtools, re-creating a DID from the same seed is not an error
+            return if existing.verkey == verkey {
+                Ok((did, verkey))
+            } else {
+                Err(AriesVcxCoreError::from_msg(
+                    AriesVcxCoreErrorKind::DuplicationDid,
+                    format!("DID {} already exists in wallet", did),
+                ))
+            };
+        }
+        state.keys.insert(verkey.clone(), signkey);
+        state.dids.insert(
+            did.clone(),
+            DidEntry {
+                verkey: verkey.clone(),
+                next_verkey: None,
+            },
+        );
+
+        Ok((did, verkey))
+    }
+
+    async fn key_for_local_did(&self, did: &str) -> VcxCoreResult<String> {
+        self.read_state()?
+            .dids
+            .get(did)
+            .map(|entry| entry.verkey.clone())
+            .ok_or_else(|| did_not_found(did))
+    }
+
+    async fn replace_did_keys_start(&self, target_did: &str) -> VcxCoreResult<String> {
+        let (verkey, signkey) = crypto::create_key(None)?;
+        let verkey = crypto::encode_verkey(&verkey);
+
+        let mut state = self.write_state()?;
+        let entry = state
+            .dids
+            .get_mut(target_did)
+            .ok_or_else(|| did_not_found(target_did))?;
+        entry.next_verkey = Some(verkey.clone());
+        state.keys.insert(verkey.clone(), signkey);
+
+        Ok(verkey)
+    }
+
+    async fn replace_did_keys_apply(&self, target_did: &str) -> VcxCoreResult<()> {
+        let mut state = self.write_state()?;
+        let entry = state
+            .dids
+            .get_mut(target_did)
+            .ok_or_else(|| did_not_found(target_did))?;
+        entry.verkey = entry.next_verkey.take().ok_or_else(|| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidState,
+                format!("Key replacement for DID {} was not started", target_did),
+            )
+        })?;
+
+        Ok(())
+    }
+
+    async fn add_wallet_record(
+        &self,
+        xtype: &str,
+        id: &str,
+        value: &str,
+        tags_json: Option<&str>,
+    ) -> VcxCoreResult<()> {
+        let mut transaction = WalletTransaction::new();
+        transaction.add_wallet_record(xtype, id, value, tags_json);
+        self.apply_operations(transaction.into_operations())
+    }
+
+    async fn get_wallet_record(&self, xtype: &str, id: &str, options_json: &str) -> VcxCoreResult<String> {
+        let options: RecordOptions = serde_json::from_str(options_json)?;
+
+        let state = self.read_state()?;
+        let record = state
+            .records
+            .get(xtype)
+            .and_then(|records_of_type| records_of_type.get(id))
+            .ok_or_else(|| record_not_found(xtype, id))?;
+
+        Ok(record_to_json(xtype, id, record, &options).to_string())
+    }
+
+    async fn delete_wallet_record(&self, xtype: &str, id: &str) -> VcxCoreResult<()> {
+        let mut transaction = WalletTransaction::new();
+        transaction.delete_wallet_record(xtype, id);
+        self.apply_operations(transaction.into_operations())
+    }
+
+    async fn update_wallet_record_value(&self, xtype: &str, id: &str, value: &str) -> VcxCoreResult<()> {
+        let mut transaction = WalletTransaction::new();
+        transaction.update_wallet_record_value(xtype, id, value);
+        self.apply_operations(transaction.into_operations())
+    }
+
+    async fn add_wallet_record_tags(&self, xtype: &str, id: &str, tags_json: &str) -> VcxCoreResult<()> {
+        let mut transaction = WalletTransaction::new();
+        transaction.add_wallet_record_tags(xtype, id, tags_json);
+        self.apply_operations(transaction.into_operations())
+    }
+
+    async fn update_wallet_record_tags(&self, xtype: &str, id: &str, tags_json: &str) -> VcxCoreResult<()> {
+        let mut transaction = WalletTransaction::new();
+        transaction.update_wallet_record_tags(xtype, id, tags_json);
+        self.apply_operations(transaction.into_operations())
+    }
+
+    async fn delete_wallet_record_tags(&self, xtype: &str, id: &str, tag_names: &str) -> VcxCoreResult<()> {
+        let mut transaction = WalletTransaction::new();
+        transaction.delete_wallet_record_tags(xtype, id, tag_names);
+        self.apply_operations(transaction.into_operations())
+    }
+
+    async fn iterate_wallet_records(
+        &self,
+        xtype: &str,
+        query: &str,
+        options: &str,
+    ) -> VcxCoreResult<Box<dyn AsyncFnIterator<Item = VcxCoreResult<String>>>> {
+        let query = Query::parse(query)?;
+        let options: SearchOptions = serde_json::from_str(options)?;
+
+        // records are snapshotted when the search is opened, same as a vdrtools search
+        let state = self.read_state()?;
+        let records = state
+            .records
+            .get(xtype)
+            .map(|records_of_type| {
+                records_of_type
+                    .iter()
+                    .filter(|(_, record)| query.matches(&record.tags))
+                    .map(|(id, record)| record_to_json(xtype, id, record, &options.record_options()).to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(Box::new(InMemoryWalletRecordIterator { records }))
+    }
+
+    async fn commit_transaction(&self, transaction: WalletTransaction) -> VcxCoreResult<()> {
+        self.apply_operations(transaction.into_operations())
+    }
+
+    async fn sign(&self, my_vk: &str, msg: &[u8]) -> VcxCoreResult<Vec<u8>> {
+        Ok(crypto::sign(&self.signkey(my_vk)?, msg))
+    }
+
+    async fn verify(&self, vk: &str, msg: &[u8], signature: &[u8]) -> VcxCoreResult<bool> {
+        crypto::verify(vk, msg, signature)
+    }
+
+    async fn pack_message(&self, sender_vk: Option<&str>, receiver_keys: &str, msg: &[u8]) -> VcxCoreResult<Vec<u8>> {
+        let receiver_keys: Vec<String> = serde_json::from_str(receiver_keys).map_err(|_| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidJson,
+                "Invalid RecipientKeys has been passed",
+            )
+        })?;
+
+        match sender_vk {
+            Some(sender_vk) => {
+                let sender_sk = self.signkey(sender_vk)?;
+                pack::pack_message(Some((sender_vk, &sender_sk)), &receiver_keys, msg)
+            }
+            None => pack::pack_message(None, &receiver_keys, msg),
+        }
+    }
+
+    async fn unpack_message(&self, msg: &[u8]) -> VcxCoreResult<Vec<u8>> {
+        let state = self.read_state()?;
+        pack::unpack_message(msg, |verkey| state.keys.get(verkey).cloned())
+    }
+
+    async fn export_entries(&self) -> VcxCoreResult<Box<dyn AsyncFnIterator<Item = VcxCoreResult<WalletEntry>>>> {
+        let state = self.read_state()?;
+
+        // a key replacement in progress is not exported, only the key generated for it
+        let keys = state.keys.iter().map(|(verkey, signkey)| WalletEntry::Key {
+            verkey: verkey.clone(),
+            signkey: crypto::encode_signkey(signkey),
+        });
+        let dids = state.dids.iter().map(|(did, entry)| WalletEntry::Did {
+            did: did.clone(),
+            verkey: entry.verkey.clone(),
+        });
+        let records = state.records.iter().flat_map(|(xtype, records_of_type)| {
+            records_of_type.iter().map(move |(id, record)| WalletEntry::Record {
+                xtype: xtype.clone(),
+                id: id.clone(),
+                value: record.value.clone(),
+                tags: record.tags.clone(),
+            })
+        });
+
+        Ok(Box::new(InMemoryWalletEntryIterator {
+            entries: keys.chain(dids).chain(records).collect(),
+        }))
+    }
+
+    async fn import_entry(&self, entry: WalletEntry) -> VcxCoreResult<()> {
+        match entry {
+            WalletEntry::Did { did, verkey } => {
+                let mut state = self.write_state()?;
+                if state.dids.contains_key(&did) {
+                    return Err(AriesVcxCoreError::from_msg(
+                        AriesVcxCoreErrorKind::DuplicationDid,
+                        format!("DID {} already exists in wallet", did),
+                    ));
+                }
+                state.dids.insert(
+                    did,
+                    DidEntry {
+                        verkey,
+                        next_verkey: None,
+                    },
+                );
+            }
+            WalletEntry::Key { verkey, signkey } => {
+                let signkey = crypto::decode_signkey(&verkey, &signkey)?;
+
+                let mut state = self.write_state()?;
+                if state.keys.contains_key(&verkey) {
+                    return Err(AriesVcxCoreError::from_msg(
+                        AriesVcxCoreErrorKind::DuplicationWalletRecord,
+                        format!("Key {} already exists in wallet", verkey),
+                    ));
+                }
+                state.keys.insert(verkey, signkey);
+            }
+            WalletEntry::Record { xtype, id, value, tags } => {
+                let tags_json = serde_json::to_string(&tags)?;
+                self.apply_operations(vec![WalletRecordOperation::Add {
+                    xtype,
+                    id,
+                    value,
+                    tags_json: Some(tags_json),
+                }])?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+struct InMemoryWalletRecordIterator {
+    records: VecDeque<String>,
+}
+
+/// Implementation of a generic [AsyncFnIterator] iterator over the records matched by an [InMemoryWallet] search.
+#[async_trait]
+impl AsyncFnIterator for InMemoryWalletRecordIterator {
+    type Item = VcxCoreResult<String>;
+
+    async fn next(&mut self) -> Option<Self::Item> {
+        self.records.pop_front().map(Ok)
+    }
+}
+
+struct InMemoryWalletEntryIterator {
+    entries: VecDeque<WalletEntry>,
+}
+
+/// Implementation of a generic [AsyncFnIterator] iterator over the entries exported from an [InMemoryWallet].
+#[async_trait]
+impl AsyncFnIterator for InMemoryWalletEntryIterator {
+    type Item = VcxCoreResult<WalletEntry>;
+
+    async fn next(&mut self) -> Option<Self::Item> {
+        self.entries.pop_front().map(Ok)
+    }
+}
+
+// mirrors the vdrtools record options, including their defaults
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct RecordOptions {
+    #[serde(default)]
+    retrieve_type: bool,
+    #[serde(default = "default_true")]
+    retrieve_value: bool,
+    #[serde(default)]
+    retrieve_tags: bool,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct SearchOptions {
+    #[serde(default)]
+    retrieve_type: bool,
+    #[serde(default = "default_true")]
+    retrieve_value: bool,
+    #[serde(default)]
+    retrieve_tags: bool,
+}
+
+impl SearchOptions {
+    fn record_options(&self) -> RecordOptions {
+        RecordOptions {
+            retrieve_type: self.retrieve_type,
+            retrieve_value: self.retrieve_value,
+            retrieve_tags: self.retrieve_tags,
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn record_to_json(xtype: &str, id: &str, record: &Record, options: &RecordOptions) -> serde_json::Value {
+    json!({
+        "type": options.retrieve_type.then_some(xtype),
+        "id": id,
+        "value": options.retrieve_value.then_some(&record.value),
+        "tags": options.retrieve_tags.then_some(&record.tags),
+    })
+}
+
+fn parse_tags(tags_json: &str) -> VcxCoreResult<Tags> {
+    serde_json::from_str(tags_json).map_err(|err| {
+        AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::InvalidJson,
+            format!("Wallet record tags must be a JSON object of strings: {}", err),
+        )
+    })
+}
+
+fn find_record<'a>(
+    records: &'a mut HashMap<String, BTreeMap<String, Record>>,
+    xtype: &str,
+    id: &str,
+) -> VcxCoreResult<&'a mut Record> {
+    records
+        .get_mut(xtype)
+        .and_then(|records_of_type| records_of_type.get_mut(id))
+        .ok_or_else(|| record_not_found(xtype, id))
+}
+
+fn record_not_found(xtype: &str, id: &str) -> AriesVcxCoreError {
+    AriesVcxCoreError::from_msg(
+        AriesVcxCoreErrorKind::WalletRecordNotFound,
+        format!("Record of type {} with id {} not found", xtype, id),
+    )
+}
+
+fn did_not_found(did: &str) -> AriesVcxCoreError {
+    AriesVcxCoreError::from_msg(
+        AriesVcxCoreErrorKind::WalletRecordNotFound,
+        format!("DID {} not found in wallet", did),
+    )
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use crate::errors::error::AriesVcxCoreErrorKind;
+    use crate::wallet::base_wallet::{AsyncFnIteratorCollect, BaseWallet, WalletTransaction};
+
+    use super::InMemoryWallet;
+
+    #[tokio::test]
+    async fn test_create_did_is_deterministic_for_seed() {
+        let wallet = InMemoryWallet::new();
+        let seed = "000000000000000000000000Trustee1";
+
+        let (did, verkey) = wallet.create_and_store_my_did(Some(seed), None).await.unwrap();
+
+        assert_eq!(did, "V4SGRU86Z58d6TV7PBUe6f");
+        assert_eq!(verkey, "GJ1SzoWzavQYfNL9XkaJdrQejfztN4XqdsiV4ct3LXKL");
+        assert_eq!(wallet.key_for_local_did(&did).await.unwrap(), verkey);
+
+        let (qualified_did, _) = InMemoryWallet::new()
+            .create_and_store_my_did(Some(seed), Some("sov"))
+            .await
+            .unwrap();
+        assert_eq!(qualified_did, "did:sov:V4SGRU86Z58d6TV7PBUe6f");
+    }
+
+    #[tokio::test]
+    async fn test_sign_and_verify() {
+        let wallet = InMemoryWallet::new();
+        let (_, verkey) = wallet.create_and_store_my_did(None, None).await.unwrap();
+        let msg = b"hello world";
+
+        let signature = wallet.sign(&verkey, msg).await.unwrap();
+
+        assert!(wallet.verify(&verkey, msg, &signature).await.unwrap());
+        assert!(!wallet.verify(&verkey, b"other message", &signature).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_replace_did_keys() {
+        let wallet = InMemoryWallet::new();
+        let (did, verkey) = wallet.create_and_store_my_did(None, None).await.unwrap();
+
+        let new_verkey = wallet.replace_did_keys_start(&did).await.unwrap();
+        assert_eq!(wallet.key_for_local_did(&did).await.unwrap(), verkey);
+
+        wallet.replace_did_keys_apply(&did).await.unwrap();
+        assert_eq!(wallet.key_for_local_did(&did).await.unwrap(), new_verkey);
+    }
+
+    #[tokio::test]
+    async fn test_pack_unpack_authcrypt() {
+        let alice = InMemoryWallet::new();
+        let bob = InMemoryWallet::new();
+        let (_, alice_vk) = alice.create_and_store_my_did(None, None).await.unwrap();
+        let (_, bob_vk) = bob.create_and_store_my_did(None, None).await.unwrap();
+
+        let packed = alice
+            .pack_message(Some(&alice_vk), &json!([bob_vk]).to_string(), b"secret message")
+            .await
+            .unwrap();
+        let unpacked: serde_json::Value = serde_json::from_slice(&bob.unpack_message(&packed).await.unwrap()).unwrap();
+
+        assert_eq!(unpacked["message"], "secret message");
+        assert_eq!(unpacked["sender_verkey"], alice_vk);
+        assert_eq!(unpacked["recipient_verkey"], bob_vk);
+        alice.unpack_message(&packed).await.unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn test_pack_unpack_anoncrypt() {
+        let alice = InMemoryWallet::new();
+        let bob = InMemoryWallet::new();
+        let (_, bob_vk) = bob.create_and_store_my_did(None, None).await.unwrap();
+
+        let packed = alice
+            .pack_message(None, &json!([bob_vk]).to_string(), b"secret message")
+            .await
+            .unwrap();
+        let unpacked: serde_json::Value = serde_json::from_slice(&bob.unpack_message(&packed).await.unwrap()).unwrap();
+
+        assert_eq!(unpacked["message"], "secret message");
+        assert!(unpacked.get("sender_verkey").is_none());
+        assert_eq!(unpacked["recipient_verkey"], bob_vk);
+    }
+
+    #[tokio::test]
+    async fn test_pack_rejects_small_order_verkey() {
+        // the identity point is a valid 32 byte verkey, but has no Curve25519 counterpart
+        let mut identity = [0u8; 32];
+        identity[0] = 1;
+        let verkey = bs58::encode(identity).into_string();
+
+        let err = InMemoryWallet::new()
+            .pack_message(None, &json!([verkey]).to_string(), b"secret message")
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxCoreErrorKind::InvalidVerkey);
+    }
+
+    #[tokio::test]
+    async fn test_record_lifecycle() {
+        let wallet = InMemoryWallet::new();
+
+        wallet
+            .add_wallet_record("type", "id", "value", Some(r#"{"a":"1"}"#))
+            .await
+            .unwrap();
+        let err = wallet.add_wallet_record("type", "id", "value", None).await.unwrap_err();
+        assert_eq!(err.kind(), AriesVcxCoreErrorKind::DuplicationWalletRecord);
+
+        wallet
+            .update_wallet_record_value("type", "id", "new value")
+            .await
+            .unwrap();
+        wallet
+            .add_wallet_record_tags("type", "id", r#"{"b":"2"}"#)
+            .await
+            .unwrap();
+        wallet
+            .delete_wallet_record_tags("type", "id", r#"["a"]"#)
+            .await
+            .unwrap();
+
+        let record: serde_json::Value = serde_json::from_str(
+            &wallet
+                .get_wallet_record("type", "id", r#"{"retrieveTags":true}"#)
+                .await
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(record["value"], "new value");
+        assert_eq!(record["tags"], json!({"b": "2"}));
+
+        wallet.delete_wallet_record("type", "id").await.unwrap();
+        let err = wallet.get_wallet_record("type", "id", "{}").await.unwrap_err();
+        assert_eq!(err.kind(), AriesVcxCoreErrorKind::WalletRecordNotFound);
+    }
+
+    #[tokio::test]
+    async fn test_iterate_records_with_wql() {
+        let wallet = InMemoryWallet::new();
+        wallet
+            .add_wallet_record("type", "1", "one", Some(r#"{"name":"alice","age":"30"}"#))
+            .await
+            .unwrap();
+        wallet
+            .add_wallet_record("type", "2", "two", Some(r#"{"name":"bob","age":"40"}"#))
+            .await
+            .unwrap();
+        wallet
+            .add_wallet_record("other", "3", "three", Some(r#"{"name":"alice"}"#))
+            .await
+            .unwrap();
+
+        async fn search(wallet: &InMemoryWallet, query: &str) -> Vec<String> {
+            let records = wallet
+                .iterate_wallet_records("type", query, "{}")
+                .await
+                .unwrap()
+                .collect()
+                .await
+                .unwrap();
+            records
+                .iter()
+                .map(|record| serde_json::from_str::<serde_json::Value>(record).unwrap()["value"].to_string())
+                .collect()
+        }
+
+        assert_eq!(search(&wallet, "{}").await, vec![r#""one""#, r#""two""#]);
+        assert_eq!(search(&wallet, r#"{"name":"alice"}"#).await, vec![r#""one""#]);
+        assert_eq!(search(&wallet, r#"{"age":{"$gt":"35"}}"#).await, vec![r#""two""#]);
+        assert_eq!(
+            search(&wallet, r#"{"$or":[{"name":"alice"},{"name":{"$like":"b%"}}]}"#).await,
+            vec![r#""one""#, r#""two""#]
+        );
+        assert_eq!(
+            search(&wallet, r#"{"$not":{"name":{"$in":["alice"]}}}"#).await,
+            vec![r#""two""#]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_transaction_is_rolled_back() {
+        let wallet = InMemoryWallet::new();
+        wallet
+            .add_wallet_record("type", "existing", "value", None)
+            .await
+            .unwrap();
+
+        let mut transaction = WalletTransaction::new();
+        transaction
+            .add_wallet_record("type", "new", "value", None)
+            .add_wallet_record("type", "existing", "value", None);
+        wallet.commit_transaction(transaction).await.unwrap_err();
+
+        let err = wallet.get_wallet_record("type", "new", "{}").await.unwrap_err();
+        assert_eq!(err.kind(), AriesVcxCoreErrorKind::WalletRecordNotFound);
+    }
+}
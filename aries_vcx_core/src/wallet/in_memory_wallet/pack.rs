@@ -0,0 +1,212 @@
+use serde::{Deserialize, Serialize};
+use sodiumoxide::crypto::{aead::chacha20poly1305_ietf, box_, sealedbox, sign};
+
+use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult};
+
+use super::crypto;
+
+// identical to the vdrtools pack format, messages packed here can be unpacked by an indy wallet and vice versa
+const PROTECTED_HEADER_ENC: &str = "xchacha20poly1305_ietf";
+const PROTECTED_HEADER_TYP: &str = "JWM/1.0";
+const PROTECTED_HEADER_ALG_AUTH: &str = "Authcrypt";
+const PROTECTED_HEADER_ALG_ANON: &str = "Anoncrypt";
+
+#[derive(Serialize, Deserialize, Debug)]
+struct Jwe {
+    protected: String,
+    iv: String,
+    ciphertext: String,
+    tag: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct Recipient {
+    encrypted_key: String,
+    header: Header,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct Header {
+    kid: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    iv: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sender: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct Protected {
+    enc: String,
+    typ: String,
+    alg: String,
+    recipients: Vec<Recipient>,
+}
+
+#[derive(Serialize, Debug)]
+struct UnpackedMessage {
+    message: String,
+    recipient_verkey: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sender_verkey: Option<String>,
+}
+
+/// Packs `msg` for every key of `receiver_keys`. Authcrypt is used when the sender verkey and signing key
+/// are provided, anoncrypt otherwise.
+pub(super) fn pack_message(
+    sender: Option<(&str, &sign::SecretKey)>,
+    receiver_keys: &[String],
+    msg: &[u8],
+) -> VcxCoreResult<Vec<u8>> {
+    if receiver_keys.is_empty() {
+        return Err(AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::InvalidInput,
+            "Empty RecipientKeys has been passed",
+        ));
+    }
+
+    let cek = chacha20poly1305_ietf::gen_key();
+
+    let mut recipients = Vec::with_capacity(receiver_keys.len());
+    for their_vk in receiver_keys {
+        let their_pk = crypto::verkey_to_curve25519(&crypto::decode_verkey(their_vk)?)?;
+
+        let recipient = match sender {
+            Some((sender_vk, sender_sk)) => {
+                let nonce = box_::gen_nonce();
+                let encrypted_key = box_::seal(&cek.0, &nonce, &their_pk, &crypto::signkey_to_curve25519(sender_sk)?);
+                let encrypted_sender = sealedbox::seal(sender_vk.as_bytes(), &their_pk);
+
+                Recipient {
+                    encrypted_key: encode(&encrypted_key),
+                    header: Header {
+                        kid: their_vk.to_string(),
+                        iv: Some(encode(&nonce.0)),
+                        sender: Some(encode(&encrypted_sender)),
+                    },
+                }
+            }
+            None => Recipient {
+                encrypted_key: encode(&sealedbox::seal(&cek.0, &their_pk)),
+                header: Header {
+                    kid: their_vk.to_string(),
+                    iv: None,
+                    sender: None,
+                },
+            },
+        };
+        recipients.push(recipient);
+    }
+
+    let protected = Protected {
+        enc: PROTECTED_HEADER_ENC.to_string(),
+        typ: PROTECTED_HEADER_TYP.to_string(),
+        alg: if sender.is_some() {
+            PROTECTED_HEADER_ALG_AUTH
+        } else {
+            PROTECTED_HEADER_ALG_ANON
+        }
+        .to_string(),
+        recipients,
+    };
+    let protected = encode(serde_json::to_string(&protected)?.as_bytes());
+
+    let nonce = chacha20poly1305_ietf::gen_nonce();
+    let mut ciphertext = msg.to_vec();
+    let tag = chacha20poly1305_ietf::seal_detached(&mut ciphertext, Some(protected.as_bytes()), &nonce, &cek);
+
+    let jwe = Jwe {
+        protected,
+        iv: encode(&nonce.0),
+        ciphertext: encode(&ciphertext),
+        tag: encode(&tag.0),
+    };
+
+    Ok(serde_json::to_vec(&jwe)?)
+}
+
+/// Unpacks a message packed by [pack_message], using the first recipient for which `find_signkey`
+/// returns a signing key.
+pub(super) fn unpack_message<F>(msg: &[u8], find_signkey: F) -> VcxCoreResult<Vec<u8>>
+where
+    F: Fn(&str) -> Option<sign::SecretKey>,
+{
+    let jwe: Jwe = serde_json::from_slice(msg)?;
+    let protected: Protected = serde_json::from_slice(&decode(&jwe.protected)?)?;
+
+    let (recipient, my_sk) = protected
+        .recipients
+        .into_iter()
+        .find_map(|recipient| find_signkey(&recipient.header.kid).map(|my_sk| (recipient, my_sk)))
+        .ok_or_else(|| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::WalletRecordNotFound,
+                "None of the message recipient keys is stored in the wallet",
+            )
+        })?;
+
+    let my_pk = crypto::verkey_to_curve25519(&crypto::decode_verkey(&recipient.header.kid)?)?;
+    let my_sk = crypto::signkey_to_curve25519(&my_sk)?;
+    let encrypted_key = decode(&recipient.encrypted_key)?;
+
+    let (sender_verkey, cek) = match (&recipient.header.sender, &recipient.header.iv) {
+        (Some(encrypted_sender), Some(iv)) => {
+            let sender_vk = sealedbox::open(&decode(encrypted_sender)?, &my_pk, &my_sk)
+                .map_err(|_| unpack_err("Failed to decrypt sender verkey"))?;
+            let sender_vk = String::from_utf8(sender_vk).map_err(|_| unpack_err("Sender verkey is not utf-8"))?;
+            let sender_pk = crypto::verkey_to_curve25519(&crypto::decode_verkey(&sender_vk)?)?;
+
+            let nonce = box_::Nonce::from_slice(&decode(iv)?).ok_or_else(|| unpack_err("Invalid recipient iv"))?;
+            let cek = box_::open(&encrypted_key, &nonce, &sender_pk, &my_sk)
+                .map_err(|_| unpack_err("Failed to decrypt content encryption key"))?;
+
+            (Some(sender_vk), cek)
+        }
+        (None, None) => {
+            let cek = sealedbox::open(&encrypted_key, &my_pk, &my_sk)
+                .map_err(|_| unpack_err("Failed to decrypt content encryption key"))?;
+
+            (None, cek)
+        }
+        _ => {
+            return Err(unpack_err(
+                "Recipient header must contain either both sender and iv or neither",
+            ))
+        }
+    };
+
+    let cek =
+        chacha20poly1305_ietf::Key::from_slice(&cek).ok_or_else(|| unpack_err("Invalid content encryption key"))?;
+    let nonce = chacha20poly1305_ietf::Nonce::from_slice(&decode(&jwe.iv)?).ok_or_else(|| unpack_err("Invalid iv"))?;
+    let tag = chacha20poly1305_ietf::Tag::from_slice(&decode(&jwe.tag)?).ok_or_else(|| unpack_err("Invalid tag"))?;
+
+    let mut message = decode(&jwe.ciphertext)?;
+    chacha20poly1305_ietf::open_detached(&mut message, Some(jwe.protected.as_bytes()), &tag, &nonce, &cek)
+        .map_err(|_| unpack_err("Failed to decrypt ciphertext"))?;
+
+    let unpacked = UnpackedMessage {
+        message: String::from_utf8(message).map_err(|_| unpack_err("Decrypted message is not utf-8"))?,
+        recipient_verkey: recipient.header.kid,
+        sender_verkey,
+    };
+
+    Ok(serde_json::to_vec(&unpacked)?)
+}
+
+fn encode(bytes: &[u8]) -> String {
+    base64::encode_config(bytes, base64::URL_SAFE)
+}
+
+fn decode(value: &str) -> VcxCoreResult<Vec<u8>> {
+    base64::decode_config(value, base64::URL_SAFE_NO_PAD).map_err(|err| {
+        AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::InvalidMessagePack,
+            format!("Invalid base64url value in packed message: {}", err),
+        )
+    })
+}
+
+fn unpack_err(msg: &str) -> AriesVcxCoreError {
+    AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::InvalidMessagePack, msg)
+}
@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult};
+
+/// Parsed form of a wallet query (WQL), evaluated against the tags of a single record.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Query {
+    And(Vec<Query>),
+    Or(Vec<Query>),
+    Not(Box<Query>),
+    Eq(String, String),
+    Neq(String, String),
+    Gt(String, String),
+    Gte(String, String),
+    Lt(String, String),
+    Lte(String, String),
+    Like(String, String),
+    In(String, Vec<String>),
+}
+
+impl Query {
+    pub(super) fn parse(query_json: &str) -> VcxCoreResult<Self> {
+        let query: Value = serde_json::from_str(query_json)
+            .map_err(|err| query_err(format!("Wallet query is not a valid JSON: {}", err)))?;
+        parse_object(&query)
+    }
+
+    pub(super) fn matches(&self, tags: &HashMap<String, String>) -> bool {
+        match self {
+            Query::And(queries) => queries.iter().all(|query| query.matches(tags)),
+            Query::Or(queries) => queries.iter().any(|query| query.matches(tags)),
+            Query::Not(query) => !query.matches(tags),
+            Query::Eq(name, value) => tags.get(name).map_or(false, |tag| tag == value),
+            Query::Neq(name, value) => tags.get(name).map_or(false, |tag| tag != value),
+            Query::Gt(name, value) => tags.get(name).map_or(false, |tag| tag > value),
+            Query::Gte(name, value) => tags.get(name).map_or(false, |tag| tag >= value),
+            Query::Lt(name, value) => tags.get(name).map_or(false, |tag| tag < value),
+            Query::Lte(name, value) => tags.get(name).map_or(false, |tag| tag <= value),
+            Query::Like(name, pattern) => tags.get(name).map_or(false, |tag| {
+                like(
+                    &tag.chars().collect::<Vec<char>>(),
+                    &pattern.chars().collect::<Vec<char>>(),
+                )
+            }),
+            Query::In(name, values) => tags.get(name).map_or(false, |tag| values.contains(tag)),
+        }
+    }
+}
+
+fn parse_object(query: &Value) -> VcxCoreResult<Query> {
+    let query = query
+        .as_object()
+        .ok_or_else(|| query_err(format!("Wallet query must be a JSON object, found: {}", query)))?;
+
+    let mut operators = Vec::with_capacity(query.len());
+    for (key, value) in query {
+        let operator = match key.as_str() {
+            "$and" => Query::And(parse_array(value)?),
+            "$or" => Query::Or(parse_array(value)?),
+            "$not" => Query::Not(Box::new(parse_object(value)?)),
+            tag_name => parse_tag_operator(tag_name, value)?,
+        };
+        operators.push(operator);
+    }
+
+    Ok(match operators.len() {
+        1 => operators.remove(0),
+        _ => Query::And(operators),
+    })
+}
+
+fn parse_array(value: &Value) -> VcxCoreResult<Vec<Query>> {
+    value
+        .as_array()
+        .ok_or_else(|| query_err(format!("Expected an array of wallet queries, found: {}", value)))?
+        .iter()
+        .map(parse_object)
+        .collect()
+}
+
+fn parse_tag_operator(tag_name: &str, value: &Value) -> VcxCoreResult<Query> {
+    let name = tag_name.to_string();
+
+    let (operator, operand) = match value {
+        Value::String(value) => return Ok(Query::Eq(name, value.to_string())),
+        Value::Object(object) if object.len() == 1 => object.iter().next(),
+        _ => None,
+    }
+    .ok_or_else(|| {
+        query_err(format!(
+            "Unsupported value for tag {} in wallet query: {}",
+            tag_name, value
+        ))
+    })?;
+
+    if operator == "$in" {
+        let values = operand
+            .as_array()
+            .and_then(|values| values.iter().map(|value| value.as_str().map(String::from)).collect())
+            .ok_or_else(|| query_err(format!("$in operator expects an array of strings, found: {}", operand)))?;
+        return Ok(Query::In(name, values));
+    }
+
+    let operand = operand
+        .as_str()
+        .ok_or_else(|| query_err(format!("{} operator expects a string, found: {}", operator, operand)))?
+        .to_string();
+
+    Ok(match operator.as_str() {
+        "$eq" => Query::Eq(name, operand),
+        "$neq" => Query::Neq(name, operand),
+        "$gt" => Query::Gt(name, operand),
+        "$gte" => Query::Gte(name, operand),
+        "$lt" => Query::Lt(name, operand),
+        "$lte" => Query::Lte(name, operand),
+        "$like" => Query::Like(name, operand),
+        _ => return Err(query_err(format!("Unknown wallet query operator: {}", operator))),
+    })
+}
+
+// SQL LIKE semantics, `%` matches any sequence of characters and `_` matches a single one
+fn like(value: &[char], pattern: &[char]) -> bool {
+    match pattern.split_first() {
+        None => value.is_empty(),
+        Some(('%', rest)) => (0..=value.len()).any(|skip| like(&value[skip..], rest)),
+        Some(('_', rest)) => !value.is_empty() && like(&value[1..], rest),
+        Some((c, rest)) => value.first() == Some(c) && like(&value[1..], rest),
+    }
+}
+
+fn query_err(msg: String) -> AriesVcxCoreError {
+    AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::InvalidInput, msg)
+}
@@ -1,7 +1,9 @@
+use std::collections::HashMap;
 use std::thread;
 
 use async_trait::async_trait;
 use futures::executor::block_on;
+use serde::Deserialize;
 use serde_json::Value;
 
 use crate::errors::error::{AriesVcxCoreError, VcxCoreResult};
@@ -11,7 +13,11 @@ use crate::{
 };
 use crate::{SearchHandle, WalletHandle};
 
-use super::base_wallet::BaseWallet;
+use super::base_wallet::{BaseWallet, WalletEntry, WalletTransaction};
+
+// types of the records vdrtools stores DIDs and keys as, exported as [WalletEntry::Did] and [WalletEntry::Key]
+const INDY_DID_RECORD_TYPE: &str = "Indy::Did";
+const INDY_KEY_RECORD_TYPE: &str = "Indy::Key";
 
 #[derive(Debug)]
 pub struct IndySdkWallet {
@@ -93,6 +99,10 @@ impl BaseWallet for IndySdkWallet {
         Ok(Box::new(iter))
     }
 
+    async fn commit_transaction(&self, transaction: WalletTransaction) -> VcxCoreResult<()> {
+        indy::wallet::commit_wallet_transaction(self.wallet_handle, transaction).await
+    }
+
     async fn sign(&self, my_vk: &str, msg: &[u8]) -> VcxCoreResult<Vec<u8>> {
         indy::signing::sign(self.wallet_handle, my_vk, msg).await
     }
@@ -108,6 +118,31 @@ impl BaseWallet for IndySdkWallet {
     async fn unpack_message(&self, msg: &[u8]) -> VcxCoreResult<Vec<u8>> {
         indy::signing::unpack_message(self.wallet_handle, msg).await
     }
+
+    async fn export_entries(&self) -> VcxCoreResult<Box<dyn AsyncFnIterator<Item = VcxCoreResult<WalletEntry>>>> {
+        let search = indy::wallet::open_search_all_wallet(self.wallet_handle).await?;
+        let iter = IndyWalletEntryIterator {
+            records: IndyWalletRecordIterator::new(self.wallet_handle, search),
+        };
+
+        Ok(Box::new(iter))
+    }
+
+    async fn import_entry(&self, entry: WalletEntry) -> VcxCoreResult<()> {
+        let (xtype, id, value, tags) = match entry {
+            WalletEntry::Did { did, verkey } => {
+                let value = json!({ "did": did, "verkey": verkey }).to_string();
+                (INDY_DID_RECORD_TYPE.to_string(), did, value, Default::default())
+            }
+            WalletEntry::Key { verkey, signkey } => {
+                let value = json!({ "verkey": verkey, "signkey": signkey }).to_string();
+                (INDY_KEY_RECORD_TYPE.to_string(), verkey, value, Default::default())
+            }
+            WalletEntry::Record { xtype, id, value, tags } => (xtype, id, value, tags),
+        };
+
+        indy::wallet::restore_wallet_record(self.wallet_handle, &xtype, &id, &value, tags).await
+    }
 }
 
 struct IndyWalletRecordIterator {
@@ -162,3 +197,67 @@ impl Drop for IndyWalletRecordIterator {
         });
     }
 }
+
+#[derive(Deserialize)]
+struct IndyExportedRecord {
+    #[serde(rename = "type")]
+    xtype: String,
+    id: String,
+    value: String,
+    #[serde(default)]
+    tags: Option<HashMap<String, String>>,
+}
+
+#[derive(Deserialize)]
+struct IndyDidValue {
+    did: String,
+    verkey: String,
+}
+
+#[derive(Deserialize)]
+struct IndyKeyValue {
+    verkey: String,
+    signkey: String,
+}
+
+/// Iterates over every record of a vdrtools wallet, translating the records vdrtools stores DIDs and keys
+/// as into their [WalletEntry] counterparts. Every other record, including the ones vdrtools keeps for itself,
+/// is exported as is.
+struct IndyWalletEntryIterator {
+    records: IndyWalletRecordIterator,
+}
+
+impl IndyWalletEntryIterator {
+    fn parse_entry(record_json: &str) -> VcxCoreResult<WalletEntry> {
+        let record: IndyExportedRecord = serde_json::from_str(record_json)?;
+
+        Ok(match record.xtype.as_str() {
+            INDY_DID_RECORD_TYPE => {
+                let IndyDidValue { did, verkey } = serde_json::from_str(&record.value)?;
+                WalletEntry::Did { did, verkey }
+            }
+            INDY_KEY_RECORD_TYPE => {
+                let IndyKeyValue { verkey, signkey } = serde_json::from_str(&record.value)?;
+                WalletEntry::Key { verkey, signkey }
+            }
+            _ => WalletEntry::Record {
+                xtype: record.xtype,
+                id: record.id,
+                value: record.value,
+                tags: record.tags.unwrap_or_default(),
+            },
+        })
+    }
+}
+
+#[async_trait]
+impl AsyncFnIterator for IndyWalletEntryIterator {
+    type Item = VcxCoreResult<WalletEntry>;
+
+    async fn next(&mut self) -> Option<Self::Item> {
+        self.records
+            .next()
+            .await
+            .map(|record_json| record_json.and_then(|record_json| Self::parse_entry(&record_json)))
+    }
+}
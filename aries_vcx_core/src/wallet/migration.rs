@@ -0,0 +1,329 @@
+//! Moves DIDs, keys and records between [BaseWallet] implementations, either directly with
+//! [migrate_wallet] or through an encrypted archive written by [export_wallet] and read by
+//! [import_wallet].
+//!
+//! The archive starts with a plain header (format version and key derivation salt), followed by
+//! chunks of newline separated JSON serialized [WalletEntry]s. Every chunk is encrypted with
+//! chacha20poly1305 under a key derived from the passphrase with argon2i; the header and a flag
+//! marking the last chunk are authenticated with each of them, so a truncated or tampered
+//! archive fails to import.
+//!
+//! Importing is not atomic, entries are stored one by one. If an import fails midway, the
+//! destination wallet holds the entries imported so far, so it should be discarded.
+
+use std::io::{Read, Write};
+use std::os::raw::c_ulonglong;
+
+use sodiumoxide::crypto::aead::chacha20poly1305_ietf;
+use sodiumoxide::randombytes;
+
+use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult};
+use crate::utils::async_fn_iterator::AsyncFnIterator;
+
+use super::base_wallet::{BaseWallet, WalletEntry};
+use super::sodium::{
+    crypto_pwhash, crypto_pwhash_alg_argon2i13, crypto_pwhash_argon2i_memlimit_moderate,
+    crypto_pwhash_argon2i_opslimit_moderate,
+};
+
+const ARCHIVE_VERSION: u32 = 1;
+// crypto_pwhash_argon2i_SALTBYTES
+const SALT_BYTES: usize = 16;
+const HEADER_BYTES: usize = 4 + SALT_BYTES;
+// entries are buffered up to this size before being encrypted, a single larger entry gets a chunk of its own
+const CHUNK_BYTES: usize = 64 * 1024;
+
+/// Copies every entry exported from `source` into `destination`, returns the number of entries copied.
+pub async fn migrate_wallet(source: &dyn BaseWallet, destination: &dyn BaseWallet) -> VcxCoreResult<usize> {
+    let mut entries = source.export_entries().await?;
+
+    let mut count = 0;
+    while let Some(entry) = entries.next().await {
+        destination.import_entry(entry?).await?;
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+/// Writes every entry exported from `wallet` into `writer` as an archive encrypted with `passphrase`,
+/// returns the number of entries written.
+pub async fn export_wallet<W: Write>(
+    wallet: &dyn BaseWallet,
+    writer: &mut W,
+    passphrase: &str,
+) -> VcxCoreResult<usize> {
+    sodiumoxide::init().ok();
+
+    let mut header = [0u8; HEADER_BYTES];
+    header[..4].copy_from_slice(&ARCHIVE_VERSION.to_be_bytes());
+    randombytes::randombytes_into(&mut header[4..]);
+    writer.write_all(&header).map_err(io_err)?;
+
+    let mut chunk_writer = ChunkWriter {
+        writer,
+        key: derive_key(passphrase, &header[4..])?,
+        header,
+        counter: 0,
+    };
+
+    let mut entries = wallet.export_entries().await?;
+    let mut buffer = Vec::with_capacity(CHUNK_BYTES);
+    let mut count = 0;
+    while let Some(entry) = entries.next().await {
+        let mut line = serde_json::to_vec(&entry?)?;
+        line.push(b'\n');
+
+        if !buffer.is_empty() && buffer.len() + line.len() > CHUNK_BYTES {
+            chunk_writer.write_chunk(&mut buffer, false)?;
+            buffer.clear();
+        }
+        buffer.extend_from_slice(&line);
+        count += 1;
+    }
+    // the last chunk is always written, even if empty, so that a truncated archive can be detected
+    chunk_writer.write_chunk(&mut buffer, true)?;
+    chunk_writer.writer.flush().map_err(io_err)?;
+
+    Ok(count)
+}
+
+/// Reads an archive written by [export_wallet] from `reader` and stores all of its entries into `wallet`,
+/// returns the number of entries imported.
+pub async fn import_wallet<R: Read>(wallet: &dyn BaseWallet, reader: &mut R, passphrase: &str) -> VcxCoreResult<usize> {
+    sodiumoxide::init().ok();
+
+    let mut header = [0u8; HEADER_BYTES];
+    reader.read_exact(&mut header).map_err(io_err)?;
+
+    let mut version = [0u8; 4];
+    version.copy_from_slice(&header[..4]);
+    let version = u32::from_be_bytes(version);
+    if version != ARCHIVE_VERSION {
+        return Err(AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::InvalidInput,
+            format!("Unsupported wallet archive version: {}", version),
+        ));
+    }
+
+    let mut chunk_reader = ChunkReader {
+        reader,
+        key: derive_key(passphrase, &header[4..])?,
+        header,
+        counter: 0,
+    };
+
+    let mut count = 0;
+    loop {
+        let (chunk, last) = chunk_reader.read_chunk()?;
+
+        for line in chunk.split(|byte| *byte == b'\n').filter(|line| !line.is_empty()) {
+            let entry: WalletEntry = serde_json::from_slice(line)?;
+            wallet.import_entry(entry).await?;
+            count += 1;
+        }
+
+        if last {
+            return Ok(count);
+        }
+    }
+}
+
+struct ChunkWriter<'a, W: Write> {
+    writer: &'a mut W,
+    key: chacha20poly1305_ietf::Key,
+    header: [u8; HEADER_BYTES],
+    counter: u64,
+}
+
+impl<'a, W: Write> ChunkWriter<'a, W> {
+    fn write_chunk(&mut self, chunk: &mut Vec<u8>, last: bool) -> VcxCoreResult<()> {
+        let nonce = chunk_nonce(self.counter);
+        let ad = chunk_ad(&self.header, last);
+        let tag = chacha20poly1305_ietf::seal_detached(chunk, Some(ad.as_slice()), &nonce, &self.key);
+        self.counter += 1;
+
+        let len = (chunk.len() + chacha20poly1305_ietf::TAGBYTES) as u32;
+        self.writer.write_all(&len.to_be_bytes()).map_err(io_err)?;
+        self.writer.write_all(chunk).map_err(io_err)?;
+        self.writer.write_all(&tag.0).map_err(io_err)?;
+
+        Ok(())
+    }
+}
+
+struct ChunkReader<'a, R: Read> {
+    reader: &'a mut R,
+    key: chacha20poly1305_ietf::Key,
+    header: [u8; HEADER_BYTES],
+    counter: u64,
+}
+
+impl<'a, R: Read> ChunkReader<'a, R> {
+    // returns the decrypted chunk and whether it is the last one of the archive
+    fn read_chunk(&mut self) -> VcxCoreResult<(Vec<u8>, bool)> {
+        let mut len = [0u8; 4];
+        self.reader.read_exact(&mut len).map_err(|_| truncated_archive())?;
+        let len = u32::from_be_bytes(len) as usize;
+        if len < chacha20poly1305_ietf::TAGBYTES {
+            return Err(corrupted_archive());
+        }
+
+        // not pre-allocated, so that a corrupted length does not exhaust memory
+        let mut chunk = Vec::new();
+        self.reader
+            .by_ref()
+            .take(len as u64)
+            .read_to_end(&mut chunk)
+            .map_err(io_err)?;
+        if chunk.len() != len {
+            return Err(truncated_archive());
+        }
+        let tag = chunk.split_off(len - chacha20poly1305_ietf::TAGBYTES);
+        let tag = chacha20poly1305_ietf::Tag::from_slice(&tag).ok_or_else(corrupted_archive)?;
+
+        let nonce = chunk_nonce(self.counter);
+        self.counter += 1;
+
+        for last in [false, true] {
+            let mut plaintext = chunk.clone();
+            let ad = chunk_ad(&self.header, last);
+            if chacha20poly1305_ietf::open_detached(&mut plaintext, Some(ad.as_slice()), &tag, &nonce, &self.key)
+                .is_ok()
+            {
+                return Ok((plaintext, last));
+            }
+        }
+
+        Err(corrupted_archive())
+    }
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> VcxCoreResult<chacha20poly1305_ietf::Key> {
+    let mut key = [0u8; chacha20poly1305_ietf::KEYBYTES];
+
+    let res = unsafe {
+        crypto_pwhash(
+            key.as_mut_ptr(),
+            key.len() as c_ulonglong,
+            passphrase.as_ptr(),
+            passphrase.len() as c_ulonglong,
+            salt.as_ptr(),
+            crypto_pwhash_argon2i_opslimit_moderate() as c_ulonglong,
+            crypto_pwhash_argon2i_memlimit_moderate(),
+            crypto_pwhash_alg_argon2i13(),
+        )
+    };
+    if res != 0 {
+        return Err(AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::InvalidState,
+            "Failed to derive wallet archive key from passphrase",
+        ));
+    }
+
+    Ok(chacha20poly1305_ietf::Key(key))
+}
+
+// the key is derived from a random salt for every archive, so a chunk counter is a safe nonce
+fn chunk_nonce(counter: u64) -> chacha20poly1305_ietf::Nonce {
+    let mut nonce = [0u8; chacha20poly1305_ietf::NONCEBYTES];
+    nonce[chacha20poly1305_ietf::NONCEBYTES - 8..].copy_from_slice(&counter.to_be_bytes());
+    chacha20poly1305_ietf::Nonce(nonce)
+}
+
+fn chunk_ad(header: &[u8], last: bool) -> Vec<u8> {
+    let mut ad = header.to_vec();
+    ad.push(last as u8);
+    ad
+}
+
+fn io_err(err: std::io::Error) -> AriesVcxCoreError {
+    AriesVcxCoreError::from_msg(
+        AriesVcxCoreErrorKind::IOError,
+        format!("Failed to access wallet archive: {}", err),
+    )
+}
+
+fn truncated_archive() -> AriesVcxCoreError {
+    AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::InvalidInput, "Wallet archive is truncated")
+}
+
+fn corrupted_archive() -> AriesVcxCoreError {
+    AriesVcxCoreError::from_msg(
+        AriesVcxCoreErrorKind::WalletAccessFailed,
+        "Wallet archive is corrupted or the passphrase is invalid",
+    )
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use crate::errors::error::AriesVcxCoreErrorKind;
+    use crate::wallet::base_wallet::BaseWallet;
+    use crate::wallet::in_memory_wallet::InMemoryWallet;
+
+    use super::{export_wallet, import_wallet, migrate_wallet};
+
+    async fn source_wallet() -> (InMemoryWallet, String, String) {
+        let wallet = InMemoryWallet::new();
+        let (did, verkey) = wallet.create_and_store_my_did(None, None).await.unwrap();
+        wallet
+            .add_wallet_record("type", "id", "value", Some(r#"{"tag":"tag value"}"#))
+            .await
+            .unwrap();
+        (wallet, did, verkey)
+    }
+
+    async fn assert_migrated(wallet: &InMemoryWallet, did: &str, verkey: &str) {
+        assert_eq!(wallet.key_for_local_did(did).await.unwrap(), verkey);
+
+        let signature = wallet.sign(verkey, b"msg").await.unwrap();
+        assert!(wallet.verify(verkey, b"msg", &signature).await.unwrap());
+
+        let record: serde_json::Value = serde_json::from_str(
+            &wallet
+                .get_wallet_record("type", "id", r#"{"retrieveTags":true}"#)
+                .await
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(record["value"], "value");
+        assert_eq!(record["tags"], json!({"tag": "tag value"}));
+    }
+
+    #[tokio::test]
+    async fn test_migrate_wallet() {
+        let (source, did, verkey) = source_wallet().await;
+        let destination = InMemoryWallet::new();
+
+        assert_eq!(migrate_wallet(&source, &destination).await.unwrap(), 3);
+
+        assert_migrated(&destination, &did, &verkey).await;
+    }
+
+    #[tokio::test]
+    async fn test_export_import_wallet() {
+        let (source, did, verkey) = source_wallet().await;
+        let mut archive = Vec::new();
+        assert_eq!(export_wallet(&source, &mut archive, "passphrase").await.unwrap(), 3);
+
+        let err = import_wallet(&InMemoryWallet::new(), &mut archive.as_slice(), "wrong passphrase")
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxCoreErrorKind::WalletAccessFailed);
+
+        let err = import_wallet(&InMemoryWallet::new(), &mut &archive[..archive.len() - 1], "passphrase")
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxCoreErrorKind::InvalidInput);
+
+        let destination = InMemoryWallet::new();
+        assert_eq!(
+            import_wallet(&destination, &mut archive.as_slice(), "passphrase")
+                .await
+                .unwrap(),
+            3
+        );
+        assert_migrated(&destination, &did, &verkey).await;
+    }
+}
@@ -1,4 +1,7 @@
 pub mod agency_client_wallet;
 pub mod base_wallet;
+pub mod in_memory_wallet;
 #[cfg(feature = "vdrtools")]
 pub mod indy_wallet;
+pub mod migration;
+mod sodium;
@@ -0,0 +1,31 @@
+//! libsodium functions not exposed by the sodiumoxide wrappers, bound directly against libsodium (same as
+//! indy-utils does).
+
+use std::os::raw::{c_int, c_ulonglong};
+
+use sodiumoxide::crypto::{box_, sign};
+
+extern "C" {
+    pub(super) fn crypto_sign_ed25519_pk_to_curve25519(
+        curve25519_pk: *mut [u8; box_::PUBLICKEYBYTES],
+        ed25519_pk: *const [u8; sign::PUBLICKEYBYTES],
+    ) -> c_int;
+    pub(super) fn crypto_sign_ed25519_sk_to_curve25519(
+        curve25519_sk: *mut [u8; box_::SECRETKEYBYTES],
+        ed25519_sk: *const [u8; sign::SECRETKEYBYTES],
+    ) -> c_int;
+
+    pub(super) fn crypto_pwhash_alg_argon2i13() -> c_int;
+    pub(super) fn crypto_pwhash_argon2i_opslimit_moderate() -> usize;
+    pub(super) fn crypto_pwhash_argon2i_memlimit_moderate() -> usize;
+    pub(super) fn crypto_pwhash(
+        out: *mut u8,
+        outlen: c_ulonglong,
+        passwd: *const u8,
+        passwdlen: c_ulonglong,
+        salt: *const u8,
+        opslimit: c_ulonglong,
+        memlimit: usize,
+        alg: c_int,
+    ) -> c_int;
+}
@@ -0,0 +1,139 @@
+use did_resolver::{
+    did_doc_builder::schema::{
+        did_doc::DidDocument, types::multibase::Multibase, verification_method::VerificationMethod,
+    },
+    did_parser::{Did, DidUrl},
+};
+
+use public_key::{Key, KeyType};
+
+use crate::error::DidKeyError;
+
+const DID_KEY_METHOD: &str = "key";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DidKey {
+    did: Did,
+    key: Key,
+}
+
+impl DidKey {
+    pub fn parse(did: Did) -> Result<Self, DidKeyError> {
+        if did.method() != DID_KEY_METHOD {
+            return Err(DidKeyError::MethodNotSupported(did.method().to_string()));
+        }
+        let key = Key::from_fingerprint(did.id())?;
+        Ok(Self { did, key })
+    }
+
+    pub fn from_key(key: Key) -> Result<Self, DidKeyError> {
+        let did = Did::parse(format!("did:{}:{}", DID_KEY_METHOD, key.fingerprint()))?;
+        Ok(Self { did, key })
+    }
+
+    // Wallet verkeys are base58 encoded raw Ed25519 public keys
+    pub fn from_verkey(verkey: &str) -> Result<Self, DidKeyError> {
+        Self::from_key(Key::from_base58(KeyType::Ed25519, verkey)?)
+    }
+
+    pub fn did(&self) -> &Did {
+        &self.did
+    }
+
+    pub fn key(&self) -> &Key {
+        &self.key
+    }
+
+    pub fn verkey(&self) -> String {
+        self.key.base58()
+    }
+
+    pub fn to_did_document(&self) -> Result<DidDocument, DidKeyError> {
+        let vm = self.verification_method(&self.key)?;
+        let reference = vm.id().clone();
+        let builder = DidDocument::builder(self.did.clone()).add_verification_method(vm);
+
+        let builder = match self.key.key_type() {
+            KeyType::Ed25519 => {
+                let key_agreement_vm = self.verification_method(&self.key.to_x25519()?)?;
+                let key_agreement_reference = key_agreement_vm.id().clone();
+                builder
+                    .add_verification_method(key_agreement_vm)
+                    .add_authentication_reference(reference.clone())
+                    .add_assertion_method_reference(reference.clone())
+                    .add_capability_invocation_refrence(reference.clone())
+                    .add_capability_delegation_refrence(reference)
+                    .add_key_agreement_refrence(key_agreement_reference)
+            }
+            KeyType::X25519 => builder.add_key_agreement_refrence(reference),
+        };
+        Ok(builder.build())
+    }
+
+    fn verification_method(&self, key: &Key) -> Result<VerificationMethod, DidKeyError> {
+        let fingerprint = key.fingerprint();
+        let id = DidUrl::parse(format!("{}#{}", self.did.did(), fingerprint))?;
+        Ok(VerificationMethod::builder(
+            id,
+            self.did.clone(),
+            key.key_type().verification_method_type().to_string(),
+        )
+        .add_public_key_multibase(Multibase::new(fingerprint)?)
+        .build())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VERKEY: &str = "8HH5gYEeNc3z7PYXmd54d4x6qAfCNrqQqEB3nS7Zfu7K";
+    const DID_KEY: &str = "did:key:z6MkmjY8GnV5i9YTDtPETC2uUAW6ejw3nk5mXF5yci5ab7th";
+
+    #[test]
+    fn test_did_key_from_verkey() {
+        let did_key = DidKey::from_verkey(VERKEY).unwrap();
+        assert_eq!(did_key.did().did(), DID_KEY);
+    }
+
+    #[test]
+    fn test_did_key_parse() {
+        let did_key = DidKey::parse(Did::parse(DID_KEY.to_string()).unwrap()).unwrap();
+        assert_eq!(did_key.key().key_type(), KeyType::Ed25519);
+        assert_eq!(did_key.verkey(), VERKEY);
+    }
+
+    #[test]
+    fn test_did_key_parse_wrong_method() {
+        let did =
+            Did::parse("did:peer:0z6MkmjY8GnV5i9YTDtPETC2uUAW6ejw3nk5mXF5yci5ab7th".to_string())
+                .unwrap();
+        assert!(matches!(
+            DidKey::parse(did),
+            Err(DidKeyError::MethodNotSupported(_))
+        ));
+    }
+
+    #[test]
+    fn test_did_key_to_did_document() {
+        let did_key = DidKey::parse(
+            Did::parse("did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK".to_string())
+                .unwrap(),
+        )
+        .unwrap();
+        let did_doc = did_key.to_did_document().unwrap();
+
+        assert_eq!(did_doc.id(), did_key.did());
+        assert_eq!(did_doc.verification_method().len(), 2);
+        assert_eq!(did_doc.authentication().len(), 1);
+        assert_eq!(did_doc.key_agreement().len(), 1);
+        assert_eq!(
+            did_doc.verification_method()[1].id(),
+            &DidUrl::parse(
+                "did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK#z6LSj72tK8brWgZja8NLRwPigth2T9QRiG1uH9oKZuKjdh9p"
+                    .to_string()
+            )
+            .unwrap()
+        );
+    }
+}
@@ -0,0 +1,16 @@
+use did_resolver::did_parser::ParseError;
+use thiserror::Error;
+
+use super::DidKeyError;
+
+#[derive(Error, Debug)]
+pub enum ParsingErrorSource {
+    #[error("DID parsing error: {0}")]
+    DidParserError(#[from] ParseError),
+}
+
+impl From<ParseError> for DidKeyError {
+    fn from(error: ParseError) -> Self {
+        DidKeyError::ParsingError(ParsingErrorSource::DidParserError(error))
+    }
+}
@@ -0,0 +1,3 @@
+pub mod did_key;
+pub mod error;
+pub mod resolution;
@@ -0,0 +1,106 @@
+use did_resolver::did_parser::Did;
+use did_resolver::shared_types::media_type::MediaType;
+use did_resolver::traits::resolvable::{resolution_options::DidResolutionOptions, DidResolvable};
+use did_resolver_key::{did_key::DidKey, error::DidKeyError, resolution::resolver::DidKeyResolver};
+use did_resolver_registry::ResolverRegistry;
+
+const DID_KEY_ED25519: &str = "did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK";
+const DID_KEY_X25519: &str = "did:key:z6LSj72tK8brWgZja8NLRwPigth2T9QRiG1uH9oKZuKjdh9p";
+
+fn downcast_error(err: Box<dyn std::error::Error + Send + Sync>) -> DidKeyError {
+    *err.downcast::<DidKeyError>().unwrap()
+}
+
+#[tokio::test]
+async fn test_did_key_resolve_ed25519() {
+    let resolver = DidKeyResolver::new();
+    let did = Did::parse(DID_KEY_ED25519.to_string()).unwrap();
+
+    let output = resolver
+        .resolve(&did, &DidResolutionOptions::default())
+        .await
+        .unwrap();
+
+    let did_doc = output.did_document();
+    assert_eq!(did_doc.id(), &did);
+    assert_eq!(did_doc.verification_method().len(), 2);
+    assert_eq!(
+        did_doc.verification_method()[0].verification_method_type(),
+        "Ed25519VerificationKey2020"
+    );
+    assert_eq!(
+        did_doc.verification_method()[1].verification_method_type(),
+        "X25519KeyAgreementKey2020"
+    );
+    assert_eq!(did_doc.authentication().len(), 1);
+    assert_eq!(did_doc.assertion_method().len(), 1);
+    assert_eq!(did_doc.key_agreement().len(), 1);
+}
+
+#[tokio::test]
+async fn test_did_key_resolve_x25519() {
+    let resolver = DidKeyResolver::new();
+    let did = Did::parse(DID_KEY_X25519.to_string()).unwrap();
+
+    let output = resolver
+        .resolve(&did, &DidResolutionOptions::default())
+        .await
+        .unwrap();
+
+    let did_doc = output.did_document();
+    assert_eq!(did_doc.verification_method().len(), 1);
+    assert_eq!(did_doc.key_agreement().len(), 1);
+    assert!(did_doc.authentication().is_empty());
+}
+
+#[tokio::test]
+async fn test_did_key_resolve_unsupported_method() {
+    let resolver = DidKeyResolver::new();
+    let did = Did::parse("did:web:example.com".to_string()).unwrap();
+
+    let err = resolver
+        .resolve(&did, &DidResolutionOptions::default())
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        downcast_error(err),
+        DidKeyError::MethodNotSupported(_)
+    ));
+}
+
+#[tokio::test]
+async fn test_did_key_resolve_unsupported_representation() {
+    let resolver = DidKeyResolver::new();
+    let did = Did::parse(DID_KEY_ED25519.to_string()).unwrap();
+    let options = DidResolutionOptions::default().set_accept(MediaType::DidLdJson);
+
+    let err = resolver.resolve(&did, &options).await.unwrap_err();
+
+    assert!(matches!(
+        downcast_error(err),
+        DidKeyError::RepresentationNotSupported(_)
+    ));
+}
+
+#[tokio::test]
+async fn test_did_key_from_verkey_resolves_through_registry() {
+    let mut registry = ResolverRegistry::new();
+    registry.register_resolver("key".to_string(), Box::new(DidKeyResolver::new()));
+
+    let verkey = "8HH5gYEeNc3z7PYXmd54d4x6qAfCNrqQqEB3nS7Zfu7K";
+    let did_key = DidKey::from_verkey(verkey).unwrap();
+
+    let output = registry
+        .resolve(did_key.did(), &DidResolutionOptions::default())
+        .await
+        .unwrap();
+
+    assert_eq!(output.did_document().id(), did_key.did());
+    assert_eq!(
+        DidKey::parse(output.did_document().id().clone())
+            .unwrap()
+            .verkey(),
+        verkey
+    );
+}
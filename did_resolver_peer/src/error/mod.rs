@@ -0,0 +1,30 @@
+pub mod parsing;
+
+use did_resolver::did_doc_builder::error::DidDocumentBuilderError;
+use public_key::PublicKeyError;
+use thiserror::Error;
+
+use self::parsing::ParsingErrorSource;
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum DidPeerError {
+    #[error("DID method not supported: {0}")]
+    MethodNotSupported(String),
+    #[error("Representation not supported: {0}")]
+    RepresentationNotSupported(String),
+    #[error("Invalid DID: {0}")]
+    InvalidDid(String),
+    #[error("Numalgo not supported: {0}")]
+    NumalgoNotSupported(char),
+    #[error("Unsupported element purpose: {0}")]
+    UnsupportedPurpose(char),
+    #[error("Public key error: {0}")]
+    PublicKeyError(#[from] PublicKeyError),
+    #[error("Parsing error: {0}")]
+    ParsingError(#[from] ParsingErrorSource),
+    #[error("DID document builder error: {0}")]
+    DidDocumentBuilderError(#[from] DidDocumentBuilderError),
+    #[error(transparent)]
+    Other(#[from] Box<dyn std::error::Error + Send + Sync>),
+}
@@ -0,0 +1,32 @@
+use did_resolver::did_parser::ParseError;
+use thiserror::Error;
+
+use super::DidPeerError;
+
+#[derive(Error, Debug)]
+pub enum ParsingErrorSource {
+    #[error("JSON parsing error: {0}")]
+    JsonError(#[from] serde_json::Error),
+    #[error("Base64 decoding error: {0}")]
+    Base64Error(#[from] base64::DecodeError),
+    #[error("DID parsing error: {0}")]
+    DidParserError(#[from] ParseError),
+}
+
+impl From<serde_json::Error> for DidPeerError {
+    fn from(error: serde_json::Error) -> Self {
+        DidPeerError::ParsingError(ParsingErrorSource::JsonError(error))
+    }
+}
+
+impl From<base64::DecodeError> for DidPeerError {
+    fn from(error: base64::DecodeError) -> Self {
+        DidPeerError::ParsingError(ParsingErrorSource::Base64Error(error))
+    }
+}
+
+impl From<ParseError> for DidPeerError {
+    fn from(error: ParseError) -> Self {
+        DidPeerError::ParsingError(ParsingErrorSource::DidParserError(error))
+    }
+}
@@ -0,0 +1,44 @@
+use did_resolver::did_parser::Did;
+use public_key::Key;
+
+use crate::{error::DidPeerError, numalgos::Purpose, service::PeerService};
+
+pub fn generate_numalgo2(
+    agreement_keys: &[Key],
+    authentication_keys: &[Key],
+    service: Option<&PeerService>,
+) -> Result<Did, DidPeerError> {
+    let mut did = "did:peer:2".to_string();
+    for key in agreement_keys {
+        append_element(&mut did, Purpose::Encryption, &key.fingerprint());
+    }
+    for key in authentication_keys {
+        append_element(&mut did, Purpose::Verification, &key.fingerprint());
+    }
+    if let Some(service) = service {
+        append_element(&mut did, Purpose::Service, &service.encode()?);
+    }
+    Ok(Did::parse(did)?)
+}
+
+fn append_element(did: &mut String, purpose: Purpose, value: &str) {
+    did.push('.');
+    did.push(purpose.code());
+    did.push_str(value);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_numalgo2_without_service() {
+        let key =
+            Key::from_fingerprint("z6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V").unwrap();
+        let did = generate_numalgo2(&[], &[key], None).unwrap();
+        assert_eq!(
+            did.did(),
+            "did:peer:2.Vz6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V"
+        );
+    }
+}
@@ -0,0 +1,5 @@
+pub mod error;
+pub mod generation;
+mod numalgos;
+pub mod resolution;
+pub mod service;
@@ -0,0 +1,30 @@
+mod numalgo0;
+mod numalgo2;
+
+use did_resolver::{
+    did_doc_builder::schema::{
+        types::multibase::Multibase, verification_method::VerificationMethod,
+    },
+    did_parser::{Did, DidUrl},
+};
+use public_key::Key;
+
+use crate::error::DidPeerError;
+
+pub(crate) use numalgo0::resolve_numalgo0;
+pub(crate) use numalgo2::{resolve_numalgo2, Purpose};
+
+fn verification_method(
+    did: &Did,
+    fragment: &str,
+    key: &Key,
+) -> Result<VerificationMethod, DidPeerError> {
+    let id = DidUrl::parse(format!("{}#{}", did.did(), fragment))?;
+    Ok(VerificationMethod::builder(
+        id,
+        did.clone(),
+        key.key_type().verification_method_type().to_string(),
+    )
+    .add_public_key_multibase(Multibase::new(key.fingerprint())?)
+    .build())
+}
@@ -0,0 +1,53 @@
+use did_resolver::{did_doc_builder::schema::did_doc::DidDocument, did_parser::Did};
+use public_key::{Key, KeyType};
+
+use crate::error::DidPeerError;
+
+use super::verification_method;
+
+// Numalgo 0 wraps a single inception key, resolved the same way as did:key
+pub(crate) fn resolve_numalgo0(did: &Did) -> Result<DidDocument, DidPeerError> {
+    let multibase = did
+        .id()
+        .strip_prefix('0')
+        .ok_or_else(|| DidPeerError::InvalidDid(did.to_string()))?;
+    let key = Key::from_fingerprint(multibase)?;
+    let vm = verification_method(did, multibase, &key)?;
+    let reference = vm.id().clone();
+
+    let builder = DidDocument::builder(did.clone()).add_verification_method(vm);
+    let builder = match key.key_type() {
+        KeyType::Ed25519 => builder
+            .add_authentication_reference(reference.clone())
+            .add_assertion_method_reference(reference),
+        KeyType::X25519 => builder.add_key_agreement_refrence(reference),
+    };
+    Ok(builder.build())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_numalgo0() {
+        let did =
+            Did::parse("did:peer:0z6MkmjY8GnV5i9YTDtPETC2uUAW6ejw3nk5mXF5yci5ab7th".to_string())
+                .unwrap();
+        let did_doc = resolve_numalgo0(&did).unwrap();
+        assert_eq!(did_doc.id(), &did);
+        assert_eq!(did_doc.verification_method().len(), 1);
+        assert_eq!(
+            did_doc.verification_method()[0].verification_method_type(),
+            "Ed25519VerificationKey2020"
+        );
+        assert_eq!(did_doc.authentication().len(), 1);
+        assert!(did_doc.key_agreement().is_empty());
+    }
+
+    #[test]
+    fn test_resolve_numalgo0_invalid_key() {
+        let did = Did::parse("did:peer:0z6Mkinvalid".to_string()).unwrap();
+        assert!(resolve_numalgo0(&did).is_err());
+    }
+}
@@ -0,0 +1,161 @@
+use did_resolver::{
+    did_doc_builder::schema::{
+        did_doc::{DidDocument, DidDocumentBuilder},
+        types::uri::Uri,
+    },
+    did_parser::{Did, DidUrl},
+};
+use public_key::Key;
+
+use crate::{error::DidPeerError, service::PeerService};
+
+use super::verification_method;
+
+// Element purpose codes
+// https://identity.foundation/peer-did-method-spec/#generating-a-didpeer2
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Purpose {
+    Assertion,
+    Encryption,
+    Verification,
+    CapabilityInvocation,
+    CapabilityDelegation,
+    Service,
+}
+
+impl Purpose {
+    pub(crate) fn code(&self) -> char {
+        match self {
+            Purpose::Assertion => 'A',
+            Purpose::Encryption => 'E',
+            Purpose::Verification => 'V',
+            Purpose::CapabilityInvocation => 'I',
+            Purpose::CapabilityDelegation => 'D',
+            Purpose::Service => 'S',
+        }
+    }
+
+    fn from_code(code: char) -> Result<Self, DidPeerError> {
+        match code {
+            'A' => Ok(Purpose::Assertion),
+            'E' => Ok(Purpose::Encryption),
+            'V' => Ok(Purpose::Verification),
+            'I' => Ok(Purpose::CapabilityInvocation),
+            'D' => Ok(Purpose::CapabilityDelegation),
+            'S' => Ok(Purpose::Service),
+            _ => Err(DidPeerError::UnsupportedPurpose(code)),
+        }
+    }
+}
+
+pub(crate) fn resolve_numalgo2(did: &Did) -> Result<DidDocument, DidPeerError> {
+    let elements = did
+        .id()
+        .strip_prefix("2.")
+        .ok_or_else(|| DidPeerError::InvalidDid(did.to_string()))?;
+
+    let mut builder = DidDocument::builder(did.clone());
+    let mut key_index = 0;
+    let mut service_index = 0;
+
+    for element in elements.split('.') {
+        let mut chars = element.chars();
+        let purpose = chars
+            .next()
+            .ok_or_else(|| DidPeerError::InvalidDid(did.to_string()))?;
+        let value = chars.as_str();
+
+        match Purpose::from_code(purpose)? {
+            Purpose::Service => {
+                for service in PeerService::decode(value)? {
+                    let fragment = match service_index {
+                        0 => "service".to_string(),
+                        index => format!("service-{}", index),
+                    };
+                    let id = Uri::new(&format!("{}#{}", did.did(), fragment))?;
+                    builder = builder.add_service(service.to_service(id)?);
+                    service_index += 1;
+                }
+            }
+            key_purpose => {
+                key_index += 1;
+                let key = Key::from_fingerprint(value)?;
+                let vm = verification_method(did, &format!("key-{}", key_index), &key)?;
+                let reference = vm.id().clone();
+                builder =
+                    add_key_reference(builder.add_verification_method(vm), key_purpose, reference);
+            }
+        }
+    }
+
+    Ok(builder.build())
+}
+
+fn add_key_reference(
+    builder: DidDocumentBuilder,
+    purpose: Purpose,
+    reference: DidUrl,
+) -> DidDocumentBuilder {
+    match purpose {
+        Purpose::Assertion => builder.add_assertion_method_reference(reference),
+        Purpose::Encryption => builder.add_key_agreement_refrence(reference),
+        Purpose::Verification => builder.add_authentication_reference(reference),
+        Purpose::CapabilityInvocation => builder.add_capability_invocation_refrence(reference),
+        Purpose::CapabilityDelegation => builder.add_capability_delegation_refrence(reference),
+        Purpose::Service => builder,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // https://identity.foundation/peer-did-method-spec/#example-peer-did-2
+    const DID_PEER_2: &str = "did:peer:2.Ez6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc.Vz6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V.Vz6MkgoLTnTypo3tDRwCkZXSccTPHRLhF4ZnjhueYAFpEX6vg.SeyJ0IjoiZG0iLCJzIjoiaHR0cHM6Ly9leGFtcGxlLmNvbS9lbmRwb2ludCIsInIiOlsiZGlkOmV4YW1wbGU6c29tZW1lZGlhdG9yI3NvbWVrZXkiXSwiYSI6WyJkaWRjb21tL3YyIiwiZGlkY29tbS9haXAyO2Vudj1yZmM1ODciXX0";
+
+    #[test]
+    fn test_resolve_numalgo2() {
+        let did = Did::parse(DID_PEER_2.to_string()).unwrap();
+        let did_doc = resolve_numalgo2(&did).unwrap();
+
+        assert_eq!(did_doc.id(), &did);
+        assert_eq!(did_doc.verification_method().len(), 3);
+        assert_eq!(did_doc.key_agreement().len(), 1);
+        assert_eq!(did_doc.authentication().len(), 2);
+        assert_eq!(
+            did_doc.verification_method()[0].id(),
+            &DidUrl::parse(format!("{}#key-1", DID_PEER_2)).unwrap()
+        );
+        assert_eq!(
+            did_doc.verification_method()[0].verification_method_type(),
+            "X25519KeyAgreementKey2020"
+        );
+
+        let service = &did_doc.service()[0];
+        assert_eq!(
+            service.id(),
+            &Uri::new(&format!("{}#service", DID_PEER_2)).unwrap()
+        );
+        assert_eq!(service.service_endpoint(), "https://example.com/endpoint");
+    }
+
+    #[test]
+    fn test_resolve_numalgo2_unsupported_purpose() {
+        let did =
+            Did::parse("did:peer:2.Xz6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V".to_string())
+                .unwrap();
+        assert!(matches!(
+            resolve_numalgo2(&did),
+            Err(DidPeerError::UnsupportedPurpose('X'))
+        ));
+    }
+
+    #[test]
+    fn test_resolve_numalgo2_invalid_service() {
+        let did = Did::parse(
+            "did:peer:2.Vz6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V.Snotaservice".to_string(),
+        )
+        .unwrap();
+        assert!(resolve_numalgo2(&did).is_err());
+    }
+}
@@ -0,0 +1,61 @@
+use async_trait::async_trait;
+use did_resolver::{
+    did_parser::Did,
+    error::GenericError,
+    shared_types::{did_document_metadata::DidDocumentMetadata, media_type::MediaType},
+    traits::resolvable::{
+        resolution_metadata::DidResolutionMetadata, resolution_options::DidResolutionOptions,
+        resolution_output::DidResolutionOutput, DidResolvable,
+    },
+};
+
+use crate::{
+    error::DidPeerError,
+    numalgos::{resolve_numalgo0, resolve_numalgo2},
+};
+
+#[derive(Default)]
+pub struct DidPeerResolver;
+
+impl DidPeerResolver {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl DidResolvable for DidPeerResolver {
+    async fn resolve(
+        &self,
+        did: &Did,
+        options: &DidResolutionOptions,
+    ) -> Result<DidResolutionOutput, GenericError> {
+        if did.method() != "peer" {
+            return Err(Box::new(DidPeerError::MethodNotSupported(
+                did.method().to_string(),
+            )));
+        }
+
+        if let Some(accept) = options.accept() {
+            if accept != &MediaType::DidJson {
+                return Err(Box::new(DidPeerError::RepresentationNotSupported(
+                    accept.to_string(),
+                )));
+            }
+        }
+
+        let did_document = match did.id().chars().next() {
+            Some('0') => resolve_numalgo0(did)?,
+            Some('2') => resolve_numalgo2(did)?,
+            Some(numalgo) => return Err(Box::new(DidPeerError::NumalgoNotSupported(numalgo))),
+            None => return Err(Box::new(DidPeerError::InvalidDid(did.to_string()))),
+        };
+
+        let did_resolution_output = DidResolutionOutput::builder(did_document)
+            .did_resolution_metadata(DidResolutionMetadata::default())
+            .did_document_metadata(DidDocumentMetadata::default())
+            .build();
+
+        Ok(did_resolution_output)
+    }
+}
@@ -0,0 +1,177 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use did_resolver::did_doc_builder::schema::{
+    service::Service,
+    types::{uri::Uri, url::Url},
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::json;
+
+use crate::error::DidPeerError;
+
+const DIDCOMM_MESSAGING: &str = "DIDCommMessaging";
+const DIDCOMM_MESSAGING_ABBREVIATED: &str = "dm";
+
+// Service as encoded in did:peer:2, with abbreviated keys and service type
+// https://identity.foundation/peer-did-method-spec/#generating-a-didpeer2
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PeerService {
+    #[serde(rename = "t")]
+    #[serde(serialize_with = "abbreviate_service_type")]
+    #[serde(deserialize_with = "expand_service_type")]
+    service_type: String,
+    #[serde(rename = "s")]
+    service_endpoint: String,
+    #[serde(rename = "r")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default)]
+    routing_keys: Vec<String>,
+    #[serde(rename = "a")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default)]
+    accept: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AbbreviatedServices {
+    One(PeerService),
+    List(Vec<PeerService>),
+}
+
+impl PeerService {
+    pub fn new(service_type: String, service_endpoint: String) -> Self {
+        Self {
+            service_type,
+            service_endpoint,
+            routing_keys: Vec::new(),
+            accept: Vec::new(),
+        }
+    }
+
+    pub fn set_routing_keys(mut self, routing_keys: Vec<String>) -> Self {
+        self.routing_keys = routing_keys;
+        self
+    }
+
+    pub fn set_accept(mut self, accept: Vec<String>) -> Self {
+        self.accept = accept;
+        self
+    }
+
+    pub fn service_type(&self) -> &str {
+        self.service_type.as_ref()
+    }
+
+    pub fn service_endpoint(&self) -> &str {
+        self.service_endpoint.as_ref()
+    }
+
+    pub fn routing_keys(&self) -> &[String] {
+        self.routing_keys.as_ref()
+    }
+
+    pub fn accept(&self) -> &[String] {
+        self.accept.as_ref()
+    }
+
+    pub(crate) fn encode(&self) -> Result<String, DidPeerError> {
+        Ok(URL_SAFE_NO_PAD.encode(serde_json::to_vec(self)?))
+    }
+
+    // A single service element may hold either one service or a list of them
+    pub(crate) fn decode(encoded: &str) -> Result<Vec<Self>, DidPeerError> {
+        let decoded = URL_SAFE_NO_PAD.decode(encoded.trim_end_matches('='))?;
+        match serde_json::from_slice(&decoded)? {
+            AbbreviatedServices::One(service) => Ok(vec![service]),
+            AbbreviatedServices::List(services) => Ok(services),
+        }
+    }
+
+    pub(crate) fn to_service(&self, id: Uri) -> Result<Service, DidPeerError> {
+        let mut builder = Service::builder(id, Url::new(&self.service_endpoint)?)?
+            .add_service_type(self.service_type.clone())?;
+        if !self.routing_keys.is_empty() {
+            builder = builder.add_extra_field("routingKeys".to_string(), json!(self.routing_keys));
+        }
+        if !self.accept.is_empty() {
+            builder = builder.add_extra_field("accept".to_string(), json!(self.accept));
+        }
+        Ok(builder.build()?)
+    }
+}
+
+fn abbreviate_service_type<S>(service_type: &str, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match service_type {
+        DIDCOMM_MESSAGING => serializer.serialize_str(DIDCOMM_MESSAGING_ABBREVIATED),
+        _ => serializer.serialize_str(service_type),
+    }
+}
+
+fn expand_service_type<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let service_type = String::deserialize(deserializer)?;
+    match service_type.as_str() {
+        DIDCOMM_MESSAGING_ABBREVIATED => Ok(DIDCOMM_MESSAGING.to_string()),
+        _ => Ok(service_type),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENCODED_SERVICE: &str = "eyJ0IjoiZG0iLCJzIjoiaHR0cHM6Ly9leGFtcGxlLmNvbS9lbmRwb2ludCIsInIiOlsiZGlkOmV4YW1wbGU6c29tZW1lZGlhdG9yI3NvbWVrZXkiXSwiYSI6WyJkaWRjb21tL3YyIiwiZGlkY29tbS9haXAyO2Vudj1yZmM1ODciXX0";
+
+    fn create_service() -> PeerService {
+        PeerService::new(
+            "DIDCommMessaging".to_string(),
+            "https://example.com/endpoint".to_string(),
+        )
+        .set_routing_keys(vec!["did:example:somemediator#somekey".to_string()])
+        .set_accept(vec![
+            "didcomm/v2".to_string(),
+            "didcomm/aip2;env=rfc587".to_string(),
+        ])
+    }
+
+    #[test]
+    fn test_service_encode() {
+        assert_eq!(create_service().encode().unwrap(), ENCODED_SERVICE);
+    }
+
+    #[test]
+    fn test_service_decode() {
+        assert_eq!(
+            PeerService::decode(ENCODED_SERVICE).unwrap(),
+            vec![create_service()]
+        );
+    }
+
+    #[test]
+    fn test_service_decode_list() {
+        let service = PeerService::new("did-communication".to_string(), "http://a.b".to_string());
+        let encoded = URL_SAFE_NO_PAD
+            .encode(serde_json::to_vec(&vec![service.clone(), create_service()]).unwrap());
+        assert_eq!(
+            PeerService::decode(&encoded).unwrap(),
+            vec![service, create_service()]
+        );
+    }
+
+    #[test]
+    fn test_service_to_service() {
+        let id = Uri::new("did:example:123#service").unwrap();
+        let service = create_service().to_service(id.clone()).unwrap();
+        assert_eq!(service.id(), &id);
+        assert_eq!(service.service_endpoint(), "https://example.com/endpoint");
+        assert_eq!(
+            service.extra_field("routingKeys").unwrap(),
+            &json!(["did:example:somemediator#somekey"])
+        );
+    }
+}
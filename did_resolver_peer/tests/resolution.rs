@@ -0,0 +1,167 @@
+use did_resolver::did_parser::Did;
+use did_resolver::shared_types::media_type::MediaType;
+use did_resolver::traits::resolvable::{resolution_options::DidResolutionOptions, DidResolvable};
+use did_resolver_peer::{
+    error::DidPeerError, generation::generate_numalgo2, resolution::resolver::DidPeerResolver,
+    service::PeerService,
+};
+use did_resolver_registry::ResolverRegistry;
+use public_key::{Key, KeyType};
+
+const DID_PEER_0: &str = "did:peer:0z6MkmjY8GnV5i9YTDtPETC2uUAW6ejw3nk5mXF5yci5ab7th";
+
+// https://identity.foundation/peer-did-method-spec/#example-peer-did-2
+const DID_PEER_2: &str = "did:peer:2.Ez6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc.Vz6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V.Vz6MkgoLTnTypo3tDRwCkZXSccTPHRLhF4ZnjhueYAFpEX6vg.SeyJ0IjoiZG0iLCJzIjoiaHR0cHM6Ly9leGFtcGxlLmNvbS9lbmRwb2ludCIsInIiOlsiZGlkOmV4YW1wbGU6c29tZW1lZGlhdG9yI3NvbWVrZXkiXSwiYSI6WyJkaWRjb21tL3YyIiwiZGlkY29tbS9haXAyO2Vudj1yZmM1ODciXX0";
+
+fn create_service() -> PeerService {
+    PeerService::new(
+        "DIDCommMessaging".to_string(),
+        "https://example.com/endpoint".to_string(),
+    )
+    .set_routing_keys(vec!["did:example:somemediator#somekey".to_string()])
+    .set_accept(vec![
+        "didcomm/v2".to_string(),
+        "didcomm/aip2;env=rfc587".to_string(),
+    ])
+}
+
+fn downcast_error(err: Box<dyn std::error::Error + Send + Sync>) -> DidPeerError {
+    *err.downcast::<DidPeerError>().unwrap()
+}
+
+#[tokio::test]
+async fn test_did_peer_resolve_numalgo0() {
+    let resolver = DidPeerResolver::new();
+    let did = Did::parse(DID_PEER_0.to_string()).unwrap();
+
+    let output = resolver
+        .resolve(&did, &DidResolutionOptions::default())
+        .await
+        .unwrap();
+
+    let did_doc = output.did_document();
+    assert_eq!(did_doc.id(), &did);
+    assert_eq!(did_doc.verification_method().len(), 1);
+    assert_eq!(did_doc.authentication().len(), 1);
+}
+
+#[tokio::test]
+async fn test_did_peer_resolve_numalgo2() {
+    let resolver = DidPeerResolver::new();
+    let did = Did::parse(DID_PEER_2.to_string()).unwrap();
+
+    let output = resolver
+        .resolve(&did, &DidResolutionOptions::default())
+        .await
+        .unwrap();
+
+    let did_doc = output.did_document();
+    assert_eq!(did_doc.id(), &did);
+    assert_eq!(did_doc.verification_method().len(), 3);
+    assert_eq!(did_doc.key_agreement().len(), 1);
+    assert_eq!(did_doc.authentication().len(), 2);
+    assert_eq!(did_doc.service().len(), 1);
+
+    let service = &did_doc.service()[0];
+    assert_eq!(service.service_endpoint(), "https://example.com/endpoint");
+    assert_eq!(
+        service.extra_field("accept").unwrap(),
+        &serde_json::json!(["didcomm/v2", "didcomm/aip2;env=rfc587"])
+    );
+}
+
+#[tokio::test]
+async fn test_did_peer_resolve_unsupported_numalgo() {
+    let resolver = DidPeerResolver::new();
+    let did = Did::parse("did:peer:1zQmZMygzYqNwU6Uhmewx5Xepf2VLp5S4HLSwwgf2aiKZuwa".to_string())
+        .unwrap();
+
+    let err = resolver
+        .resolve(&did, &DidResolutionOptions::default())
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        downcast_error(err),
+        DidPeerError::NumalgoNotSupported('1')
+    ));
+}
+
+#[tokio::test]
+async fn test_did_peer_resolve_unsupported_method() {
+    let resolver = DidPeerResolver::new();
+    let did = Did::parse("did:web:example.com".to_string()).unwrap();
+
+    let err = resolver
+        .resolve(&did, &DidResolutionOptions::default())
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        downcast_error(err),
+        DidPeerError::MethodNotSupported(_)
+    ));
+}
+
+#[tokio::test]
+async fn test_did_peer_resolve_unsupported_representation() {
+    let resolver = DidPeerResolver::new();
+    let did = Did::parse(DID_PEER_0.to_string()).unwrap();
+    let options = DidResolutionOptions::default().set_accept(MediaType::DidLdJson);
+
+    let err = resolver.resolve(&did, &options).await.unwrap_err();
+
+    assert!(matches!(
+        downcast_error(err),
+        DidPeerError::RepresentationNotSupported(_)
+    ));
+}
+
+#[test]
+fn test_did_peer_generate_numalgo2() {
+    let agreement_key =
+        Key::from_fingerprint("z6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc").unwrap();
+    let authentication_keys = vec![
+        Key::from_fingerprint("z6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V").unwrap(),
+        Key::from_fingerprint("z6MkgoLTnTypo3tDRwCkZXSccTPHRLhF4ZnjhueYAFpEX6vg").unwrap(),
+    ];
+
+    let did = generate_numalgo2(
+        &[agreement_key],
+        &authentication_keys,
+        Some(&create_service()),
+    )
+    .unwrap();
+
+    assert_eq!(did.did(), DID_PEER_2);
+}
+
+#[tokio::test]
+async fn test_did_peer_generate_and_resolve_through_registry() {
+    let mut registry = ResolverRegistry::new();
+    registry.register_resolver("peer".to_string(), Box::new(DidPeerResolver::new()));
+
+    let key = Key::from_base58(
+        KeyType::Ed25519,
+        "8HH5gYEeNc3z7PYXmd54d4x6qAfCNrqQqEB3nS7Zfu7K",
+    )
+    .unwrap();
+    let service = PeerService::new(
+        "did-communication".to_string(),
+        "http://localhost:8080".to_string(),
+    );
+    let did = generate_numalgo2(&[], &[key], Some(&service)).unwrap();
+
+    let output = registry
+        .resolve(&did, &DidResolutionOptions::default())
+        .await
+        .unwrap();
+
+    let did_doc = output.did_document();
+    assert_eq!(did_doc.id(), &did);
+    assert_eq!(did_doc.authentication().len(), 1);
+    assert_eq!(
+        did_doc.service()[0].service_endpoint(),
+        "http://localhost:8080/"
+    );
+}
@@ -10,6 +10,7 @@ use aries_vcx::{
     },
     utils::devsetup::SetupProfile,
 };
+use aries_vcx_core::ledger::{base_ledger::BaseLedger, file_ledger::FileLedger};
 use did_resolver::did_parser::Did;
 use did_resolver::traits::resolvable::{resolution_options::DidResolutionOptions, DidResolvable};
 use did_resolver_sov::reader::ConcreteAttrReader;
@@ -44,6 +45,43 @@ async fn write_service_on_ledger_and_resolve_did_doc() {
     .await;
 }
 
+#[tokio::test]
+async fn write_service_on_file_ledger_and_resolve_did_doc() {
+    let did = "V4SGRU86Z58d6TV7PBUe6f";
+    let ledger: Arc<dyn BaseLedger> = Arc::new(FileLedger::in_memory());
+    ledger
+        .publish_nym(
+            did,
+            did,
+            Some("GJ1SzoWzavQYfNL9XkaJdrQejfztN4XqdsiV4ct3LXKL"),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    let endpoint = EndpointDidSov::create()
+        .set_service_endpoint("http://localhost:8080".parse().unwrap())
+        .set_types(Some(vec![DidSovServiceType::Endpoint]));
+    ledger
+        .add_attr(
+            did,
+            &serde_json::json!({ "endpoint": endpoint }).to_string(),
+        )
+        .await
+        .unwrap();
+
+    let resolver = DidSovResolver::new(Arc::<ConcreteAttrReader>::new(ledger.into()));
+    let did = format!("did:sov:{did}");
+    let did_doc = resolver
+        .resolve(
+            &Did::parse(did.clone()).unwrap(),
+            &DidResolutionOptions::default(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(did_doc.did_document().id().to_string(), did);
+}
+
 #[tokio::test]
 async fn test_error_handling_during_resolution() {
     SetupProfile::run(|init| async move {
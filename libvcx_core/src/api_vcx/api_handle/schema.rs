@@ -1,5 +1,4 @@
 use std::string::ToString;
-use std::sync::Arc;
 
 use serde_json;
 
@@ -88,11 +87,9 @@ pub async fn prepare_schema_for_endorser(
     })?;
     let profile = get_main_profile()?;
     let schema = Schema::create(&profile, source_id, &issuer_did, &name, &version, &data).await?;
-    let schema_json = schema.get_schema_json(&profile).await?;
     let schema_id = schema.get_schema_id();
-    let ledger = Arc::clone(&profile).inject_ledger();
-    let schema_request = ledger.build_schema_request(&issuer_did, &schema_json).await?;
-    let schema_request = ledger.set_endorser(&issuer_did, &schema_request, &endorser).await?;
+    let schema_request = schema.prepare_for_endorser(&profile, &endorser).await?;
+    let schema_request = schema_request.get_request().to_string();
 
     debug!("prepared schema for endorser with id: {}", schema_id);
 
@@ -499,6 +499,17 @@ impl WalletService {
             .map_err(|err| WalletService::_map_wallet_storage_error(err, type_, name))
     }
 
+    /// Applies all operations atomically: either every operation is persisted or, if any
+    /// of them fails, the wallet is left as it was before the call.
+    pub async fn batch_records(
+        &self,
+        wallet_handle: WalletHandle,
+        operations: &[WalletOperation],
+    ) -> IndyResult<()> {
+        let wallet = self.get_wallet(wallet_handle).await?;
+        wallet.batch(operations).await
+    }
+
     pub async fn delete_indy_record<T>(
         &self,
         wallet_handle: WalletHandle,
@@ -649,13 +660,15 @@ impl WalletService {
         .await
     }
 
-    #[allow(dead_code)] // TODO: Should we implement getting all records or delete everywhere?
-    pub fn search_all_records(&self, _wallet_handle: WalletHandle) -> IndyResult<WalletSearch> {
-        //        match self.wallets.lock().await.get(&wallet_handle) {
-        //            Some(wallet) => wallet.search_all_records(),
-        //            None => Err(IndyError::InvalidHandle(wallet_handle.to_string()))
-        //        }
-        unimplemented!()
+    pub async fn search_all_records(
+        &self,
+        wallet_handle: WalletHandle,
+    ) -> IndyResult<WalletSearch> {
+        let wallet = self.get_wallet(wallet_handle).await?;
+
+        Ok(WalletSearch {
+            iter: wallet.get_all().await?,
+        })
     }
 
     pub async fn upsert_indy_object<T>(
@@ -1067,6 +1080,65 @@ impl WalletRecord {
     }
 }
 
+/// A single record write applied as part of [WalletService::batch_records].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WalletOperation {
+    Add {
+        type_: String,
+        name: String,
+        value: String,
+        tags: Tags,
+    },
+    UpdateValue {
+        type_: String,
+        name: String,
+        value: String,
+    },
+    AddTags {
+        type_: String,
+        name: String,
+        tags: Tags,
+    },
+    UpdateTags {
+        type_: String,
+        name: String,
+        tags: Tags,
+    },
+    DeleteTags {
+        type_: String,
+        name: String,
+        tag_names: Vec<String>,
+    },
+    Delete {
+        type_: String,
+        name: String,
+    },
+}
+
+impl WalletOperation {
+    pub fn get_type(&self) -> &str {
+        match self {
+            WalletOperation::Add { type_, .. }
+            | WalletOperation::UpdateValue { type_, .. }
+            | WalletOperation::AddTags { type_, .. }
+            | WalletOperation::UpdateTags { type_, .. }
+            | WalletOperation::DeleteTags { type_, .. }
+            | WalletOperation::Delete { type_, .. } => type_,
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        match self {
+            WalletOperation::Add { name, .. }
+            | WalletOperation::UpdateValue { name, .. }
+            | WalletOperation::AddTags { name, .. }
+            | WalletOperation::UpdateTags { name, .. }
+            | WalletOperation::DeleteTags { name, .. }
+            | WalletOperation::Delete { name, .. } => name,
+        }
+    }
+}
+
 fn default_true() -> bool {
     true
 }
@@ -8,7 +8,7 @@ use indy_utils::environment;
 use serde::Deserialize;
 use sqlx::{
     sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions},
-    ConnectOptions, SqlitePool,
+    ConnectOptions, Sqlite, SqlitePool, Transaction,
 };
 
 use async_trait::async_trait;
@@ -16,7 +16,10 @@ use log::LevelFilter;
 
 use crate::{
     language,
-    storage::{StorageIterator, StorageRecord, Tag, TagName, WalletStorage, WalletStorageType},
+    storage::{
+        StorageIterator, StorageOperation, StorageRecord, Tag, TagName, WalletStorage,
+        WalletStorageType,
+    },
     wallet::EncryptedValue,
     RecordOptions, SearchOptions,
 };
@@ -215,192 +218,35 @@ impl WalletStorage for SQLiteStorage {
         tags: &[Tag],
     ) -> IndyResult<()> {
         let mut tx = self.pool.begin().await?;
-
-        let id = sqlx::query("INSERT INTO items (type, name, value, key) VALUES (?1, ?2, ?3, ?4)")
-            .bind(type_)
-            .bind(id)
-            .bind(&value.data)
-            .bind(&value.key)
-            .execute(&mut tx)
-            .await?
-            .last_insert_rowid();
-
-        for tag in tags {
-            match *tag {
-                Tag::Encrypted(ref tag_name, ref tag_data) => {
-                    sqlx::query(
-                        "INSERT INTO tags_encrypted (item_id, name, value) VALUES (?1, ?2, ?3)",
-                    )
-                    .bind(id)
-                    .bind(tag_name)
-                    .bind(tag_data)
-                    .execute(&mut tx)
-                    .await?
-                }
-                Tag::PlainText(ref tag_name, ref tag_data) => {
-                    sqlx::query(
-                        "INSERT INTO tags_plaintext (item_id, name, value) VALUES (?1, ?2, ?3)",
-                    )
-                    .bind(id)
-                    .bind(tag_name)
-                    .bind(tag_data)
-                    .execute(&mut tx)
-                    .await?
-                }
-            };
-        }
-
+        _add(&mut tx, type_, id, value, tags).await?;
         tx.commit().await?;
         Ok(())
     }
 
     async fn update(&self, type_: &[u8], id: &[u8], value: &EncryptedValue) -> IndyResult<()> {
         let mut tx = self.pool.begin().await?;
-
-        let row_updated =
-            sqlx::query("UPDATE items SET value = ?1, key = ?2 WHERE type = ?3 AND name = ?4")
-                .bind(&value.data)
-                .bind(&value.key)
-                .bind(&type_)
-                .bind(&id)
-                .execute(&mut tx)
-                .await?
-                .rows_affected();
-
-        match row_updated {
-            1 => {
-                tx.commit().await?;
-                Ok(())
-            }
-            0 => Err(err_msg(
-                IndyErrorKind::WalletItemNotFound,
-                "Item to update not found",
-            )),
-            _ => Err(err_msg(
-                IndyErrorKind::InvalidState,
-                "More than one row update. Seems wallet structure is inconsistent",
-            )),
-        }
+        _update(&mut tx, type_, id, value).await?;
+        tx.commit().await?;
+        Ok(())
     }
 
     async fn add_tags(&self, type_: &[u8], id: &[u8], tags: &[Tag]) -> IndyResult<()> {
         let mut tx = self.pool.begin().await?;
-
-        let (item_id,): (i64,) =
-            sqlx::query_as("SELECT id FROM items WHERE type = ?1 AND name = ?2")
-                .bind(type_)
-                .bind(id)
-                .fetch_one(&mut tx)
-                .await?;
-
-        for tag in tags {
-            match *tag {
-                Tag::Encrypted(ref tag_name, ref tag_data) => {
-                    sqlx::query(
-                        "INSERT OR REPLACE INTO tags_encrypted (item_id, name, value) VALUES (?1, ?2, ?3)",
-                    )
-                    .bind(item_id)
-                    .bind(tag_name)
-                    .bind(tag_data)
-                    .execute(&mut tx)
-                    .await?
-                }
-                Tag::PlainText(ref tag_name, ref tag_data) => {
-                    sqlx::query(
-                        "INSERT OR REPLACE INTO tags_plaintext (item_id, name, value) VALUES (?1, ?2, ?3)",
-                    )
-                    .bind(item_id)
-                    .bind(tag_name)
-                    .bind(tag_data)
-                    .execute(&mut tx)
-                    .await?
-                }
-            };
-        }
-
+        _add_tags(&mut tx, type_, id, tags).await?;
         tx.commit().await?;
         Ok(())
     }
 
     async fn update_tags(&self, type_: &[u8], id: &[u8], tags: &[Tag]) -> IndyResult<()> {
         let mut tx = self.pool.begin().await?;
-
-        let (item_id,): (i64,) =
-            sqlx::query_as("SELECT id FROM items WHERE type = ?1 AND name = ?2")
-                .bind(type_)
-                .bind(&id)
-                .fetch_one(&mut tx)
-                .await?;
-
-        sqlx::query("DELETE FROM tags_encrypted WHERE item_id = ?1")
-            .bind(item_id)
-            .execute(&mut tx)
-            .await?;
-
-        sqlx::query("DELETE FROM tags_plaintext WHERE item_id = ?1")
-            .bind(item_id)
-            .execute(&mut tx)
-            .await?;
-
-        for tag in tags {
-            match *tag {
-                Tag::Encrypted(ref tag_name, ref tag_data) => {
-                    sqlx::query(
-                        "INSERT INTO tags_encrypted (item_id, name, value) VALUES (?1, ?2, ?3)",
-                    )
-                    .bind(item_id)
-                    .bind(tag_name)
-                    .bind(tag_data)
-                    .execute(&mut tx)
-                    .await?
-                }
-                Tag::PlainText(ref tag_name, ref tag_data) => {
-                    sqlx::query(
-                        "INSERT INTO tags_plaintext (item_id, name, value) VALUES (?1, ?2, ?3)",
-                    )
-                    .bind(item_id)
-                    .bind(tag_name)
-                    .bind(tag_data)
-                    .execute(&mut tx)
-                    .await?
-                }
-            };
-        }
-
+        _update_tags(&mut tx, type_, id, tags).await?;
         tx.commit().await?;
-
         Ok(())
     }
 
     async fn delete_tags(&self, type_: &[u8], id: &[u8], tag_names: &[TagName]) -> IndyResult<()> {
         let mut tx = self.pool.begin().await?;
-
-        let (item_id,): (i64,) =
-            sqlx::query_as("SELECT id FROM items WHERE type = ?1 AND name = ?2")
-                .bind(type_)
-                .bind(id)
-                .fetch_one(&mut tx)
-                .await?;
-
-        for tag_name in tag_names {
-            match *tag_name {
-                TagName::OfEncrypted(ref tag_name) => {
-                    sqlx::query("DELETE FROM tags_encrypted WHERE item_id = ?1 AND name = ?2")
-                        .bind(item_id)
-                        .bind(tag_name)
-                        .execute(&mut tx)
-                        .await?
-                }
-                TagName::OfPlain(ref tag_name) => {
-                    sqlx::query("DELETE FROM tags_plaintext WHERE item_id = ?1 AND name = ?2")
-                        .bind(item_id)
-                        .bind(tag_name)
-                        .execute(&mut tx)
-                        .await?
-                }
-            };
-        }
-
+        _delete_tags(&mut tx, type_, id, tag_names).await?;
         tx.commit().await?;
         Ok(())
     }
@@ -433,28 +279,47 @@ impl WalletStorage for SQLiteStorage {
     ///
     async fn delete(&self, type_: &[u8], id: &[u8]) -> IndyResult<()> {
         let mut tx = self.pool.begin().await?;
+        _delete(&mut tx, type_, id).await?;
+        tx.commit().await?;
+        Ok(())
+    }
 
-        let rows_affected = sqlx::query("DELETE FROM items where type = ?1 AND name = ?2")
-            .bind(type_)
-            .bind(id)
-            .execute(&mut tx)
-            .await?
-            .rows_affected();
+    ///
+    /// Applies the provided operations inside a single SQLite transaction.
+    /// The transaction is only committed if every operation succeeds, otherwise it is
+    /// rolled back when dropped and the storage is left untouched.
+    ///
+    async fn batch(&self, operations: &[StorageOperation]) -> IndyResult<()> {
+        let mut tx = self.pool.begin().await?;
 
-        match rows_affected {
-            1 => {
-                tx.commit().await?;
-                Ok(())
+        for operation in operations {
+            match operation {
+                StorageOperation::Add {
+                    type_,
+                    id,
+                    value,
+                    tags,
+                } => _add(&mut tx, type_, id, value, tags).await?,
+                StorageOperation::Update { type_, id, value } => {
+                    _update(&mut tx, type_, id, value).await?
+                }
+                StorageOperation::AddTags { type_, id, tags } => {
+                    _add_tags(&mut tx, type_, id, tags).await?
+                }
+                StorageOperation::UpdateTags { type_, id, tags } => {
+                    _update_tags(&mut tx, type_, id, tags).await?
+                }
+                StorageOperation::DeleteTags {
+                    type_,
+                    id,
+                    tag_names,
+                } => _delete_tags(&mut tx, type_, id, tag_names).await?,
+                StorageOperation::Delete { type_, id } => _delete(&mut tx, type_, id).await?,
             }
-            0 => Err(err_msg(
-                IndyErrorKind::WalletItemNotFound,
-                "Item to delete not found",
-            )),
-            _ => Err(err_msg(
-                IndyErrorKind::InvalidState,
-                "More than one row deleted. Seems wallet structure is inconsistent",
-            )),
         }
+
+        tx.commit().await?;
+        Ok(())
     }
 
     async fn get_storage_metadata(&self) -> IndyResult<Vec<u8>> {
@@ -687,6 +552,212 @@ impl WalletStorage for SQLiteStorage {
     }
 }
 
+async fn _add(
+    tx: &mut Transaction<'_, Sqlite>,
+    type_: &[u8],
+    id: &[u8],
+    value: &EncryptedValue,
+    tags: &[Tag],
+) -> IndyResult<()> {
+    let id = sqlx::query("INSERT INTO items (type, name, value, key) VALUES (?1, ?2, ?3, ?4)")
+        .bind(type_)
+        .bind(id)
+        .bind(&value.data)
+        .bind(&value.key)
+        .execute(&mut *tx)
+        .await?
+        .last_insert_rowid();
+
+    for tag in tags {
+        match *tag {
+            Tag::Encrypted(ref tag_name, ref tag_data) => {
+                sqlx::query("INSERT INTO tags_encrypted (item_id, name, value) VALUES (?1, ?2, ?3)")
+                    .bind(id)
+                    .bind(tag_name)
+                    .bind(tag_data)
+                    .execute(&mut *tx)
+                    .await?
+            }
+            Tag::PlainText(ref tag_name, ref tag_data) => {
+                sqlx::query("INSERT INTO tags_plaintext (item_id, name, value) VALUES (?1, ?2, ?3)")
+                    .bind(id)
+                    .bind(tag_name)
+                    .bind(tag_data)
+                    .execute(&mut *tx)
+                    .await?
+            }
+        };
+    }
+
+    Ok(())
+}
+
+async fn _update(
+    tx: &mut Transaction<'_, Sqlite>,
+    type_: &[u8],
+    id: &[u8],
+    value: &EncryptedValue,
+) -> IndyResult<()> {
+    let row_updated =
+        sqlx::query("UPDATE items SET value = ?1, key = ?2 WHERE type = ?3 AND name = ?4")
+            .bind(&value.data)
+            .bind(&value.key)
+            .bind(&type_)
+            .bind(&id)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+
+    match row_updated {
+        1 => Ok(()),
+        0 => Err(err_msg(
+            IndyErrorKind::WalletItemNotFound,
+            "Item to update not found",
+        )),
+        _ => Err(err_msg(
+            IndyErrorKind::InvalidState,
+            "More than one row update. Seems wallet structure is inconsistent",
+        )),
+    }
+}
+
+async fn _add_tags(
+    tx: &mut Transaction<'_, Sqlite>,
+    type_: &[u8],
+    id: &[u8],
+    tags: &[Tag],
+) -> IndyResult<()> {
+    let (item_id,): (i64,) = sqlx::query_as("SELECT id FROM items WHERE type = ?1 AND name = ?2")
+        .bind(type_)
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+    for tag in tags {
+        match *tag {
+            Tag::Encrypted(ref tag_name, ref tag_data) => sqlx::query(
+                "INSERT OR REPLACE INTO tags_encrypted (item_id, name, value) VALUES (?1, ?2, ?3)",
+            )
+            .bind(item_id)
+            .bind(tag_name)
+            .bind(tag_data)
+            .execute(&mut *tx)
+            .await?,
+            Tag::PlainText(ref tag_name, ref tag_data) => sqlx::query(
+                "INSERT OR REPLACE INTO tags_plaintext (item_id, name, value) VALUES (?1, ?2, ?3)",
+            )
+            .bind(item_id)
+            .bind(tag_name)
+            .bind(tag_data)
+            .execute(&mut *tx)
+            .await?,
+        };
+    }
+
+    Ok(())
+}
+
+async fn _update_tags(
+    tx: &mut Transaction<'_, Sqlite>,
+    type_: &[u8],
+    id: &[u8],
+    tags: &[Tag],
+) -> IndyResult<()> {
+    let (item_id,): (i64,) = sqlx::query_as("SELECT id FROM items WHERE type = ?1 AND name = ?2")
+        .bind(type_)
+        .bind(&id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+    sqlx::query("DELETE FROM tags_encrypted WHERE item_id = ?1")
+        .bind(item_id)
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query("DELETE FROM tags_plaintext WHERE item_id = ?1")
+        .bind(item_id)
+        .execute(&mut *tx)
+        .await?;
+
+    for tag in tags {
+        match *tag {
+            Tag::Encrypted(ref tag_name, ref tag_data) => {
+                sqlx::query("INSERT INTO tags_encrypted (item_id, name, value) VALUES (?1, ?2, ?3)")
+                    .bind(item_id)
+                    .bind(tag_name)
+                    .bind(tag_data)
+                    .execute(&mut *tx)
+                    .await?
+            }
+            Tag::PlainText(ref tag_name, ref tag_data) => {
+                sqlx::query("INSERT INTO tags_plaintext (item_id, name, value) VALUES (?1, ?2, ?3)")
+                    .bind(item_id)
+                    .bind(tag_name)
+                    .bind(tag_data)
+                    .execute(&mut *tx)
+                    .await?
+            }
+        };
+    }
+
+    Ok(())
+}
+
+async fn _delete_tags(
+    tx: &mut Transaction<'_, Sqlite>,
+    type_: &[u8],
+    id: &[u8],
+    tag_names: &[TagName],
+) -> IndyResult<()> {
+    let (item_id,): (i64,) = sqlx::query_as("SELECT id FROM items WHERE type = ?1 AND name = ?2")
+        .bind(type_)
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+    for tag_name in tag_names {
+        match *tag_name {
+            TagName::OfEncrypted(ref tag_name) => {
+                sqlx::query("DELETE FROM tags_encrypted WHERE item_id = ?1 AND name = ?2")
+                    .bind(item_id)
+                    .bind(tag_name)
+                    .execute(&mut *tx)
+                    .await?
+            }
+            TagName::OfPlain(ref tag_name) => {
+                sqlx::query("DELETE FROM tags_plaintext WHERE item_id = ?1 AND name = ?2")
+                    .bind(item_id)
+                    .bind(tag_name)
+                    .execute(&mut *tx)
+                    .await?
+            }
+        };
+    }
+
+    Ok(())
+}
+
+async fn _delete(tx: &mut Transaction<'_, Sqlite>, type_: &[u8], id: &[u8]) -> IndyResult<()> {
+    let rows_affected = sqlx::query("DELETE FROM items where type = ?1 AND name = ?2")
+        .bind(type_)
+        .bind(id)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+    match rows_affected {
+        1 => Ok(()),
+        0 => Err(err_msg(
+            IndyErrorKind::WalletItemNotFound,
+            "Item to delete not found",
+        )),
+        _ => Err(err_msg(
+            IndyErrorKind::InvalidState,
+            "More than one row deleted. Seems wallet structure is inconsistent",
+        )),
+    }
+}
+
 #[async_trait]
 impl WalletStorageType for SQLiteStorageType {
     ///
@@ -942,3 +1013,304 @@ impl WalletStorageType for SQLiteStorageType {
         }))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use indy_utils::{assert_kind, environment};
+
+    use super::{super::Tag, *};
+
+    #[tokio::test]
+    async fn sqlite_storage_batch_works_for_add() {
+        _cleanup("sqlite_storage_batch_works_for_add").await;
+
+        {
+            let storage = _storage("sqlite_storage_batch_works_for_add").await;
+
+            storage
+                .batch(&[
+                    StorageOperation::Add {
+                        type_: _type1(),
+                        id: _id1(),
+                        value: _value1(),
+                        tags: _tags(),
+                    },
+                    StorageOperation::Add {
+                        type_: _type1(),
+                        id: _id2(),
+                        value: _value2(),
+                        tags: _new_tags(),
+                    },
+                ])
+                .await
+                .unwrap();
+
+            let record = storage
+                .get(
+                    &_type1(),
+                    &_id1(),
+                    r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##,
+                )
+                .await
+                .unwrap();
+            assert_eq!(record.value.unwrap(), _value1());
+            assert_eq!(_sort(record.tags.unwrap()), _sort(_tags()));
+
+            let record = storage
+                .get(
+                    &_type1(),
+                    &_id2(),
+                    r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##,
+                )
+                .await
+                .unwrap();
+            assert_eq!(record.value.unwrap(), _value2());
+            assert_eq!(_sort(record.tags.unwrap()), _sort(_new_tags()));
+        }
+
+        _cleanup("sqlite_storage_batch_works_for_add").await;
+    }
+
+    #[tokio::test]
+    async fn sqlite_storage_batch_works_for_update() {
+        _cleanup("sqlite_storage_batch_works_for_update").await;
+
+        {
+            let storage = _storage("sqlite_storage_batch_works_for_update").await;
+
+            storage
+                .add(&_type1(), &_id1(), &_value1(), &_tags())
+                .await
+                .unwrap();
+
+            storage
+                .batch(&[
+                    StorageOperation::Update {
+                        type_: _type1(),
+                        id: _id1(),
+                        value: _value2(),
+                    },
+                    StorageOperation::UpdateTags {
+                        type_: _type1(),
+                        id: _id1(),
+                        tags: _new_tags(),
+                    },
+                ])
+                .await
+                .unwrap();
+
+            let record = storage
+                .get(
+                    &_type1(),
+                    &_id1(),
+                    r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##,
+                )
+                .await
+                .unwrap();
+            assert_eq!(record.value.unwrap(), _value2());
+            assert_eq!(_sort(record.tags.unwrap()), _sort(_new_tags()));
+        }
+
+        _cleanup("sqlite_storage_batch_works_for_update").await;
+    }
+
+    #[tokio::test]
+    async fn sqlite_storage_batch_works_for_delete() {
+        _cleanup("sqlite_storage_batch_works_for_delete").await;
+
+        {
+            let storage = _storage("sqlite_storage_batch_works_for_delete").await;
+
+            storage
+                .add(&_type1(), &_id1(), &_value1(), &_tags())
+                .await
+                .unwrap();
+
+            storage
+                .add(&_type1(), &_id2(), &_value2(), &_tags())
+                .await
+                .unwrap();
+
+            storage
+                .batch(&[
+                    StorageOperation::Delete {
+                        type_: _type1(),
+                        id: _id1(),
+                    },
+                    StorageOperation::Delete {
+                        type_: _type1(),
+                        id: _id2(),
+                    },
+                ])
+                .await
+                .unwrap();
+
+            let res = storage
+                .get(
+                    &_type1(),
+                    &_id1(),
+                    r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##,
+                )
+                .await;
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+            let res = storage
+                .get(
+                    &_type1(),
+                    &_id2(),
+                    r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##,
+                )
+                .await;
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+        }
+
+        _cleanup("sqlite_storage_batch_works_for_delete").await;
+    }
+
+    #[tokio::test]
+    async fn sqlite_storage_batch_rolls_back_on_failure() {
+        _cleanup("sqlite_storage_batch_rolls_back_on_failure").await;
+
+        {
+            let storage = _storage("sqlite_storage_batch_rolls_back_on_failure").await;
+
+            storage
+                .add(&_type1(), &_id1(), &_value1(), &_tags())
+                .await
+                .unwrap();
+
+            let res = storage
+                .batch(&[
+                    StorageOperation::Update {
+                        type_: _type1(),
+                        id: _id1(),
+                        value: _value2(),
+                    },
+                    StorageOperation::Add {
+                        type_: _type2(),
+                        id: _id2(),
+                        value: _value2(),
+                        tags: _tags(),
+                    },
+                    StorageOperation::Delete {
+                        type_: _type1(),
+                        id: _id2(),
+                    },
+                ])
+                .await;
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+            let record = storage
+                .get(
+                    &_type1(),
+                    &_id1(),
+                    r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": false}"##,
+                )
+                .await
+                .unwrap();
+            assert_eq!(record.value.unwrap(), _value1());
+
+            let res = storage
+                .get(
+                    &_type2(),
+                    &_id2(),
+                    r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": false}"##,
+                )
+                .await;
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+        }
+
+        _cleanup("sqlite_storage_batch_rolls_back_on_failure").await;
+    }
+
+    fn _config() -> String {
+        json!({ "path": environment::tmp_path() }).to_string()
+    }
+
+    async fn _cleanup(name: &str) {
+        SQLiteStorageType::new()
+            .delete_storage(name, Some(&_config()), None)
+            .await
+            .ok();
+    }
+
+    async fn _storage(name: &str) -> Box<dyn WalletStorage> {
+        let storage_type = SQLiteStorageType::new();
+
+        storage_type
+            .create_storage(name, Some(&_config()), None, &_metadata())
+            .await
+            .unwrap();
+
+        storage_type
+            .open_storage(name, Some(&_config()), None)
+            .await
+            .unwrap()
+    }
+
+    fn _metadata() -> Vec<u8> {
+        return vec![
+            1, 2, 3, 4, 5, 6, 7, 8, 1, 2, 3, 4, 5, 6, 7, 8, 1, 2, 3, 4, 5, 6, 7, 8, 1, 2, 3, 4, 5,
+            6, 7, 8, 1, 2, 3, 4, 5, 6, 7, 8, 1, 2, 3, 4, 5, 6, 7, 8, 1, 2, 3, 4, 5, 6, 7, 8, 1, 2,
+            3, 4, 5, 6, 7, 8,
+        ];
+    }
+
+    fn _type(i: u8) -> Vec<u8> {
+        vec![i, 1 + i, 2 + i]
+    }
+
+    fn _type1() -> Vec<u8> {
+        _type(1)
+    }
+
+    fn _type2() -> Vec<u8> {
+        _type(2)
+    }
+
+    fn _id(i: u8) -> Vec<u8> {
+        vec![3 + i, 4 + i, 5 + i]
+    }
+
+    fn _id1() -> Vec<u8> {
+        _id(1)
+    }
+
+    fn _id2() -> Vec<u8> {
+        _id(2)
+    }
+
+    fn _value(i: u8) -> EncryptedValue {
+        EncryptedValue {
+            data: vec![6 + i, 7 + i, 8 + i],
+            key: [9 + i, 10 + i, 11 + i].repeat(20),
+        }
+    }
+
+    fn _value1() -> EncryptedValue {
+        _value(1)
+    }
+
+    fn _value2() -> EncryptedValue {
+        _value(2)
+    }
+
+    fn _tags() -> Vec<Tag> {
+        vec![
+            Tag::Encrypted(vec![1, 5, 8], vec![3, 5, 6]),
+            Tag::PlainText(vec![1, 5, 8, 1], "Plain value".to_string()),
+        ]
+    }
+
+    fn _new_tags() -> Vec<Tag> {
+        vec![
+            Tag::Encrypted(vec![1, 1, 1], vec![2, 2, 2]),
+            Tag::PlainText(vec![1, 1, 1], String::from("tag_value_3")),
+        ]
+    }
+
+    fn _sort(mut v: Vec<Tag>) -> Vec<Tag> {
+        v.sort();
+        v
+    }
+}
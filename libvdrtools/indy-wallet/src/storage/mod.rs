@@ -12,7 +12,7 @@ pub enum Tag {
     PlainText(Vec<u8>, String),
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub enum TagName {
     OfEncrypted(Vec<u8>),
     OfPlain(Vec<u8>),
@@ -42,6 +42,41 @@ impl StorageRecord {
     }
 }
 
+/// A single write applied as part of [WalletStorage::batch].
+#[derive(Clone, Debug)]
+pub enum StorageOperation {
+    Add {
+        type_: Vec<u8>,
+        id: Vec<u8>,
+        value: EncryptedValue,
+        tags: Vec<Tag>,
+    },
+    Update {
+        type_: Vec<u8>,
+        id: Vec<u8>,
+        value: EncryptedValue,
+    },
+    AddTags {
+        type_: Vec<u8>,
+        id: Vec<u8>,
+        tags: Vec<Tag>,
+    },
+    UpdateTags {
+        type_: Vec<u8>,
+        id: Vec<u8>,
+        tags: Vec<Tag>,
+    },
+    DeleteTags {
+        type_: Vec<u8>,
+        id: Vec<u8>,
+        tag_names: Vec<TagName>,
+    },
+    Delete {
+        type_: Vec<u8>,
+        id: Vec<u8>,
+    },
+}
+
 #[async_trait]
 pub trait StorageIterator: Send + Sync {
     async fn next(&mut self) -> Result<Option<StorageRecord>, IndyError>;
@@ -74,6 +109,9 @@ pub trait WalletStorage: Send + Sync {
         tag_names: &[TagName],
     ) -> Result<(), IndyError>;
     async fn delete(&self, type_: &[u8], id: &[u8]) -> Result<(), IndyError>;
+    /// Applies all operations within a single storage transaction. Either every operation
+    /// is committed or, if any of them fails, none of them are.
+    async fn batch(&self, operations: &[StorageOperation]) -> Result<(), IndyError>;
     async fn get_storage_metadata(&self) -> Result<Vec<u8>, IndyError>;
     async fn set_storage_metadata(&self, metadata: &[u8]) -> Result<(), IndyError>;
     async fn get_all(&self) -> Result<Box<dyn StorageIterator>, IndyError>;
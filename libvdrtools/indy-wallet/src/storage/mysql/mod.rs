@@ -12,12 +12,15 @@ use query::{wql_to_sql, wql_to_sql_count};
 use serde::Deserialize;
 use sqlx::{
     mysql::{MySqlConnectOptions, MySqlPoolOptions, MySqlRow},
-    ConnectOptions, MySqlPool, Row,
+    ConnectOptions, MySql, MySqlPool, Row, Transaction,
 };
 
 use crate::{
     language,
-    storage::{StorageIterator, StorageRecord, Tag, TagName, WalletStorage, WalletStorageType},
+    storage::{
+        StorageIterator, StorageOperation, StorageRecord, Tag, TagName, WalletStorage,
+        WalletStorageType,
+    },
     wallet::EncryptedValue,
     RecordOptions, SearchOptions,
 };
@@ -283,59 +286,16 @@ impl WalletStorage for MySqlStorage {
         tags: &[Tag],
     ) -> IndyResult<()> {
         let mut tx = self.write_pool.begin().await?;
-
-        sqlx::query(
-            r#"
-            INSERT INTO items (type, name, value, tags, wallet_id)
-            VALUE (?, ?, ?, ?, ?)
-            "#,
-        )
-        .bind(&base64::encode(type_))
-        .bind(&base64::encode(id))
-        .bind(&value.to_bytes())
-        .bind(&_tags_to_json(tags)?)
-        .bind(&self.wallet_id)
-        .execute(&mut tx)
-        .await?;
-
+        _add(&mut tx, self.wallet_id, type_, id, value, tags).await?;
         tx.commit().await?;
         Ok(())
     }
 
     async fn update(&self, type_: &[u8], id: &[u8], value: &EncryptedValue) -> IndyResult<()> {
         let mut tx = self.write_pool.begin().await?;
-
-        let row_updated = sqlx::query(
-            r#"
-            UPDATE items
-            SET value = ?
-            WHERE type = ?
-                AND name = ?
-                AND wallet_id = ?
-            "#,
-        )
-        .bind(&value.to_bytes())
-        .bind(&base64::encode(type_))
-        .bind(&base64::encode(id))
-        .bind(&self.wallet_id)
-        .execute(&mut tx)
-        .await?
-        .rows_affected();
-
-        match row_updated {
-            1 => {
-                tx.commit().await?;
-                Ok(())
-            }
-            0 => Err(err_msg(
-                IndyErrorKind::WalletItemNotFound,
-                "Item to update not found",
-            )),
-            _ => Err(err_msg(
-                IndyErrorKind::InvalidState,
-                "More than one row update. Seems wallet structure is inconsistent",
-            )),
-        }
+        _update(&mut tx, self.wallet_id, type_, id, value).await?;
+        tx.commit().await?;
+        Ok(())
     }
 
     async fn add_tags(&self, type_: &[u8], id: &[u8], tags: &[Tag]) -> IndyResult<()> {
@@ -344,81 +304,17 @@ impl WalletStorage for MySqlStorage {
             return Ok(());
         }
 
-        let tag_paths = _tags_to_plain(tags)
-            .into_iter()
-            .map(|(tag, val)| format!(r#"'$."{}"', "{}""#, tag, val))
-            .collect::<Vec<_>>()
-            .join(",");
-
         let mut tx = self.write_pool.begin().await?;
-
-        let row_updated = sqlx::query(&format!(
-            r#"
-            UPDATE items
-                SET tags = JSON_SET(tags, {})
-            WHERE type = ?
-                AND name = ?
-                AND wallet_id = ?
-            "#,
-            tag_paths
-        ))
-        .bind(&base64::encode(type_))
-        .bind(&base64::encode(id))
-        .bind(&self.wallet_id)
-        .execute(&mut tx)
-        .await?
-        .rows_affected();
-
-        match row_updated {
-            1 => {
-                tx.commit().await?;
-                Ok(())
-            }
-            0 => Err(err_msg(
-                IndyErrorKind::WalletItemNotFound,
-                "Item to update not found",
-            )),
-            _ => Err(err_msg(
-                IndyErrorKind::InvalidState,
-                "More than one row update. Seems wallet structure is inconsistent",
-            )),
-        }
+        _add_tags(&mut tx, self.wallet_id, type_, id, tags).await?;
+        tx.commit().await?;
+        Ok(())
     }
 
     async fn update_tags(&self, type_: &[u8], id: &[u8], tags: &[Tag]) -> IndyResult<()> {
         let mut tx = self.write_pool.begin().await?;
-
-        let row_updated = sqlx::query(
-            r#"
-            UPDATE items
-            SET tags = ?
-            WHERE type = ?
-                AND name = ?
-                AND wallet_id = ?
-            "#,
-        )
-        .bind(&_tags_to_json(tags)?)
-        .bind(&base64::encode(type_))
-        .bind(&base64::encode(id))
-        .bind(&self.wallet_id)
-        .execute(&mut tx)
-        .await?
-        .rows_affected();
-
-        match row_updated {
-            1 => {
-                tx.commit().await?;
-                Ok(())
-            }
-            0 => Err(err_msg(
-                IndyErrorKind::WalletItemNotFound,
-                "Item to update not found",
-            )),
-            _ => Err(err_msg(
-                IndyErrorKind::InvalidState,
-                "More than one row update. Seems wallet structure is inconsistent",
-            )),
-        }
+        _update_tags(&mut tx, self.wallet_id, type_, id, tags).await?;
+        tx.commit().await?;
+        Ok(())
     }
 
     async fn delete_tags(&self, type_: &[u8], id: &[u8], tag_names: &[TagName]) -> IndyResult<()> {
@@ -428,44 +324,9 @@ impl WalletStorage for MySqlStorage {
         }
 
         let mut tx = self.write_pool.begin().await?;
-
-        let tag_name_paths = _tag_names_to_plain(tag_names)
-            .into_iter()
-            .map(|tag_name| format!(r#"'$."{}"'"#, tag_name))
-            .collect::<Vec<_>>()
-            .join(",");
-
-        let row_updated = sqlx::query(&format!(
-            r#"
-            UPDATE items
-            SET tags = JSON_REMOVE(tags, {})
-            WHERE type = ?
-                AND name = ?
-                AND wallet_id = ?
-            "#,
-            tag_name_paths
-        ))
-        .bind(&base64::encode(type_))
-        .bind(&base64::encode(id))
-        .bind(&self.wallet_id)
-        .execute(&mut tx)
-        .await?
-        .rows_affected();
-
-        match row_updated {
-            1 => {
-                tx.commit().await?;
-                Ok(())
-            }
-            0 => Err(err_msg(
-                IndyErrorKind::WalletItemNotFound,
-                "Item to update not found",
-            )),
-            _ => Err(err_msg(
-                IndyErrorKind::InvalidState,
-                "More than one row update. Seems wallet structure is inconsistent",
-            )),
-        }
+        _delete_tags(&mut tx, self.wallet_id, type_, id, tag_names).await?;
+        tx.commit().await?;
+        Ok(())
     }
 
     ///
@@ -496,35 +357,55 @@ impl WalletStorage for MySqlStorage {
     ///
     async fn delete(&self, type_: &[u8], id: &[u8]) -> IndyResult<()> {
         let mut tx = self.write_pool.begin().await?;
+        _delete(&mut tx, self.wallet_id, type_, id).await?;
+        tx.commit().await?;
+        Ok(())
+    }
 
-        let rows_affected = sqlx::query(
-            r#"
-            DELETE FROM items
-            WHERE type = ?
-                AND name = ?
-                AND wallet_id = ?"#,
-        )
-        .bind(&base64::encode(type_))
-        .bind(&base64::encode(id))
-        .bind(&self.wallet_id)
-        .execute(&mut tx)
-        .await?
-        .rows_affected();
+    ///
+    /// Applies the provided operations inside a single MySQL transaction on the write pool.
+    /// The transaction is only committed if every operation succeeds, otherwise it is
+    /// rolled back when dropped and the storage is left untouched.
+    ///
+    async fn batch(&self, operations: &[StorageOperation]) -> IndyResult<()> {
+        let mut tx = self.write_pool.begin().await?;
 
-        match rows_affected {
-            1 => {
-                tx.commit().await?;
-                Ok(())
+        for operation in operations {
+            match operation {
+                StorageOperation::Add {
+                    type_,
+                    id,
+                    value,
+                    tags,
+                } => _add(&mut tx, self.wallet_id, type_, id, value, tags).await?,
+                StorageOperation::Update { type_, id, value } => {
+                    _update(&mut tx, self.wallet_id, type_, id, value).await?
+                }
+                StorageOperation::AddTags { type_, id, tags } => {
+                    if !tags.is_empty() {
+                        _add_tags(&mut tx, self.wallet_id, type_, id, tags).await?
+                    }
+                }
+                StorageOperation::UpdateTags { type_, id, tags } => {
+                    _update_tags(&mut tx, self.wallet_id, type_, id, tags).await?
+                }
+                StorageOperation::DeleteTags {
+                    type_,
+                    id,
+                    tag_names,
+                } => {
+                    if !tag_names.is_empty() {
+                        _delete_tags(&mut tx, self.wallet_id, type_, id, tag_names).await?
+                    }
+                }
+                StorageOperation::Delete { type_, id } => {
+                    _delete(&mut tx, self.wallet_id, type_, id).await?
+                }
             }
-            0 => Err(err_msg(
-                IndyErrorKind::WalletItemNotFound,
-                "Item to delete not found",
-            )),
-            _ => Err(err_msg(
-                IndyErrorKind::InvalidState,
-                "More than one row deleted. Seems wallet structure is inconsistent",
-            )),
         }
+
+        tx.commit().await?;
+        Ok(())
     }
 
     async fn get_storage_metadata(&self) -> IndyResult<Vec<u8>> {
@@ -710,6 +591,198 @@ impl WalletStorage for MySqlStorage {
     }
 }
 
+async fn _add(
+    tx: &mut Transaction<'_, MySql>,
+    wallet_id: i64,
+    type_: &[u8],
+    id: &[u8],
+    value: &EncryptedValue,
+    tags: &[Tag],
+) -> IndyResult<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO items (type, name, value, tags, wallet_id)
+        VALUE (?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(&base64::encode(type_))
+    .bind(&base64::encode(id))
+    .bind(&value.to_bytes())
+    .bind(&_tags_to_json(tags)?)
+    .bind(&wallet_id)
+    .execute(&mut *tx)
+    .await?;
+
+    Ok(())
+}
+
+async fn _update(
+    tx: &mut Transaction<'_, MySql>,
+    wallet_id: i64,
+    type_: &[u8],
+    id: &[u8],
+    value: &EncryptedValue,
+) -> IndyResult<()> {
+    let row_updated = sqlx::query(
+        r#"
+        UPDATE items
+        SET value = ?
+        WHERE type = ?
+            AND name = ?
+            AND wallet_id = ?
+        "#,
+    )
+    .bind(&value.to_bytes())
+    .bind(&base64::encode(type_))
+    .bind(&base64::encode(id))
+    .bind(&wallet_id)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    _check_single_row_updated(row_updated)
+}
+
+async fn _add_tags(
+    tx: &mut Transaction<'_, MySql>,
+    wallet_id: i64,
+    type_: &[u8],
+    id: &[u8],
+    tags: &[Tag],
+) -> IndyResult<()> {
+    let tag_paths = _tags_to_plain(tags)
+        .into_iter()
+        .map(|(tag, val)| format!(r#"'$."{}"', "{}""#, tag, val))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    let row_updated = sqlx::query(&format!(
+        r#"
+        UPDATE items
+            SET tags = JSON_SET(tags, {})
+        WHERE type = ?
+            AND name = ?
+            AND wallet_id = ?
+        "#,
+        tag_paths
+    ))
+    .bind(&base64::encode(type_))
+    .bind(&base64::encode(id))
+    .bind(&wallet_id)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    _check_single_row_updated(row_updated)
+}
+
+async fn _update_tags(
+    tx: &mut Transaction<'_, MySql>,
+    wallet_id: i64,
+    type_: &[u8],
+    id: &[u8],
+    tags: &[Tag],
+) -> IndyResult<()> {
+    let row_updated = sqlx::query(
+        r#"
+        UPDATE items
+        SET tags = ?
+        WHERE type = ?
+            AND name = ?
+            AND wallet_id = ?
+        "#,
+    )
+    .bind(&_tags_to_json(tags)?)
+    .bind(&base64::encode(type_))
+    .bind(&base64::encode(id))
+    .bind(&wallet_id)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    _check_single_row_updated(row_updated)
+}
+
+async fn _delete_tags(
+    tx: &mut Transaction<'_, MySql>,
+    wallet_id: i64,
+    type_: &[u8],
+    id: &[u8],
+    tag_names: &[TagName],
+) -> IndyResult<()> {
+    let tag_name_paths = _tag_names_to_plain(tag_names)
+        .into_iter()
+        .map(|tag_name| format!(r#"'$."{}"'"#, tag_name))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    let row_updated = sqlx::query(&format!(
+        r#"
+        UPDATE items
+        SET tags = JSON_REMOVE(tags, {})
+        WHERE type = ?
+            AND name = ?
+            AND wallet_id = ?
+        "#,
+        tag_name_paths
+    ))
+    .bind(&base64::encode(type_))
+    .bind(&base64::encode(id))
+    .bind(&wallet_id)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    _check_single_row_updated(row_updated)
+}
+
+async fn _delete(
+    tx: &mut Transaction<'_, MySql>,
+    wallet_id: i64,
+    type_: &[u8],
+    id: &[u8],
+) -> IndyResult<()> {
+    let rows_affected = sqlx::query(
+        r#"
+        DELETE FROM items
+        WHERE type = ?
+            AND name = ?
+            AND wallet_id = ?"#,
+    )
+    .bind(&base64::encode(type_))
+    .bind(&base64::encode(id))
+    .bind(&wallet_id)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    match rows_affected {
+        1 => Ok(()),
+        0 => Err(err_msg(
+            IndyErrorKind::WalletItemNotFound,
+            "Item to delete not found",
+        )),
+        _ => Err(err_msg(
+            IndyErrorKind::InvalidState,
+            "More than one row deleted. Seems wallet structure is inconsistent",
+        )),
+    }
+}
+
+fn _check_single_row_updated(row_updated: u64) -> IndyResult<()> {
+    match row_updated {
+        1 => Ok(()),
+        0 => Err(err_msg(
+            IndyErrorKind::WalletItemNotFound,
+            "Item to update not found",
+        )),
+        _ => Err(err_msg(
+            IndyErrorKind::InvalidState,
+            "More than one row update. Seems wallet structure is inconsistent",
+        )),
+    }
+}
+
 #[async_trait]
 impl WalletStorageType for MySqlStorageType {
     ///
@@ -1885,6 +1958,103 @@ mod tests {
         _cleanup("mysql_storage_delete_tags_works_for_non_existing_id").await;
     }
 
+    #[async_std::test]
+    #[cfg(feature = "mysql")]
+    async fn mysql_storage_batch_works() {
+        _cleanup("mysql_storage_batch_works").await;
+
+        {
+            let storage = _storage("mysql_storage_batch_works").await;
+
+            storage
+                .add(&_type1(), &_id1(), &_value1(), &_tags())
+                .await
+                .unwrap();
+
+            storage
+                .batch(&[
+                    StorageOperation::Update {
+                        type_: _type1(),
+                        id: _id1(),
+                        value: _value2(),
+                    },
+                    StorageOperation::Add {
+                        type_: _type1(),
+                        id: _id2(),
+                        value: _value1(),
+                        tags: _tags(),
+                    },
+                ])
+                .await
+                .unwrap();
+
+            let record = storage
+                .get(
+                    &_type1(),
+                    &_id1(),
+                    r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": false}"##,
+                )
+                .await
+                .unwrap();
+            assert_eq!(record.value.unwrap(), _value2());
+
+            let record = storage
+                .get(
+                    &_type1(),
+                    &_id2(),
+                    r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##,
+                )
+                .await
+                .unwrap();
+            assert_eq!(record.value.unwrap(), _value1());
+            assert_eq!(_sort(record.tags.unwrap()), _sort(_tags()));
+        }
+
+        _cleanup("mysql_storage_batch_works").await;
+    }
+
+    #[async_std::test]
+    #[cfg(feature = "mysql")]
+    async fn mysql_storage_batch_rolls_back_on_failure() {
+        _cleanup("mysql_storage_batch_rolls_back_on_failure").await;
+
+        {
+            let storage = _storage("mysql_storage_batch_rolls_back_on_failure").await;
+
+            storage
+                .add(&_type1(), &_id1(), &_value1(), &_tags())
+                .await
+                .unwrap();
+
+            let res = storage
+                .batch(&[
+                    StorageOperation::Update {
+                        type_: _type1(),
+                        id: _id1(),
+                        value: _value2(),
+                    },
+                    StorageOperation::Delete {
+                        type_: _type1(),
+                        id: _id2(),
+                    },
+                ])
+                .await;
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+            let record = storage
+                .get(
+                    &_type1(),
+                    &_id1(),
+                    r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": false}"##,
+                )
+                .await
+                .unwrap();
+            assert_eq!(record.value.unwrap(), _value1());
+        }
+
+        _cleanup("mysql_storage_batch_rolls_back_on_failure").await;
+    }
+
     fn _config() -> Option<&'static str> {
         Some(
             r#"
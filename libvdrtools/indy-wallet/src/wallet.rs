@@ -16,8 +16,8 @@ use crate::{
     iterator::WalletIterator,
     query_encryption::encrypt_query,
     storage,
-    storage::StorageRecord,
-    RecordOptions, WalletRecord,
+    storage::{StorageOperation, StorageRecord},
+    RecordOptions, WalletOperation, WalletRecord,
 };
 use futures::future::join;
 
@@ -320,6 +320,120 @@ impl Wallet {
         Ok(())
     }
 
+    pub async fn batch(&self, operations: &[WalletOperation]) -> IndyResult<()> {
+        let encrypted_operations: Vec<StorageOperation> = operations
+            .iter()
+            .map(|operation| self._encrypt_operation(operation))
+            .collect();
+
+        self.storage.batch(&encrypted_operations).await?;
+
+        for (operation, encrypted_operation) in operations.iter().zip(encrypted_operations.iter()) {
+            let type_ = operation.get_type();
+            match encrypted_operation {
+                StorageOperation::Add {
+                    type_: etype,
+                    id: ename,
+                    value: evalue,
+                    tags: etags,
+                } => self.cache.add(type_, etype, ename, evalue, etags),
+                StorageOperation::Update {
+                    type_: etype,
+                    id: ename,
+                    value: evalue,
+                } => self.cache.update(type_, etype, ename, evalue).await,
+                StorageOperation::AddTags {
+                    type_: etype,
+                    id: ename,
+                    tags: etags,
+                } => self.cache.add_tags(type_, etype, ename, etags).await,
+                StorageOperation::UpdateTags {
+                    type_: etype,
+                    id: ename,
+                    tags: etags,
+                } => self.cache.update_tags(type_, etype, ename, etags).await,
+                StorageOperation::DeleteTags {
+                    type_: etype,
+                    id: ename,
+                    tag_names: etag_names,
+                } => {
+                    self.cache
+                        .delete_tags(type_, etype, ename, etag_names)
+                        .await
+                }
+                StorageOperation::Delete {
+                    type_: etype,
+                    id: ename,
+                } => self.cache.delete(type_, etype, ename).await,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn _encrypt_operation(&self, operation: &WalletOperation) -> StorageOperation {
+        let etype = encrypt_as_searchable(
+            operation.get_type().as_bytes(),
+            &self.keys.type_key,
+            &self.keys.item_hmac_key,
+        );
+
+        let ename = encrypt_as_searchable(
+            operation.get_name().as_bytes(),
+            &self.keys.name_key,
+            &self.keys.item_hmac_key,
+        );
+
+        let encrypt_tags = |tags| {
+            encrypt_tags(
+                tags,
+                &self.keys.tag_name_key,
+                &self.keys.tag_value_key,
+                &self.keys.tags_hmac_key,
+            )
+        };
+
+        match operation {
+            WalletOperation::Add { value, tags, .. } => StorageOperation::Add {
+                type_: etype,
+                id: ename,
+                value: EncryptedValue::encrypt(value, &self.keys.value_key),
+                tags: encrypt_tags(tags),
+            },
+            WalletOperation::UpdateValue { value, .. } => StorageOperation::Update {
+                type_: etype,
+                id: ename,
+                value: EncryptedValue::encrypt(value, &self.keys.value_key),
+            },
+            WalletOperation::AddTags { tags, .. } => StorageOperation::AddTags {
+                type_: etype,
+                id: ename,
+                tags: encrypt_tags(tags),
+            },
+            WalletOperation::UpdateTags { tags, .. } => StorageOperation::UpdateTags {
+                type_: etype,
+                id: ename,
+                tags: encrypt_tags(tags),
+            },
+            WalletOperation::DeleteTags { tag_names, .. } => {
+                let tag_names: Vec<&str> = tag_names.iter().map(String::as_str).collect();
+                StorageOperation::DeleteTags {
+                    type_: etype,
+                    id: ename,
+                    tag_names: encrypt_tag_names(
+                        &tag_names,
+                        &self.keys.tag_name_key,
+                        &self.keys.tags_hmac_key,
+                    ),
+                }
+            }
+            WalletOperation::Delete { .. } => StorageOperation::Delete {
+                type_: etype,
+                id: ename,
+            },
+        }
+    }
+
     pub async fn get(
         &self,
         type_: &str,
@@ -3,7 +3,9 @@ use std::{collections::HashMap, sync::Arc};
 use futures::lock::Mutex;
 use indy_api_types::{domain::wallet::Tags, errors::prelude::*, SearchHandle, WalletHandle};
 use indy_utils::next_search_handle;
-use indy_wallet::{RecordOptions, SearchOptions, WalletRecord, WalletSearch, WalletService};
+use indy_wallet::{
+    RecordOptions, SearchOptions, WalletOperation, WalletRecord, WalletSearch, WalletService,
+};
 
 pub struct NonSecretsController {
     wallet_service: Arc<WalletService>,
@@ -269,6 +271,72 @@ impl NonSecretsController {
         res
     }
 
+    /// Apply several non-secret record writes atomically
+    ///
+    /// #Params
+
+    /// wallet_handle: wallet handle (created by open_wallet)
+    /// operations: list of record writes (add, update value, add/update/delete tags, delete)
+    ///   to apply. Either all of them are persisted or, if any of them fails, none are.
+    pub async fn batch_records(
+        &self,
+        wallet_handle: WalletHandle,
+        operations: Vec<WalletOperation>,
+    ) -> IndyResult<()> {
+        trace!(
+            "batch_records > wallet_handle {:?} operations {:?}",
+            wallet_handle,
+            operations
+        );
+
+        for operation in operations.iter() {
+            self._check_type(operation.get_type())?;
+        }
+
+        self.wallet_service
+            .batch_records(wallet_handle, &operations)
+            .await?;
+
+        let res = Ok(());
+        trace!("batch_records < {:?}", res);
+        res
+    }
+
+    /// Store a record exported from another wallet as is, including the records vdrtools keeps
+    /// for itself (DIDs, keys, ...), which are otherwise not accessible through this controller.
+    /// Meant to be used for wallet migration only.
+    ///
+    /// #Params
+
+    /// wallet_handle: wallet handle (created by open_wallet)
+    /// type_: type of the exported record
+    /// id: the id of the exported record
+    /// value: the value of the exported record
+    /// tags: the tags of the exported record
+    pub async fn restore_record(
+        &self,
+        wallet_handle: WalletHandle,
+        type_: String,
+        id: String,
+        value: String,
+        tags: Tags,
+    ) -> IndyResult<()> {
+        trace!(
+            "restore_record > wallet_handle {:?} type_ {:?} id {:?}",
+            wallet_handle,
+            type_,
+            id
+        );
+
+        self.wallet_service
+            .add_record(wallet_handle, &type_, &id, &value, &tags)
+            .await?;
+
+        let res = Ok(());
+        trace!("restore_record < {:?}", res);
+        res
+    }
+
     /// Get an wallet record by id
     ///
     /// #Params
@@ -396,6 +464,39 @@ impl NonSecretsController {
         res
     }
 
+    /// Search for every record stored in the wallet, regardless of its type. Records vdrtools
+    /// keeps for itself (DIDs, keys, ...) are included, which makes the search suitable for
+    /// wallet migration only.
+    ///
+    /// Records are fetched with `fetch_search_next_records` and always contain their type,
+    /// value and tags.
+    ///
+    /// #Params
+    /// wallet_handle: wallet handle (created by open_wallet)
+    ///
+    /// #Returns
+    /// search_handle: Wallet search handle that can be used later
+    ///   to fetch records by small batches (with indy_fetch_wallet_search_next_records)
+    pub async fn open_search_all(&self, wallet_handle: WalletHandle) -> IndyResult<SearchHandle> {
+        trace!("open_search_all > wallet_handle {:?}", wallet_handle);
+
+        let search = self
+            .wallet_service
+            .search_all_records(wallet_handle)
+            .await?;
+
+        let search_handle = next_search_handle();
+
+        self.searches
+            .lock()
+            .await
+            .insert(search_handle, Arc::new(Mutex::new(search)));
+
+        let res = Ok(search_handle);
+        trace!("open_search_all < {:?}", search_handle);
+        res
+    }
+
     /// Fetch next records for wallet search.
     ///
     /// Not if there are no records this call returns WalletNoRecords error.
@@ -72,6 +72,8 @@ pub use indy_api_types::{
     INVALID_POOL_HANDLE, INVALID_SEARCH_HANDLE, INVALID_WALLET_HANDLE,
 };
 
+pub use indy_wallet::WalletOperation;
+
 pub use services::AnoncredsHelpers;
 
 // Global (lazy inited) instance of Locator
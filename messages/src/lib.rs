@@ -25,9 +25,9 @@ use crate::{
     msg_fields::{
         protocols::{
             basic_message::BasicMessage, connection::Connection, cred_issuance::CredentialIssuance,
-            discover_features::DiscoverFeatures, notification::Notification, out_of_band::OutOfBand,
-            present_proof::PresentProof, report_problem::ProblemReport, revocation::Revocation, routing::Forward,
-            trust_ping::TrustPing,
+            did_exchange::DidExchange, did_rotate::DidRotate, discover_features::DiscoverFeatures,
+            notification::Notification, out_of_band::OutOfBand, present_proof::PresentProof,
+            report_problem::ProblemReport, revocation::Revocation, routing::Forward, trust_ping::TrustPing,
         },
         traits::DelayedSerde,
     },
@@ -62,6 +62,8 @@ pub enum AriesMessage {
     BasicMessage(BasicMessage),
     OutOfBand(OutOfBand),
     Notification(Notification),
+    DidRotate(DidRotate),
+    DidExchange(DidExchange),
 }
 
 impl DelayedSerde for AriesMessage {
@@ -136,6 +138,12 @@ impl DelayedSerde for AriesMessage {
             Protocol::NotificationType(msg_type) => {
                 Notification::delayed_deserialize((msg_type, kind_str), deserializer).map(From::from)
             }
+            Protocol::DidRotateType(msg_type) => {
+                DidRotate::delayed_deserialize((msg_type, kind_str), deserializer).map(From::from)
+            }
+            Protocol::DidExchangeType(msg_type) => {
+                DidExchange::delayed_deserialize((msg_type, kind_str), deserializer).map(From::from)
+            }
         }
     }
 
@@ -155,6 +163,8 @@ impl DelayedSerde for AriesMessage {
             Self::BasicMessage(v) => MsgWithType::from(v).serialize(serializer),
             Self::OutOfBand(v) => v.delayed_serialize(serializer),
             Self::Notification(v) => v.delayed_serialize(serializer),
+            Self::DidRotate(v) => v.delayed_serialize(serializer),
+            Self::DidExchange(v) => v.delayed_serialize(serializer),
         }
     }
 }
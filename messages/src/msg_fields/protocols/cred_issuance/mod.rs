@@ -5,6 +5,7 @@ pub mod issue_credential;
 pub mod offer_credential;
 pub mod propose_credential;
 pub mod request_credential;
+pub mod v2;
 
 use std::str::FromStr;
 
@@ -18,6 +19,7 @@ use self::{
     offer_credential::{OfferCredential, OfferCredentialContent, OfferCredentialDecorators},
     propose_credential::{ProposeCredential, ProposeCredentialContent, ProposeCredentialDecorators},
     request_credential::{RequestCredential, RequestCredentialContent, RequestCredentialDecorators},
+    v2::CredentialIssuanceV2,
 };
 use super::notification::ack::AckDecorators;
 use crate::{
@@ -42,6 +44,7 @@ pub enum CredentialIssuance {
     RequestCredential(RequestCredential),
     IssueCredential(IssueCredential),
     Ack(AckCredential),
+    V2(CredentialIssuanceV2),
 }
 
 impl DelayedSerde for CredentialIssuance {
@@ -54,6 +57,9 @@ impl DelayedSerde for CredentialIssuance {
         let (protocol, kind_str) = msg_type;
         let kind = match protocol {
             CredentialIssuanceKind::V1(CredentialIssuanceTypeV1::V1_0(kind)) => kind.kind_from_str(kind_str),
+            CredentialIssuanceKind::V2(protocol) => {
+                return CredentialIssuanceV2::delayed_deserialize((protocol, kind_str), deserializer).map(From::from)
+            }
         };
 
         match kind.map_err(D::Error::custom)? {
@@ -80,6 +86,7 @@ impl DelayedSerde for CredentialIssuance {
             Self::RequestCredential(v) => MsgWithType::from(v).serialize(serializer),
             Self::IssueCredential(v) => MsgWithType::from(v).serialize(serializer),
             Self::Ack(v) => MsgWithType::from(v).serialize(serializer),
+            Self::V2(v) => v.delayed_serialize(serializer),
         }
     }
 }
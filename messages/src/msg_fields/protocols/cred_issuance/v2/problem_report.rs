@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    msg_fields::protocols::report_problem::{ProblemReportContent, ProblemReportDecorators},
+    msg_parts::MsgParts,
+};
+
+pub type CredIssuanceProblemReportV2 = MsgParts<CredIssuanceProblemReportV2Content, ProblemReportDecorators>;
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(transparent)]
+pub struct CredIssuanceProblemReportV2Content(pub ProblemReportContent);
+
+impl CredIssuanceProblemReportV2Content {
+    pub fn new(code: String) -> Self {
+        Self(ProblemReportContent::new(code))
+    }
+}
@@ -0,0 +1,152 @@
+use serde::{Deserialize, Serialize};
+
+use super::{AttachmentFormatSpecifier, CredentialPreviewV2};
+use crate::{
+    decorators::{attachment::Attachment, thread::Thread, timing::Timing},
+    msg_parts::MsgParts,
+};
+
+pub type ProposeCredentialV2 = MsgParts<ProposeCredentialV2Content, ProposeCredentialV2Decorators>;
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct ProposeCredentialV2Content {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub goal_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub credential_preview: Option<CredentialPreviewV2>,
+    pub formats: Vec<AttachmentFormatSpecifier<ProposeCredentialAttachmentFormatType>>,
+    #[serde(rename = "filters~attach")]
+    pub filters_attach: Vec<Attachment>,
+}
+
+impl ProposeCredentialV2Content {
+    pub fn new(
+        formats: Vec<AttachmentFormatSpecifier<ProposeCredentialAttachmentFormatType>>,
+        filters_attach: Vec<Attachment>,
+    ) -> Self {
+        Self {
+            comment: None,
+            goal_code: None,
+            credential_preview: None,
+            formats,
+            filters_attach,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Default, PartialEq)]
+pub struct ProposeCredentialV2Decorators {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "~thread")]
+    pub thread: Option<Thread>,
+    #[serde(rename = "~timing")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<Timing>,
+}
+
+#[derive(Copy, Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub enum ProposeCredentialAttachmentFormatType {
+    #[serde(rename = "hlindy/cred-filter@v2.0")]
+    HyperledgerIndyCredentialFilter2_0,
+    #[serde(rename = "anoncreds/credential-filter@v1.0")]
+    AnoncredsCredentialFilter1_0,
+    #[serde(rename = "aries/ld-proof-vc-detail@v1.0")]
+    AriesLdProofVcDetail1_0,
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::field_reassign_with_default)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        decorators::{
+            attachment::tests::make_extended_attachment, thread::tests::make_extended_thread,
+            timing::tests::make_extended_timing,
+        },
+        maybe_known::MaybeKnown,
+        misc::test_utils,
+        msg_fields::protocols::cred_issuance::CredentialAttr,
+        msg_types::cred_issuance::CredentialIssuanceTypeV2_0,
+    };
+
+    #[test]
+    fn test_minimal_propose_cred_v2() {
+        let attachment = make_extended_attachment();
+        let format = AttachmentFormatSpecifier::new(
+            attachment.id.clone().unwrap(),
+            ProposeCredentialAttachmentFormatType::HyperledgerIndyCredentialFilter2_0,
+        );
+        let content = ProposeCredentialV2Content::new(vec![format], vec![attachment]);
+
+        let decorators = ProposeCredentialV2Decorators::default();
+
+        let expected = json!({
+            "formats": [{
+                "attach_id": content.filters_attach[0].id,
+                "format": "hlindy/cred-filter@v2.0"
+            }],
+            "filters~attach": content.filters_attach,
+        });
+
+        test_utils::test_msg(
+            content,
+            decorators,
+            CredentialIssuanceTypeV2_0::ProposeCredential,
+            expected,
+        );
+    }
+
+    #[test]
+    fn test_extended_propose_cred_v2() {
+        let attachment = make_extended_attachment();
+        let format = AttachmentFormatSpecifier::new(
+            attachment.id.clone().unwrap(),
+            ProposeCredentialAttachmentFormatType::AnoncredsCredentialFilter1_0,
+        );
+        let attribute = CredentialAttr::new("test_attribute_name".to_owned(), "test_attribute_value".to_owned());
+        let mut content = ProposeCredentialV2Content::new(vec![format], vec![attachment]);
+        content.comment = Some("test_comment".to_owned());
+        content.goal_code = Some("test_goal_code".to_owned());
+        content.credential_preview = Some(CredentialPreviewV2::new(vec![attribute]));
+
+        let mut decorators = ProposeCredentialV2Decorators::default();
+        decorators.thread = Some(make_extended_thread());
+        decorators.timing = Some(make_extended_timing());
+
+        let expected = json!({
+            "formats": content.formats,
+            "filters~attach": content.filters_attach,
+            "comment": content.comment,
+            "goal_code": content.goal_code,
+            "credential_preview": {
+                "@type": "https://didcomm.org/issue-credential/2.0/credential-preview",
+                "attributes": [{ "name": "test_attribute_name", "value": "test_attribute_value" }]
+            },
+            "~thread": decorators.thread,
+            "~timing": decorators.timing
+        });
+
+        test_utils::test_msg(
+            content,
+            decorators,
+            CredentialIssuanceTypeV2_0::ProposeCredential,
+            expected,
+        );
+    }
+
+    #[test]
+    fn test_unknown_format_propose_cred_v2() {
+        let format: AttachmentFormatSpecifier<ProposeCredentialAttachmentFormatType> =
+            serde_json::from_value(json!({ "attach_id": "1", "format": "dif/credential-manifest@v1.0" })).unwrap();
+
+        assert_eq!(
+            format.format,
+            MaybeKnown::Unknown("dif/credential-manifest@v1.0".to_owned())
+        );
+    }
+}
@@ -0,0 +1,128 @@
+use serde::{Deserialize, Serialize};
+
+use super::AttachmentFormatSpecifier;
+use crate::{
+    decorators::{attachment::Attachment, thread::Thread, timing::Timing},
+    msg_parts::MsgParts,
+};
+
+pub type RequestCredentialV2 = MsgParts<RequestCredentialV2Content, RequestCredentialV2Decorators>;
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct RequestCredentialV2Content {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub goal_code: Option<String>,
+    pub formats: Vec<AttachmentFormatSpecifier<RequestCredentialAttachmentFormatType>>,
+    #[serde(rename = "requests~attach")]
+    pub requests_attach: Vec<Attachment>,
+}
+
+impl RequestCredentialV2Content {
+    pub fn new(
+        formats: Vec<AttachmentFormatSpecifier<RequestCredentialAttachmentFormatType>>,
+        requests_attach: Vec<Attachment>,
+    ) -> Self {
+        Self {
+            comment: None,
+            goal_code: None,
+            formats,
+            requests_attach,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Default, PartialEq)]
+pub struct RequestCredentialV2Decorators {
+    #[serde(rename = "~thread")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread: Option<Thread>,
+    #[serde(rename = "~timing")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<Timing>,
+}
+
+#[derive(Copy, Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub enum RequestCredentialAttachmentFormatType {
+    #[serde(rename = "hlindy/cred-req@v2.0")]
+    HyperledgerIndyCredentialRequest2_0,
+    #[serde(rename = "anoncreds/credential-request@v1.0")]
+    AnoncredsCredentialRequest1_0,
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::field_reassign_with_default)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        decorators::{
+            attachment::tests::make_extended_attachment, thread::tests::make_extended_thread,
+            timing::tests::make_extended_timing,
+        },
+        misc::test_utils,
+        msg_types::cred_issuance::CredentialIssuanceTypeV2_0,
+    };
+
+    #[test]
+    fn test_minimal_request_cred_v2() {
+        let attachment = make_extended_attachment();
+        let format = AttachmentFormatSpecifier::new(
+            attachment.id.clone().unwrap(),
+            RequestCredentialAttachmentFormatType::HyperledgerIndyCredentialRequest2_0,
+        );
+        let content = RequestCredentialV2Content::new(vec![format], vec![attachment]);
+
+        let decorators = RequestCredentialV2Decorators::default();
+
+        let expected = json!({
+            "formats": [{
+                "attach_id": content.requests_attach[0].id,
+                "format": "hlindy/cred-req@v2.0"
+            }],
+            "requests~attach": content.requests_attach,
+        });
+
+        test_utils::test_msg(
+            content,
+            decorators,
+            CredentialIssuanceTypeV2_0::RequestCredential,
+            expected,
+        );
+    }
+
+    #[test]
+    fn test_extended_request_cred_v2() {
+        let attachment = make_extended_attachment();
+        let format = AttachmentFormatSpecifier::new(
+            attachment.id.clone().unwrap(),
+            RequestCredentialAttachmentFormatType::AnoncredsCredentialRequest1_0,
+        );
+        let mut content = RequestCredentialV2Content::new(vec![format], vec![attachment]);
+        content.comment = Some("test_comment".to_owned());
+        content.goal_code = Some("test_goal_code".to_owned());
+
+        let mut decorators = RequestCredentialV2Decorators::default();
+        decorators.thread = Some(make_extended_thread());
+        decorators.timing = Some(make_extended_timing());
+
+        let expected = json!({
+            "formats": content.formats,
+            "requests~attach": content.requests_attach,
+            "comment": content.comment,
+            "goal_code": content.goal_code,
+            "~thread": decorators.thread,
+            "~timing": decorators.timing
+        });
+
+        test_utils::test_msg(
+            content,
+            decorators,
+            CredentialIssuanceTypeV2_0::RequestCredential,
+            expected,
+        );
+    }
+}
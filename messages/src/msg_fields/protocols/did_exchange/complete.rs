@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+
+// Bind `shared_vcx::misc::serde_ignored::SerdeIgnored` type as `NoDecorators`.
+use shared_vcx::misc::serde_ignored::SerdeIgnored as NoDecorators;
+
+use crate::{
+    decorators::{thread::Thread, timing::Timing},
+    msg_parts::MsgParts,
+};
+
+pub type Complete = MsgParts<CompleteContent, CompleteDecorators>;
+
+#[derive(Clone, Debug, Deserialize, Serialize, Default, PartialEq)]
+#[serde(transparent)]
+pub struct CompleteContent(NoDecorators);
+
+/// The thread of a `complete` message must carry both the `thid` of the exchange
+/// and the `pthid` of the invitation that started it.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct CompleteDecorators {
+    #[serde(rename = "~thread")]
+    pub thread: Thread,
+    #[serde(rename = "~timing")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<Timing>,
+}
+
+impl CompleteDecorators {
+    pub fn new(thread: Thread) -> Self {
+        Self { thread, timing: None }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::field_reassign_with_default)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        decorators::{thread::tests::make_extended_thread, timing::tests::make_extended_timing},
+        misc::test_utils,
+        msg_types::did_exchange::DidExchangeTypeV1_0,
+    };
+
+    #[test]
+    fn test_minimal_did_exchange_complete() {
+        let content = CompleteContent::default();
+
+        let decorators = CompleteDecorators::new(make_extended_thread());
+
+        let expected = json!({
+            "~thread": decorators.thread
+        });
+
+        test_utils::test_msg(content, decorators, DidExchangeTypeV1_0::Complete, expected);
+    }
+
+    #[test]
+    fn test_extended_did_exchange_complete() {
+        let content = CompleteContent::default();
+
+        let mut decorators = CompleteDecorators::new(make_extended_thread());
+        decorators.timing = Some(make_extended_timing());
+
+        let expected = json!({
+            "~thread": decorators.thread,
+            "~timing": decorators.timing
+        });
+
+        test_utils::test_msg(content, decorators, DidExchangeTypeV1_0::Complete, expected);
+    }
+}
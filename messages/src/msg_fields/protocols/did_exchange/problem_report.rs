@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    msg_fields::protocols::report_problem::{ProblemReportContent, ProblemReportDecorators},
+    msg_parts::MsgParts,
+};
+
+pub type DidExchangeProblemReport = MsgParts<DidExchangeProblemReportContent, ProblemReportDecorators>;
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(transparent)]
+pub struct DidExchangeProblemReportContent(pub ProblemReportContent);
+
+impl DidExchangeProblemReportContent {
+    pub fn new(code: String) -> Self {
+        Self(ProblemReportContent::new(code))
+    }
+}
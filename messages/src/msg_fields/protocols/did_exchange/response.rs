@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    decorators::{attachment::Attachment, thread::Thread, timing::Timing},
+    msg_parts::MsgParts,
+};
+
+pub type Response = MsgParts<ResponseContent, ResponseDecorators>;
+
+/// When present, the `did_doc~attach` is signed with the key the responder used in its invitation,
+/// proving the requester talks to the party it invited.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct ResponseContent {
+    pub did: String,
+    #[serde(rename = "did_doc~attach")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub did_doc: Option<Attachment>,
+}
+
+impl ResponseContent {
+    pub fn new(did: String) -> Self {
+        Self { did, did_doc: None }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct ResponseDecorators {
+    #[serde(rename = "~thread")]
+    pub thread: Thread,
+    #[serde(rename = "~timing")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<Timing>,
+}
+
+impl ResponseDecorators {
+    pub fn new(thread: Thread) -> Self {
+        Self { thread, timing: None }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::field_reassign_with_default)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        decorators::{
+            attachment::tests::make_extended_attachment, thread::tests::make_extended_thread,
+            timing::tests::make_extended_timing,
+        },
+        misc::test_utils,
+        msg_types::did_exchange::DidExchangeTypeV1_0,
+    };
+
+    #[test]
+    fn test_minimal_did_exchange_response() {
+        let content = ResponseContent::new("did:sov:test_did".to_owned());
+
+        let decorators = ResponseDecorators::new(make_extended_thread());
+
+        let expected = json!({
+            "did": content.did,
+            "~thread": decorators.thread
+        });
+
+        test_utils::test_msg(content, decorators, DidExchangeTypeV1_0::Response, expected);
+    }
+
+    #[test]
+    fn test_extended_did_exchange_response() {
+        let mut content = ResponseContent::new("did:peer:test_did".to_owned());
+        content.did_doc = Some(make_extended_attachment());
+
+        let mut decorators = ResponseDecorators::new(make_extended_thread());
+        decorators.timing = Some(make_extended_timing());
+
+        let expected = json!({
+            "did": content.did,
+            "did_doc~attach": content.did_doc,
+            "~thread": decorators.thread,
+            "~timing": decorators.timing
+        });
+
+        test_utils::test_msg(content, decorators, DidExchangeTypeV1_0::Response, expected);
+    }
+}
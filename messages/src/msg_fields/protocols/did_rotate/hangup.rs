@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    decorators::{thread::Thread, timing::Timing},
+    msg_parts::MsgParts,
+};
+
+pub type Hangup = MsgParts<HangupContent, HangupDecorators>;
+
+/// Sent instead of a [`super::rotate::Rotate`] when the rotating party ends the relationship,
+/// so the message carries no fields of its own.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct HangupContent {}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct HangupDecorators {
+    #[serde(rename = "~thread")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread: Option<Thread>,
+    #[serde(rename = "~timing")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<Timing>,
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::field_reassign_with_default)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        decorators::{thread::tests::make_extended_thread, timing::tests::make_extended_timing},
+        misc::test_utils,
+        msg_types::did_rotate::DidRotateTypeV1_0,
+    };
+
+    #[test]
+    fn test_minimal_hangup() {
+        let content = HangupContent::default();
+
+        let decorators = HangupDecorators::default();
+
+        let expected = json!({});
+
+        test_utils::test_msg(content, decorators, DidRotateTypeV1_0::Hangup, expected);
+    }
+
+    #[test]
+    fn test_extended_hangup() {
+        let content = HangupContent::default();
+
+        let mut decorators = HangupDecorators::default();
+        decorators.thread = Some(make_extended_thread());
+        decorators.timing = Some(make_extended_timing());
+
+        let expected = json!({
+            "~thread": decorators.thread,
+            "~timing": decorators.timing
+        });
+
+        test_utils::test_msg(content, decorators, DidRotateTypeV1_0::Hangup, expected);
+    }
+}
@@ -0,0 +1,77 @@
+//! Module containing the `DID rotate` protocol messages, as defined in the [RFC](<https://github.com/hyperledger/aries-rfcs/blob/main/features/0794-did-rotate/README.md>).
+
+pub mod ack;
+pub mod hangup;
+pub mod problem_report;
+pub mod rotate;
+
+use derive_more::From;
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+use self::{
+    ack::{AckDidRotate, AckDidRotateContent},
+    hangup::{Hangup, HangupContent, HangupDecorators},
+    problem_report::{DidRotateProblemReport, DidRotateProblemReportContent},
+    rotate::{Rotate, RotateContent, RotateDecorators},
+};
+use super::{notification::ack::AckDecorators, report_problem::ProblemReportDecorators};
+use crate::{
+    misc::utils::{into_msg_with_type, transit_to_aries_msg},
+    msg_fields::traits::DelayedSerde,
+    msg_types::{
+        protocols::did_rotate::{DidRotateType as DidRotateKind, DidRotateTypeV1, DidRotateTypeV1_0},
+        MsgWithType,
+    },
+};
+
+#[derive(Clone, Debug, From, PartialEq)]
+pub enum DidRotate {
+    Rotate(Rotate),
+    Ack(AckDidRotate),
+    ProblemReport(DidRotateProblemReport),
+    Hangup(Hangup),
+}
+
+impl DelayedSerde for DidRotate {
+    type MsgType<'a> = (DidRotateKind, &'a str);
+
+    fn delayed_deserialize<'de, D>(msg_type: Self::MsgType<'de>, deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (protocol, kind_str) = msg_type;
+
+        let kind = match protocol {
+            DidRotateKind::V1(DidRotateTypeV1::V1_0(kind)) => kind.kind_from_str(kind_str),
+        };
+
+        match kind.map_err(D::Error::custom)? {
+            DidRotateTypeV1_0::Rotate => Rotate::deserialize(deserializer).map(From::from),
+            DidRotateTypeV1_0::Ack => AckDidRotate::deserialize(deserializer).map(From::from),
+            DidRotateTypeV1_0::ProblemReport => DidRotateProblemReport::deserialize(deserializer).map(From::from),
+            DidRotateTypeV1_0::Hangup => Hangup::deserialize(deserializer).map(From::from),
+        }
+    }
+
+    fn delayed_serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::Rotate(v) => MsgWithType::from(v).serialize(serializer),
+            Self::Ack(v) => MsgWithType::from(v).serialize(serializer),
+            Self::ProblemReport(v) => MsgWithType::from(v).serialize(serializer),
+            Self::Hangup(v) => MsgWithType::from(v).serialize(serializer),
+        }
+    }
+}
+
+transit_to_aries_msg!(RotateContent: RotateDecorators, DidRotate);
+transit_to_aries_msg!(AckDidRotateContent: AckDecorators, DidRotate);
+transit_to_aries_msg!(DidRotateProblemReportContent: ProblemReportDecorators, DidRotate);
+transit_to_aries_msg!(HangupContent: HangupDecorators, DidRotate);
+
+into_msg_with_type!(Rotate, DidRotateTypeV1_0, Rotate);
+into_msg_with_type!(AckDidRotate, DidRotateTypeV1_0, Ack);
+into_msg_with_type!(DidRotateProblemReport, DidRotateTypeV1_0, ProblemReport);
+into_msg_with_type!(Hangup, DidRotateTypeV1_0, Hangup);
@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    decorators::{please_ack::PleaseAck, thread::Thread, timing::Timing},
+    msg_parts::MsgParts,
+};
+
+pub type Rotate = MsgParts<RotateContent, RotateDecorators>;
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct RotateContent {
+    pub to_did: String,
+}
+
+impl RotateContent {
+    pub fn new(to_did: String) -> Self {
+        Self { to_did }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct RotateDecorators {
+    #[serde(rename = "~thread")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread: Option<Thread>,
+    #[serde(rename = "~please_ack")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub please_ack: Option<PleaseAck>,
+    #[serde(rename = "~timing")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<Timing>,
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::field_reassign_with_default)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        decorators::{
+            please_ack::tests::make_minimal_please_ack, thread::tests::make_extended_thread,
+            timing::tests::make_extended_timing,
+        },
+        misc::test_utils,
+        msg_types::did_rotate::DidRotateTypeV1_0,
+    };
+
+    #[test]
+    fn test_minimal_rotate() {
+        let content = RotateContent::new("test_did".to_owned());
+
+        let decorators = RotateDecorators::default();
+
+        let expected = json!({
+            "to_did": content.to_did
+        });
+
+        test_utils::test_msg(content, decorators, DidRotateTypeV1_0::Rotate, expected);
+    }
+
+    #[test]
+    fn test_extended_rotate() {
+        let content = RotateContent::new("test_did".to_owned());
+
+        let mut decorators = RotateDecorators::default();
+        decorators.thread = Some(make_extended_thread());
+        decorators.please_ack = Some(make_minimal_please_ack());
+        decorators.timing = Some(make_extended_timing());
+
+        let expected = json!({
+            "to_did": content.to_did,
+            "~thread": decorators.thread,
+            "~please_ack": decorators.please_ack,
+            "~timing": decorators.timing
+        });
+
+        test_utils::test_msg(content, decorators, DidRotateTypeV1_0::Rotate, expected);
+    }
+}
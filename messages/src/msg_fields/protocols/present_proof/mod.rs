@@ -4,6 +4,7 @@ pub mod ack;
 pub mod present;
 pub mod propose;
 pub mod request;
+pub mod v2;
 
 use derive_more::From;
 use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
@@ -13,6 +14,7 @@ use self::{
     present::{Presentation, PresentationContent, PresentationDecorators},
     propose::{ProposePresentation, ProposePresentationContent, ProposePresentationDecorators},
     request::{RequestPresentation, RequestPresentationContent, RequestPresentationDecorators},
+    v2::PresentProofV2,
 };
 use super::notification::ack::AckDecorators;
 use crate::{
@@ -30,6 +32,7 @@ pub enum PresentProof {
     RequestPresentation(RequestPresentation),
     Presentation(Presentation),
     Ack(AckPresentation),
+    V2(PresentProofV2),
 }
 
 impl DelayedSerde for PresentProof {
@@ -43,6 +46,9 @@ impl DelayedSerde for PresentProof {
 
         let kind = match protocol {
             PresentProofType::V1(PresentProofTypeV1::V1_0(kind)) => kind.kind_from_str(kind_str),
+            PresentProofType::V2(protocol) => {
+                return PresentProofV2::delayed_deserialize((protocol, kind_str), deserializer).map(From::from)
+            }
         };
 
         match kind.map_err(D::Error::custom)? {
@@ -63,6 +69,7 @@ impl DelayedSerde for PresentProof {
             Self::RequestPresentation(v) => MsgWithType::from(v).serialize(serializer),
             Self::Presentation(v) => MsgWithType::from(v).serialize(serializer),
             Self::Ack(v) => MsgWithType::from(v).serialize(serializer),
+            Self::V2(v) => v.delayed_serialize(serializer),
         }
     }
 }
@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    decorators::{attachment::Attachment, thread::Thread, timing::Timing},
+    msg_fields::protocols::cred_issuance::v2::AttachmentFormatSpecifier,
+    msg_parts::MsgParts,
+};
+
+pub type RequestPresentationV2 = MsgParts<RequestPresentationV2Content, RequestPresentationV2Decorators>;
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct RequestPresentationV2Content {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub goal_code: Option<String>,
+    /// Whether the verifier will answer the presentation with an ack or a problem report.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub will_confirm: Option<bool>,
+    pub formats: Vec<AttachmentFormatSpecifier<RequestPresentationAttachmentFormatType>>,
+    #[serde(rename = "request_presentations~attach")]
+    pub request_presentations_attach: Vec<Attachment>,
+}
+
+impl RequestPresentationV2Content {
+    pub fn new(
+        formats: Vec<AttachmentFormatSpecifier<RequestPresentationAttachmentFormatType>>,
+        request_presentations_attach: Vec<Attachment>,
+    ) -> Self {
+        Self {
+            comment: None,
+            goal_code: None,
+            will_confirm: None,
+            formats,
+            request_presentations_attach,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Default, PartialEq)]
+pub struct RequestPresentationV2Decorators {
+    #[serde(rename = "~thread")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread: Option<Thread>,
+    #[serde(rename = "~timing")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<Timing>,
+}
+
+#[derive(Copy, Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub enum RequestPresentationAttachmentFormatType {
+    #[serde(rename = "hlindy/proof-req@v2.0")]
+    HyperledgerIndyProofRequest2_0,
+    #[serde(rename = "anoncreds/proof-request@v1.0")]
+    AnoncredsProofRequest1_0,
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::field_reassign_with_default)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        decorators::{
+            attachment::tests::make_extended_attachment, thread::tests::make_extended_thread,
+            timing::tests::make_extended_timing,
+        },
+        misc::test_utils,
+        msg_types::present_proof::PresentProofTypeV2_0,
+    };
+
+    fn make_request_content() -> RequestPresentationV2Content {
+        let attachment = make_extended_attachment();
+        let format = AttachmentFormatSpecifier::new(
+            attachment.id.clone().unwrap(),
+            RequestPresentationAttachmentFormatType::AnoncredsProofRequest1_0,
+        );
+        RequestPresentationV2Content::new(vec![format], vec![attachment])
+    }
+
+    #[test]
+    fn test_minimal_request_proof_v2() {
+        let content = make_request_content();
+
+        let decorators = RequestPresentationV2Decorators::default();
+
+        let expected = json!({
+            "formats": [{
+                "attach_id": content.request_presentations_attach[0].id,
+                "format": "anoncreds/proof-request@v1.0"
+            }],
+            "request_presentations~attach": content.request_presentations_attach,
+        });
+
+        test_utils::test_msg(content, decorators, PresentProofTypeV2_0::RequestPresentation, expected);
+    }
+
+    #[test]
+    fn test_extended_request_proof_v2() {
+        let mut content = make_request_content();
+        content.comment = Some("test_comment".to_owned());
+        content.goal_code = Some("test_goal_code".to_owned());
+        content.will_confirm = Some(true);
+
+        let mut decorators = RequestPresentationV2Decorators::default();
+        decorators.thread = Some(make_extended_thread());
+        decorators.timing = Some(make_extended_timing());
+
+        let expected = json!({
+            "formats": content.formats,
+            "request_presentations~attach": content.request_presentations_attach,
+            "comment": content.comment,
+            "goal_code": content.goal_code,
+            "will_confirm": true,
+            "~thread": decorators.thread,
+            "~timing": decorators.timing
+        });
+
+        test_utils::test_msg(content, decorators, PresentProofTypeV2_0::RequestPresentation, expected);
+    }
+}
@@ -10,6 +10,7 @@ use crate::msg_types::{role::Role, MsgKindType};
 #[msg_type(protocol = "issue-credential")]
 pub enum CredentialIssuanceType {
     V1(CredentialIssuanceTypeV1),
+    V2(CredentialIssuanceTypeV2),
 }
 
 #[derive(Copy, Clone, Debug, From, TryInto, PartialEq, TransitiveFrom, MessageType)]
@@ -20,6 +21,14 @@ pub enum CredentialIssuanceTypeV1 {
     V1_0(MsgKindType<CredentialIssuanceTypeV1_0>),
 }
 
+#[derive(Copy, Clone, Debug, From, TryInto, PartialEq, TransitiveFrom, MessageType)]
+#[transitive(into(CredentialIssuanceType, Protocol))]
+#[msg_type(major = 2)]
+pub enum CredentialIssuanceTypeV2 {
+    #[msg_type(minor = 0, roles = "Role::Holder, Role::Issuer")]
+    V2_0(MsgKindType<CredentialIssuanceTypeV2_0>),
+}
+
 #[derive(Copy, Clone, Debug, AsRefStr, EnumString, PartialEq)]
 #[strum(serialize_all = "kebab-case")]
 pub enum CredentialIssuanceTypeV1_0 {
@@ -31,6 +40,18 @@ pub enum CredentialIssuanceTypeV1_0 {
     Ack,
 }
 
+#[derive(Copy, Clone, Debug, AsRefStr, EnumString, PartialEq)]
+#[strum(serialize_all = "kebab-case")]
+pub enum CredentialIssuanceTypeV2_0 {
+    OfferCredential,
+    ProposeCredential,
+    RequestCredential,
+    IssueCredential,
+    CredentialPreview,
+    Ack,
+    ProblemReport,
+}
+
 #[cfg(test)]
 mod tests {
     use serde_json::json;
@@ -116,4 +137,38 @@ mod tests {
             CredentialIssuanceTypeV1::new_v1_0(),
         )
     }
+
+    #[test]
+    fn test_protocol_issue_credential_v2() {
+        test_utils::test_serde(
+            Protocol::from(CredentialIssuanceTypeV2::new_v2_0()),
+            json!("https://didcomm.org/issue-credential/2.0"),
+        )
+    }
+
+    #[test]
+    fn test_version_resolution_issue_credential_v2() {
+        test_utils::test_msg_type_resolution(
+            "https://didcomm.org/issue-credential/2.255",
+            CredentialIssuanceTypeV2::new_v2_0(),
+        )
+    }
+
+    #[test]
+    fn test_msg_type_offer_v2() {
+        test_utils::test_msg_type(
+            "https://didcomm.org/issue-credential/2.0",
+            "offer-credential",
+            CredentialIssuanceTypeV2::new_v2_0(),
+        )
+    }
+
+    #[test]
+    fn test_msg_type_problem_report_v2() {
+        test_utils::test_msg_type(
+            "https://didcomm.org/issue-credential/2.0",
+            "problem-report",
+            CredentialIssuanceTypeV2::new_v2_0(),
+        )
+    }
 }
@@ -0,0 +1,92 @@
+use derive_more::From;
+use messages_macros::MessageType;
+use strum_macros::{AsRefStr, EnumString};
+use transitive::TransitiveFrom;
+
+use super::Protocol;
+use crate::msg_types::{role::Role, MsgKindType};
+
+#[derive(Copy, Clone, Debug, From, PartialEq, MessageType)]
+#[msg_type(protocol = "did-rotate")]
+pub enum DidRotateType {
+    V1(DidRotateTypeV1),
+}
+
+#[derive(Copy, Clone, Debug, From, PartialEq, TransitiveFrom, MessageType)]
+#[transitive(into(DidRotateType, Protocol))]
+#[msg_type(major = 1)]
+pub enum DidRotateTypeV1 {
+    #[msg_type(minor = 0, roles = "Role::RotatingParty, Role::ObservingParty")]
+    V1_0(MsgKindType<DidRotateTypeV1_0>),
+}
+
+#[derive(Copy, Clone, Debug, AsRefStr, EnumString, PartialEq)]
+#[strum(serialize_all = "kebab-case")]
+pub enum DidRotateTypeV1_0 {
+    Rotate,
+    Ack,
+    ProblemReport,
+    Hangup,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::misc::test_utils;
+
+    #[test]
+    fn test_protocol_did_rotate() {
+        test_utils::test_serde(
+            Protocol::from(DidRotateTypeV1::new_v1_0()),
+            json!("https://didcomm.org/did-rotate/1.0"),
+        )
+    }
+
+    #[test]
+    fn test_version_resolution_did_rotate() {
+        test_utils::test_msg_type_resolution("https://didcomm.org/did-rotate/1.255", DidRotateTypeV1::new_v1_0())
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_unsupported_version_did_rotate() {
+        test_utils::test_serde(
+            Protocol::from(DidRotateTypeV1::new_v1_0()),
+            json!("https://didcomm.org/did-rotate/2.0"),
+        )
+    }
+
+    #[test]
+    fn test_msg_type_rotate() {
+        test_utils::test_msg_type(
+            "https://didcomm.org/did-rotate/1.0",
+            "rotate",
+            DidRotateTypeV1::new_v1_0(),
+        )
+    }
+
+    #[test]
+    fn test_msg_type_ack() {
+        test_utils::test_msg_type("https://didcomm.org/did-rotate/1.0", "ack", DidRotateTypeV1::new_v1_0())
+    }
+
+    #[test]
+    fn test_msg_type_problem_report() {
+        test_utils::test_msg_type(
+            "https://didcomm.org/did-rotate/1.0",
+            "problem-report",
+            DidRotateTypeV1::new_v1_0(),
+        )
+    }
+
+    #[test]
+    fn test_msg_type_hangup() {
+        test_utils::test_msg_type(
+            "https://didcomm.org/did-rotate/1.0",
+            "hangup",
+            DidRotateTypeV1::new_v1_0(),
+        )
+    }
+}
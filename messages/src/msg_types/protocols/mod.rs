@@ -6,9 +6,10 @@ use shared_vcx::misc::utils::CowStr;
 
 use self::{
     basic_message::BasicMessageType, connection::ConnectionType, cred_issuance::CredentialIssuanceType,
-    discover_features::DiscoverFeaturesType, notification::NotificationType, out_of_band::OutOfBandType,
-    present_proof::PresentProofType, report_problem::ReportProblemType, revocation::RevocationType,
-    routing::RoutingType, signature::SignatureType, trust_ping::TrustPingType,
+    did_exchange::DidExchangeType, did_rotate::DidRotateType, discover_features::DiscoverFeaturesType,
+    notification::NotificationType, out_of_band::OutOfBandType, present_proof::PresentProofType,
+    report_problem::ReportProblemType, revocation::RevocationType, routing::RoutingType, signature::SignatureType,
+    trust_ping::TrustPingType,
 };
 use crate::{
     error::{MsgTypeError, MsgTypeResult},
@@ -18,6 +19,8 @@ use crate::{
 pub mod basic_message;
 pub mod connection;
 pub mod cred_issuance;
+pub mod did_exchange;
+pub mod did_rotate;
 pub mod discover_features;
 pub mod notification;
 pub mod out_of_band;
@@ -57,6 +60,8 @@ pub enum Protocol {
     BasicMessageType(BasicMessageType),
     OutOfBandType(OutOfBandType),
     NotificationType(NotificationType),
+    DidRotateType(DidRotateType),
+    DidExchangeType(DidExchangeType),
 }
 
 /// Utility macro to avoid harder to read and error prone calling
@@ -92,6 +97,8 @@ impl Protocol {
         match_protocol!(BasicMessageType, protocol, major, minor);
         match_protocol!(OutOfBandType, protocol, major, minor);
         match_protocol!(NotificationType, protocol, major, minor);
+        match_protocol!(DidRotateType, protocol, major, minor);
+        match_protocol!(DidExchangeType, protocol, major, minor);
 
         Err(MsgTypeError::unknown_protocol(protocol.to_owned()))
     }
@@ -111,6 +118,8 @@ impl Protocol {
             Self::BasicMessageType(v) => v.as_protocol_parts(),
             Self::OutOfBandType(v) => v.as_protocol_parts(),
             Self::NotificationType(v) => v.as_protocol_parts(),
+            Self::DidRotateType(v) => v.as_protocol_parts(),
+            Self::DidExchangeType(v) => v.as_protocol_parts(),
         }
     }
 
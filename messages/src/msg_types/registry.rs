@@ -6,10 +6,20 @@ use super::{role::Role, Protocol};
 use crate::{
     maybe_known::MaybeKnown,
     msg_types::protocols::{
-        basic_message::BasicMessageTypeV1, connection::ConnectionTypeV1, cred_issuance::CredentialIssuanceTypeV1,
-        discover_features::DiscoverFeaturesTypeV1, notification::NotificationTypeV1, out_of_band::OutOfBandTypeV1,
-        present_proof::PresentProofTypeV1, report_problem::ReportProblemTypeV1, revocation::RevocationTypeV2,
-        routing::RoutingTypeV1, signature::SignatureTypeV1, trust_ping::TrustPingTypeV1,
+        basic_message::BasicMessageTypeV1,
+        connection::ConnectionTypeV1,
+        cred_issuance::{CredentialIssuanceTypeV1, CredentialIssuanceTypeV2},
+        did_exchange::DidExchangeTypeV1,
+        did_rotate::DidRotateTypeV1,
+        discover_features::DiscoverFeaturesTypeV1,
+        notification::NotificationTypeV1,
+        out_of_band::OutOfBandTypeV1,
+        present_proof::{PresentProofTypeV1, PresentProofTypeV2},
+        report_problem::ReportProblemTypeV1,
+        revocation::RevocationTypeV2,
+        routing::RoutingTypeV1,
+        signature::SignatureTypeV1,
+        trust_ping::TrustPingTypeV1,
     },
 };
 type RegistryMap = HashMap<(&'static str, u8), Vec<RegistryEntry>>;
@@ -64,10 +74,14 @@ lazy_static! {
         map_insert(&mut m, extract_parts!(ConnectionTypeV1::new_v1_0()));
         map_insert(&mut m, extract_parts!(SignatureTypeV1::new_v1_0()));
         map_insert(&mut m, extract_parts!(CredentialIssuanceTypeV1::new_v1_0()));
+        map_insert(&mut m, extract_parts!(CredentialIssuanceTypeV2::new_v2_0()));
+        map_insert(&mut m, extract_parts!(DidExchangeTypeV1::new_v1_0()));
+        map_insert(&mut m, extract_parts!(DidRotateTypeV1::new_v1_0()));
         map_insert(&mut m, extract_parts!(DiscoverFeaturesTypeV1::new_v1_0()));
         map_insert(&mut m, extract_parts!(NotificationTypeV1::new_v1_0()));
         map_insert(&mut m, extract_parts!(OutOfBandTypeV1::new_v1_1()));
         map_insert(&mut m, extract_parts!(PresentProofTypeV1::new_v1_0()));
+        map_insert(&mut m, extract_parts!(PresentProofTypeV2::new_v2_0()));
         map_insert(&mut m, extract_parts!(ReportProblemTypeV1::new_v1_0()));
         map_insert(&mut m, extract_parts!(RevocationTypeV2::new_v2_0()));
         map_insert(&mut m, extract_parts!(TrustPingTypeV1::new_v1_0()));
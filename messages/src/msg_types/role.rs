@@ -18,4 +18,8 @@ pub enum Role {
     Notified,
     Notifier,
     Mediator,
+    #[serde(rename = "rotating_party")]
+    RotatingParty,
+    #[serde(rename = "observing_party")]
+    ObservingParty,
 }
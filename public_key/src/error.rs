@@ -0,0 +1,12 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum PublicKeyError {
+    #[error("Unsupported key type with multicodec prefix: {0:?}")]
+    UnsupportedKeyType(Vec<u8>),
+    #[error("Invalid key: {0}")]
+    InvalidKey(String),
+    #[error("Base58 decoding error: {0}")]
+    Base58Error(#[from] bs58::decode::Error),
+}
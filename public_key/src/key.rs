@@ -0,0 +1,181 @@
+use curve25519_dalek::edwards::CompressedEdwardsY;
+
+use crate::error::PublicKeyError;
+
+const ED25519_MULTICODEC_PREFIX: [u8; 2] = [0xed, 0x01];
+const X25519_MULTICODEC_PREFIX: [u8; 2] = [0xec, 0x01];
+const MULTIBASE_BASE58_BTC_PREFIX: char = 'z';
+const PUBLIC_KEY_LENGTH: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyType {
+    Ed25519,
+    X25519,
+}
+
+impl KeyType {
+    fn multicodec_prefix(&self) -> [u8; 2] {
+        match self {
+            KeyType::Ed25519 => ED25519_MULTICODEC_PREFIX,
+            KeyType::X25519 => X25519_MULTICODEC_PREFIX,
+        }
+    }
+
+    pub fn verification_method_type(&self) -> &'static str {
+        match self {
+            KeyType::Ed25519 => "Ed25519VerificationKey2020",
+            KeyType::X25519 => "X25519KeyAgreementKey2020",
+        }
+    }
+}
+
+// Public key as it appears in did:key and did:peer, i.e. multicodec-prefixed and multibase (base58btc) encoded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Key {
+    key_type: KeyType,
+    key: Vec<u8>,
+}
+
+impl Key {
+    pub fn new(key_type: KeyType, key: Vec<u8>) -> Result<Self, PublicKeyError> {
+        if key.len() != PUBLIC_KEY_LENGTH {
+            return Err(PublicKeyError::InvalidKey(format!(
+                "Expected {} bytes long public key, got {}",
+                PUBLIC_KEY_LENGTH,
+                key.len()
+            )));
+        }
+        Ok(Self { key_type, key })
+    }
+
+    pub fn from_base58(key_type: KeyType, key: &str) -> Result<Self, PublicKeyError> {
+        Self::new(key_type, bs58::decode(key).into_vec()?)
+    }
+
+    pub fn from_fingerprint(fingerprint: &str) -> Result<Self, PublicKeyError> {
+        let encoded = fingerprint
+            .strip_prefix(MULTIBASE_BASE58_BTC_PREFIX)
+            .ok_or_else(|| {
+                PublicKeyError::InvalidKey(format!(
+                    "Expected base58btc multibase, got {}",
+                    fingerprint
+                ))
+            })?;
+        let decoded = bs58::decode(encoded).into_vec()?;
+        if decoded.len() < 2 {
+            return Err(PublicKeyError::InvalidKey(format!(
+                "Key too short: {}",
+                fingerprint
+            )));
+        }
+        let (prefix, key) = decoded.split_at(2);
+        let key_type = match [prefix[0], prefix[1]] {
+            ED25519_MULTICODEC_PREFIX => KeyType::Ed25519,
+            X25519_MULTICODEC_PREFIX => KeyType::X25519,
+            _ => return Err(PublicKeyError::UnsupportedKeyType(prefix.to_vec())),
+        };
+        Self::new(key_type, key.to_vec())
+    }
+
+    pub fn key_type(&self) -> KeyType {
+        self.key_type
+    }
+
+    pub fn key(&self) -> &[u8] {
+        self.key.as_ref()
+    }
+
+    pub fn base58(&self) -> String {
+        bs58::encode(&self.key).into_string()
+    }
+
+    // Multicodec-prefixed key encoded as base58btc multibase
+    pub fn fingerprint(&self) -> String {
+        let mut prefixed = self.key_type.multicodec_prefix().to_vec();
+        prefixed.extend_from_slice(&self.key);
+        format!(
+            "{}{}",
+            MULTIBASE_BASE58_BTC_PREFIX,
+            bs58::encode(prefixed).into_string()
+        )
+    }
+
+    // Maps the Ed25519 point onto the birationally equivalent Curve25519 to get a key agreement key
+    // https://w3c-ccg.github.io/did-method-key/#encryption-method-creation-algorithm
+    pub fn to_x25519(&self) -> Result<Key, PublicKeyError> {
+        match self.key_type {
+            KeyType::X25519 => Ok(self.clone()),
+            KeyType::Ed25519 => {
+                let compressed = CompressedEdwardsY::from_slice(&self.key);
+                let edwards = compressed.decompress().ok_or_else(|| {
+                    PublicKeyError::InvalidKey(format!(
+                        "Not a valid Ed25519 public key: {}",
+                        self.base58()
+                    ))
+                })?;
+                Key::new(KeyType::X25519, edwards.to_montgomery().to_bytes().to_vec())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VERKEY: &str = "8HH5gYEeNc3z7PYXmd54d4x6qAfCNrqQqEB3nS7Zfu7K";
+    const VERKEY_FINGERPRINT: &str = "z6MkmjY8GnV5i9YTDtPETC2uUAW6ejw3nk5mXF5yci5ab7th";
+
+    #[test]
+    fn test_key_fingerprint() {
+        let key = Key::from_base58(KeyType::Ed25519, VERKEY).unwrap();
+        assert_eq!(key.fingerprint(), VERKEY_FINGERPRINT);
+    }
+
+    #[test]
+    fn test_key_from_fingerprint() {
+        let key = Key::from_fingerprint(VERKEY_FINGERPRINT).unwrap();
+        assert_eq!(key.key_type(), KeyType::Ed25519);
+        assert_eq!(key.base58(), VERKEY);
+    }
+
+    #[test]
+    fn test_key_from_fingerprint_x25519() {
+        let key =
+            Key::from_fingerprint("z6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc").unwrap();
+        assert_eq!(key.key_type(), KeyType::X25519);
+    }
+
+    #[test]
+    fn test_key_from_fingerprint_invalid_encoding() {
+        let key = Key::from_fingerprint(VERKEY);
+        assert!(matches!(key, Err(PublicKeyError::InvalidKey(_))));
+    }
+
+    #[test]
+    fn test_key_from_fingerprint_unsupported_prefix() {
+        let mut prefixed = vec![0x12, 0x00];
+        prefixed.extend_from_slice(&[0; PUBLIC_KEY_LENGTH]);
+        let fingerprint = format!("z{}", bs58::encode(prefixed).into_string());
+        let key = Key::from_fingerprint(&fingerprint);
+        assert!(matches!(key, Err(PublicKeyError::UnsupportedKeyType(_))));
+    }
+
+    #[test]
+    fn test_key_invalid_length() {
+        let key = Key::new(KeyType::Ed25519, vec![0; 16]);
+        assert!(matches!(key, Err(PublicKeyError::InvalidKey(_))));
+    }
+
+    #[test]
+    fn test_key_to_x25519() {
+        let key =
+            Key::from_fingerprint("z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK").unwrap();
+        let x25519 = key.to_x25519().unwrap();
+        assert_eq!(x25519.key_type(), KeyType::X25519);
+        assert_eq!(
+            x25519.fingerprint(),
+            "z6LSj72tK8brWgZja8NLRwPigth2T9QRiG1uH9oKZuKjdh9p"
+        );
+    }
+}
@@ -0,0 +1,5 @@
+mod error;
+mod key;
+
+pub use error::PublicKeyError;
+pub use key::{Key, KeyType};